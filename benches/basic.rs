@@ -124,6 +124,111 @@ fn draw_triangle(b: &mut Bencher) {
     });
 }
 
+#[bench]
+#[cfg(feature = "null_backend")]
+fn draw_same_parameters(b: &mut Bencher) {
+    let backend = glium::backend::null::NullBackend::new();
+    let display = unsafe {
+        glium::backend::Context::new(backend.clone(), false, Default::default()).unwrap()
+    };
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    // the state cache skips the state changes of all the draws but the first one
+    let params = glium::DrawParameters {
+        blend: glium::Blend::alpha_blending(),
+        depth: glium::Depth {
+            test: glium::DepthTest::IfLess,
+            write: true,
+            .. Default::default()
+        },
+        backface_culling: glium::BackfaceCullingMode::CullClockwise,
+        .. Default::default()
+    };
+
+    let mut target = glium::Frame::new(display.clone(), (800, 600));
+
+    b.iter(|| {
+        backend.clear_calls();
+        target.draw(glium::vertex::EmptyVertexAttributes { len: 3 },
+                    &glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList),
+                    &program, &uniform!{}, &params).unwrap();
+    });
+
+    target.finish().unwrap();
+}
+
+#[bench]
+#[cfg(feature = "null_backend")]
+fn draw_twenty_uniforms(b: &mut Bencher) {
+    struct FloatUniforms(Vec<f32>);
+
+    impl glium::uniforms::Uniforms for FloatUniforms {
+        fn visit_values<'a, F>(&'a self, mut output: F)
+            where F: FnMut(&str, glium::uniforms::UniformValue<'a>)
+        {
+            for (i, &value) in self.0.iter().enumerate() {
+                output(&format!("u{}", i), glium::uniforms::UniformValue::Float(value));
+            }
+        }
+    }
+
+    let backend = glium::backend::null::NullBackend::new();
+    for i in 0 .. 20 {
+        backend.add_uniform(&format!("u{}", i), 0x1406 /* GL_FLOAT */);
+    }
+
+    let display = unsafe {
+        glium::backend::Context::new(backend.clone(), false, Default::default()).unwrap()
+    };
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    // only one of the twenty values changes between two draws, and only this one is uploaded
+    let mut uniforms = FloatUniforms(vec![0.0; 20]);
+    let mut target = glium::Frame::new(display.clone(), (800, 600));
+
+    b.iter(|| {
+        backend.clear_calls();
+        uniforms.0[0] += 1.0;
+        target.draw(glium::vertex::EmptyVertexAttributes { len: 3 },
+                    &glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList),
+                    &program, &uniforms, &Default::default()).unwrap();
+    });
+
+    target.finish().unwrap();
+}
+
 #[bench]
 fn build_buffer(b: &mut Bencher) {
     let display = support::build_context();
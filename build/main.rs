@@ -45,11 +45,14 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
             "GL_ARB_invalidate_subdata",
             "GL_ARB_multi_draw_indirect",
             "GL_ARB_occlusion_query",
+            "GL_ARB_parallel_shader_compile",
+            "GL_ARB_pipeline_statistics_query",
             "GL_ARB_pixel_buffer_object",
             "GL_ARB_robustness",
             "GL_ARB_seamless_cube_map",
             "GL_ARB_shader_image_load_store",
             "GL_ARB_shader_objects",
+            "GL_ARB_stencil_texturing",
             "GL_ARB_texture_buffer_object",
             "GL_ARB_texture_float",
             "GL_ARB_texture_multisample",
@@ -75,8 +78,11 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
             "GL_EXT_texture_filter_anisotropic",
             "GL_EXT_texture_integer",
             "GL_EXT_texture_sRGB",
+            "GL_EXT_texture_sRGB_decode",
             "GL_EXT_transform_feedback",
             "GL_GREMEDY_string_marker",
+            "GL_KHR_no_error",
+            "GL_KHR_parallel_shader_compile",
             "GL_KHR_robustness",
             "GL_NVX_gpu_memory_info",
             "GL_NV_conditional_render",
@@ -101,7 +107,9 @@ fn generate_gl_bindings<W>(dest: &mut W) where W: Write {
             "GL_EXT_occlusion_query_boolean",
             "GL_EXT_primitive_bounding_box",
             "GL_EXT_robustness",
+            "GL_EXT_texture_rg",
             "GL_KHR_debug",
+            "GL_KHR_parallel_shader_compile",
             "GL_NV_copy_buffer",
             "GL_NV_framebuffer_multisample",
             "GL_NV_internalformat_sample_query",
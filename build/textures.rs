@@ -455,6 +455,37 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
                             }}
                         ", myname = name)).unwrap();
             },
+            TextureType::Stencil | TextureType::DepthStencil
+                if dimensions == TextureDimensions::Texture2d =>
+            {
+                (writeln!(dest, "
+                            impl<'a> AsUniformValue for &'a {myname} {{
+                                #[inline]
+                                fn as_uniform_value(&self) -> UniformValue {{
+                                    UniformValue::{myname}(*self, None)
+                                }}
+                            }}
+
+                            impl<'a> AsUniformValue for Sampler<'a, {myname}> {{
+                                #[inline]
+                                fn as_uniform_value(&self) -> UniformValue {{
+                                    UniformValue::{myname}(self.0, Some(self.1))
+                                }}
+                            }}
+
+                            impl {myname} {{
+                                /// Builds a `Sampler` marker object that allows you to indicate
+                                /// how the texture should be sampled from inside a shader.
+                                ///
+                                /// Stencil values can't be filtered, therefore the sampler must
+                                /// use `Nearest` filtering.
+                                #[inline]
+                                pub fn sampled(&self) -> Sampler<{myname}> {{
+                                    Sampler(self, Default::default())
+                                }}
+                            }}
+                        ", myname = name)).unwrap();
+            },
             _ => ()
         }
     }
@@ -837,6 +868,41 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
             }}
         ")).unwrap();
 
+    // writing the `supports_gather` function
+    if dimensions == TextureDimensions::Texture2d && ty != TextureType::Stencil &&
+       ty != TextureType::DepthStencil
+    {
+        let check = if ty == TextureType::Depth {
+            "is_texture_gather_components_supported"
+        } else {
+            "is_texture_gather_supported"
+        };
+
+        (write!(dest, "
+                /// Returns true if this texture can be read with `textureGather` in a shader.
+                ///
+                /// `textureGather` returns one component of each of the four texels that would
+                /// be used for bilinear filtering, which is useful for example to write a custom
+                /// percentage-closer filter for shadows. The variants are:
+                ///
+                ///  - `textureGather(sampler, coords)` returns the first component.
+                ///  - `textureGather(sampler, coords, comp)` returns the component `comp`, and
+                ///    requires OpenGL 4.0 or `GL_ARB_gpu_shader5`.
+                ///  - `textureGatherOffset(sampler, coords, offset)` adds a constant offset in
+                ///    texels to the coordinates.
+                ///  - `textureGatherOffsets(sampler, coords, offsets)` uses a different offset
+                ///    for each texel, and requires OpenGL 4.0 or `GL_ARB_gpu_shader5`.
+                ///
+                /// Depth textures are gathered from a `sampler2DShadow` with
+                /// `textureGather(sampler, coords, refZ)`, which returns the result of the four
+                /// comparisons. This requires OpenGL 4.0 or `GL_ARB_gpu_shader5` too.
+                #[inline]
+                pub fn supports_gather(&self) -> bool {{
+                    crate::texture::ty_support::{check}(&**self.0.get_context())
+                }}
+            ", check = check)).unwrap();
+    }
+
     // writing the `read` functions
     // TODO: implement for other types too
     if dimensions == TextureDimensions::Texture2d &&
@@ -907,6 +973,36 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
             "#)).unwrap();
     }
 
+    // writing the `read_pixel` function for integral textures
+    if dimensions == TextureDimensions::Texture2d &&
+       (ty == TextureType::Integral || ty == TextureType::Unsigned)
+    {
+        let pixel = if ty == TextureType::Integral { "(i32, i32, i32, i32)" }
+                    else { "(u32, u32, u32, u32)" };
+
+        (write!(dest, r#"
+                /// Reads the value of a single pixel of the texture.
+                ///
+                /// This is typically used for picking: render the identifier of each object to an
+                /// integral texture, then read the pixel under the cursor. The components that
+                /// don't exist in the texture's format are returned as 0, except for alpha which
+                /// is returned as 1.
+                ///
+                /// You should avoid doing this during performance-critical operations, as it
+                /// waits for the GPU to finish drawing.
+                ///
+                /// # Panic
+                ///
+                /// Panics if the coordinates are out of range.
+                pub fn read_pixel(&self, x: u32, y: u32) -> {pixel} {{
+                    let rect = Rect {{ left: x, bottom: y, width: 1, height: 1 }};
+                    let data: Vec<Vec<{pixel}>> = self.0.main_level().first_layer()
+                                                      .into_image(None).unwrap().raw_read(&rect);
+                    data[0][0]
+                }}
+            "#, pixel = pixel)).unwrap();
+    }
+
     // writing the `read_compressed_data` function
     if is_compressed && !dimensions.is_array() {
         (write!(dest, r#"
@@ -928,7 +1024,8 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
     // writing the `write` function
     // TODO: implement for other types too
     if dimensions == TextureDimensions::Texture2d &&
-            (ty == TextureType::Regular || ty == TextureType::Srgb || is_compressed)
+            (ty == TextureType::Regular || ty == TextureType::Srgb || is_compressed ||
+             ty == TextureType::Integral || ty == TextureType::Unsigned)
     {
         let compressed_restrictions = if is_compressed {
             r#" ///
@@ -957,6 +1054,35 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
                 pub fn write<'a, T>(&self, rect: Rect, data: T) where T: {data_source_trait}<'a> {{
                     self.main_level().write(rect, data)
                 }}
+
+                /// Uploads the whole content of a mipmap level of the texture.
+                ///
+                /// The dimensions of each level are the dimensions of the previous one divided by
+                /// two, rounded down, with a minimum of one. This can be used to upload mipmaps
+                /// that have been generated offline, for example in a texture created with
+                /// `MipmapsOption::EmptyMipmaps`.
+                ///
+                /// ## Panic
+                ///
+                /// Panics if the texture doesn't have this level, or if the dimensions of `data`
+                /// don't match the dimensions of the level.
+                pub fn write_level<'a, T>(&self, level: u32, data: T)
+                                          where T: {data_source_trait}<'a>
+                {{
+                    let mipmap = match self.mipmap(level) {{
+                        Some(m) => m,
+                        None => panic!("The texture doesn't have a mipmap level {{}}", level),
+                    }};
+
+                    let rect = Rect {{
+                        left: 0,
+                        bottom: 0,
+                        width: mipmap.width(),
+                        height: mipmap.height(),
+                    }};
+
+                    mipmap.write(rect, data)
+                }}
             "#, data_source_trait = data_source_trait,
                 compressed_restrictions = compressed_restrictions)).unwrap();
     }
@@ -1133,7 +1259,8 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
         // writing the `write` function for mipmaps.
         // TODO: implement for other types too
         if dimensions == TextureDimensions::Texture2d &&
-                (ty == TextureType::Regular || ty == TextureType::Srgb || is_compressed)
+                (ty == TextureType::Regular || ty == TextureType::Srgb || is_compressed ||
+                 ty == TextureType::Integral || ty == TextureType::Unsigned)
         {
             let compressed_restrictions = if is_compressed {
                 r#" ///
@@ -1271,6 +1398,17 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
                 }}", name = name).unwrap();
         }
 
+        if dimensions == TextureDimensions::Texture3d {
+            writeln!(dest,
+                "/// Provides an object representing a single slice of this 3D texture, for example
+                /// to use it as a framebuffer attachment.
+                ///
+                /// Returns `None` if `z` is out of range.
+                pub fn slice(&self, z: u32) -> Option<{name}Image<'t>> {{
+                    self.0.layer(z).map(|l| {name}Image(l.into_image(None).unwrap(), self.1))
+                }}", name = name).unwrap();
+        }
+
         // closing `impl Mipmap` block
         (writeln!(dest, "}}")).unwrap();
 
@@ -1322,20 +1460,52 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
         }
     }
 
-    // the `Image` struct, only for cubemaps
-    if dimensions.is_cube() {
+    // the `Image` struct, only for cubemaps and 3D textures
+    if dimensions.is_cube() || dimensions == TextureDimensions::Texture3d {
         // writing the struct
-        (write!(dest, r#"
-                /// Represents a single image of a mipmap level of a layer of `{name}`.
-                #[derive(Copy, Clone)]
-                pub struct {name}Image<'t>(TextureAnyImage<'t>, &'t {name});
-            "#, name = name)).unwrap();
+        if dimensions.is_cube() {
+            (write!(dest, r#"
+                    /// Represents a single image of a mipmap level of a layer of `{name}`.
+                    #[derive(Copy, Clone)]
+                    pub struct {name}Image<'t>(TextureAnyImage<'t>, &'t {name});
+                "#, name = name)).unwrap();
+        } else {
+            (write!(dest, r#"
+                    /// Represents a single slice of a mipmap level of `{name}`.
+                    #[derive(Copy, Clone)]
+                    pub struct {name}Image<'t>(TextureAnyImage<'t>, &'t {name});
+                "#, name = name)).unwrap();
+        }
 
         // opening `impl Image` block
         (writeln!(dest, "impl<'t> {}Image<'t> {{", name)).unwrap();
 
-        // dimensions getters
-        write_dimensions_getters(dest, dimensions, "self.0", false);
+        // dimensions getters, a slice of a 3D texture is a 2D image
+        let image_dimensions = if dimensions == TextureDimensions::Texture3d {
+            TextureDimensions::Texture2d
+        } else {
+            dimensions
+        };
+        write_dimensions_getters(dest, image_dimensions, "self.0", false);
+
+        // writing the `write_compressed_data` function for the faces of cubemaps
+        if dimensions == TextureDimensions::Cubemap && is_compressed {
+            (write!(dest, r#"
+                    /// Uploads compressed data that covers the whole face.
+                    ///
+                    /// Returns `Err` if the format isn't supported or doesn't match the texture.
+                    ///
+                    /// ## Panic
+                    ///
+                    /// Panics if the size of `data` doesn't match the dimensions of the face.
+                    #[inline]
+                    pub fn write_compressed_data(&self, data: &[u8], format: {format})
+                                                 -> Result<(), ()>
+                    {{
+                        self.0.raw_upload_compressed_data(data, {client_format_any}(format))
+                    }}
+                "#, format = relevant_format, client_format_any = client_format_any_ty)).unwrap();
+        }
 
         // closing `impl Image` block
         (writeln!(dest, "}}")).unwrap();
@@ -1349,15 +1519,18 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
     }
 
     // implement the attachments traits
-    {
-        let attachment_type = if dimensions.is_cube() {
-            format!("{}Image", name)
-        } else if dimensions.is_array() {
-            format!("{}LayerMipmap", name)
-        } else {
-            format!("{}Mipmap", name)
-        };
+    let attachment_types = if dimensions.is_cube() {
+        vec![format!("{}Image", name)]
+    } else if dimensions.is_array() {
+        vec![format!("{}LayerMipmap", name)]
+    } else if dimensions == TextureDimensions::Texture3d {
+        // attaching the mipmap itself attaches its first slice
+        vec![format!("{}Mipmap", name), format!("{}Image", name)]
+    } else {
+        vec![format!("{}Mipmap", name)]
+    };
 
+    for attachment_type in attachment_types {
         match ty {
             TextureType::Regular | TextureType::Srgb | TextureType::Integral | TextureType::Unsigned => {
                 (writeln!(dest, "
@@ -1403,6 +1576,33 @@ fn build_texture<W: Write>(dest: &mut W, ty: TextureType, dimensions: TextureDim
         }
     }
 
+    // implement the layered attachments traits
+    if dimensions.is_array() || dimensions.is_cube() || dimensions == TextureDimensions::Texture3d {
+        match ty {
+            TextureType::Regular | TextureType::Srgb | TextureType::Integral | TextureType::Unsigned => {
+                (writeln!(dest, "
+                        impl<'t> crate::framebuffer::ToLayeredColorAttachment<'t> for {name}Mipmap<'t> {{
+                            #[inline]
+                            fn to_layered_color_attachment(self) -> crate::framebuffer::LayeredColorAttachment<'t> {{
+                                crate::framebuffer::LayeredColorAttachment::new(self.0)
+                            }}
+                        }}
+                    ", name = name)).unwrap();
+            },
+            TextureType::Depth => {
+                (writeln!(dest, "
+                        impl<'t> crate::framebuffer::ToLayeredDepthAttachment<'t> for {name}Mipmap<'t> {{
+                            #[inline]
+                            fn to_layered_depth_attachment(self) -> crate::framebuffer::LayeredDepthAttachment<'t> {{
+                                crate::framebuffer::LayeredDepthAttachment::new(self.0)
+                            }}
+                        }}
+                    ", name = name)).unwrap();
+            },
+            _ => ()
+        }
+    }
+
     // closing `mod module {`
     writeln!(dest, "}}").unwrap();
 }
@@ -13,6 +13,29 @@ use super::glutin::{PossiblyCurrent as Pc, ContextCurrentState};
 use takeable_option::Takeable;
 
 /// A headless glutin context.
+///
+/// This is a context without any visible window, which is useful for example for automated
+/// rendering tests or for generating images on a server. Depending on the platform, glutin
+/// creates it with a pbuffer, a surfaceless EGL context or a hidden window.
+///
+/// The content of the default framebuffer of a headless context is unspecified, and it may
+/// not even exist. Instead you should draw to a texture and read it back:
+///
+/// ```no_run
+/// use glium::Surface;
+/// use glium::glutin::{self, dpi::PhysicalSize};
+///
+/// let event_loop = glutin::event_loop::EventLoop::new();
+/// let context = glutin::ContextBuilder::new()
+///     .build_headless(&event_loop, PhysicalSize::new(800, 600))
+///     .unwrap();
+/// let display = glium::HeadlessRenderer::new(context).unwrap();
+///
+/// let texture = glium::Texture2d::empty(&display, 800, 600).unwrap();
+/// texture.as_surface().clear_color(0.0, 0.0, 1.0, 1.0);
+///
+/// let image: glium::texture::RawImage2d<'_, u8> = texture.read();
+/// ```
 pub struct Headless {
     context: Rc<context::Context>,
     glutin: Rc<RefCell<Takeable<glutin::Context<Pc>>>>,
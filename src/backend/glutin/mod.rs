@@ -25,13 +25,17 @@ use std::ops::Deref;
 use std::os::raw::c_void;
 use std::rc::Rc;
 use takeable_option::Takeable;
-use crate::{Frame, IncompatibleOpenGl, SwapBuffersError};
+use crate::{Frame, IncompatibleOpenGl, SwapBuffersError, SwapIntervalNotSupportedError};
 
 /// A GL context combined with a facade for drawing upon.
 ///
 /// The `Display` uses **glutin** for the **Window** and its associated GL **Context**.
 ///
 /// These are stored alongside a glium-specific context.
+///
+/// Several displays can be created and used from the same thread, for example one per
+/// viewport. The right OpenGL context is made current automatically before each operation, but
+/// switching between contexts is expensive. See the documentation of `Context::new`.
 #[derive(Clone)]
 pub struct Display {
     // contains everything related to the current context and its state
@@ -54,6 +58,9 @@ pub enum DisplayCreationError {
     GlutinCreationError(glutin::CreationError),
     /// The OpenGL implementation is too old.
     IncompatibleOpenGl(IncompatibleOpenGl),
+    /// A debug context has been requested in no-error mode. The debug output can't report
+    /// anything if the driver doesn't check for errors.
+    NoErrorWithDebug,
 }
 
 impl std::fmt::Debug for Display {
@@ -67,11 +74,81 @@ impl Display {
     ///
     /// Performs a compatibility check to make sure that all core elements of glium are supported
     /// by the implementation.
+    ///
+    /// The version, the profile and the flags of the OpenGL context are chosen with the
+    /// context builder. For example, this requests a core profile OpenGL 3.3 debug context
+    /// instead of whatever the driver offers by default:
+    ///
+    /// ```no_run
+    /// # let events_loop = glium::glutin::event_loop::EventLoop::new();
+    /// # let wb = glium::glutin::window::WindowBuilder::new();
+    /// use glium::glutin::{Api, ContextBuilder, GlProfile, GlRequest};
+    ///
+    /// let cb = ContextBuilder::new()
+    ///     .with_gl(GlRequest::Specific(Api::OpenGl, (3, 3)))
+    ///     .with_gl_profile(GlProfile::Core)
+    ///     .with_gl_debug_flag(true);
+    ///
+    /// let display = glium::Display::new(wb, cb, &events_loop).unwrap();
+    /// assert_eq!(display.get_opengl_profile(), Some(glium::Profile::Core));
+    /// ```
+    ///
+    /// If the requested version or profile can't be created, this returns a
+    /// `DisplayCreationError::GlutinCreationError` that describes the problem, for example
+    /// `glutin::CreationError::OpenGlVersionNotSupported`.
+    ///
+    /// By default the driver flushes the commands queue of the context each time another
+    /// context is made current. When switching often between several contexts, you can ask
+    /// for this implicit flush to be skipped. This requires `GL_KHR_context_flush_control`,
+    /// and `get_release_behavior` tells whether the request has been honored:
+    ///
+    /// ```no_run
+    /// # let events_loop = glium::glutin::event_loop::EventLoop::new();
+    /// # let wb = glium::glutin::window::WindowBuilder::new();
+    /// let mut cb = glium::glutin::ContextBuilder::new();
+    /// cb.pf_reqs.release_behavior = glium::backend::ReleaseBehavior::None.into();
+    ///
+    /// let display = glium::Display::new(wb, cb, &events_loop).unwrap();
+    /// if display.get_release_behavior() == glium::backend::ReleaseBehavior::None {
+    ///     // the commands must now be flushed manually before using another context that
+    ///     // shares objects with this one
+    ///     display.flush();
+    /// }
+    /// ```
+    ///
+    /// Applications that are known to use OpenGL correctly can ask for a context that doesn't
+    /// check for errors at all, which saves some CPU time in the driver. This is done by
+    /// requesting the `NoError` robustness, which sets `GL_CONTEXT_FLAG_NO_ERROR_BIT` if
+    /// `GL_KHR_no_error` is supported and is silently ignored otherwise. `is_no_error` tells
+    /// whether the request has been honored.
+    ///
+    /// **Warning**: in this mode any misuse of OpenGL, including the ones that glium doesn't
+    /// check for, is undefined behavior instead of being reported as an error. It usually
+    /// results in a crash or a hang of the driver. Only enable it in release builds of an
+    /// application that has been tested with a regular context.
+    ///
+    /// ```no_run
+    /// # let events_loop = glium::glutin::event_loop::EventLoop::new();
+    /// # let wb = glium::glutin::window::WindowBuilder::new();
+    /// let cb = glium::glutin::ContextBuilder::new()
+    ///     .with_gl_robustness(glium::glutin::Robustness::NoError);
+    ///
+    /// let display = glium::Display::new(wb, cb, &events_loop).unwrap();
+    /// println!("no-error mode: {}", display.is_no_error());
+    /// ```
+    ///
+    /// Since a no-error context doesn't report anything, it can't be combined with
+    /// `with_gl_debug_flag(true)`. Requesting both returns
+    /// `DisplayCreationError::NoErrorWithDebug`.
     pub fn new<T: ContextCurrentState, E>(
         wb: glutin::window::WindowBuilder,
         cb: glutin::ContextBuilder<'_, T>,
         events_loop: &glutin::event_loop::EventLoop<E>,
     ) -> Result<Self, DisplayCreationError> {
+        if cb.gl_attr.debug && cb.gl_attr.robustness == glutin::Robustness::NoError {
+            return Err(DisplayCreationError::NoErrorWithDebug);
+        }
+
         let gl_window = cb.build_windowed(wb, events_loop)?;
         Self::from_gl_window(gl_window).map_err(From::from)
     }
@@ -139,6 +216,10 @@ impl Display {
         cb: glutin::ContextBuilder<'_, T>,
         events_loop: &glutin::event_loop::EventLoop<()>,
     ) -> Result<(), DisplayCreationError> {
+        if cb.gl_attr.debug && cb.gl_attr.robustness == glutin::Robustness::NoError {
+            return Err(DisplayCreationError::NoErrorWithDebug);
+        }
+
         // Share the display lists of the existing context.
         let new_gl_window = {
             let gl_window = self.gl_window.borrow();
@@ -194,6 +275,9 @@ impl fmt::Display for DisplayCreationError {
         match self {
             DisplayCreationError::GlutinCreationError(err) => write!(fmt, "{}", err),
             DisplayCreationError::IncompatibleOpenGl(err) => write!(fmt, "{}", err),
+            DisplayCreationError::NoErrorWithDebug => {
+                write!(fmt, "A debug context can't be created in no-error mode")
+            },
         }
     }
 }
@@ -204,6 +288,7 @@ impl Error for DisplayCreationError {
         match *self {
             DisplayCreationError::GlutinCreationError(ref err) => Some(err),
             DisplayCreationError::IncompatibleOpenGl(ref err) => Some(err),
+            DisplayCreationError::NoErrorWithDebug => None,
         }
     }
 }
@@ -222,6 +307,26 @@ impl From<IncompatibleOpenGl> for DisplayCreationError {
     }
 }
 
+impl From<backend::ReleaseBehavior> for glutin::ReleaseBehavior {
+    #[inline]
+    fn from(behavior: backend::ReleaseBehavior) -> glutin::ReleaseBehavior {
+        match behavior {
+            backend::ReleaseBehavior::None => glutin::ReleaseBehavior::None,
+            backend::ReleaseBehavior::Flush => glutin::ReleaseBehavior::Flush,
+        }
+    }
+}
+
+impl From<glutin::ReleaseBehavior> for backend::ReleaseBehavior {
+    #[inline]
+    fn from(behavior: glutin::ReleaseBehavior) -> backend::ReleaseBehavior {
+        match behavior {
+            glutin::ReleaseBehavior::None => backend::ReleaseBehavior::None,
+            glutin::ReleaseBehavior::Flush => backend::ReleaseBehavior::Flush,
+        }
+    }
+}
+
 impl Deref for Display {
     type Target = Context;
     #[inline]
@@ -292,4 +397,184 @@ unsafe impl Backend for GlutinBackend {
         let new_gl_window = gl_window.make_current().unwrap();
         Takeable::insert(&mut gl_window_takeable, new_gl_window);
     }
+
+    fn set_swap_interval(&self, interval: u32) -> Result<(), SwapIntervalNotSupportedError> {
+        use crate::glutin::platform::ContextTraitExt;
+        use std::mem;
+
+        // glutin only lets us choose vsync when the context is created, so we call the
+        // platform-specific functions ourselves
+        let gl_window = self.borrow();
+
+        unsafe {
+            if let Some(display) = gl_window.get_egl_display() {
+                // `eglGetProcAddress` only returns the core functions since EGL 1.5, therefore
+                // we look for `eglSwapInterval` in the EGL library first
+                let mut swap_interval = egl_library_function("eglSwapInterval\0");
+                if swap_interval.is_null() {
+                    swap_interval = gl_window.get_proc_address("eglSwapInterval");
+                }
+                if swap_interval.is_null() {
+                    return Err(SwapIntervalNotSupportedError);
+                }
+
+                let swap_interval: extern "system" fn(*const c_void, i32) -> u32 =
+                    mem::transmute(swap_interval);
+                return if swap_interval(display, interval as i32) != 0 {
+                    Ok(())
+                } else {
+                    Err(SwapIntervalNotSupportedError)
+                };
+            }
+
+            #[cfg(target_os = "windows")]
+            {
+                // `WGL_EXT_swap_control`
+                let swap_interval = gl_window.get_proc_address("wglSwapIntervalEXT");
+                if !swap_interval.is_null() {
+                    let swap_interval: extern "system" fn(i32) -> i32 =
+                        mem::transmute(swap_interval);
+                    if swap_interval(interval as i32) != 0 {
+                        return Ok(());
+                    }
+                }
+            }
+
+            #[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd",
+                      target_os = "netbsd", target_os = "openbsd"))]
+            {
+                use std::os::raw::{c_int, c_ulong};
+
+                // `glXGetProcAddress` returns non-null pointers even for functions that the
+                // implementation doesn't support, so the extensions must be checked first
+                let extensions = glx_extensions(&*gl_window);
+                let has_extension = |name| extensions.split(' ').any(|e| e == name);
+
+                // `GLX_EXT_swap_control`, which sets the interval of the current drawable
+                if has_extension("GLX_EXT_swap_control") {
+                    let get_current_display = gl_window.get_proc_address("glXGetCurrentDisplay");
+                    let get_current_drawable = gl_window.get_proc_address("glXGetCurrentDrawable");
+                    let swap_interval = gl_window.get_proc_address("glXSwapIntervalEXT");
+
+                    if !get_current_display.is_null() && !get_current_drawable.is_null() &&
+                       !swap_interval.is_null()
+                    {
+                        let get_current_display: extern "C" fn() -> *mut c_void =
+                            mem::transmute(get_current_display);
+                        let get_current_drawable: extern "C" fn() -> c_ulong =
+                            mem::transmute(get_current_drawable);
+                        let swap_interval: extern "C" fn(*mut c_void, c_ulong, c_int) =
+                            mem::transmute(swap_interval);
+
+                        // errors are reported asynchronously through Xlib
+                        swap_interval(get_current_display(), get_current_drawable(),
+                                      interval as c_int);
+                        return Ok(());
+                    }
+                }
+
+                // `GLX_MESA_swap_control`, which unlike `GLX_SGI_swap_control` accepts 0
+                if has_extension("GLX_MESA_swap_control") {
+                    let swap_interval = gl_window.get_proc_address("glXSwapIntervalMESA");
+                    if !swap_interval.is_null() {
+                        let swap_interval: extern "C" fn(u32) -> i32 =
+                            mem::transmute(swap_interval);
+                        if swap_interval(interval) == 0 {
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+        }
+
+        Err(SwapIntervalNotSupportedError)
+    }
+}
+
+/// Returns the list of GLX extensions supported by the screen of the current context, or an
+/// empty string if the list can't be obtained.
+#[cfg(any(target_os = "linux", target_os = "dragonfly", target_os = "freebsd",
+          target_os = "netbsd", target_os = "openbsd"))]
+unsafe fn glx_extensions(gl_window: &glutin::WindowedContext<Pc>) -> String {
+    use std::ffi::CStr;
+    use std::mem;
+    use std::os::raw::{c_char, c_int};
+
+    const GLX_SCREEN: c_int = 0x800C;
+
+    let get_current_display = gl_window.get_proc_address("glXGetCurrentDisplay");
+    let get_current_context = gl_window.get_proc_address("glXGetCurrentContext");
+    let query_context = gl_window.get_proc_address("glXQueryContext");
+    let query_extensions_string = gl_window.get_proc_address("glXQueryExtensionsString");
+
+    if get_current_display.is_null() || get_current_context.is_null() ||
+       query_context.is_null() || query_extensions_string.is_null()
+    {
+        return String::new();
+    }
+
+    let get_current_display: extern "C" fn() -> *mut c_void = mem::transmute(get_current_display);
+    let get_current_context: extern "C" fn() -> *mut c_void = mem::transmute(get_current_context);
+    let query_context: extern "C" fn(*mut c_void, *mut c_void, c_int, *mut c_int) -> c_int =
+        mem::transmute(query_context);
+    let query_extensions_string: extern "C" fn(*mut c_void, c_int) -> *const c_char =
+        mem::transmute(query_extensions_string);
+
+    let display = get_current_display();
+    if display.is_null() {
+        return String::new();
+    }
+
+    // the screen stays 0 if the query fails, which is the right value most of the time
+    let mut screen = 0;
+    query_context(display, get_current_context(), GLX_SCREEN, &mut screen);
+
+    let extensions = query_extensions_string(display, screen);
+    if extensions.is_null() {
+        return String::new();
+    }
+
+    CStr::from_ptr(extensions).to_string_lossy().into_owned()
+}
+
+/// Looks for a function in the EGL library. `name` must end with a nul character.
+///
+/// Returns a null pointer if the library or the function can't be found.
+#[cfg(unix)]
+unsafe fn egl_library_function(name: &str) -> *const c_void {
+    use std::os::raw::{c_char, c_int};
+    use std::ptr;
+
+    extern "C" {
+        fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void;
+        fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    }
+
+    const RTLD_LAZY: c_int = 1;
+
+    debug_assert!(name.ends_with('\0'));
+
+    for library in &["libEGL.so.1\0", "libEGL.so\0"] {
+        // the library has already been loaded by glutin, and the handle is never closed so that
+        // the function stays valid
+        let handle = dlopen(library.as_ptr() as *const c_char, RTLD_LAZY);
+        if handle.is_null() {
+            continue;
+        }
+
+        let function = dlsym(handle, name.as_ptr() as *const c_char);
+        if !function.is_null() {
+            return function;
+        }
+    }
+
+    ptr::null()
+}
+
+/// Looks for a function in the EGL library. `name` must end with a nul character.
+///
+/// Always returns a null pointer, as the EGL library is only looked for on Unix systems.
+#[cfg(not(unix))]
+unsafe fn egl_library_function(_: &str) -> *const c_void {
+    std::ptr::null()
 }
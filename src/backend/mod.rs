@@ -18,6 +18,7 @@ use std::os::raw::c_void;
 
 use crate::CapabilitiesSource;
 use crate::SwapBuffersError;
+use crate::SwapIntervalNotSupportedError;
 
 use crate::context::Capabilities;
 use crate::context::ExtensionsList;
@@ -28,6 +29,8 @@ pub use crate::context::ReleaseBehavior;
 
 #[cfg(feature = "glutin")]
 pub mod glutin;
+#[cfg(feature = "null_backend")]
+pub mod null;
 
 /// Trait for types that can be used as a backend for a glium context.
 ///
@@ -50,6 +53,15 @@ pub unsafe trait Backend {
 
     /// Makes the OpenGL context the current context in the current thread.
     unsafe fn make_current(&self);
+
+    /// Sets the minimum number of vertical refreshes between two buffer swaps. `0` disables
+    /// vsync, so that `swap_buffers` returns without waiting for the next refresh.
+    ///
+    /// Supposes that the context has been made current before this function is called. The
+    /// default implementation returns an error.
+    fn set_swap_interval(&self, _interval: u32) -> Result<(), SwapIntervalNotSupportedError> {
+        Err(SwapIntervalNotSupportedError)
+    }
 }
 
 unsafe impl<T> Backend for Rc<T> where T: Backend {
@@ -72,6 +84,10 @@ unsafe impl<T> Backend for Rc<T> where T: Backend {
     unsafe fn make_current(&self) {
         self.deref().make_current();
     }
+
+    fn set_swap_interval(&self, interval: u32) -> Result<(), SwapIntervalNotSupportedError> {
+        self.deref().set_swap_interval(interval)
+    }
 }
 
 /// Trait for types that provide a safe access for glium functions.
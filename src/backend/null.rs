@@ -0,0 +1,655 @@
+#![cfg(feature = "null_backend")]
+/*!
+
+Backend that doesn't need a GPU or a window.
+
+The `NullBackend` pretends to be an OpenGL implementation. glium loads its functions like any
+other, but instead of calling a driver they record their name and return canned values. This
+makes it possible to create a `Context` in unit tests or on a continuous integration server,
+and to check the code that depends on the version, the extensions or the limits of the
+implementation.
+
+```
+use glium::CapabilitiesSource;
+use glium::backend::null::NullBackend;
+
+let backend = NullBackend::with_version("OpenGL ES 2.0", "OpenGL ES GLSL ES 1.00",
+                                        &["GL_OES_depth_texture"]);
+let context = unsafe {
+    glium::backend::Context::new(backend.clone(), false,
+                                 glium::debug::DebugCallbackBehavior::Ignore).unwrap()
+};
+
+assert!(context.get_extensions().gl_oes_depth_texture);
+assert!(backend.get_calls().contains(&"glGetString"));
+```
+
+Only the functions that glium needs to create a context, to query its state, to clear the
+framebuffer, to compile programs, to create textures and to draw without vertex buffers are
+provided. Shaders always compile and programs always link, and the programs only have the
+uniforms that have been added with `add_uniform`. Calling any other function panics, as it would
+with a driver that doesn't support it.
+
+# Features
+
+Only available if the 'null_backend' feature is enabled.
+
+*/
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::cmp;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_void;
+use std::ptr;
+use std::rc::Rc;
+
+use crate::backend::Backend;
+use crate::gl;
+use crate::SwapBuffersError;
+
+thread_local!(static CURRENT: RefCell<Option<Rc<State>>> = RefCell::new(None));
+
+/// A backend whose OpenGL functions don't do anything.
+///
+/// Cloning the backend returns an object that shares the same fake implementation. Keep a clone
+/// around before passing the backend to `Context::new` in order to inspect the calls later.
+#[derive(Clone)]
+pub struct NullBackend {
+    state: Rc<State>,
+}
+
+/// The fake OpenGL implementation.
+struct State {
+    version: CString,
+    glsl_version: CString,
+    extensions: Vec<CString>,
+    extensions_list: CString,
+    integers: RefCell<HashMap<gl::types::GLenum, Vec<gl::types::GLint>>>,
+    errors: RefCell<VecDeque<gl::types::GLenum>>,
+    dimensions: RefCell<(u32, u32)>,
+    calls: RefCell<Vec<&'static str>>,
+    next_id: Cell<gl::types::GLuint>,
+    texture_formats: RefCell<Vec<gl::types::GLenum>>,
+    uniforms: RefCell<Vec<(CString, gl::types::GLenum)>>,
+}
+
+impl NullBackend {
+    /// Builds a backend that reports OpenGL 3.3 with the `GL_ARB_compatibility` extension.
+    #[inline]
+    pub fn new() -> NullBackend {
+        NullBackend::with_version("3.3.0", "3.30", &["GL_ARB_compatibility"])
+    }
+
+    /// Builds a backend that reports the given strings for `GL_VERSION`,
+    /// `GL_SHADING_LANGUAGE_VERSION` and the list of extensions.
+    ///
+    /// # Panic
+    ///
+    /// Panics if one of the strings contains a nul character.
+    pub fn with_version(version: &str, glsl_version: &str, extensions: &[&str]) -> NullBackend {
+        let mut integers = HashMap::new();
+        integers.insert(gl::NUM_EXTENSIONS, vec![extensions.len() as gl::types::GLint]);
+        integers.insert(gl::CONTEXT_PROFILE_MASK,
+                        vec![gl::CONTEXT_COMPATIBILITY_PROFILE_BIT as gl::types::GLint]);
+        integers.insert(gl::RED_BITS, vec![8]);
+        integers.insert(gl::GREEN_BITS, vec![8]);
+        integers.insert(gl::BLUE_BITS, vec![8]);
+        integers.insert(gl::ALPHA_BITS, vec![8]);
+        integers.insert(gl::DEPTH_BITS, vec![24]);
+        integers.insert(gl::STENCIL_BITS, vec![8]);
+        integers.insert(gl::MAX_COMBINED_TEXTURE_IMAGE_UNITS, vec![48]);
+        integers.insert(gl::MAX_TEXTURE_SIZE, vec![16384]);
+        integers.insert(gl::MAX_TEXTURE_BUFFER_SIZE, vec![65536]);
+        integers.insert(gl::MAX_VIEWPORT_DIMS, vec![16384, 16384]);
+        integers.insert(gl::MAX_DRAW_BUFFERS, vec![8]);
+        integers.insert(gl::MAX_COLOR_ATTACHMENTS, vec![8]);
+        integers.insert(gl::MAX_UNIFORM_BUFFER_BINDINGS, vec![36]);
+        integers.insert(gl::UNIFORM_BUFFER_OFFSET_ALIGNMENT, vec![256]);
+        integers.insert(gl::MAX_TRANSFORM_FEEDBACK_SEPARATE_ATTRIBS, vec![4]);
+
+        NullBackend {
+            state: Rc::new(State {
+                version: CString::new(version).unwrap(),
+                glsl_version: CString::new(glsl_version).unwrap(),
+                extensions: extensions.iter().map(|e| CString::new(*e).unwrap()).collect(),
+                extensions_list: CString::new(extensions.join(" ")).unwrap(),
+                integers: RefCell::new(integers),
+                errors: RefCell::new(VecDeque::new()),
+                dimensions: RefCell::new((800, 600)),
+                calls: RefCell::new(Vec::new()),
+                next_id: Cell::new(1),
+                texture_formats: RefCell::new(Vec::new()),
+                uniforms: RefCell::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Sets the values that `glGetIntegerv` returns for `pname`.
+    ///
+    /// The limits of the implementation are queried when the `Context` is created, therefore
+    /// this must be called before `Context::new` in order to change them.
+    ///
+    /// `glGetIntegerv` only writes as many values as `pname` is supposed to return, for example
+    /// two for `GL_MAX_VIEWPORT_DIMS`. Extra values are ignored and missing values are zero.
+    pub fn set_integer(&self, pname: u32, values: &[i32]) {
+        self.state.integers.borrow_mut().insert(pname, values.to_vec());
+    }
+
+    /// Adds an error to the list of errors returned by `glGetError`.
+    ///
+    /// The errors are returned in the order in which they have been added, then `glGetError`
+    /// returns `GL_NO_ERROR` again.
+    pub fn push_error(&self, error: u32) {
+        self.state.errors.borrow_mut().push_back(error);
+    }
+
+    /// Sets the value returned by `get_framebuffer_dimensions`. The default is `(800, 600)`.
+    pub fn set_framebuffer_dimensions(&self, dimensions: (u32, u32)) {
+        *self.state.dimensions.borrow_mut() = dimensions;
+    }
+
+    /// Adds a uniform of type `ty`, for example `GL_FLOAT_VEC4`, to the active uniforms of the
+    /// programs.
+    ///
+    /// Programs are introspected when they are linked, therefore this must be called before
+    /// creating the programs that use this uniform. The location of a uniform is its index in the
+    /// list.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the name contains a nul character.
+    pub fn add_uniform(&self, name: &str, ty: u32) {
+        self.state.uniforms.borrow_mut().push((CString::new(name).unwrap(), ty));
+    }
+
+    /// Returns the names of the OpenGL functions that have been called, in order.
+    ///
+    /// `swap_buffers` is recorded as `SwapBuffers`.
+    pub fn get_calls(&self) -> Vec<&'static str> {
+        self.state.calls.borrow().clone()
+    }
+
+    /// Clears the list of calls returned by `get_calls`.
+    pub fn clear_calls(&self) {
+        self.state.calls.borrow_mut().clear();
+    }
+
+    /// Returns the internal formats that have been passed to `glTexImage2D` and
+    /// `glTexStorage2D`, in order.
+    pub fn get_texture_formats(&self) -> Vec<u32> {
+        self.state.texture_formats.borrow().clone()
+    }
+}
+
+impl Default for NullBackend {
+    #[inline]
+    fn default() -> NullBackend {
+        NullBackend::new()
+    }
+}
+
+unsafe impl Backend for NullBackend {
+    fn swap_buffers(&self) -> Result<(), SwapBuffersError> {
+        self.state.calls.borrow_mut().push("SwapBuffers");
+        Ok(())
+    }
+
+    unsafe fn get_proc_address(&self, symbol: &str) -> *const c_void {
+        get_proc_address(symbol)
+    }
+
+    fn get_framebuffer_dimensions(&self) -> (u32, u32) {
+        *self.state.dimensions.borrow()
+    }
+
+    fn is_current(&self) -> bool {
+        CURRENT.with(|current| {
+            current.borrow().as_ref().map_or(false, |c| Rc::ptr_eq(c, &self.state))
+        })
+    }
+
+    unsafe fn make_current(&self) {
+        CURRENT.with(|current| *current.borrow_mut() = Some(self.state.clone()));
+    }
+}
+
+/// Returns the number of values that `glGetIntegerv` writes for `pname`.
+fn integer_values_count(pname: gl::types::GLenum) -> usize {
+    match pname {
+        gl::MAX_VIEWPORT_DIMS | gl::DEPTH_RANGE | gl::VIEWPORT_BOUNDS_RANGE => 2,
+        gl::VIEWPORT | gl::SCISSOR_BOX | gl::COLOR_WRITEMASK => 4,
+        _ => 1,
+    }
+}
+
+/// Calls `f` with the implementation that is current in this thread, after recording `name`.
+///
+/// Returns `None` without calling `f` if no implementation is current. The functions are called
+/// by glium through a C ABI, therefore they must return a harmless value instead of panicking.
+fn with_current<R, F>(name: &'static str, f: F) -> Option<R> where F: FnOnce(&State) -> R {
+    CURRENT.with(|current| {
+        let current = current.borrow();
+        let state = current.as_ref()?;
+        state.calls.borrow_mut().push(name);
+        Some(f(state))
+    })
+}
+
+macro_rules! functions {
+    ($($name:ident($($ty:ty),*);)+) => (
+        mod noop {
+            use crate::gl::types::*;
+
+            $(
+                #[allow(non_snake_case)]
+                pub extern "system" fn $name($(_: $ty),*) {
+                    super::with_current(concat!("gl", stringify!($name)), |_| ());
+                }
+            )+
+        }
+
+        fn get_proc_address(symbol: &str) -> *const c_void {
+            match symbol {
+                $(
+                    concat!("gl", stringify!($name)) => noop::$name as *const c_void,
+                )+
+                "glGetError" => get_error as *const c_void,
+                "glGetString" => get_string as *const c_void,
+                "glGetStringi" => get_stringi as *const c_void,
+                "glGetIntegerv" => get_integerv as *const c_void,
+                "glGetIntegeri_v" => get_integeri_v as *const c_void,
+                "glGetBooleanv" => get_booleanv as *const c_void,
+                "glGetFloatv" => get_floatv as *const c_void,
+                "glGetFramebufferAttachmentParameteriv" => {
+                    get_framebuffer_attachment_parameteriv as *const c_void
+                },
+                "glGetInternalformativ" => get_internalformativ as *const c_void,
+                "glGenTextures" => gen_textures as *const c_void,
+                "glGenVertexArrays" => gen_vertex_arrays as *const c_void,
+                "glCreateShader" => create_shader as *const c_void,
+                "glCreateShaderObjectARB" => create_shader_object_arb as *const c_void,
+                "glGetShaderiv" => get_shaderiv as *const c_void,
+                "glGetObjectParameterivARB" => get_object_parameteriv_arb as *const c_void,
+                "glCreateProgram" => create_program as *const c_void,
+                "glCreateProgramObjectARB" => create_program_object_arb as *const c_void,
+                "glGetProgramiv" => get_programiv as *const c_void,
+                "glGetActiveUniform" => get_active_uniform as *const c_void,
+                "glGetUniformLocation" => get_uniform_location as *const c_void,
+                "glTexImage2D" => tex_image_2d as *const c_void,
+                "glTexStorage2D" => tex_storage_2d as *const c_void,
+                _ => ptr::null(),
+            }
+        }
+    );
+}
+
+functions! {
+    Enable(GLenum);
+    Disable(GLenum);
+    Flush();
+    Finish();
+    Hint(GLenum, GLenum);
+    Viewport(GLint, GLint, GLsizei, GLsizei);
+    Scissor(GLint, GLint, GLsizei, GLsizei);
+    BindFramebuffer(GLenum, GLuint);
+    ColorMask(GLboolean, GLboolean, GLboolean, GLboolean);
+    ColorMaski(GLuint, GLboolean, GLboolean, GLboolean, GLboolean);
+    DepthMask(GLboolean);
+    StencilMask(GLuint);
+    BlendFuncSeparate(GLenum, GLenum, GLenum, GLenum);
+    ClearColor(GLfloat, GLfloat, GLfloat, GLfloat);
+    ClearDepth(GLdouble);
+    ClearDepthf(GLfloat);
+    ClearStencil(GLint);
+    Clear(GLbitfield);
+    ShaderSource(GLuint, GLsizei, *const *const GLchar, *const GLint);
+    ShaderSourceARB(GLhandleARB, GLsizei, *const *const GLcharARB, *const GLint);
+    CompileShader(GLuint);
+    CompileShaderARB(GLhandleARB);
+    DeleteShader(GLuint);
+    DeleteObjectARB(GLhandleARB);
+    AttachShader(GLuint, GLuint);
+    AttachObjectARB(GLhandleARB, GLhandleARB);
+    LinkProgram(GLuint);
+    LinkProgramARB(GLhandleARB);
+    UseProgram(GLuint);
+    Uniform1f(GLint, GLfloat);
+    Uniform4fv(GLint, GLsizei, *const GLfloat);
+    UseProgramObjectARB(GLhandleARB);
+    DeleteProgram(GLuint);
+    ActiveTexture(GLenum);
+    BindTexture(GLenum, GLuint);
+    DeleteTextures(GLsizei, *const GLuint);
+    TexParameteri(GLenum, GLenum, GLint);
+    PixelStorei(GLenum, GLint);
+    BindBuffer(GLenum, GLuint);
+    GenerateMipmap(GLenum);
+    BindVertexArray(GLuint);
+    DeleteVertexArrays(GLsizei, *const GLuint);
+    DrawArrays(GLenum, GLint, GLsizei);
+}
+
+extern "system" fn get_error() -> gl::types::GLenum {
+    with_current("glGetError", |state| {
+        state.errors.borrow_mut().pop_front().unwrap_or(gl::NO_ERROR)
+    }).unwrap_or(gl::NO_ERROR)
+}
+
+extern "system" fn get_string(name: gl::types::GLenum) -> *const gl::types::GLubyte {
+    with_current("glGetString", |state| {
+        let value = match name {
+            gl::VERSION => &state.version,
+            gl::SHADING_LANGUAGE_VERSION => &state.glsl_version,
+            gl::EXTENSIONS => &state.extensions_list,
+            gl::VENDOR | gl::RENDERER => return b"glium\0".as_ptr(),
+            _ => return ptr::null(),
+        };
+
+        value.as_ptr() as *const gl::types::GLubyte
+    }).unwrap_or(ptr::null())
+}
+
+extern "system" fn get_stringi(name: gl::types::GLenum, index: gl::types::GLuint)
+                               -> *const gl::types::GLubyte
+{
+    with_current("glGetStringi", |state| {
+        match (name, state.extensions.get(index as usize)) {
+            (gl::EXTENSIONS, Some(ext)) => ext.as_ptr() as *const gl::types::GLubyte,
+            _ => ptr::null(),
+        }
+    }).unwrap_or(ptr::null())
+}
+
+unsafe extern "system" fn get_integerv(pname: gl::types::GLenum, data: *mut gl::types::GLint) {
+    with_current("glGetIntegerv", |state| {
+        let integers = state.integers.borrow();
+        let values = integers.get(&pname).map(|v| &v[..]).unwrap_or(&[]);
+
+        for i in 0 .. integer_values_count(pname) {
+            *data.add(i) = values.get(i).cloned().unwrap_or(0);
+        }
+    });
+}
+
+unsafe extern "system" fn get_integeri_v(_: gl::types::GLenum, _: gl::types::GLuint,
+                                         data: *mut gl::types::GLint)
+{
+    with_current("glGetIntegeri_v", |_| *data = 0);
+}
+
+unsafe extern "system" fn get_booleanv(pname: gl::types::GLenum, data: *mut gl::types::GLboolean) {
+    with_current("glGetBooleanv", |_| {
+        *data = if pname == gl::SHADER_COMPILER { gl::TRUE } else { gl::FALSE };
+    });
+}
+
+unsafe extern "system" fn get_floatv(_: gl::types::GLenum, data: *mut gl::types::GLfloat) {
+    with_current("glGetFloatv", |_| *data = 0.0);
+}
+
+unsafe extern "system" fn get_framebuffer_attachment_parameteriv(_: gl::types::GLenum,
+                                                                 _: gl::types::GLenum,
+                                                                 _: gl::types::GLenum,
+                                                                 data: *mut gl::types::GLint)
+{
+    with_current("glGetFramebufferAttachmentParameteriv", |_| *data = 0);
+}
+
+unsafe extern "system" fn get_internalformativ(_: gl::types::GLenum, _: gl::types::GLenum,
+                                               _: gl::types::GLenum, buf_size: gl::types::GLsizei,
+                                               data: *mut gl::types::GLint)
+{
+    with_current("glGetInternalformativ", |_| {
+        if buf_size >= 1 {
+            *data = 0;
+        }
+    });
+}
+
+/// Returns a new object name.
+fn gen_name(state: &State) -> gl::types::GLuint {
+    let id = state.next_id.get();
+    state.next_id.set(id + 1);
+    id
+}
+
+/// Writes `n` new object names to `names`, after recording `function`.
+unsafe fn gen_names(function: &'static str, n: gl::types::GLsizei, names: *mut gl::types::GLuint) {
+    with_current(function, |state| {
+        for i in 0 .. n as usize {
+            *names.add(i) = gen_name(state);
+        }
+    });
+}
+
+unsafe extern "system" fn gen_textures(n: gl::types::GLsizei, textures: *mut gl::types::GLuint) {
+    gen_names("glGenTextures", n, textures)
+}
+
+unsafe extern "system" fn gen_vertex_arrays(n: gl::types::GLsizei,
+                                            arrays: *mut gl::types::GLuint)
+{
+    gen_names("glGenVertexArrays", n, arrays)
+}
+
+extern "system" fn create_shader(_: gl::types::GLenum) -> gl::types::GLuint {
+    with_current("glCreateShader", gen_name).unwrap_or(0)
+}
+
+extern "system" fn create_shader_object_arb(_: gl::types::GLenum) -> gl::types::GLhandleARB {
+    with_current("glCreateShaderObjectARB", |state| gen_name(state) as usize)
+        .unwrap_or(0) as gl::types::GLhandleARB
+}
+
+extern "system" fn create_program() -> gl::types::GLuint {
+    with_current("glCreateProgram", gen_name).unwrap_or(0)
+}
+
+extern "system" fn create_program_object_arb() -> gl::types::GLhandleARB {
+    with_current("glCreateProgramObjectARB", |state| gen_name(state) as usize)
+        .unwrap_or(0) as gl::types::GLhandleARB
+}
+
+/// Returns the value of a parameter of a shader or of a program. Shaders always compile and
+/// programs always link, without any log, and programs don't have any active variable except
+/// for the uniforms of the implementation.
+fn object_parameter(state: &State, pname: gl::types::GLenum) -> gl::types::GLint {
+    match pname {
+        gl::COMPILE_STATUS | gl::LINK_STATUS | gl::COMPLETION_STATUS_ARB => 1,
+        gl::ACTIVE_UNIFORMS => state.uniforms.borrow().len() as gl::types::GLint,
+        gl::ACTIVE_UNIFORM_MAX_LENGTH => {
+            state.uniforms.borrow().iter()
+                 .map(|&(ref name, _)| name.as_bytes_with_nul().len() as gl::types::GLint)
+                 .max().unwrap_or(0)
+        },
+        _ => 0,
+    }
+}
+
+unsafe extern "system" fn get_shaderiv(_: gl::types::GLuint, pname: gl::types::GLenum,
+                                       data: *mut gl::types::GLint)
+{
+    with_current("glGetShaderiv", |state| *data = object_parameter(state, pname));
+}
+
+unsafe extern "system" fn get_programiv(_: gl::types::GLuint, pname: gl::types::GLenum,
+                                        data: *mut gl::types::GLint)
+{
+    with_current("glGetProgramiv", |state| *data = object_parameter(state, pname));
+}
+
+unsafe extern "system" fn get_object_parameteriv_arb(_: gl::types::GLhandleARB,
+                                                     pname: gl::types::GLenum,
+                                                     data: *mut gl::types::GLint)
+{
+    let pname = match pname {
+        gl::OBJECT_COMPILE_STATUS_ARB => gl::COMPILE_STATUS,
+        gl::OBJECT_LINK_STATUS_ARB => gl::LINK_STATUS,
+        pname => pname,
+    };
+
+    with_current("glGetObjectParameterivARB", |state| *data = object_parameter(state, pname));
+}
+
+unsafe extern "system" fn get_active_uniform(_: gl::types::GLuint, index: gl::types::GLuint,
+                                             buf_size: gl::types::GLsizei,
+                                             length: *mut gl::types::GLsizei,
+                                             size: *mut gl::types::GLint,
+                                             ty: *mut gl::types::GLenum,
+                                             name: *mut gl::types::GLchar)
+{
+    with_current("glGetActiveUniform", |state| {
+        let uniforms = state.uniforms.borrow();
+        let &(ref uniform_name, uniform_ty) = match uniforms.get(index as usize) {
+            Some(uniform) => uniform,
+            None => return,
+        };
+
+        let bytes = uniform_name.as_bytes();
+        let written = cmp::min(bytes.len(), cmp::max(buf_size, 1) as usize - 1);
+        ptr::copy_nonoverlapping(bytes.as_ptr() as *const gl::types::GLchar, name, written);
+        *name.add(written) = 0;
+
+        if !length.is_null() {
+            *length = written as gl::types::GLsizei;
+        }
+        *size = 1;
+        *ty = uniform_ty;
+    });
+}
+
+unsafe extern "system" fn get_uniform_location(_: gl::types::GLuint,
+                                               name: *const gl::types::GLchar)
+                                               -> gl::types::GLint
+{
+    with_current("glGetUniformLocation", |state| {
+        let name = CStr::from_ptr(name);
+        state.uniforms.borrow().iter()
+             .position(|&(ref uniform_name, _)| uniform_name.as_c_str() == name)
+             .map_or(-1, |location| location as gl::types::GLint)
+    }).unwrap_or(-1)
+}
+
+extern "system" fn tex_image_2d(_: gl::types::GLenum, _: gl::types::GLint,
+                                internal_format: gl::types::GLint, _: gl::types::GLsizei,
+                                _: gl::types::GLsizei, _: gl::types::GLint, _: gl::types::GLenum,
+                                _: gl::types::GLenum, _: *const c_void)
+{
+    with_current("glTexImage2D", |state| {
+        state.texture_formats.borrow_mut().push(internal_format as gl::types::GLenum)
+    });
+}
+
+extern "system" fn tex_storage_2d(_: gl::types::GLenum, _: gl::types::GLsizei,
+                                  internal_format: gl::types::GLenum, _: gl::types::GLsizei,
+                                  _: gl::types::GLsizei)
+{
+    with_current("glTexStorage2D", |state| {
+        state.texture_formats.borrow_mut().push(internal_format)
+    });
+}
+
+/// Builds a `Context` that uses a clone of `backend`, for the unit tests of glium.
+#[cfg(test)]
+pub(crate) fn build_context(backend: NullBackend) -> (NullBackend, Rc<crate::backend::Context>) {
+    let context = unsafe {
+        crate::backend::Context::new(backend.clone(), false,
+                                     crate::debug::DebugCallbackBehavior::Ignore).unwrap()
+    };
+
+    (backend, context)
+}
+
+/// Builds a program that doesn't use any vertex attribute, for the unit tests of glium.
+#[cfg(test)]
+pub(crate) fn build_program(context: &Rc<crate::backend::Context>) -> crate::Program {
+    crate::Program::from_source(context,
+        "
+            #version 100
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 100
+            void main() {
+                gl_FragColor = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_context, NullBackend};
+    use crate::backend::{Backend, Context};
+    use crate::debug::DebugCallbackBehavior;
+    use crate::gl;
+    use crate::version::{Api, Version};
+    use crate::{CapabilitiesSource, Frame, Surface};
+
+    #[test]
+    fn reports_version_and_extensions() {
+        let (_, context) = build_context(NullBackend::with_version("OpenGL ES 3.0",
+                                                                   "OpenGL ES GLSL ES 3.00",
+                                                                   &["GL_EXT_texture_rg"]));
+
+        assert_eq!(context.get_opengl_version(), &Version(Api::GlEs, 3, 0));
+        assert_eq!(context.get_glsl_version(), Some(Version(Api::GlEs, 3, 0)));
+        assert!(context.get_extensions().gl_ext_texture_rg);
+        assert!(!context.get_extensions().gl_arb_compatibility);
+    }
+
+    #[test]
+    fn incompatible_version() {
+        let backend = NullBackend::with_version("1.1.0", "", &[]);
+        let result = unsafe { Context::new(backend, false, DebugCallbackBehavior::Ignore) };
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn canned_integers() {
+        let backend = NullBackend::new();
+        backend.set_integer(gl::MAX_TEXTURE_SIZE, &[2048]);
+        let (_, context) = build_context(backend);
+
+        assert_eq!(context.get_capabilities().max_texture_size, 2048);
+    }
+
+    #[test]
+    fn integers_count() {
+        let backend = NullBackend::new();
+        backend.set_integer(gl::MAX_TEXTURE_SIZE, &[2048, 1, 2, 3]);
+        backend.set_integer(gl::MAX_VIEWPORT_DIMS, &[1024]);
+        let (_, context) = build_context(backend);
+
+        assert_eq!(context.get_capabilities().max_texture_size, 2048);
+        assert_eq!(context.get_capabilities().max_viewport_dims, (1024, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn queued_error() {
+        let (backend, context) = build_context(NullBackend::new());
+
+        backend.push_error(gl::INVALID_OPERATION);
+        context.assert_no_error(None);
+    }
+
+    #[test]
+    fn records_calls() {
+        let (backend, context) = build_context(NullBackend::new());
+        assert!(backend.is_current());
+
+        backend.clear_calls();
+
+        let mut frame = Frame::new(context.clone(), (800, 600));
+        frame.clear_color(0.0, 0.0, 0.0, 1.0);
+        frame.finish().unwrap();
+
+        let calls = backend.get_calls();
+        assert!(calls.contains(&"glClearColor"));
+        assert_eq!(calls.iter().filter(|&&c| c == "glClear").count(), 1);
+        assert_eq!(calls.last(), Some(&"SwapBuffers"));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::backend::Facade;
 use crate::context::CommandContext;
+use crate::context::deletion::{self, DeferredObject};
 use crate::context::Context;
 use crate::version::Version;
 use crate::CapabilitiesSource;
@@ -112,6 +113,8 @@ impl Alloc {
             create_buffer(&mut ctxt, size, Some(data), ty, mode)
         }?;
 
+        ctxt.allocated_memory.set(ctxt.allocated_memory.get() + size);
+
         Ok(Alloc {
             context: facade.get_context().clone(),
             id,
@@ -136,6 +139,8 @@ impl Alloc {
             create_buffer::<()>(&mut ctxt, size, None, ty, mode)
         }?;
 
+        ctxt.allocated_memory.set(ctxt.allocated_memory.get() + size);
+
         Ok(Alloc {
             context: facade.get_context().clone(),
             id,
@@ -437,7 +442,7 @@ impl Alloc {
         assert!(offset_bytes + mem::size_of_val(data) <= self.size);
 
         if self.persistent_mapping.is_some() {
-            let mapping = Mapping { mapping: self.map_shared(offset_bytes .. offset_bytes + mem::size_of_val(data), false, true) };
+            let mapping = Mapping { mapping: self.map_shared(offset_bytes .. offset_bytes + mem::size_of_val(data), false, true, false) };
             ptr::copy_nonoverlapping(data.to_void_ptr() as *const u8, <D as Content>::to_void_ptr(&mapping) as *mut u8, mem::size_of_val(data));
 
         } else if self.immutable {
@@ -584,7 +589,11 @@ impl Alloc {
     /// If you pass `false` for `read`, you **must not** read the returned buffer. If you pass
     /// `false` for `write`, you **must not** write the returned buffer.
     ///
-    unsafe fn map_shared<D: ?Sized>(&self, bytes_range: Range<usize>, read: bool, write: bool)
+    /// If you pass `true` for `explicit_flush`, only the ranges passed to `flush_range` are
+    /// written back to the buffer.
+    ///
+    unsafe fn map_shared<D: ?Sized>(&self, bytes_range: Range<usize>, read: bool, write: bool,
+                                    explicit_flush: bool)
                                     -> MappingImpl<'_, D> where D: Content
     {
         if let Some(existing_mapping) = self.persistent_mapping {
@@ -600,7 +609,7 @@ impl Alloc {
                 buffer: self,
                 offset_bytes: bytes_range.start,
                 data,
-                needs_flushing: write,
+                needs_flushing: write && !explicit_flush,
             }
 
         } else {
@@ -644,7 +653,8 @@ impl Alloc {
                 original_buffer_offset: bytes_range.start,
                 temporary_buffer,
                 temporary_buffer_data: data,
-                needs_flushing: write,
+                needs_flushing: write && !explicit_flush,
+                flushed_ranges: Vec::new(),
             }
         }
     }
@@ -672,11 +682,15 @@ impl Alloc {
     /// If you pass `false` for `read`, you **must not** read the returned buffer. If you pass
     /// `false` for `write`, you **must not** write the returned buffer.
     ///
-    unsafe fn map_impl<D: ?Sized>(&mut self, bytes_range: Range<usize>, read: bool, write: bool)
+    /// If you pass `true` for `explicit_flush`, only the ranges passed to `flush_range` are
+    /// written back to the buffer.
+    ///
+    unsafe fn map_impl<D: ?Sized>(&mut self, bytes_range: Range<usize>, read: bool, write: bool,
+                                  explicit_flush: bool)
                                   -> MappingImpl<'_, D> where D: Content
     {
         if self.persistent_mapping.is_some() || self.immutable {
-            self.map_shared(bytes_range, read, write)
+            self.map_shared(bytes_range, read, write, explicit_flush)
 
         } else {
             let data = {
@@ -705,7 +719,7 @@ impl Alloc {
             MappingImpl::RegularMapping {
                 buffer: self,
                 data,
-                needs_flushing: write,
+                needs_flushing: write && !explicit_flush,
             }
         }
     }
@@ -726,7 +740,7 @@ impl Alloc {
                                  -> Mapping<'_, D> where D: Content
     {
         Mapping {
-            mapping: self.map_impl(bytes_range, true, true)
+            mapping: self.map_impl(bytes_range, true, true, false)
         }
     }
 
@@ -746,7 +760,7 @@ impl Alloc {
                                       -> ReadMapping<'_, D> where D: Content
     {
         ReadMapping {
-            mapping: self.map_impl(bytes_range, true, false)
+            mapping: self.map_impl(bytes_range, true, false, false)
         }
     }
 
@@ -766,7 +780,28 @@ impl Alloc {
                                        -> WriteMapping<'_, D> where D: Content
     {
         WriteMapping {
-            mapping: self.map_impl(bytes_range, false, true)
+            mapping: self.map_impl(bytes_range, false, true, false)
+        }
+    }
+
+    /// Returns a write-only mapping in memory of the content of the buffer, where only the
+    /// ranges passed to `WriteMapping::flush_range` are written back to the buffer.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the `bytes_range` is not aligned to a mappable slice.
+    ///
+    /// # Unsafety
+    ///
+    /// If the buffer uses persistent mapping, the caller of this function must handle
+    /// synchronization.
+    ///
+    #[inline]
+    pub unsafe fn map_write_explicit_flush<D: ?Sized>(&mut self, bytes_range: Range<usize>)
+                                                      -> WriteMapping<'_, D> where D: Content
+    {
+        WriteMapping {
+            mapping: self.map_impl(bytes_range, false, true, true)
         }
     }
 
@@ -788,7 +823,7 @@ impl Alloc {
         let size_to_read = range.end - range.start;
 
         if self.persistent_mapping.is_some() {
-            let mapping = ReadMapping { mapping: self.map_shared(range, true, false) };
+            let mapping = ReadMapping { mapping: self.map_shared(range, true, false, false) };
             <D as Content>::read(size_to_read, |output| {
                 ptr::copy_nonoverlapping(<D as Content>::to_void_ptr(&mapping) as *const u8, output as *mut D as *mut u8, size_to_read);
                 Ok(())
@@ -822,6 +857,20 @@ impl Alloc {
                                                 size_to_read as gl::types::GLsizeiptr,
                                                 output as *mut _ as *mut _);
 
+                } else if ctxt.version >= &Version(Api::GlEs, 3, 0) {
+                    // OpenGL ES doesn't have `glGetBufferSubData`, so we map the buffer instead
+                    if size_to_read != 0 {
+                        let mapping = map_buffer(&mut ctxt, self.id, self.ty, range, true, false)
+                                                .unwrap();
+                        if mapping.is_null() {
+                            return Err(ReadError::NotSupported);
+                        }
+
+                        ptr::copy_nonoverlapping(mapping as *const u8,
+                                                 output as *mut D as *mut u8, size_to_read);
+                        unmap_buffer(&mut ctxt, self.id, self.ty);
+                    }
+
                 } else if ctxt.version >= &Version(Api::GlEs, 1, 0) {
                     return Err(ReadError::NotSupported);
 
@@ -874,7 +923,11 @@ impl Drop for Alloc {
             self.assert_unmapped(&mut ctxt);
             self.assert_not_transform_feedback(&mut ctxt);
             VertexAttributesSystem::purge_buffer(&mut ctxt, self.id);
-            destroy_buffer(&mut ctxt, self.id);
+            ctxt.allocated_memory.set(ctxt.allocated_memory.get() - self.size);
+
+            if !deletion::defer(&mut ctxt, DeferredObject::Buffer(self.id)) {
+                destroy_buffer(&mut ctxt, self.id);
+            }
         }
     }
 }
@@ -903,6 +956,8 @@ enum MappingImpl<'b, D: ?Sized> {
         temporary_buffer: gl::types::GLuint,
         temporary_buffer_data: *mut D,
         needs_flushing: bool,
+        // ranges of the temporary buffer that must be copied to the original buffer, in bytes
+        flushed_ranges: Vec<Range<usize>>,
     },
 
     RegularMapping {
@@ -929,7 +984,7 @@ impl<'a, D: ?Sized> Drop for MappingImpl<'a, D> {
 
             &mut MappingImpl::TemporaryBuffer { original_buffer, original_buffer_offset,
                                                 temporary_buffer, temporary_buffer_data,
-                                                needs_flushing } =>
+                                                needs_flushing, ref flushed_ranges } =>
             {
                 let mut ctxt = original_buffer.context.make_current();
                 original_buffer.barrier_for_buffer_update(&mut ctxt);
@@ -943,6 +998,12 @@ impl<'a, D: ?Sized> Drop for MappingImpl<'a, D> {
                     if needs_flushing {
                         copy_buffer(&mut ctxt, temporary_buffer, 0, original_buffer.id,
                                     original_buffer_offset, mem::size_of_val(&*temporary_buffer_data)).unwrap();
+                    } else {
+                        for range in flushed_ranges {
+                            copy_buffer(&mut ctxt, temporary_buffer, range.start, original_buffer.id,
+                                        original_buffer_offset + range.start,
+                                        range.end - range.start).unwrap();
+                        }
                     }
 
                     destroy_buffer(&mut ctxt, temporary_buffer);
@@ -1092,6 +1153,51 @@ impl<'b, D> WriteMapping<'b, [D]> where [D]: Content, D: Copy {
         let slice = self.get_slice();
         slice[index] = value;
     }
+
+    /// Writes back the `len` elements starting at `offset` to the buffer.
+    ///
+    /// If the mapping was obtained with `map_write_explicit_flush`, only the ranges passed to
+    /// this function are written back to the buffer and the rest of the buffer keeps its
+    /// previous content. Otherwise the whole mapping is written back when it is destroyed
+    /// anyway, and calling this function only makes the range visible earlier.
+    ///
+    /// # Panic
+    ///
+    /// Panics if out of range.
+    ///
+    pub fn flush_range(&mut self, offset: usize, len: usize) {
+        assert!(offset.checked_add(len).map_or(false, |end| end <= self.len()),
+                "Range out of bounds");
+
+        let elem_size = mem::size_of::<D>();
+        let range = offset * elem_size .. (offset + len) * elem_size;
+        if range.start == range.end {
+            return;
+        }
+
+        match self.mapping {
+            MappingImpl::PersistentMapping { buffer, offset_bytes, .. } => {
+                let mut ctxt = buffer.context.make_current();
+                unsafe {
+                    flush_range(&mut ctxt, buffer.id, buffer.ty,
+                                offset_bytes + range.start .. offset_bytes + range.end);
+                }
+            },
+
+            MappingImpl::TemporaryBuffer { original_buffer, temporary_buffer,
+                                           ref mut flushed_ranges, .. } =>
+            {
+                let mut ctxt = original_buffer.context.make_current();
+                unsafe { flush_range(&mut ctxt, temporary_buffer, original_buffer.ty, range.clone()); }
+                flushed_ranges.push(range);
+            },
+
+            MappingImpl::RegularMapping { ref buffer, .. } => {
+                let mut ctxt = buffer.context.make_current();
+                unsafe { flush_range(&mut ctxt, buffer.id, buffer.ty, range); }
+            },
+        }
+    }
 }
 
 /// Returns true if reading from a buffer is supported by the backend.
@@ -1617,8 +1723,8 @@ unsafe fn copy_buffer(ctxt: &mut CommandContext<'_>, source: gl::types::GLuint,
     Ok(())
 }
 
-/// Destroys a buffer.
-unsafe fn destroy_buffer(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint) {
+/// Destroys a buffer and removes it from the state of the context.
+pub unsafe fn destroy_buffer(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint) {
     // FIXME: uncomment this and move it from Buffer's destructor
     //self.context.vertex_array_objects.purge_buffer(&mut ctxt, id);
 
@@ -48,6 +48,7 @@ pub use self::view::{Buffer, BufferAny, BufferMutSlice};
 pub use self::view::{BufferSlice, BufferAnySlice};
 pub use self::alloc::{Mapping, WriteMapping, ReadMapping, ReadError, CopyError};
 pub use self::alloc::{is_buffer_read_supported};
+pub(crate) use self::alloc::destroy_buffer;
 pub use self::fences::Inserter;
 
 /// DEPRECATED. Only here for backwards compatibility.
@@ -94,6 +94,15 @@ impl<T: ?Sized> Buffer<T> where T: Content {
         self.alloc.as_ref().unwrap().get_size()
     }
 
+    /// Returns an estimate of the number of bytes of video memory used by this buffer.
+    ///
+    /// This is the size of the buffer, and doesn't include the padding or the metadata that the
+    /// driver may add.
+    #[inline]
+    pub fn memory_size(&self) -> usize {
+        self.get_size()
+    }
+
     /// Returns true if this buffer uses persistent mapping.
     #[inline]
     pub fn is_persistent(&self) -> bool {
@@ -141,6 +150,9 @@ impl<T: ?Sized> Buffer<T> where T: Content {
     }
 
     /// Reads the content of the buffer.
+    ///
+    /// This waits until the GPU has finished writing to the buffer, for example with transform
+    /// feedback or from a compute shader. Returns `ReadError::NotSupported` on OpenGL ES 2.
     pub fn read(&self) -> Result<T::Owned, ReadError> {
         self.fence.as_ref().unwrap().wait(&mut self.alloc.as_ref().unwrap().get_context().make_current(),
                                           0 .. self.get_size());
@@ -203,6 +215,24 @@ impl<T: ?Sized> Buffer<T> where T: Content {
         unsafe { self.alloc.as_mut().unwrap().map_write(0 .. size) }
     }
 
+    /// Maps the buffer in memory for writing only, where only the ranges passed to
+    /// `WriteMapping::flush_range` are written back to the buffer.
+    ///
+    /// This is more efficient than `map_write` when only a few sparse parts of the buffer are
+    /// modified. The content of the parts that are not flushed is left untouched.
+    ///
+    /// # Implementation
+    ///
+    /// Same as `map_write`, except that `GL_MAP_FLUSH_EXPLICIT_BIT` is used and
+    /// `glFlushMappedBufferRange` is only called for the flushed ranges.
+    ///
+    pub fn map_write_explicit_flush(&mut self) -> WriteMapping<'_, T> {
+        self.fence.as_ref().unwrap().wait(&mut self.alloc.as_ref().unwrap().get_context().make_current(),
+                                          0 .. self.get_size());
+        let size = self.get_size();
+        unsafe { self.alloc.as_mut().unwrap().map_write_explicit_flush(0 .. size) }
+    }
+
     /// Copies the content of the buffer to another buffer.
     ///
     /// # Panic
@@ -782,12 +812,12 @@ impl<'a, T: ?Sized> BufferExt for BufferSlice<'a, T> where T: Content {
 
     #[inline]
     fn prepare_and_bind_for_uniform(&self, ctxt: &mut CommandContext<'_>, index: gl::types::GLuint) {
-        self.alloc.prepare_and_bind_for_uniform(ctxt, index, 0 .. self.alloc.get_size());
+        self.alloc.prepare_and_bind_for_uniform(ctxt, index, self.bytes_start .. self.bytes_end);
     }
 
     #[inline]
     fn prepare_and_bind_for_shared_storage(&self, ctxt: &mut CommandContext<'_>, index: gl::types::GLuint) {
-        self.alloc.prepare_and_bind_for_shared_storage(ctxt, index, 0 .. self.alloc.get_size());
+        self.alloc.prepare_and_bind_for_shared_storage(ctxt, index, self.bytes_start .. self.bytes_end);
     }
 
     #[inline]
@@ -869,6 +899,17 @@ impl<'a, T: ?Sized> BufferMutSlice<'a, T> where T: Content + 'a {
         unsafe { self.alloc.map_write(self.bytes_start .. self.bytes_end) }
     }
 
+    /// Maps the buffer in memory for writing only, where only the ranges passed to
+    /// `WriteMapping::flush_range` are written back to the buffer.
+    ///
+    /// See `Buffer::map_write_explicit_flush`.
+    #[inline]
+    pub fn map_write_explicit_flush(self) -> WriteMapping<'a, T> {
+        self.fence.wait(&mut self.alloc.get_context().make_current(),
+                        self.bytes_start .. self.bytes_end);
+        unsafe { self.alloc.map_write_explicit_flush(self.bytes_start .. self.bytes_end) }
+    }
+
     /// Uploads some data in this buffer.
     ///
     /// # Implementation
@@ -1146,8 +1187,9 @@ impl BufferAny {
     /// For example, trying to read some `(u8, u8, u8, u8)`s from a buffer of 7 bytes will panic.
     ///
     #[inline]
-    pub unsafe fn read<T>(&self) -> Result<T::Owned, ReadError> where T: Content {
-        // TODO: add check
+    pub unsafe fn read<T: ?Sized>(&self) -> Result<T::Owned, ReadError> where T: Content {
+        assert!(<T as Content>::is_size_suitable(self.get_size()),
+                "The size of the buffer is not a multiple of the size of the data");
         self.fence.wait(&mut self.alloc.get_context().make_current(), 0 .. self.get_size());
         self.alloc.read::<T>(0 .. self.get_size())
     }
@@ -1312,6 +1354,25 @@ impl<'a> BufferAnySlice<'a> {
         self.alloc.invalidate(self.bytes_start, self.get_size());
     }
 
+    /// Builds a slice that contains `size` bytes starting at `offset` bytes from the start of
+    /// this slice. Returns `None` if out of range.
+    #[inline]
+    pub(crate) fn slice_bytes(&self, offset: usize, size: usize) -> Option<BufferAnySlice<'a>> {
+        let bytes_start = self.bytes_start.checked_add(offset)?;
+        let bytes_end = bytes_start.checked_add(size)?;
+        if bytes_end > self.bytes_end {
+            return None;
+        }
+
+        Some(BufferAnySlice {
+            alloc: self.alloc,
+            bytes_start,
+            bytes_end,
+            elements_size: size,
+            fence: self.fence,
+        })
+    }
+
     /// Returns the context corresponding to this buffer.
     #[inline]
     pub fn get_context(&self) -> &Rc<Context> {
@@ -1400,12 +1461,12 @@ impl<'a> BufferExt for BufferAnySlice<'a> {
 
     #[inline]
     fn prepare_and_bind_for_uniform(&self, ctxt: &mut CommandContext<'_>, index: gl::types::GLuint) {
-        self.alloc.prepare_and_bind_for_uniform(ctxt, index, 0 .. self.alloc.get_size());
+        self.alloc.prepare_and_bind_for_uniform(ctxt, index, self.bytes_start .. self.bytes_end);
     }
 
     #[inline]
     fn prepare_and_bind_for_shared_storage(&self, ctxt: &mut CommandContext<'_>, index: gl::types::GLuint) {
-        self.alloc.prepare_and_bind_for_shared_storage(ctxt, index, 0 .. self.alloc.get_size());
+        self.alloc.prepare_and_bind_for_shared_storage(ctxt, index, self.bytes_start .. self.bytes_end);
     }
 
     #[inline]
@@ -0,0 +1,280 @@
+/*!
+Lists of draw commands that are recorded once and replayed at each frame.
+
+Many scenes draw the same objects in the same order at each frame, with only some uniforms
+changing. Instead of calling `draw` for each object at each frame, you can record the draw
+commands once in a `CommandList` and replay them with `Surface::execute`.
+
+```no_run
+# #[macro_use] extern crate glium;
+# fn main() {
+use glium::Surface;
+use glium::command_list::CommandList;
+# let display: glium::Display = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+# let vertex_buffer: glium::VertexBuffer<u8> = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+# let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+# let program: glium::Program = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+
+let mut list = CommandList::new(&display);
+let command = list.draw(&vertex_buffer, &indices, &program, uniform! { time: 0.0f32 },
+                        &Default::default()).unwrap();
+
+let mut time = 0.0f32;
+loop {
+    time += 0.01;
+    list.set_uniforms(command, uniform! { time: time });
+
+    let mut target = display.draw();
+    target.clear_color(0.0, 0.0, 0.0, 1.0);
+    target.execute(&list).unwrap();
+    target.finish().unwrap();
+}
+# }
+```
+
+The checks that only depend on the context, the program, the primitives and the draw
+parameters are done when a command is recorded, and `draw` returns the error immediately.
+Executing the list on one of glium's surfaces then makes the context current only once for
+the whole list, and submits the commands one after the other. The OpenGL state is cached as
+usual, which means that the state that doesn't change between two commands isn't sent again.
+
+The commands borrow the buffers, programs and textures they use, which must therefore outlive
+the list. The uniforms are owned by the list and can be replaced with `set_uniforms`.
+
+*/
+use std::fmt;
+use std::ptr;
+use std::rc::Rc;
+
+use crate::backend::Facade;
+use crate::context::Context;
+use crate::ContextExt;
+use crate::fbo::ValidatedAttachments;
+use crate::index::IndicesSource;
+use crate::ops;
+use crate::uniforms::{Uniforms, UniformValue};
+use crate::vertex::{MultiVerticesSource, VerticesSource};
+use crate::{DrawError, DrawParameters, Program, Surface};
+
+/// A list of draw commands that can be executed on a surface.
+///
+/// See the documentation of the `command_list` module.
+pub struct CommandList<'a> {
+    context: Rc<Context>,
+    commands: Vec<DrawCommand<'a>>,
+}
+
+/// Identifies a command of a `CommandList`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DrawCommandId(usize);
+
+/// A draw command that has been validated against the context.
+struct DrawCommand<'a> {
+    vertex_buffers: Vec<VerticesSource<'a>>,
+    indices: IndicesSource<'a>,
+    program: &'a Program,
+    uniforms: Box<dyn DynUniforms + 'a>,
+    draw_parameters: DrawParameters<'a>,
+    vertices_per_patch: Option<u16>,
+}
+
+impl<'a> CommandList<'a> {
+    /// Builds a new empty list. The commands are validated against the context of `facade`.
+    #[inline]
+    pub fn new<F: ?Sized>(facade: &F) -> CommandList<'a> where F: Facade {
+        CommandList {
+            context: facade.get_context().clone(),
+            commands: Vec::new(),
+        }
+    }
+
+    /// Adds a draw command at the end of the list.
+    ///
+    /// The parameters are the same as `Surface::draw`, except that the uniforms are moved in the
+    /// list. Returns an error if the command can't be drawn with this context, for example if the
+    /// primitives don't match the input of the geometry shader.
+    pub fn draw<V, I, U>(&mut self, vertex_buffers: V, indices: I, program: &'a Program,
+                         uniforms: U, draw_parameters: &DrawParameters<'a>)
+                         -> Result<DrawCommandId, DrawError>
+                         where V: MultiVerticesSource<'a>, I: Into<IndicesSource<'a>>,
+                               U: Uniforms + 'a
+    {
+        let indices = indices.into();
+        let vertices_per_patch = ops::validate_draw(&self.context, &indices, program,
+                                                    draw_parameters)?;
+
+        self.commands.push(DrawCommand {
+            vertex_buffers: vertex_buffers.iter().collect(),
+            indices,
+            program,
+            uniforms: Box::new(uniforms),
+            draw_parameters: draw_parameters.clone(),
+            vertices_per_patch,
+        });
+
+        Ok(DrawCommandId(self.commands.len() - 1))
+    }
+
+    /// Replaces the uniforms of a command.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the command doesn't belong to this list, or if the list has been cleared since.
+    #[inline]
+    pub fn set_uniforms<U>(&mut self, command: DrawCommandId, uniforms: U) where U: Uniforms + 'a {
+        self.commands[command.0].uniforms = Box::new(uniforms);
+    }
+
+    /// Returns the number of commands in the list.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Returns true if the list doesn't contain any command.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Removes all the commands of the list.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+
+    /// Executes the commands one by one with `Surface::draw`.
+    pub(crate) fn draw_each<S: ?Sized>(&self, surface: &mut S) -> Result<(), DrawError>
+                                       where S: Surface
+    {
+        for command in self.commands.iter() {
+            surface.draw(command.vertex_buffers.clone(), command.indices.clone(), command.program,
+                         &UniformsRef(&*command.uniforms), &command.draw_parameters)?;
+        }
+
+        Ok(())
+    }
+
+    /// Executes all the commands while the context is current.
+    ///
+    /// The commands are checked against the surface before anything is drawn, so that an error
+    /// doesn't leave the list half-executed.
+    pub(crate) fn execute(&self, context: &Context,
+                          framebuffer: Option<&ValidatedAttachments<'_>>,
+                          dimensions: (u32, u32), has_depth_buffer: bool)
+                          -> Result<(), DrawError>
+    {
+        assert!(ptr::eq(&*self.context, context),
+                "The command list was recorded with another context");
+
+        for command in self.commands.iter() {
+            let depth = &command.draw_parameters.depth;
+            if !has_depth_buffer && (depth.test.requires_depth_buffer() || depth.write) {
+                return Err(DrawError::NoDepthBuffer);
+            }
+
+            ops::validate_framebuffer(framebuffer, &command.draw_parameters)?;
+        }
+
+        let mut ctxt = context.make_current();
+
+        // drawing with a lost context has no effect, so we report it instead
+        if ctxt.state.lost_context {
+            return Err(DrawError::ContextLost);
+        }
+
+        for command in self.commands.iter() {
+            ops::draw_with_context(&mut ctxt, context, framebuffer,
+                                   command.vertex_buffers.as_slice().iter().cloned(),
+                                   command.indices.clone(), command.program,
+                                   &UniformsRef(&*command.uniforms), &command.draw_parameters,
+                                   dimensions, command.vertices_per_patch)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Debug for CommandList<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "CommandList {{ {} commands }}", self.commands.len())
+    }
+}
+
+/// Object-safe version of `Uniforms`, so that the uniforms of different commands can be stored
+/// in the same list.
+trait DynUniforms {
+    fn visit<'s>(&'s self, output: &mut dyn FnMut(&str, UniformValue<'s>));
+}
+
+impl<U> DynUniforms for U where U: Uniforms {
+    #[inline]
+    fn visit<'s>(&'s self, output: &mut dyn FnMut(&str, UniformValue<'s>)) {
+        self.visit_values(|name, value| output(name, value))
+    }
+}
+
+/// Implements `Uniforms` for the uniforms of a command.
+struct UniformsRef<'r, 'u>(&'r (dyn DynUniforms + 'u));
+
+impl<'r, 'u> Uniforms for UniformsRef<'r, 'u> {
+    #[inline]
+    fn visit_values<'b, F: FnMut(&str, UniformValue<'b>)>(&'b self, mut output: F) {
+        self.0.visit(&mut output)
+    }
+}
+
+#[cfg(all(test, feature = "null_backend"))]
+mod tests {
+    use crate::backend::null::{build_context, build_program, NullBackend};
+    use crate::command_list::CommandList;
+    use crate::index::{NoIndices, PrimitiveType};
+    use crate::uniforms::EmptyUniforms;
+    use crate::vertex::EmptyVertexAttributes;
+    use crate::{DrawError, Frame, Surface};
+
+    #[test]
+    fn execute() {
+        let (backend, context) = build_context(NullBackend::new());
+        let program = build_program(&context);
+
+        let mut list = CommandList::new(&context);
+        let first = list.draw(EmptyVertexAttributes { len: 3 },
+                              NoIndices(PrimitiveType::TrianglesList), &program, EmptyUniforms,
+                              &Default::default()).unwrap();
+        list.draw(EmptyVertexAttributes { len: 6 }, NoIndices(PrimitiveType::TrianglesList),
+                  &program, EmptyUniforms, &Default::default()).unwrap();
+        list.set_uniforms(first, EmptyUniforms);
+        assert_eq!(list.len(), 2);
+
+        let mut frame = Frame::new(context.clone(), (800, 600));
+        frame.execute(&list).unwrap();
+
+        // the state set by the first command is still valid for the second one and for the
+        // next execution
+        backend.clear_calls();
+        frame.execute(&list).unwrap();
+        assert_eq!(backend.get_calls(), vec!["glDrawArrays", "glDrawArrays"]);
+
+        frame.finish().unwrap();
+    }
+
+    #[test]
+    fn validated_when_recorded() {
+        let (_backend, context) = build_context(NullBackend::new());
+        let program = build_program(&context);
+
+        let mut list = CommandList::new(&context);
+        let result = list.draw(EmptyVertexAttributes { len: 3 },
+                               NoIndices(PrimitiveType::Patches { vertices_per_patch: 3 }),
+                               &program, EmptyUniforms, &Default::default());
+
+        match result {
+            Err(DrawError::TessellationNotSupported) |
+            Err(DrawError::PatchesWithoutTessellation) => (),
+            _ => panic!(),
+        }
+
+        assert!(list.is_empty());
+    }
+}
@@ -16,7 +16,7 @@ use crate::CapabilitiesSource;
 use crate::image_format::TextureFormat;
 
 /// Describes the OpenGL context profile.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Profile {
     /// The context uses only future-compatible functions and definitions.
     Core,
@@ -24,6 +24,27 @@ pub enum Profile {
     Compatibility
 }
 
+/// Describes the format of the default framebuffer, as chosen by the windowing system.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct BackbufferFormat {
+    /// Number of bits of the red component.
+    pub red_bits: u16,
+    /// Number of bits of the green component.
+    pub green_bits: u16,
+    /// Number of bits of the blue component.
+    pub blue_bits: u16,
+    /// Number of bits of the alpha component.
+    pub alpha_bits: u16,
+    /// Number of bits of the depth buffer. `None` if there is no depth buffer.
+    pub depth_bits: Option<u16>,
+    /// Number of bits of the stencil buffer. `None` if there is no stencil buffer.
+    pub stencil_bits: Option<u16>,
+    /// Number of samples per pixel. `None` if the framebuffer isn't multisampled.
+    pub samples: Option<u16>,
+    /// True if the framebuffer is in sRGB.
+    pub srgb: bool,
+}
+
 /// Represents the capabilities of the context.
 ///
 /// Contrary to the state, these values never change.
@@ -41,6 +62,10 @@ pub struct Capabilities {
     /// The company responsible for this GL implementation.
     pub vendor: String,
 
+    /// Returns a version or release number of the shading language. Vendor-specific
+    /// information may follow the version number. Empty if the backend doesn't support GLSL.
+    pub glsl_version: String,
+
     /// The name of the renderer. This name is typically specific to a particular
     /// configuration of a hardware platform.
     pub renderer: String,
@@ -61,6 +86,10 @@ pub struct Capabilities {
     /// True if out-of-bound access on the GPU side can't result in crashes.
     pub robustness: bool,
 
+    /// The context has been created with `GL_CONTEXT_FLAG_NO_ERROR_BIT`, which means that the
+    /// driver doesn't check for errors and that any misuse results in undefined behavior.
+    pub no_error: bool,
+
     /// True if it is possible for the OpenGL context to be lost.
     pub can_lose_context: bool,
 
@@ -79,6 +108,14 @@ pub struct Capabilities {
     /// Number of bits in the default framebuffer's stencil buffer
     pub stencil_bits: Option<u16>,
 
+    /// Number of bits of the red, green, blue and alpha components of the default
+    /// framebuffer's color buffer.
+    pub color_bits: (u16, u16, u16, u16),
+
+    /// Number of samples per pixel of the default framebuffer. `None` if it isn't
+    /// multisampled.
+    pub samples: Option<u16>,
+
     /// Informations about formats when used to create textures.
     pub internal_formats_textures: HashMap<TextureFormat, FormatInfos, BuildHasherDefault<FnvHasher>>,
 
@@ -104,9 +141,17 @@ pub struct Capabilities {
     /// Maximum width and height of `glViewport`.
     pub max_viewport_dims: (gl::types::GLint, gl::types::GLint),
 
+    /// Maximum number of viewports that can be used at once. `None` if viewport arrays are
+    /// not supported.
+    pub max_viewports: Option<gl::types::GLint>,
+
     /// Maximum number of elements that can be passed with `glDrawBuffers`.
     pub max_draw_buffers: gl::types::GLint,
 
+    /// Maximum number of clip distances that can be enabled at once. Always 0 on OpenGL ES
+    /// without `GL_EXT_clip_cull_distance`.
+    pub max_clip_distances: gl::types::GLint,
+
     /// Maximum number of vertices per patch. `None` if tessellation is not supported.
     pub max_patch_vertices: Option<gl::types::GLint>,
 
@@ -122,6 +167,14 @@ pub struct Capabilities {
     /// Number of available buffer bind points for `GL_UNIFORM_BUFFER`.
     pub max_indexed_uniform_buffer: gl::types::GLint,
 
+    /// Alignment in bytes of the offset of a range of buffer bound to `GL_UNIFORM_BUFFER`.
+    /// `0` if uniform buffers aren't supported.
+    pub uniform_buffer_offset_alignment: gl::types::GLint,
+
+    /// Alignment in bytes of the offset of a range of buffer bound to
+    /// `GL_SHADER_STORAGE_BUFFER`. `0` if shader storage buffers aren't supported.
+    pub shader_storage_buffer_offset_alignment: gl::types::GLint,
+
     /// Number of work groups for compute shaders.
     pub max_compute_work_group_count: (gl::types::GLint, gl::types::GLint, gl::types::GLint),
 
@@ -148,6 +201,31 @@ pub struct FormatInfos {
     pub multisamples: Option<Vec<gl::types::GLint>>,
 }
 
+/// Kind of object whose capabilities are queried with `Context::internal_format_info`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum InternalFormatTarget {
+    /// A regular two-dimensional texture.
+    Texture2d,
+    /// A multisample two-dimensional texture.
+    Texture2dMultisample,
+    /// A render buffer.
+    RenderBuffer,
+}
+
+/// What the implementation can do with an internal format for a given target.
+///
+/// Returned by `Context::internal_format_info`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InternalFormatInfo {
+    /// True if the format can be used at all with this target.
+    pub supported: bool,
+    /// True if an object of this format can be attached to a framebuffer.
+    pub renderable: bool,
+    /// Numbers of samples that can be used for multisampling, in decreasing order. Empty if
+    /// multisampling isn't supported or if the list is unknown.
+    pub samples: Vec<u32>,
+}
+
 /// Defines what happens when you change the current context.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ReleaseBehavior {
@@ -171,14 +249,16 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
                                -> Capabilities
 {
     // GL_CONTEXT_FLAGS are only available from GL 3.0 onwards
-    let (debug, forward_compatible) = if version >= &Version(Api::Gl, 3, 0) {
+    let (debug, forward_compatible, no_error) = if version >= &Version(Api::Gl, 3, 0) {
         let mut val = 0;
         gl.GetIntegerv(gl::CONTEXT_FLAGS, &mut val);
         let val = val as gl::types::GLenum;
         ((val & gl::CONTEXT_FLAG_DEBUG_BIT) != 0,
-         (val & gl::CONTEXT_FLAG_FORWARD_COMPATIBLE_BIT) != 0)
+         (val & gl::CONTEXT_FLAG_FORWARD_COMPATIBLE_BIT) != 0,
+         (version >= &Version(Api::Gl, 4, 6) || extensions.gl_khr_no_error) &&
+            (val & gl::CONTEXT_FLAG_NO_ERROR_BIT_KHR) != 0)
     } else {
-        (false, false)
+        (false, false, false)
     };
 
     // getting the value of `GL_RENDERER`
@@ -208,6 +288,19 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
                                         .expect("glGetString(GL_VENDOR) returned a non-UTF8 string")
         },
 
+        glsl_version: {
+            if version >= &Version(Api::Gl, 2, 0) || version >= &Version(Api::GlEs, 2, 0) {
+                let s = gl.GetString(gl::SHADING_LANGUAGE_VERSION);
+                if s.is_null() {
+                    String::new()
+                } else {
+                    String::from_utf8_lossy(CStr::from_ptr(s as *const _).to_bytes()).into_owned()
+                }
+            } else {
+                String::new()
+            }
+        },
+
         profile: {
             if version >= &Version(Api::Gl, 3, 2) {
                 let mut val = 0;
@@ -229,6 +322,8 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
 
         forward_compatible,
 
+        no_error,
+
         robustness: if version >= &Version(Api::Gl, 4, 5) || version >= &Version(Api::GlEs, 3, 2) ||
                        (version >= &Version(Api::Gl, 3, 0) && extensions.gl_arb_robustness)
         {
@@ -383,6 +478,50 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        color_bits: {
+            // see the comment about `depth_bits`
+            if (version >= &Version(Api::Gl, 3, 0) && !extensions.gl_arb_compatibility) ||
+               version >= &Version(Api::GlEs, 3, 0)
+            {
+                let attachment = if version >= &Version(Api::GlEs, 3, 0) {
+                    gl::BACK
+                } else {
+                    gl::FRONT_LEFT
+                };
+
+                let mut values = [0; 4];
+                let names = [gl::FRAMEBUFFER_ATTACHMENT_RED_SIZE,
+                             gl::FRAMEBUFFER_ATTACHMENT_GREEN_SIZE,
+                             gl::FRAMEBUFFER_ATTACHMENT_BLUE_SIZE,
+                             gl::FRAMEBUFFER_ATTACHMENT_ALPHA_SIZE];
+                for (value, &name) in values.iter_mut().zip(names.iter()) {
+                    gl.GetFramebufferAttachmentParameteriv(gl::FRAMEBUFFER, attachment, name,
+                                                           value);
+                }
+                (values[0] as u16, values[1] as u16, values[2] as u16, values[3] as u16)
+
+            } else {
+                let mut values = [0; 4];
+                gl.GetIntegerv(gl::RED_BITS, &mut values[0]);
+                gl.GetIntegerv(gl::GREEN_BITS, &mut values[1]);
+                gl.GetIntegerv(gl::BLUE_BITS, &mut values[2]);
+                gl.GetIntegerv(gl::ALPHA_BITS, &mut values[3]);
+                (values[0] as u16, values[1] as u16, values[2] as u16, values[3] as u16)
+            }
+        },
+
+        samples: {
+            let mut value = 0;
+            if version >= &Version(Api::Gl, 1, 3) || version >= &Version(Api::GlEs, 2, 0) {
+                gl.GetIntegerv(gl::SAMPLES, &mut value);
+            }
+
+            match value {
+                0 | 1 => None,
+                v => Some(v as u16),
+            }
+        },
+
         internal_formats_textures: get_internal_formats(gl, version, extensions, false),
         internal_formats_renderbuffers: get_internal_formats(gl, version, extensions, true),
 
@@ -438,6 +577,19 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             (val[0], val[1])
         },
 
+        max_viewports: if version >= &Version(Api::Gl, 4, 1) ||
+            extensions.gl_arb_viewport_array
+        {
+            Some({
+                let mut val = 0;
+                gl.GetIntegerv(gl::MAX_VIEWPORTS, &mut val);
+                val
+            })
+
+        } else {
+            None
+        },
+
         max_draw_buffers: {
             if version >= &Version(Api::Gl, 2, 0) ||
                 version >= &Version(Api::GlEs, 3, 0) ||
@@ -451,6 +603,14 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        max_clip_distances: if version.0 == Api::Gl || extensions.gl_ext_clip_cull_distance {
+            let mut val = 0;
+            gl.GetIntegerv(gl::MAX_CLIP_DISTANCES, &mut val);
+            val
+        } else {
+            0
+        },
+
         max_patch_vertices: if version >= &Version(Api::Gl, 4, 0) ||
             extensions.gl_arb_tessellation_shader
         {
@@ -506,6 +666,30 @@ pub unsafe fn get_capabilities(gl: &gl::Gl, version: &Version, extensions: &Exte
             }
         },
 
+        uniform_buffer_offset_alignment: {
+            if version >= &Version(Api::Gl, 3, 1) || version >= &Version(Api::GlEs, 3, 0) ||
+               extensions.gl_arb_uniform_buffer_object
+            {
+                let mut val = 0;
+                gl.GetIntegerv(gl::UNIFORM_BUFFER_OFFSET_ALIGNMENT, &mut val);
+                val
+            } else {
+                0
+            }
+        },
+
+        shader_storage_buffer_offset_alignment: {
+            if version >= &Version(Api::Gl, 4, 3) || version >= &Version(Api::GlEs, 3, 1) ||
+               extensions.gl_arb_shader_storage_buffer_object
+            {
+                let mut val = 0;
+                gl.GetIntegerv(gl::SHADER_STORAGE_BUFFER_OFFSET_ALIGNMENT, &mut val);
+                val
+            } else {
+                0
+            }
+        },
+
         max_compute_work_group_count: if version >= &Version(Api::Gl, 4, 3) ||
                                          version >= &Version(Api::GlEs, 3, 1) ||
                                          extensions.gl_arb_compute_shader
@@ -753,3 +937,30 @@ pub fn get_internal_format(gl: &gl::Gl, version: &Version, extensions: &Extensio
         }
     }
 }
+
+#[cfg(all(test, feature = "null_backend"))]
+mod tests {
+    use crate::backend::null::{build_context, NullBackend};
+    use crate::gl;
+    use crate::CapabilitiesSource;
+
+    #[test]
+    fn max_clip_distances() {
+        let backend = NullBackend::new();
+        backend.set_integer(gl::MAX_CLIP_DISTANCES, &[8]);
+        let (_, context) = build_context(backend);
+        assert_eq!(context.get_capabilities().max_clip_distances, 8);
+
+        // the query is invalid on OpenGL ES without `GL_EXT_clip_cull_distance`
+        let backend = NullBackend::with_version("OpenGL ES 3.0", "OpenGL ES GLSL ES 3.00", &[]);
+        backend.set_integer(gl::MAX_CLIP_DISTANCES, &[8]);
+        let (_, context) = build_context(backend);
+        assert_eq!(context.get_capabilities().max_clip_distances, 0);
+
+        let backend = NullBackend::with_version("OpenGL ES 3.0", "OpenGL ES GLSL ES 3.00",
+                                                &["GL_EXT_clip_cull_distance"]);
+        backend.set_integer(gl::MAX_CLIP_DISTANCES, &[8]);
+        let (_, context) = build_context(backend);
+        assert_eq!(context.get_capabilities().max_clip_distances, 8);
+    }
+}
@@ -0,0 +1,92 @@
+//! Deletion of OpenGL objects once the GPU has finished using them.
+
+use std::mem;
+
+use crate::gl;
+use crate::buffer;
+use crate::context::CommandContext;
+use crate::sync::{self, LinearSyncFence};
+use crate::texture;
+
+/// An object whose deletion has been deferred.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DeferredObject {
+    /// A buffer. Destroyed with `glDeleteBuffers`.
+    Buffer(gl::types::GLuint),
+    /// A texture. Destroyed with `glDeleteTextures`.
+    Texture(gl::types::GLuint),
+}
+
+/// List of objects that have been dropped but not yet deleted.
+#[derive(Default)]
+pub struct DeferredDeletions {
+    /// True if objects that are dropped must be added to the list instead of being deleted.
+    pub enabled: bool,
+
+    /// The objects, with a fence that is signaled when the GPU no longer uses them. The fences
+    /// are in the order in which they have been created.
+    pending: Vec<(LinearSyncFence, DeferredObject)>,
+}
+
+impl DeferredDeletions {
+    /// Returns the number of objects waiting to be deleted.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+/// Adds an object to the list of deferred deletions, if deferred deletion is enabled.
+///
+/// Returns false if the object must be deleted immediately instead, either because deferred
+/// deletion is disabled or because fences aren't supported.
+pub unsafe fn defer(ctxt: &mut CommandContext<'_>, object: DeferredObject) -> bool {
+    if !ctxt.deferred_deletions.enabled {
+        return false;
+    }
+
+    // the fence is signaled when all the commands issued until now, including the last ones
+    // that use the object, have been executed
+    let fence = match sync::new_linear_sync_fence(ctxt) {
+        Ok(fence) => fence,
+        Err(_) => return false,
+    };
+
+    ctxt.deferred_deletions.pending.push((fence, object));
+    true
+}
+
+/// Deletes the objects that the GPU has finished using.
+///
+/// If `all` is true, deletes all the objects without waiting.
+pub unsafe fn process(ctxt: &mut CommandContext<'_>, all: bool) {
+    if ctxt.deferred_deletions.pending.is_empty() {
+        return;
+    }
+
+    let mut pending = mem::replace(&mut ctxt.deferred_deletions.pending, Vec::new());
+
+    // since fences are signaled in order, we stop at the first one that isn't signaled
+    let mut num_signaled = 0;
+    if all {
+        num_signaled = pending.len();
+    } else {
+        for &(ref fence, _) in pending.iter() {
+            if !sync::is_linear_sync_fence_signaled(ctxt, fence) {
+                break;
+            }
+            num_signaled += 1;
+        }
+    }
+
+    ctxt.deferred_deletions.pending = pending.split_off(num_signaled);
+
+    for (fence, object) in pending {
+        sync::destroy_linear_sync_fence(ctxt, fence);
+
+        match object {
+            DeferredObject::Buffer(id) => buffer::destroy_buffer(ctxt, id),
+            DeferredObject::Texture(id) => texture::destroy_texture(ctxt, id),
+        }
+    }
+}
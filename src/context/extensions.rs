@@ -54,6 +54,7 @@ extensions! {
     "GL_APPLE_framebuffer_multisample" => gl_apple_framebuffer_multisample,
     "GL_APPLE_sync" => gl_apple_sync,
     "GL_APPLE_vertex_array_object" => gl_apple_vertex_array_object,
+    "GL_ARB_base_instance" => gl_arb_base_instance,
     "GL_ARB_bindless_texture" => gl_arb_bindless_texture,
     "GL_ARB_buffer_storage" => gl_arb_buffer_storage,
     "GL_ARB_compute_shader" => gl_arb_compute_shader,
@@ -64,6 +65,7 @@ extensions! {
     "GL_ARB_direct_state_access" => gl_arb_direct_state_access,
     "GL_ARB_draw_buffers" => gl_arb_draw_buffers,
     "GL_ARB_draw_elements_base_vertex" => gl_arb_draw_elements_base_vertex,
+    "GL_ARB_draw_indirect" => gl_arb_draw_indirect,
     "GL_ARB_compatibility" => gl_arb_compatibility,
     "GL_ARB_ES2_compatibility" => gl_arb_es2_compatibility,
     "GL_ARB_ES3_compatibility" => gl_arb_es3_compatibility,
@@ -75,13 +77,17 @@ extensions! {
     "GL_ARB_framebuffer_sRGB" => gl_arb_framebuffer_srgb,
     "GL_ARB_geometry_shader4" => gl_arb_geometry_shader4,
     "GL_ARB_get_program_binary" => gl_arb_get_programy_binary,
+    "GL_ARB_gpu_shader5" => gl_arb_gpu_shader5,
     "GL_ARB_gpu_shader_fp64" => gl_arb_gpu_shader_fp64,
     "GL_ARB_gpu_shader_int64" => gl_arb_gpu_shader_int64,
     "GL_ARB_instanced_arrays" => gl_arb_instanced_arrays,
     "GL_ARB_internalformat_query" => gl_arb_internalformat_query,
+    "GL_ARB_internalformat_query2" => gl_arb_internalformat_query2,
     "GL_ARB_invalidate_subdata" => gl_arb_invalidate_subdata,
     "GL_ARB_occlusion_query" => gl_arb_occlusion_query,
     "GL_ARB_occlusion_query2" => gl_arb_occlusion_query2,
+    "GL_ARB_parallel_shader_compile" => gl_arb_parallel_shader_compile,
+    "GL_ARB_pipeline_statistics_query" => gl_arb_pipeline_statistics_query,
     "GL_ARB_pixel_buffer_object" => gl_arb_pixel_buffer_object,
     "GL_ARB_program_interface_query" => gl_arb_program_interface_query,
     "GL_ARB_query_buffer_object" => gl_arb_query_buffer_object,
@@ -98,6 +104,7 @@ extensions! {
     "GL_ARB_shader_storage_buffer_object" => gl_arb_shader_storage_buffer_object,
     "GL_ARB_shader_subroutine" => gl_arb_shader_subroutine,
     "GL_ARB_sync" => gl_arb_sync,
+    "GL_ARB_stencil_texturing" => gl_arb_stencil_texturing,
     "GL_ARB_tessellation_shader" => gl_arb_tessellation_shader,
     "GL_ARB_texture_buffer_object" => gl_arb_texture_buffer_object,
     "GL_ARB_texture_buffer_object_rgb32" => gl_arb_texture_buffer_object_rgb32,
@@ -105,6 +112,7 @@ extensions! {
     "GL_ARB_texture_cube_map" => gl_arb_texture_cube_map,
     "GL_ARB_texture_cube_map_array" => gl_arb_texture_cube_map_array,
     "GL_ARB_texture_float" => gl_arb_texture_float,
+    "GL_ARB_texture_gather" => gl_arb_texture_gather,
     "GL_ARB_texture_multisample" => gl_arb_texture_multisample,
     "GL_ARB_texture_non_power_of_two" => gl_arb_texture_non_power_of_two,
     "GL_ARB_texture_rg" => gl_arb_texture_rg,
@@ -112,21 +120,27 @@ extensions! {
     "GL_ARB_texture_stencil8" => gl_arb_texture_stencil8,
     "GL_ARB_texture_storage" => gl_arb_texture_storage,
     "GL_ARB_timer_query" => gl_arb_timer_query,
+    "GL_ARB_transform_feedback2" => gl_arb_transform_feedback2,
     "GL_ARB_transform_feedback3" => gl_arb_transform_feedback3,
+    "GL_ARB_transform_feedback_instanced" => gl_arb_transform_feedback_instanced,
     "GL_ARB_uniform_buffer_object" => gl_arb_uniform_buffer_object,
     "GL_ARB_vertex_array_object" => gl_arb_vertex_array_object,
+    "GL_ARB_vertex_attrib_64bit" => gl_arb_vertex_attrib_64bit,
     "GL_ARB_vertex_buffer_object" => gl_arb_vertex_buffer_object,
     "GL_ARB_vertex_half_float" => gl_arb_vertex_half_float,
     "GL_ARB_vertex_shader" => gl_arb_vertex_shader,
     "GL_ARB_vertex_type_10f_11f_11f_rev" => gl_arb_vertex_type_10f_11f_11f_rev,
     "GL_ARB_vertex_type_2_10_10_10_rev" => gl_arb_vertex_type_2_10_10_10_rev,
+    "GL_ARB_viewport_array" => gl_arb_viewport_array,
     "GL_ARM_rgba8" => gl_arm_rgba8,
     "GL_ATI_meminfo" => gl_ati_meminfo,
     "GL_ATI_draw_buffers" => gl_ati_draw_buffers,
     "GL_ATI_texture_float" => gl_ati_texture_float,
     "GL_EXT_blend_minmax" => gl_ext_blend_minmax,
     "GL_EXT_buffer_storage" => gl_ext_buffer_storage,
+    "GL_EXT_clip_cull_distance" => gl_ext_clip_cull_distance,
     "GL_EXT_debug_marker" => gl_ext_debug_marker,
+    "GL_EXT_depth_clamp" => gl_ext_depth_clamp,
     "GL_EXT_direct_state_access" => gl_ext_direct_state_access,
     "GL_EXT_disjoint_timer_query" => gl_ext_disjoint_timer_query,
     "GL_EXT_framebuffer_blit" => gl_ext_framebuffer_blit,
@@ -147,6 +161,7 @@ extensions! {
     "GL_EXT_sRGB_write_control" => gl_ext_srgb_write_control,
     "GL_EXT_texture3D" => gl_ext_texture3d,
     "GL_EXT_texture_array" => gl_ext_texture_array,
+    "GL_EXT_texture_border_clamp" => gl_ext_texture_border_clamp,
     "GL_EXT_texture_buffer" => gl_ext_texture_buffer,
     "GL_EXT_texture_buffer_object" => gl_ext_texture_buffer_object,
     "GL_EXT_texture_compression_s3tc" => gl_ext_texture_compression_s3tc,
@@ -154,15 +169,21 @@ extensions! {
     "GL_EXT_texture_cube_map_array" => gl_ext_texture_cube_map_array,
     "GL_EXT_texture_filter_anisotropic" => gl_ext_texture_filter_anisotropic,
     "GL_EXT_texture_integer" => gl_ext_texture_integer,
+    "GL_EXT_texture_rg" => gl_ext_texture_rg,
     "GL_EXT_texture_shared_exponent" => gl_ext_texture_shared_exponent,
     "GL_EXT_texture_snorm" => gl_ext_texture_snorm,
     "GL_EXT_texture_sRGB" => gl_ext_texture_srgb,
+    "GL_EXT_texture_sRGB_decode" => gl_ext_texture_srgb_decode,
     "GL_EXT_transform_feedback" => gl_ext_transform_feedback,
+    "GL_EXT_unpack_subimage" => gl_ext_unpack_subimage,
     "GL_GREMEDY_string_marker" => gl_gremedy_string_marker,
     "GL_KHR_debug" => gl_khr_debug,
     "GL_KHR_context_flush_control" => gl_khr_context_flush_control,
+    "GL_KHR_no_error" => gl_khr_no_error,
+    "GL_KHR_parallel_shader_compile" => gl_khr_parallel_shader_compile,
     "GL_KHR_robustness" => gl_khr_robustness,
     "GL_KHR_robust_buffer_access_behavior" => gl_khr_robust_buffer_access_behavior,
+    "GL_KHR_texture_compression_astc_ldr" => gl_khr_texture_compression_astc_ldr,
     "GL_NV_fbo_color_attachments" => gl_nv_fbo_color_attachments,
     "GL_NV_conditional_render" => gl_nv_conditional_render,
     "GL_NV_copy_buffer" => gl_nv_copy_buffer,
@@ -188,12 +209,15 @@ extensions! {
     "GL_OES_packed_depth_stencil" => gl_oes_packed_depth_stencil,
     "GL_OES_primitive_bounding_box" => gl_oes_primitive_bounding_box,
     "GL_OES_rgb8_rgba8" => gl_oes_rgb8_rgba8,
+    "GL_OES_standard_derivatives" => gl_oes_standard_derivatives,
     "GL_OES_stencil1" => gl_oes_stencil1,
     "GL_OES_stencil4" => gl_oes_stencil4,
     "GL_OES_tessellation_shader" => gl_oes_tessellation_shader,
     "GL_OES_texture_3D" => gl_oes_texture_3d,
+    "GL_OES_texture_border_clamp" => gl_oes_texture_border_clamp,
     "GL_OES_texture_buffer" => gl_oes_texture_buffer,
     "GL_OES_texture_cube_map_array" => gl_oes_texture_cube_map_array,
+    "GL_OES_texture_npot" => gl_oes_texture_npot,
     "GL_OES_texture_stencil8" => gl_oes_texture_stencil8,
     "GL_OES_texture_storage_multisample_2d_array" => gl_oes_texture_storage_multisample_2d_array,
     "GL_OES_vertex_array_object" => gl_oes_vertex_array_object,
@@ -2,7 +2,7 @@
 
 use crate::gl;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::mem;
 use std::ptr;
 use std::str;
@@ -13,13 +13,22 @@ use std::ffi::CStr;
 use std::rc::Rc;
 use std::os::raw;
 use std::hash::BuildHasherDefault;
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
 
 use fnv::FnvHasher;
 
 use crate::IncompatibleOpenGl;
 use crate::SwapBuffersError;
+use crate::SwapIntervalNotSupportedError;
+use crate::DrawError;
+use crate::Program;
 use crate::CapabilitiesSource;
 use crate::ContextExt;
+use crate::ToGlEnum;
+use crate::image_format::TextureFormat;
+use crate::program::{PrecisionFormat, PrecisionType, ShaderType};
 use crate::backend::Backend;
 use crate::version;
 use crate::version::Api;
@@ -29,15 +38,18 @@ use crate::debug;
 use crate::fbo;
 use crate::ops;
 use crate::sampler_object;
+use crate::sync;
 use crate::texture;
 use crate::uniforms;
 use crate::vertex_array_object;
 
-pub use self::capabilities::{ReleaseBehavior, Capabilities, Profile};
+pub use self::capabilities::{ReleaseBehavior, Capabilities, Profile, BackbufferFormat};
+pub use self::capabilities::{InternalFormatInfo, InternalFormatTarget};
 pub use self::extensions::ExtensionsList;
 pub use self::state::GlState;
 
 mod capabilities;
+pub(crate) mod deletion;
 mod extensions;
 mod state;
 
@@ -95,6 +107,26 @@ pub struct Context {
     /// List of images handles that are resident. We need to call `MakeImageHandleResidentARB`
     /// when rebuilding the context.
     resident_image_handles: RefCell<Vec<(gl::types::GLuint64, gl::types::GLenum)>>,
+
+    /// List of objects that have been dropped but whose deletion is deferred until the GPU
+    /// has finished using them.
+    deferred_deletions: RefCell<deletion::DeferredDeletions>,
+
+    /// Estimated number of bytes used by the buffers and textures that are alive.
+    allocated_memory: Cell<usize>,
+
+    /// Number of texture units, starting from unit 0, that glium must not use.
+    reserved_texture_units: Cell<u16>,
+
+    /// If `Some`, draw calls check that all the uniforms of the program have been assigned a
+    /// value, except for the uniforms whose name is in the set.
+    unset_uniforms_check: RefCell<Option<HashSet<String>>>,
+
+    /// If true, draw calls validate the program against the current state.
+    program_validation: Cell<bool>,
+
+    /// Callback that is called when glium detects that the context has been lost.
+    context_lost_callback: RefCell<Option<Box<dyn FnMut() + Send>>>,
 }
 
 /// This struct is a guard that is returned when you want to access the OpenGL backend.
@@ -134,6 +166,15 @@ pub struct CommandContext<'a> {
     /// List of image handles and their access that need to be made resident.
     pub resident_image_handles: RefMut<'a, Vec<(gl::types::GLuint64, gl::types::GLenum)>>,
 
+    /// List of objects whose deletion is deferred.
+    pub deferred_deletions: RefMut<'a, deletion::DeferredDeletions>,
+
+    /// Estimated number of bytes used by the buffers and textures that are alive.
+    pub allocated_memory: &'a Cell<usize>,
+
+    /// Number of texture units, starting from unit 0, that glium must not use.
+    pub reserved_texture_units: &'a Cell<u16>,
+
     /// This marker is here to prevent `CommandContext` from implementing `Send`
     // TODO: use this when possible
     //impl<'a, 'b> !Send for CommandContext<'a, 'b> {}
@@ -150,6 +191,19 @@ impl Context {
     /// current in the same thread as this context. Passing `true` makes things safe but
     /// is slightly slower.
     ///
+    /// # Multiple contexts on the same thread
+    ///
+    /// Several contexts created with `check_current_context` set to `true` can be used from the
+    /// same thread, for example one `Display` per viewport of an editor. Before each operation,
+    /// glium asks the backend whether its context is current and only makes it current if it
+    /// isn't, so switching only happens when you start using another context.
+    ///
+    /// Making a context current is expensive: the driver usually flushes the commands of the
+    /// previous context and may synchronize with the GPU. Group the operations on each context
+    /// together (for example draw a whole frame on one display, then on the next one) instead
+    /// of interleaving them. You can use `is_current` to know whether an operation will trigger
+    /// a switch.
+    ///
     /// The OpenGL context must be newly-created. If you make modifications to the context before
     /// passing it to this function, glium's state cache may mismatch the actual one.
     ///
@@ -210,9 +264,16 @@ impl Context {
             samplers,
             resident_texture_handles,
             resident_image_handles,
+            deferred_deletions: RefCell::new(Default::default()),
+            allocated_memory: Cell::new(0),
+            reserved_texture_units: Cell::new(0),
+            unset_uniforms_check: RefCell::new(None),
+            program_validation: Cell::new(false),
+            context_lost_callback: RefCell::new(None),
         });
 
-        if context.debug_callback.is_some() {
+        // a no-error context doesn't produce any debug message
+        if context.debug_callback.is_some() && !context.capabilities.no_error {
             init_debug_callback(&context, synchronous);
         }
 
@@ -229,11 +290,27 @@ impl Context {
             if ctxt.version >= &Version(Api::Gl, 3, 2) && ctxt.extensions.gl_arb_seamless_cube_map {
                 ctxt.gl.Enable(gl::TEXTURE_CUBE_MAP_SEAMLESS);
             }
+
+            // letting the driver choose how many threads are used to compile shaders
+            if ctxt.extensions.gl_khr_parallel_shader_compile {
+                ctxt.gl.MaxShaderCompilerThreadsKHR(0xffffffff);
+            } else if ctxt.extensions.gl_arb_parallel_shader_compile {
+                ctxt.gl.MaxShaderCompilerThreadsARB(0xffffffff);
+            }
         }
 
         Ok(context)
     }
 
+    /// Returns true if the backend's OpenGL context is the current one in this thread.
+    ///
+    /// If this returns `false`, the next operation on this context will make it current, which
+    /// can be expensive. See the documentation of `new`.
+    #[inline]
+    pub fn is_current(&self) -> bool {
+        self.backend.borrow().is_current()
+    }
+
     /// Calls `get_framebuffer_dimensions` on the backend object stored by this context.
     #[inline]
     pub fn get_framebuffer_dimensions(&self) -> (u32, u32) {
@@ -314,12 +391,121 @@ impl Context {
 
         // swapping
         let err = backend.swap_buffers();
+        drop(backend);
+
         if let Err(SwapBuffersError::ContextLost) = err {
             self.state.borrow_mut().lost_context = true;
+            self.notify_context_lost();
+            return err;
         }
+
+        // if the user wants to be notified, we check the reset status once per frame
+        if self.capabilities.can_lose_context && self.context_lost_callback.borrow().is_some() {
+            self.is_context_lost();
+        }
+
+        self.process_deferred_deletions();
+
         err
     }
 
+    /// Sets the minimum number of vertical refreshes between two buffer swaps.
+    ///
+    /// With vsync, swapping the buffers blocks until the next refresh of the screen, which ties
+    /// the frame rate to the refresh rate. Passing `0` disables vsync, so that `swap_buffers`
+    /// (and `Frame::finish`) return immediately. This is useful to measure the raw rendering
+    /// time, or to implement your own frame pacing.
+    ///
+    /// Swapping the buffers never waits for the GPU to finish its work on glium's side. With an
+    /// interval of `0`, the only waits left are the ones the driver inserts when too many frames
+    /// are queued.
+    ///
+    /// Returns an error if the backend doesn't support changing the swap interval. The
+    /// driver or the user's settings can also silently override the interval.
+    pub fn set_swap_interval(&self, interval: u32) -> Result<(), SwapIntervalNotSupportedError> {
+        let _ctxt = self.make_current();
+        self.backend.borrow().set_swap_interval(interval)
+    }
+
+    /// Enables or disables the deferred deletion of buffers and textures.
+    ///
+    /// When enabled, dropping a buffer or a texture doesn't immediately destroy the underlying
+    /// OpenGL object. Instead, glium inserts a fence in the commands queue and only destroys the
+    /// object once the GPU has finished executing all the commands that were issued before it
+    /// was dropped. This is useful when resources are streamed in and out aggressively, or when
+    /// the objects are shared with other contexts.
+    ///
+    /// Pending deletions are processed every time the buffers are swapped, or manually with
+    /// `process_deferred_deletions`. Disabled by default. If the backend doesn't support fences,
+    /// objects are always destroyed immediately.
+    #[inline]
+    pub fn set_deferred_deletion(&self, enabled: bool) {
+        self.deferred_deletions.borrow_mut().enabled = enabled;
+    }
+
+    /// Destroys the objects whose deletion has been deferred and that the GPU has finished
+    /// using. See `set_deferred_deletion`.
+    #[inline]
+    pub fn process_deferred_deletions(&self) {
+        if self.deferred_deletions.borrow().len() == 0 {
+            return;
+        }
+
+        let mut ctxt = self.make_current();
+        unsafe { deletion::process(&mut ctxt, false); }
+    }
+
+    /// Returns the number of objects whose deletion has been deferred and that haven't been
+    /// destroyed yet. See `set_deferred_deletion`.
+    #[inline]
+    pub fn get_pending_deletions_count(&self) -> usize {
+        self.deferred_deletions.borrow().len()
+    }
+
+    /// Forbids glium from using the texture units `0 .. count`, so that you can bind textures
+    /// to them with raw OpenGL calls.
+    ///
+    /// By default glium picks any available texture unit when it binds the textures used by a
+    /// draw call, and binds textures to the active texture unit when it creates or modifies
+    /// them. After calling this function, glium only uses the units starting from `count`,
+    /// which means that the textures that you bind to the reserved units stay bound. Pass `0`
+    /// to let glium use all the units again.
+    ///
+    /// Glium assigns units among all the `GL_MAX_COMBINED_TEXTURE_IMAGE_UNITS` of the context,
+    /// so the reserved units are taken from this total. The per-stage limit,
+    /// `GL_MAX_TEXTURE_IMAGE_UNITS` for fragment shaders, only limits the number of textures
+    /// that a shader uses and isn't affected. Drawing panics if not enough units remain for
+    /// the textures of a draw call.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `count` is not smaller than `GL_MAX_COMBINED_TEXTURE_IMAGE_UNITS`, as glium
+    /// needs at least one texture unit.
+    pub fn reserve_texture_units(&self, count: u32) {
+        assert!(count < self.capabilities.max_combined_texture_image_units as u32,
+                "Can't reserve all the texture units");
+
+        let mut ctxt = self.make_current();
+        ctxt.reserved_texture_units.set(count as u16);
+
+        // glium binds textures to the active unit when creating or modifying them
+        if ctxt.state.active_texture < count {
+            while ctxt.state.texture_units.len() <= count as usize {
+                ctxt.state.texture_units.push(Default::default());
+            }
+
+            unsafe { ctxt.gl.ActiveTexture(gl::TEXTURE0 + count); }
+            ctxt.state.active_texture = count;
+        }
+    }
+
+    /// Returns the number of texture units that glium must not use. See
+    /// `reserve_texture_units`.
+    #[inline]
+    pub fn get_reserved_texture_units(&self) -> u32 {
+        self.reserved_texture_units.get() as u32
+    }
+
     /// Returns the OpenGL version
     #[inline]
     #[deprecated(note = "use `get_opengl_version` instead.")]
@@ -339,6 +525,96 @@ impl Context {
         version::get_supported_glsl_version(self.get_opengl_version())
     }
 
+    /// Returns the most recent GLSL version supported by the compiler, as reported by
+    /// `glGetString(GL_SHADING_LANGUAGE_VERSION)`.
+    ///
+    /// Contrary to `get_glsl_version_string`, the returned value can be compared with other
+    /// versions. If the string can't be parsed, the version is deduced from the OpenGL version
+    /// instead. Returns `None` if the backend doesn't support GLSL.
+    ///
+    /// ```no_run
+    /// # let display: glium::Display = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// use glium::{Api, Version};
+    ///
+    /// if display.get_glsl_version() >= Some(Version(Api::Gl, 4, 3)) {
+    ///     // `#version 430` can be used
+    /// }
+    /// ```
+    pub fn get_glsl_version(&self) -> Option<Version> {
+        let version = self.get_opengl_version();
+
+        version::parse_glsl_version(&self.capabilities().glsl_version, version.0)
+            .or_else(|| {
+                if version >= &Version(Api::Gl, 2, 0) || version >= &Version(Api::GlEs, 2, 0) {
+                    Some(version::get_supported_glsl_version(version))
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Returns a string containing the version of GLSL supported by the compiler.
+    ///
+    /// Vendor-specific information may follow the version number. Empty if the backend doesn't
+    /// support GLSL.
+    #[inline]
+    pub fn get_glsl_version_string(&self) -> &str {
+        &self.capabilities().glsl_version
+    }
+
+    /// Returns the range and the precision of the variables declared with `precision` in a
+    /// shader of type `shader`, with `glGetShaderPrecisionFormat`.
+    ///
+    /// OpenGL ES implementations may use fewer bits than requested, and don't have to support
+    /// `highp` in fragment shaders. Desktop OpenGL ignores precision qualifiers, therefore this
+    /// returns `PrecisionFormat::FULL_FLOAT` or `PrecisionFormat::FULL_INT` if the function isn't
+    /// supported.
+    ///
+    /// Returns `None` if `shader` is neither a vertex nor a fragment shader.
+    ///
+    /// ```no_run
+    /// # let display: glium::Display = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// use glium::program::{PrecisionType, ShaderType};
+    ///
+    /// let highp = display.get_shader_precision_format(ShaderType::Fragment,
+    ///                                                 PrecisionType::HighFloat).unwrap();
+    /// let precision = if highp.is_supported() { "highp" } else { "mediump" };
+    /// ```
+    pub fn get_shader_precision_format(&self, shader: ShaderType, precision: PrecisionType)
+                                       -> Option<PrecisionFormat>
+    {
+        match shader {
+            ShaderType::Vertex | ShaderType::Fragment => (),
+            _ => return None,
+        };
+
+        let ctxt = self.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 4, 1)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 2, 0)) &&
+           !ctxt.extensions.gl_arb_es2_compatibility
+        {
+            return Some(if precision.is_float() {
+                PrecisionFormat::FULL_FLOAT
+            } else {
+                PrecisionFormat::FULL_INT
+            });
+        }
+
+        let mut range = [0, 0];
+        let mut bits = 0;
+        unsafe {
+            ctxt.gl.GetShaderPrecisionFormat(shader.to_opengl_type(), precision.to_glenum(),
+                                             range.as_mut_ptr(), &mut bits);
+        }
+
+        Some(PrecisionFormat {
+            range_min: range[0],
+            range_max: range[1],
+            precision: bits,
+        })
+    }
+
     /// Returns true if the given GLSL version is supported.
     #[inline]
     pub fn is_glsl_version_supported(&self, version: &Version) -> bool {
@@ -383,6 +659,121 @@ impl Context {
         self.capabilities().forward_compatible
     }
 
+    /// Returns the format of the default framebuffer, as chosen by the windowing system.
+    ///
+    /// This can be used to know whether the default framebuffer is precise enough to be
+    /// rendered to directly, or whether an intermediate texture is needed.
+    pub fn backbuffer_format(&self) -> BackbufferFormat {
+        let capabilities = self.capabilities();
+        let (red_bits, green_bits, blue_bits, alpha_bits) = capabilities.color_bits;
+
+        BackbufferFormat {
+            red_bits,
+            green_bits,
+            blue_bits,
+            alpha_bits,
+            depth_bits: capabilities.depth_bits,
+            stencil_bits: capabilities.stencil_bits,
+            samples: capabilities.samples,
+            srgb: capabilities.srgb,
+        }
+    }
+
+    /// Returns the list of formats in which the implementation can return program binaries.
+    ///
+    /// A binary returned by `Program::get_binary` can only be loaded back if its format is
+    /// still in this list. When caching binaries on disk, store this list alongside them in
+    /// order to detect that the driver has been updated and that the cache is stale.
+    ///
+    /// Returns an empty list if program binaries aren't supported.
+    pub fn program_binary_formats(&self) -> Vec<u32> {
+        let ctxt = self.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 4, 1)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 0)) &&
+           !ctxt.extensions.gl_arb_get_programy_binary
+        {
+            return Vec::new();
+        }
+
+        unsafe {
+            let mut num_formats = 0;
+            ctxt.gl.GetIntegerv(gl::NUM_PROGRAM_BINARY_FORMATS, &mut num_formats);
+            if num_formats <= 0 {
+                return Vec::new();
+            }
+
+            let mut formats: Vec<gl::types::GLint> = vec![0; num_formats as usize];
+            ctxt.gl.GetIntegerv(gl::PROGRAM_BINARY_FORMATS, formats.as_mut_ptr());
+            formats.into_iter().map(|f| f as u32).collect()
+        }
+    }
+
+    /// Returns what the implementation can do with a texture format for the given target.
+    ///
+    /// With OpenGL 4.3 or `GL_ARB_internalformat_query2`, the driver is asked directly.
+    /// Otherwise the result is determined conservatively from the version and the extensions,
+    /// and may report a format as unsupported even if the driver would accept it.
+    pub fn internal_format_info(&self, target: InternalFormatTarget, format: TextureFormat)
+                                -> InternalFormatInfo
+    {
+        let ctxt = self.make_current();
+
+        let samples = {
+            let infos = match target {
+                InternalFormatTarget::RenderBuffer => {
+                    &self.capabilities.internal_formats_renderbuffers
+                },
+                _ => &self.capabilities.internal_formats_textures,
+            };
+
+            match infos.get(&format).and_then(|i| i.multisamples.as_ref()) {
+                Some(samples) => samples.iter().map(|&s| s as u32).collect(),
+                None => Vec::new(),
+            }
+        };
+
+        if ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.extensions.gl_arb_internalformat_query2 {
+            let gl_target = match target {
+                InternalFormatTarget::Texture2d => gl::TEXTURE_2D,
+                InternalFormatTarget::Texture2dMultisample => gl::TEXTURE_2D_MULTISAMPLE,
+                InternalFormatTarget::RenderBuffer => gl::RENDERBUFFER,
+            };
+
+            unsafe {
+                let mut supported = 0;
+                ctxt.gl.GetInternalformativ(gl_target, format.to_glenum(),
+                                            gl::INTERNALFORMAT_SUPPORTED, 1, &mut supported);
+
+                let mut renderable = 0;
+                ctxt.gl.GetInternalformativ(gl_target, format.to_glenum(),
+                                            gl::FRAMEBUFFER_RENDERABLE, 1, &mut renderable);
+                let renderable = renderable as gl::types::GLenum;
+
+                return InternalFormatInfo {
+                    supported: supported == gl::TRUE as gl::types::GLint,
+                    renderable: renderable == gl::FULL_SUPPORT || renderable == gl::CAVEAT_SUPPORT,
+                    samples,
+                };
+            }
+        }
+
+        let supported = match target {
+            InternalFormatTarget::Texture2d => format.is_supported_for_textures(self),
+            InternalFormatTarget::Texture2dMultisample => {
+                format.is_supported_for_textures(self) &&
+                texture::is_texture_2d_multisample_supported(self)
+            },
+            InternalFormatTarget::RenderBuffer => format.is_supported_for_renderbuffers(self),
+        };
+
+        InternalFormatInfo {
+            supported,
+            renderable: supported && format.is_renderable(self),
+            samples,
+        }
+    }
+
     /// Returns this context's OpenGL profile if available.
     ///
     /// The context profile is available from OpenGL 3.2 onwards. Returns `None` if not supported.
@@ -399,6 +790,36 @@ impl Context {
         self.capabilities().robustness
     }
 
+    /// Returns true if the context has been created in no-error mode.
+    ///
+    /// In this mode the driver skips all error checking. `get_error` always returns `None`, the
+    /// debug output isn't enabled, and any incorrect use of OpenGL is undefined behavior and
+    /// will most likely crash the program. See `Display::new` to create such a context.
+    #[inline]
+    pub fn is_no_error(&self) -> bool {
+        self.capabilities().no_error
+    }
+
+    /// Returns the alignment in bytes that the offset of a range of a uniform buffer must
+    /// respect in order to be used as a uniform block. This corresponds to
+    /// `GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT`.
+    ///
+    /// Returns `0` if uniform buffers aren't supported. See `UniformBuffer::block_range`.
+    #[inline]
+    pub fn get_uniform_buffer_offset_alignment(&self) -> usize {
+        self.capabilities().uniform_buffer_offset_alignment as usize
+    }
+
+    /// Returns the alignment in bytes that the offset of a range of a buffer must respect in
+    /// order to be used as a shader storage block. This corresponds to
+    /// `GL_SHADER_STORAGE_BUFFER_OFFSET_ALIGNMENT`.
+    ///
+    /// Returns `0` if shader storage buffers aren't supported.
+    #[inline]
+    pub fn get_shader_storage_buffer_offset_alignment(&self) -> usize {
+        self.capabilities().shader_storage_buffer_offset_alignment as usize
+    }
+
     /// Returns true if a context loss is possible.
     #[inline]
     pub fn is_context_loss_possible(&self) -> bool {
@@ -412,6 +833,10 @@ impl Context {
     /// If it has been determined that the context has been lost before, then the function
     /// immediately returns true. Otherwise, calls `glGetGraphicsResetStatus`. If this function
     /// is not available, returns false.
+    ///
+    /// Once the loss has been detected, drawing returns `DrawError::ContextLost` instead of
+    /// silently doing nothing. Call `rebuild` with a new backend to recover. See also
+    /// `on_context_lost`.
     pub fn is_context_lost(&self) -> bool {
         if self.state.borrow().lost_context {
             return true;
@@ -432,14 +857,57 @@ impl Context {
             false
         };
 
-        if lost { ctxt.state.lost_context = true; }
+        if lost {
+            ctxt.state.lost_context = true;
+            drop(ctxt);
+            self.notify_context_lost();
+        }
+
         lost
     }
 
+    /// Registers a callback that is called once when glium detects that the context has been
+    /// lost, replacing the previous one.
+    ///
+    /// The loss is detected when swapping buffers, and when calling `is_context_lost`. As long
+    /// as a callback is registered and a context loss is possible, glium checks the reset status
+    /// of the context after each swap, so that you don't have to poll `is_context_lost` every
+    /// frame. The callback should stop the rendering and schedule the recreation of the
+    /// resources, for example with `rebuild`. After a rebuild, the callback is called again if
+    /// the new context is lost.
+    ///
+    /// The callback is called on the thread that uses the context, while no glium object is
+    /// borrowed. It can call functions of the context, but drawing returns
+    /// `DrawError::ContextLost`.
+    /// Registering a new callback from the callback replaces it once it returns.
+    pub fn on_context_lost<F>(&self, callback: F) where F: FnMut() + Send + 'static {
+        *self.context_lost_callback.borrow_mut() = Some(Box::new(callback));
+    }
+
+    /// Calls the callback registered with `on_context_lost`, if any.
+    fn notify_context_lost(&self) {
+        // the callback is taken out during the call, so that it can use the context
+        let callback = self.context_lost_callback.borrow_mut().take();
+        if let Some(mut callback) = callback {
+            callback();
+
+            let mut slot = self.context_lost_callback.borrow_mut();
+            if slot.is_none() {
+                *slot = Some(callback);
+            }
+        }
+    }
+
     /// Returns the behavior when the current OpenGL context is changed.
     ///
     /// The most common value is `Flush`. In order to get `None` you must explicitly request it
-    /// during creation.
+    /// during creation, which is only possible with `GL_KHR_context_flush_control`. With the
+    /// glutin backend, this is done by setting `release_behavior` in the `pf_reqs` of the
+    /// context builder.
+    ///
+    /// If the value is `None`, you must call `flush` yourself before using another context
+    /// that shares objects with this one, otherwise the other context may not see the
+    /// modifications.
     #[inline]
     pub fn get_release_behavior(&self) -> ReleaseBehavior {
         self.capabilities().release_behavior
@@ -499,6 +967,102 @@ impl Context {
         }
     }
 
+    /// Enables or disables checking that the uniforms of programs have been given a value.
+    ///
+    /// When enabled, drawing with a program that contains a uniform that has never been
+    /// assigned a value, neither by a previous draw call nor with
+    /// `Program::set_persistent_uniforms`, returns `DrawError::UniformNotSet`. This catches
+    /// forgotten uniforms, which OpenGL silently leaves at zero. The uniforms whose name is in
+    /// `allowed` are left unchecked.
+    ///
+    /// The members of uniform blocks aren't checked, as their value comes from the buffer bound
+    /// to the block.
+    ///
+    /// A missing uniform is reported as an error rather than as a warning, because glium only
+    /// has the debug output of OpenGL to report warnings, which most users don't enable. Add
+    /// the uniforms that you leave unset on purpose to `allowed` instead.
+    ///
+    /// Pass `None` to disable the check, which is the default. Since the check has a cost for
+    /// each draw call, you should only enable it while debugging.
+    pub fn set_unset_uniforms_check(&self, allowed: Option<&[&str]>) {
+        *self.unset_uniforms_check.borrow_mut() =
+            allowed.map(|names| names.iter().map(|&n| n.to_owned()).collect());
+    }
+
+    /// Returns an error if the check of unset uniforms is enabled and the program has a uniform
+    /// that has never been assigned a value.
+    pub(crate) fn check_unset_uniforms(&self, program: &Program) -> Result<(), DrawError> {
+        let check = self.unset_uniforms_check.borrow();
+        let allowed = match *check {
+            Some(ref allowed) => allowed,
+            None => return Ok(()),
+        };
+
+        match program.find_unset_uniform(allowed) {
+            Some(name) => Err(DrawError::UniformNotSet { name: name.to_owned() }),
+            None => Ok(()),
+        }
+    }
+
+    /// Enables or disables the validation of programs before each draw call.
+    ///
+    /// When enabled, glium calls `glValidateProgram` before each draw call and returns
+    /// `DrawError::ProgramValidationFailed` if the program can't be executed given the current
+    /// state. This is the same check as `Program::validate`.
+    ///
+    /// This is disabled by default. Validating a program is slow, so you should only enable it
+    /// while debugging.
+    #[inline]
+    pub fn set_program_validation(&self, enabled: bool) {
+        self.program_validation.set(enabled);
+    }
+
+    /// Returns true if programs are validated before each draw call.
+    #[inline]
+    pub(crate) fn is_program_validation_enabled(&self) -> bool {
+        self.program_validation.get()
+    }
+
+    /// Returns the total amount of video memory in bytes, as reported by the driver.
+    ///
+    /// `GL_ATI_meminfo` only reports the free memory. With this extension, the returned value is
+    /// the free memory of the texture pool plus the estimated memory used by the objects of
+    /// glium (see `estimated_gpu_memory`).
+    ///
+    /// Returns `None` if neither `GL_NVX_gpu_memory_info` nor `GL_ATI_meminfo` is supported.
+    pub fn get_total_video_memory(&self) -> Option<usize> {
+        unsafe {
+            let ctxt = self.make_current();
+
+            let mut value: [gl::types::GLint; 4] = [0; 4];
+
+            if ctxt.extensions.gl_nvx_gpu_memory_info {
+                ctxt.gl.GetIntegerv(gl::GPU_MEMORY_INFO_TOTAL_AVAILABLE_MEMORY_NVX,
+                                    &mut value[0]);
+                Some(value[0] as usize * 1024)
+
+            } else if ctxt.extensions.gl_ati_meminfo {
+                ctxt.gl.GetIntegerv(gl::TEXTURE_FREE_MEMORY_ATI, &mut value[0]);
+                Some(value[0] as usize * 1024 + self.allocated_memory.get())
+
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Returns an estimate of the number of bytes of video memory used by the buffers and
+    /// textures created with glium that are still alive.
+    ///
+    /// This is computed from the sizes of the buffers and from the dimensions and formats of
+    /// the textures, and doesn't include the padding or the metadata that the driver may add.
+    /// Textures whose size is unknown (see `memory_size`) and textures created from an existing
+    /// OpenGL object are not counted.
+    #[inline]
+    pub fn estimated_gpu_memory(&self) -> usize {
+        self.allocated_memory.get()
+    }
+
     /// Reads the content of the front buffer.
     ///
     /// You will only see the data that has finished being drawn.
@@ -533,6 +1097,11 @@ impl Context {
     ///
     /// **If `action` manipulates any OpenGL state, it must be restored before `action`
     /// completes.**
+    ///
+    /// Glium keeps a copy of the OpenGL state (enabled capabilities, blending function,
+    /// bound objects, current program, etc.) in order to avoid redundant state changes, and
+    /// assumes that this copy is always accurate. Any state that is left modified by `action`
+    /// may therefore be silently ignored or lead to incorrect rendering in subsequent draws.
     #[inline]
     pub unsafe fn exec_in_context<'a, T, F>(&self, action: F) -> T
                                             where T: Send + 'static,
@@ -555,12 +1124,123 @@ impl Context {
         };
     }
 
+    /// Calls `glGetError()` and returns the first error that is pending, if any.
+    ///
+    /// OpenGL can record several errors at once. All of them are removed from the queue, but
+    /// only the oldest one is returned.
+    ///
+    /// This doesn't require the debug output to be enabled. However it forces the driver to
+    /// wait until all the previously issued commands have been processed, so it should
+    /// only be used sparingly, for example while tracking down a bug.
+    ///
+    /// If the context has been created in no-error mode (see `is_no_error`), this always
+    /// returns `None`.
+    pub fn get_error(&self) -> Option<crate::GlError> {
+        let ctxt = self.make_current();
+
+        let first = crate::GlError::from_gl_enum(unsafe { ctxt.gl.GetError() });
+
+        // drain the remaining errors ; the number of iterations is bounded in case the
+        // context is lost and the driver keeps returning an error
+        if first.is_some() {
+            for _ in 0 .. 16 {
+                if unsafe { ctxt.gl.GetError() } == gl::NO_ERROR {
+                    break;
+                }
+            }
+        }
+
+        first
+    }
+
     /// DEPRECATED. Renamed `finish`.
     #[inline]
     pub fn synchronize(&self) {
         self.finish();
     }
 
+    /// Calls `glHint()` to tell the driver how to balance quality and speed for some
+    /// operations.
+    ///
+    /// These are just hints and the driver is free to disregard them. The hints that are
+    /// available depend on the version of OpenGL:
+    ///
+    /// - `Hint::GenerateMipmap` affects the quality of the mipmaps generated by glium. It is
+    ///   available on OpenGL ES and on compatibility profiles, but has been removed from core
+    ///   profiles.
+    /// - `Hint::FragmentShaderDerivative` affects the precision of `dFdx`, `dFdy` and `fwidth`.
+    ///   It is available on OpenGL 2.0, OpenGL ES 3.0 or with `GL_OES_standard_derivatives`.
+    ///
+    /// Returns an error if the hint isn't supported by the backend.
+    pub fn set_hint(&self, target: Hint, mode: HintMode) -> Result<(), HintNotSupportedError> {
+        let ctxt = self.make_current();
+
+        let target = match target {
+            Hint::GenerateMipmap => {
+                if !(ctxt.version >= &Version(Api::Gl, 1, 4) &&
+                     self.capabilities.profile != Some(Profile::Core)) &&
+                   !(ctxt.version >= &Version(Api::GlEs, 2, 0))
+                {
+                    return Err(HintNotSupportedError);
+                }
+
+                gl::GENERATE_MIPMAP_HINT
+            },
+
+            Hint::FragmentShaderDerivative => {
+                if !(ctxt.version >= &Version(Api::Gl, 2, 0)) &&
+                   !(ctxt.version >= &Version(Api::GlEs, 3, 0)) &&
+                   !ctxt.extensions.gl_oes_standard_derivatives
+                {
+                    return Err(HintNotSupportedError);
+                }
+
+                gl::FRAGMENT_SHADER_DERIVATIVE_HINT
+            },
+        };
+
+        let mode = match mode {
+            HintMode::Fastest => gl::FASTEST,
+            HintMode::Nicest => gl::NICEST,
+            HintMode::DontCare => gl::DONT_CARE,
+        };
+
+        unsafe { ctxt.gl.Hint(target, mode); }
+        Ok(())
+    }
+
+    /// Chooses whether the colors written by the fragment shader are clamped to `[0.0, 1.0]`.
+    ///
+    /// By default OpenGL only clamps the colors written to fixed-point attachments, and float
+    /// attachments such as `RGBA16F` receive the unclamped values. However some compatibility
+    /// contexts clamp all the colors, in which case HDR values above `1.0` are silently lost.
+    /// Passing `false` disables the clamping for all attachments, and passing `true` enables it
+    /// for all attachments.
+    ///
+    /// This corresponds to `glClampColor(GL_CLAMP_FRAGMENT_COLOR, ...)`, which requires OpenGL
+    /// 3.0 and has been removed from core profiles. Core profiles and OpenGL ES never clamp the
+    /// colors written to float attachments, therefore passing `false` always succeeds on them.
+    ///
+    /// Returns an error if the clamping can't be changed.
+    pub fn set_clamp_color(&self, clamp: bool) -> Result<(), ClampColorNotSupportedError> {
+        let mut ctxt = self.make_current();
+
+        if !(ctxt.version >= &Version(Api::Gl, 3, 0)) ||
+           self.capabilities.profile == Some(Profile::Core)
+        {
+            return if clamp { Err(ClampColorNotSupportedError) } else { Ok(()) };
+        }
+
+        let value = if clamp { gl::TRUE } else { gl::FALSE } as gl::types::GLenum;
+
+        if ctxt.state.clamp_fragment_color != value {
+            unsafe { ctxt.gl.ClampColor(gl::CLAMP_FRAGMENT_COLOR, value); }
+            ctxt.state.clamp_fragment_color = value;
+        }
+
+        Ok(())
+    }
+
     /// Calls `glFinish()`. This waits until all the previously issued commands have finished
     /// being executed.
     ///
@@ -575,6 +1255,69 @@ impl Context {
         unsafe { ctxt.gl.Finish(); }
     }
 
+    /// Makes the writes of the previous draw calls visible to the fragment shaders of the next
+    /// draw calls, but only for the same pixels.
+    ///
+    /// glium automatically inserts the barriers that are needed when a buffer or a texture is
+    /// used in a different way after it has been written by a shader. However it can't know
+    /// that a fragment shader reads what a previous draw call wrote with image stores or
+    /// shader storage blocks, for example with tiled deferred shading techniques. When each
+    /// fragment only reads the data that was written for its own pixel, this barrier is much
+    /// cheaper than a full barrier on tiled GPUs.
+    ///
+    /// Uses `glMemoryBarrierByRegion` with OpenGL 4.5 and OpenGL ES 3.1, and falls back to a
+    /// regular `glMemoryBarrier` if only that is available. Does nothing if neither is
+    /// supported, since shaders can't write incoherently in that case.
+    pub fn memory_barrier_by_region(&self, barrier: RegionBarrier) {
+        let bits = barrier.to_bits();
+        if bits == 0 {
+            return;
+        }
+
+        let ctxt = self.make_current();
+
+        unsafe {
+            if ctxt.version >= &Version(Api::Gl, 4, 5) ||
+               ctxt.version >= &Version(Api::GlEs, 3, 1) ||
+               ctxt.extensions.gl_arb_es3_1_compatibility
+            {
+                ctxt.gl.MemoryBarrierByRegion(bits);
+
+            } else if ctxt.version >= &Version(Api::Gl, 4, 2) ||
+                      ctxt.extensions.gl_arb_shader_image_load_store
+            {
+                ctxt.gl.MemoryBarrier(bits);
+            }
+        }
+    }
+
+    /// Waits until all the commands that have been issued have finished being executed, or until
+    /// `timeout` has elapsed. Returns true if the commands have finished.
+    ///
+    /// Contrary to `finish`, this uses a fence and doesn't necessarily stall the whole driver,
+    /// and the wait is bounded. This is useful before reading back data, or before destroying
+    /// resources that the GPU might still be using.
+    ///
+    /// If the backend doesn't support sync objects, this calls `glFinish()` and returns true.
+    pub fn wait_until_idle(&self, timeout: Duration) -> bool {
+        let mut ctxt = self.make_current();
+
+        unsafe {
+            match sync::new_linear_sync_fence(&mut ctxt) {
+                Ok(fence) => {
+                    let timeout = timeout.as_nanos().min(u64::max_value() as u128) as u64;
+                    let signaled = sync::wait_linear_sync_fence_timeout(&mut ctxt, &fence, timeout);
+                    sync::destroy_linear_sync_fence(&mut ctxt, fence);
+                    signaled
+                },
+                Err(_) => {
+                    ctxt.gl.Finish();
+                    true
+                },
+            }
+        }
+    }
+
     /// Calls `glFlush()`. This starts executing the commands that you have issued if it is not
     /// yet the case.
     ///
@@ -656,6 +1399,9 @@ impl ContextExt for Context {
             samplers: self.samplers.borrow_mut(),
             resident_texture_handles: self.resident_texture_handles.borrow_mut(),
             resident_image_handles: self.resident_image_handles.borrow_mut(),
+            deferred_deletions: self.deferred_deletions.borrow_mut(),
+            allocated_memory: &self.allocated_memory,
+            reserved_texture_units: &self.reserved_texture_units,
             marker: PhantomData,
         }
     }
@@ -707,9 +1453,13 @@ impl Drop for Context {
                 samplers: self.samplers.borrow_mut(),
                 resident_texture_handles: self.resident_texture_handles.borrow_mut(),
                 resident_image_handles: self.resident_image_handles.borrow_mut(),
+                deferred_deletions: self.deferred_deletions.borrow_mut(),
+                allocated_memory: &self.allocated_memory,
+                reserved_texture_units: &self.reserved_texture_units,
                 marker: PhantomData,
             };
 
+            deletion::process(&mut ctxt, true);
             fbo::FramebuffersContainer::cleanup(&mut ctxt);
             vertex_array_object::VertexAttributesSystem::cleanup(&mut ctxt);
 
@@ -790,6 +1540,86 @@ fn check_gl_compatibility(version: &Version, extensions: &ExtensionsList)
     }
 }
 
+/// An operation whose behavior can be tweaked with `Context::set_hint`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Hint {
+    /// The quality of the mipmaps generated by the implementation.
+    GenerateMipmap,
+
+    /// The precision of the derivatives computed in fragment shaders.
+    FragmentShaderDerivative,
+}
+
+/// The value of a hint passed to `Context::set_hint`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HintMode {
+    /// The most efficient option should be chosen.
+    Fastest,
+
+    /// The most correct, or highest quality, option should be chosen.
+    Nicest,
+
+    /// No preference. This is the default value of every hint.
+    DontCare,
+}
+
+/// The kinds of memory accesses that must be ordered by `Context::memory_barrier_by_region`.
+///
+/// Each field corresponds to one of the bits accepted by `glMemoryBarrierByRegion`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RegionBarrier {
+    /// Atomic counters. Corresponds to `GL_ATOMIC_COUNTER_BARRIER_BIT`.
+    pub atomic_counter: bool,
+    /// Reads and writes of the framebuffer attachments. Corresponds to
+    /// `GL_FRAMEBUFFER_BARRIER_BIT`.
+    pub framebuffer: bool,
+    /// Image loads, stores and atomics. Corresponds to `GL_SHADER_IMAGE_ACCESS_BARRIER_BIT`.
+    pub shader_image_access: bool,
+    /// Shader storage blocks. Corresponds to `GL_SHADER_STORAGE_BARRIER_BIT`.
+    pub shader_storage: bool,
+    /// Texture fetches. Corresponds to `GL_TEXTURE_FETCH_BARRIER_BIT`.
+    pub texture_fetch: bool,
+    /// Uniform buffers. Corresponds to `GL_UNIFORM_BARRIER_BIT`.
+    pub uniform: bool,
+}
+
+impl RegionBarrier {
+    fn to_bits(&self) -> gl::types::GLbitfield {
+        let mut bits = 0;
+        if self.atomic_counter { bits |= gl::ATOMIC_COUNTER_BARRIER_BIT; }
+        if self.framebuffer { bits |= gl::FRAMEBUFFER_BARRIER_BIT; }
+        if self.shader_image_access { bits |= gl::SHADER_IMAGE_ACCESS_BARRIER_BIT; }
+        if self.shader_storage { bits |= gl::SHADER_STORAGE_BARRIER_BIT; }
+        if self.texture_fetch { bits |= gl::TEXTURE_FETCH_BARRIER_BIT; }
+        if self.uniform { bits |= gl::UNIFORM_BARRIER_BIT; }
+        bits
+    }
+}
+
+/// Error that happens when a hint is not supported by the backend.
+#[derive(Copy, Clone, Debug)]
+pub struct HintNotSupportedError;
+
+impl fmt::Display for HintNotSupportedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("This hint is not supported by the backend")
+    }
+}
+
+impl Error for HintNotSupportedError {}
+
+/// Error that happens when the clamping of the fragment colors can't be changed.
+#[derive(Copy, Clone, Debug)]
+pub struct ClampColorNotSupportedError;
+
+impl fmt::Display for ClampColorNotSupportedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Changing the clamping of the fragment colors is not supported by the backend")
+    }
+}
+
+impl Error for ClampColorNotSupportedError {}
+
 /// Describes the behavior that the debug output should have.
 pub enum DebugCallbackBehavior {
     /// Don't do anything. This is the default behavior in release.
@@ -992,3 +1822,56 @@ fn init_debug_callback(context: &Rc<Context>, synchronous: bool) {
         }
     }
 }
+
+/// Reports a warning about a suspicious usage of glium through the debug output.
+///
+/// The message is sent to the debug callback with a low severity and the
+/// `UndefinedBehavior` type. It is ignored if the debug output isn't enabled.
+pub(crate) fn debug_warning(ctxt: &CommandContext<'_>, message: &str) {
+    if ctxt.state.enabled_debug_output != Some(true) {
+        return;
+    }
+
+    let length = message.len() as gl::types::GLsizei;
+    let message = message.as_ptr() as *const gl::types::GLchar;
+
+    unsafe {
+        if ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+           (ctxt.version >= &Version(Api::Gl, 1, 0) && ctxt.extensions.gl_khr_debug)
+        {
+            ctxt.gl.DebugMessageInsert(gl::DEBUG_SOURCE_THIRD_PARTY,
+                                       gl::DEBUG_TYPE_UNDEFINED_BEHAVIOR, 0,
+                                       gl::DEBUG_SEVERITY_LOW, length, message);
+
+        } else if ctxt.extensions.gl_khr_debug {
+            ctxt.gl.DebugMessageInsertKHR(gl::DEBUG_SOURCE_THIRD_PARTY_KHR,
+                                          gl::DEBUG_TYPE_UNDEFINED_BEHAVIOR_KHR, 0,
+                                          gl::DEBUG_SEVERITY_LOW_KHR, length, message);
+
+        } else if ctxt.extensions.gl_arb_debug_output {
+            ctxt.gl.DebugMessageInsertARB(gl::DEBUG_SOURCE_THIRD_PARTY_ARB,
+                                          gl::DEBUG_TYPE_UNDEFINED_BEHAVIOR_ARB, 0,
+                                          gl::DEBUG_SEVERITY_LOW_ARB, length, message);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "null_backend"))]
+mod tests {
+    use crate::backend::null::{build_context, NullBackend};
+    use crate::program::{PrecisionFormat, PrecisionType, ShaderType};
+
+    #[test]
+    fn desktop_shader_precision() {
+        let (_, context) = build_context(NullBackend::new());
+
+        assert_eq!(context.get_shader_precision_format(ShaderType::Fragment,
+                                                       PrecisionType::LowFloat),
+                   Some(PrecisionFormat::FULL_FLOAT));
+        assert_eq!(context.get_shader_precision_format(ShaderType::Vertex,
+                                                       PrecisionType::HighInt),
+                   Some(PrecisionFormat::FULL_INT));
+        assert_eq!(context.get_shader_precision_format(ShaderType::Compute,
+                                                       PrecisionType::HighInt), None);
+    }
+}
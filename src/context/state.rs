@@ -5,6 +5,10 @@ use smallvec::SmallVec;
 /// Represents the current OpenGL state.
 ///
 /// The current state is passed to each function and can be freely updated.
+///
+/// Every function that modifies the OpenGL state compares the new value with the one stored
+/// here first, and skips the `gl*` call if they are the same. This is why the state must never
+/// be modified behind glium's back.
 pub struct GlState {
     /// Whether we have detected that the context has been lost.
     ///
@@ -33,12 +37,18 @@ pub struct GlState {
     /// Whether DEPTH_CLAMP_FAR is enabled.
     pub enabled_depth_clamp_far: bool,
 
+    /// Whether GL_COLOR_LOGIC_OP is enabled
+    pub enabled_color_logic_op: bool,
+
     /// Whether GL_DITHER is enabled
     pub enabled_dither: bool,
 
     /// Whether GL_FRAMEBUFFER_SRGB is enabled
     pub enabled_framebuffer_srgb: bool,
 
+    /// Whether GL_LINE_STIPPLE is enabled
+    pub enabled_line_stipple: bool,
+
     /// Whether GL_MULTISAMPLE is enabled
     pub enabled_multisample: bool,
 
@@ -54,6 +64,9 @@ pub struct GlState {
     /// Whether GL_PRIMITIVE_RESTART_FIXED_INDEX is enabled
     pub enabled_primitive_fixed_restart: bool,
 
+    /// Whether GL_PRIMITIVE_RESTART is enabled
+    pub enabled_primitive_restart: bool,
+
     /// Whether GL_RASTERIZER_DISCARD is enabled
     pub enabled_rasterizer_discard: bool,
 
@@ -63,6 +76,9 @@ pub struct GlState {
     /// Whether GL_SAMPLE_COVERAGE is enabled
     pub enabled_sample_coverage: bool,
 
+    /// Whether GL_SAMPLE_MASK is enabled
+    pub enabled_sample_mask: bool,
+
     /// Whether GL_SCISSOR_TEST is enabled
     pub enabled_scissor_test: bool,
 
@@ -97,9 +113,15 @@ pub struct GlState {
     /// The latest value passed to `glClearStencil`.
     pub clear_stencil: gl::types::GLint,
 
-    /// The latest values passed to ``glColorMask`.
-    pub color_mask: (gl::types::GLboolean, gl::types::GLboolean,
-                     gl::types::GLboolean, gl::types::GLboolean),
+    /// The latest values passed to `glColorMask`. `None` if different values have been passed
+    /// to `glColorMaski` for the various attachments.
+    pub color_mask: Option<(gl::types::GLboolean, gl::types::GLboolean,
+                            gl::types::GLboolean, gl::types::GLboolean)>,
+
+    /// The latest values passed to `glColorMaski`, indexed by draw buffer. Only meaningful if
+    /// `color_mask` is `None`.
+    pub color_masks: SmallVec<[(gl::types::GLboolean, gl::types::GLboolean,
+                                gl::types::GLboolean, gl::types::GLboolean) ; 8]>,
 
     /// The latest buffer bound to `GL_ARRAY_BUFFER`.
     pub array_buffer_binding: gl::types::GLuint,
@@ -211,9 +233,15 @@ pub struct GlState {
     /// The latest value passed to `glLineWidth`.
     pub line_width: gl::types::GLfloat,
 
+    /// The latest values passed to `glLineStipple`.
+    pub line_stipple: (gl::types::GLint, gl::types::GLushort),
+
     /// The latest value passed to `glPointSize`.
     pub point_size: gl::types::GLfloat,
 
+    /// The latest value passed to `glFrontFace`.
+    pub front_face: gl::types::GLenum,
+
     /// The latest value passed to `glCullFace`.
     pub cull_face: gl::types::GLenum,
 
@@ -226,21 +254,36 @@ pub struct GlState {
     /// The latest value passed to `glHint` for smoothing.
     pub smooth: (gl::types::GLenum, gl::types::GLenum),
 
+    /// The latest value passed to `glLogicOp`.
+    pub logic_op: gl::types::GLenum,
+
     /// The latest value passed to `glProvokingVertex`.
     pub provoking_vertex: gl::types::GLenum,
 
     /// The latest value passed to `glPixelStore` with `GL_UNPACK_ALIGNMENT`.
     pub pixel_store_unpack_alignment: gl::types::GLint,
 
+    /// The latest value passed to `glPixelStore` with `GL_UNPACK_ROW_LENGTH`.
+    pub pixel_store_unpack_row_length: gl::types::GLint,
+
     /// The latest value passed to `glPixelStore` with `GL_PACK_ALIGNMENT`.
     pub pixel_store_pack_alignment: gl::types::GLint,
 
-    /// The latest value passed to `glClampColor`.
+    /// The latest value passed to `glClampColor` with `GL_CLAMP_READ_COLOR`.
     pub clamp_color: gl::types::GLenum,
 
+    /// The latest value passed to `glClampColor` with `GL_CLAMP_FRAGMENT_COLOR`.
+    pub clamp_fragment_color: gl::types::GLenum,
+
     /// The latest value passed to `glPatchParameter` with `GL_PATCH_VERTICES`.
     pub patch_patch_vertices: gl::types::GLint,
 
+    /// The latest value passed to `glPatchParameterfv` with `GL_PATCH_DEFAULT_OUTER_LEVEL`.
+    pub patch_default_outer_level: [f32; 4],
+
+    /// The latest value passed to `glPatchParameterfv` with `GL_PATCH_DEFAULT_INNER_LEVEL`.
+    pub patch_default_inner_level: [f32; 2],
+
     /// The id of the active texture unit.
     /// IMPORTANT: this is a raw number (0, 1, 2, ...), not an
     ///            enumeration (GL_TEXTURE0, GL_TEXTURE1, ...).
@@ -267,9 +310,16 @@ pub struct GlState {
     /// Current query being used for GL_TIME_ELAPSED​.
     pub time_elapsed_query: gl::types::GLuint,
 
+    /// Current queries being used for each pipeline statistic, in the order of the
+    /// `PipelineStatistic` enum.
+    pub pipeline_statistics_queries: [gl::types::GLuint; 10],
+
     /// Latest value passed to `glBeginConditionalRender​`.
     pub conditional_render: Option<(gl::types::GLuint, gl::types::GLenum)>,
 
+    /// The transform feedback object bound to `GL_TRANSFORM_FEEDBACK`.
+    pub transform_feedback: gl::types::GLuint,
+
     /// If `glBeginTransformFeedback​` has been called, the current primitive types. Otherwise None.
     // TODO: move this inside transform feedback objects
     pub transform_feedback_enabled: Option<gl::types::GLenum>,
@@ -278,9 +328,15 @@ pub struct GlState {
     // TODO: move this inside transform feedback objects
     pub transform_feedback_paused: bool,
 
+    /// The latest value passed to `glSampleMaski` for the first word of the mask.
+    pub sample_mask: gl::types::GLbitfield,
+
     /// The latest value passed to `glPrimitiveBoundingBox`.
     pub primitive_bounding_box: (f32, f32, f32, f32, f32, f32, f32, f32),
 
+    /// The latest value passed to `glPrimitiveRestartIndex`.
+    pub primitive_restart_index: gl::types::GLuint,
+
     /// Current draw call ID.
     /// We maintain a counter that is incremented at each draw call.
     pub next_draw_call_id: u64,
@@ -384,8 +440,10 @@ impl Default for GlState {
             enabled_depth_test: false,
             enabled_depth_clamp_near: false,
             enabled_depth_clamp_far: false,
+            enabled_color_logic_op: false,
             enabled_dither: false,
             enabled_framebuffer_srgb: false,
+            enabled_line_stipple: false,
             enabled_multisample: true,
             enabled_polygon_offset_fill: false,
             enabled_polygon_offset_line: false,
@@ -393,11 +451,13 @@ impl Default for GlState {
             enabled_rasterizer_discard: false,
             enabled_sample_alpha_to_coverage: false,
             enabled_sample_coverage: false,
+            enabled_sample_mask: false,
             enabled_scissor_test: false,
             enabled_stencil_test: false,
             enabled_line_smooth: false,
             enabled_polygon_smooth: false,
             enabled_primitive_fixed_restart: false,
+            enabled_primitive_restart: false,
             enabled_program_point_size: false,
             enabled_clip_planes: 0,
 
@@ -406,7 +466,8 @@ impl Default for GlState {
             clear_color: (0.0, 0.0, 0.0, 0.0),
             clear_depth: 1.0,
             clear_stencil: 0,
-            color_mask: (1, 1, 1, 1),
+            color_mask: Some((1, 1, 1, 1)),
+            color_masks: SmallVec::new(),
             array_buffer_binding: 0,
             pixel_pack_buffer_binding: 0,
             pixel_unpack_buffer_binding: 0,
@@ -442,15 +503,22 @@ impl Default for GlState {
             viewport: None,
             scissor: None,
             line_width: 1.0,
+            line_stipple: (1, 0xFFFF),
             point_size: 1.0,
+            front_face: gl::CCW,
             cull_face: gl::BACK,
             polygon_mode: gl::FILL,
             smooth: (gl::DONT_CARE, gl::DONT_CARE),
+            logic_op: gl::COPY,
             provoking_vertex: gl::LAST_VERTEX_CONVENTION,
             pixel_store_unpack_alignment: 4,
+            pixel_store_unpack_row_length: 0,
             pixel_store_pack_alignment: 4,
             clamp_color: gl::FIXED_ONLY,
+            clamp_fragment_color: gl::FIXED_ONLY,
             patch_patch_vertices: 3,
+            patch_default_outer_level: [1.0, 1.0, 1.0, 1.0],
+            patch_default_inner_level: [1.0, 1.0],
             active_texture: 0,
             texture_units: small_vec_one(),
             samples_passed_query: 0,
@@ -459,10 +527,14 @@ impl Default for GlState {
             primitives_generated_query: 0,
             transform_feedback_primitives_written_query: 0,
             time_elapsed_query: 0,
+            pipeline_statistics_queries: [0; 10],
             conditional_render: None,
+            sample_mask: 0xffffffff,
+            transform_feedback: 0,
             transform_feedback_enabled: None,
             transform_feedback_paused: false,
             primitive_bounding_box: (-1.0, -1.0, -1.0, -1.0, 1.0, 1.0, 1.0, 1.0),
+            primitive_restart_index: 0,
             polygon_offset: (0.0, 0.0),
 
             next_draw_call_id: 1,
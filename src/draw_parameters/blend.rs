@@ -20,6 +20,12 @@ pub struct Blend {
     /// The blending function for alpha channels.
     pub alpha: BlendingFunction,
     /// A constant color that can be used in the blending functions.
+    ///
+    /// This value is only used by the `ConstantColor`, `OneMinusConstantColor`,
+    /// `ConstantAlpha` and `OneMinusConstantAlpha` factors. For example a cross-fade between
+    /// the source and the destination can be done by using `ConstantAlpha` as the source
+    /// factor, `OneMinusConstantAlpha` as the destination factor, and putting the progress
+    /// of the transition in the alpha component of this value.
     pub constant_value: (f32, f32, f32, f32),
 }
 
@@ -165,22 +171,34 @@ pub enum LinearBlendingFactor {
     OneMinusDestinationAlpha,
 
     /// Multiply the source or destination component by the corresponding value
-    /// in `Blend::const_value`.
+    /// in `Blend::constant_value`.
     ConstantColor,
 
     /// Multiply the source or destination component by `1.0` minus the corresponding
-    /// value in `Blend::const_value`.
+    /// value in `Blend::constant_value`.
     OneMinusConstantColor,
 
-    /// Multiply the source or destination component by the alpha value of `Blend::const_value`.
+    /// Multiply the source or destination component by the alpha value of `Blend::constant_value`.
     ConstantAlpha,
 
     /// Multiply the source or destination component by `1.0` minus the alpha value of
-    /// `Blend::const_value`.
+    /// `Blend::constant_value`.
     OneMinusConstantAlpha,
 }
 
 impl LinearBlendingFactor {
+    /// Returns true if the factor uses the value of `Blend::constant_value`.
+    #[inline]
+    fn uses_constant_value(&self) -> bool {
+        match *self {
+            LinearBlendingFactor::ConstantColor |
+            LinearBlendingFactor::OneMinusConstantColor |
+            LinearBlendingFactor::ConstantAlpha |
+            LinearBlendingFactor::OneMinusConstantAlpha => true,
+            _ => false,
+        }
+    }
+
     fn to_glenum(&self) -> gl::types::GLenum {
         match *self {
             LinearBlendingFactor::Zero => gl::ZERO,
@@ -281,22 +299,10 @@ pub fn sync_blending(ctxt: &mut CommandContext<'_>, blend: Blend) -> Result<(),
             .unwrap_or((LinearBlendingFactor::One, LinearBlendingFactor::Zero));
 
         // Updating the blending color if necessary.
-        if (color_factor_src == LinearBlendingFactor::ConstantColor ||
-           color_factor_src == LinearBlendingFactor::OneMinusConstantColor ||
-           color_factor_dst == LinearBlendingFactor::ConstantColor ||
-           color_factor_dst == LinearBlendingFactor::OneMinusConstantColor ||
-           alpha_factor_src == LinearBlendingFactor::ConstantColor ||
-           alpha_factor_src == LinearBlendingFactor::OneMinusConstantColor ||
-           alpha_factor_dst == LinearBlendingFactor::ConstantColor ||
-           alpha_factor_dst == LinearBlendingFactor::OneMinusConstantColor ||
-           color_factor_src == LinearBlendingFactor::ConstantAlpha ||
-           color_factor_src == LinearBlendingFactor::OneMinusConstantAlpha ||
-           color_factor_dst == LinearBlendingFactor::ConstantAlpha ||
-           color_factor_dst == LinearBlendingFactor::OneMinusConstantAlpha ||
-           alpha_factor_src == LinearBlendingFactor::ConstantAlpha ||
-           alpha_factor_src == LinearBlendingFactor::OneMinusConstantAlpha ||
-           alpha_factor_dst == LinearBlendingFactor::ConstantAlpha ||
-           alpha_factor_dst == LinearBlendingFactor::OneMinusConstantAlpha) && ctxt.state.blend_color != blend.constant_value {
+        if (color_factor_src.uses_constant_value() || color_factor_dst.uses_constant_value() ||
+            alpha_factor_src.uses_constant_value() || alpha_factor_dst.uses_constant_value()) &&
+           ctxt.state.blend_color != blend.constant_value
+        {
             let (r, g, b, a) = blend.constant_value;
             unsafe { ctxt.gl.BlendColor(r, g, b, a); }
             ctxt.state.blend_color = blend.constant_value;
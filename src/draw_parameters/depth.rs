@@ -149,7 +149,11 @@ pub enum DepthClamp {
 
     /// Clamp the depth values. All samples will always be drawn.
     ///
-    /// This value is only supported on OpenGL.
+    /// This is typically used when rendering shadow maps, so that shadow casters located
+    /// between the light and the near plane aren't clipped.
+    ///
+    /// This value is supported on OpenGL 3.2 and above, or if `GL_ARB_depth_clamp`,
+    /// `GL_NV_depth_clamp` or `GL_EXT_depth_clamp` is available.
     Clamp,
 
     /// Depth values inferior to `0.0` will be clamped to `0.0`.
@@ -163,9 +167,16 @@ pub enum DepthClamp {
     ClampFar,
 }
 
+/// Returns true if `GL_DEPTH_CLAMP` can be enabled and disabled.
+fn is_depth_clamp_supported(ctxt: &CommandContext<'_>) -> bool {
+    ctxt.version >= &Version(Api::Gl, 3, 2) || ctxt.extensions.gl_arb_depth_clamp ||
+    ctxt.extensions.gl_nv_depth_clamp || ctxt.extensions.gl_ext_depth_clamp
+}
+
 pub fn sync_depth(ctxt: &mut CommandContext<'_>, depth: &Depth) -> Result<(), DrawError> {
     // depth clamp
     {
+        let clamp_supported = is_depth_clamp_supported(ctxt);
         let state = &mut *ctxt.state;
         match (depth.clamp, &mut state.enabled_depth_clamp_near,
                &mut state.enabled_depth_clamp_far)
@@ -174,9 +185,7 @@ pub fn sync_depth(ctxt: &mut CommandContext<'_>, depth: &Depth) -> Result<(), Dr
             (DepthClamp::Clamp, &mut true, &mut true) => (),
 
             (DepthClamp::NoClamp, near, far) => {
-                if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_depth_clamp ||
-                   ctxt.extensions.gl_nv_depth_clamp
-                {
+                if clamp_supported {
                     unsafe { ctxt.gl.Disable(gl::DEPTH_CLAMP) };
                     *near = false;
                     *far = false;
@@ -186,9 +195,7 @@ pub fn sync_depth(ctxt: &mut CommandContext<'_>, depth: &Depth) -> Result<(), Dr
             },
 
             (DepthClamp::Clamp, near, far) => {
-                if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_depth_clamp ||
-                   ctxt.extensions.gl_nv_depth_clamp
-                {
+                if clamp_supported {
                     unsafe { ctxt.gl.Enable(gl::DEPTH_CLAMP) };
                     *near = true;
                     *far = true;
@@ -67,7 +67,7 @@ use crate::context::Context;
 use crate::version::Version;
 use crate::version::Api;
 
-use crate::index::PrimitiveType;
+use crate::index::{IndexType, PrimitiveType};
 
 use crate::QueryExt;
 use crate::CapabilitiesSource;
@@ -77,12 +77,14 @@ use crate::ToGlEnum;
 use crate::vertex::TransformFeedbackSession;
 
 use std::ops::Range;
+use std::iter;
 
 pub use self::blend::{Blend, BlendingFunction, LinearBlendingFactor};
 pub use self::depth::{Depth, DepthTest, DepthClamp};
 pub use self::query::{QueryCreationError};
 pub use self::query::{SamplesPassedQuery, TimeElapsedQuery, PrimitivesGeneratedQuery};
 pub use self::query::{AnySamplesPassedQuery, TransformFeedbackPrimitivesWrittenQuery};
+pub use self::query::{PipelineStatisticsQuery, PipelineStatistic};
 pub use self::stencil::{StencilTest, StencilOperation, Stencil};
 
 mod blend;
@@ -131,6 +133,19 @@ mod stencil;
 /// By doing so you can use backface culling to discard all the triangles that are not
 /// facing the screen, and increase your framerate.
 ///
+/// # Rendering to a texture
+///
+/// The winding is determined in window coordinates, where `(-1, -1)` is the bottom-left corner
+/// of the target. OpenGL uses this origin for the default framebuffer and for textures alike, so
+/// the same culling mode works on both as long as you use the same projection. This is why glium
+/// never adjusts the culling mode depending on the kind of surface: doing so would invert the
+/// culling of the draws that use the same projection for both, which is the common case.
+///
+/// However images are often stored with their first row at the top, and it is common to
+/// render to a texture with a Y-flipped projection matrix so that the texture can be read
+/// back or saved in this order. Flipping the Y axis inverts the winding of all the triangles,
+/// and you must then use `flipped()` to get the same result as when drawing to the window.
+///
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BackfaceCullingMode {
     /// All triangles are always drawn.
@@ -143,6 +158,96 @@ pub enum BackfaceCullingMode {
     CullClockwise
 }
 
+impl BackfaceCullingMode {
+    /// Returns the culling mode that discards the same triangles when the Y axis of the
+    /// projection is flipped.
+    ///
+    /// ```
+    /// use glium::BackfaceCullingMode;
+    /// assert_eq!(BackfaceCullingMode::CullClockwise.flipped(),
+    ///            BackfaceCullingMode::CullCounterClockwise);
+    /// ```
+    #[inline]
+    pub fn flipped(self) -> BackfaceCullingMode {
+        match self {
+            BackfaceCullingMode::CullingDisabled => BackfaceCullingMode::CullingDisabled,
+            BackfaceCullingMode::CullCounterClockwise => BackfaceCullingMode::CullClockwise,
+            BackfaceCullingMode::CullClockwise => BackfaceCullingMode::CullCounterClockwise,
+        }
+    }
+
+    /// Returns the face that must be culled to get the same result with the lower-level
+    /// `cull_face` parameter, given the winding of the front faces.
+    ///
+    /// ```
+    /// use glium::draw_parameters::{BackfaceCullingMode, CullFace, Winding};
+    /// assert_eq!(BackfaceCullingMode::CullClockwise.to_cull_face(Winding::CounterClockwise),
+    ///            Some(CullFace::Back));
+    /// assert_eq!(BackfaceCullingMode::CullClockwise.to_cull_face(Winding::Clockwise),
+    ///            Some(CullFace::Front));
+    /// ```
+    #[inline]
+    pub fn to_cull_face(self, front_face: Winding) -> Option<CullFace> {
+        match (self, front_face) {
+            (BackfaceCullingMode::CullingDisabled, _) => None,
+            (BackfaceCullingMode::CullCounterClockwise, Winding::CounterClockwise) => Some(CullFace::Front),
+            (BackfaceCullingMode::CullCounterClockwise, Winding::Clockwise) => Some(CullFace::Back),
+            (BackfaceCullingMode::CullClockwise, Winding::CounterClockwise) => Some(CullFace::Back),
+            (BackfaceCullingMode::CullClockwise, Winding::Clockwise) => Some(CullFace::Front),
+        }
+    }
+}
+
+/// The winding of the vertices of a triangle, as seen on the target surface.
+///
+/// Used by the `front_face` draw parameter to choose which triangles are considered to be
+/// facing the camera.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Winding {
+    /// The vertices are in clockwise order.
+    Clockwise,
+
+    /// The vertices are in counterclockwise order. This is the default value of OpenGL.
+    CounterClockwise,
+}
+
+impl ToGlEnum for Winding {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            Winding::Clockwise => gl::CW,
+            Winding::CounterClockwise => gl::CCW,
+        }
+    }
+}
+
+/// Which faces of the triangles must be discarded.
+///
+/// Whether a triangle is a front face or a back face depends on the `front_face` draw
+/// parameter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CullFace {
+    /// Triangles that face the camera won't be drawn.
+    Front,
+
+    /// Triangles that don't face the camera won't be drawn.
+    Back,
+
+    /// No triangle will be drawn. Points and lines are still drawn.
+    FrontAndBack,
+}
+
+impl ToGlEnum for CullFace {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            CullFace::Front => gl::FRONT,
+            CullFace::Back => gl::BACK,
+            CullFace::FrontAndBack => gl::FRONT_AND_BACK,
+        }
+    }
+}
+
 /// Defines how the device should render polygons.
 ///
 /// The usual value is `Fill`, which fills the content of polygon with the color. However other
@@ -227,6 +332,70 @@ pub enum ProvokingVertex {
     FirstVertex,
 }
 
+/// Bitwise operation applied between the source and the destination colors.
+///
+/// In the descriptions below, `s` is the color written by the fragment shader and `d` is the
+/// color that already exists in the framebuffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogicOp {
+    /// `0`
+    Clear,
+    /// `1`
+    Set,
+    /// `s`
+    Copy,
+    /// `!s`
+    CopyInverted,
+    /// `d`
+    Noop,
+    /// `!d`
+    Invert,
+    /// `s & d`
+    And,
+    /// `!(s & d)`
+    Nand,
+    /// `s | d`
+    Or,
+    /// `!(s | d)`
+    Nor,
+    /// `s ^ d`
+    Xor,
+    /// `!(s ^ d)`
+    Equiv,
+    /// `s & !d`
+    AndReverse,
+    /// `!s & d`
+    AndInverted,
+    /// `s | !d`
+    OrReverse,
+    /// `!s | d`
+    OrInverted,
+}
+
+impl ToGlEnum for LogicOp {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            LogicOp::Clear => gl::CLEAR,
+            LogicOp::Set => gl::SET,
+            LogicOp::Copy => gl::COPY,
+            LogicOp::CopyInverted => gl::COPY_INVERTED,
+            LogicOp::Noop => gl::NOOP,
+            LogicOp::Invert => gl::INVERT,
+            LogicOp::And => gl::AND,
+            LogicOp::Nand => gl::NAND,
+            LogicOp::Or => gl::OR,
+            LogicOp::Nor => gl::NOR,
+            LogicOp::Xor => gl::XOR,
+            LogicOp::Equiv => gl::EQUIV,
+            LogicOp::AndReverse => gl::AND_REVERSE,
+            LogicOp::AndInverted => gl::AND_INVERTED,
+            LogicOp::OrReverse => gl::OR_REVERSE,
+            LogicOp::OrInverted => gl::OR_INVERTED,
+        }
+    }
+}
+
 /// Represents the parameters to use when drawing.
 ///
 /// Example:
@@ -252,6 +421,9 @@ pub struct DrawParameters<'a> {
 
     /// The effect that the GPU will use to merge the existing pixel with the pixel that is
     /// being written.
+    ///
+    /// Integer color attachments can't be blended. Drawing to a framebuffer that has one returns
+    /// a `BlendingWithIntegralAttachment` error if this isn't the default value.
     pub blend: Blend,
 
     /// Allows you to disable some color components.
@@ -264,11 +436,50 @@ pub struct DrawParameters<'a> {
     /// is `(true, true, true, true)`.
     pub color_mask: (bool, bool, bool, bool),
 
+    /// Color masks to use for each draw buffer, which corresponds to the fragment shader output
+    /// of the same location. Default is `None`.
+    ///
+    /// If specified, this takes precedence over `color_mask`. This allows you for example to
+    /// write to some attachments of a `MultiOutputFrameBuffer` and not to the others with a
+    /// single draw call. Attachments beyond the end of the slice are not written to.
+    ///
+    /// Requires OpenGL 3.0 or OpenGL ES 3.2, otherwise drawing will return
+    /// `IndexedColorMaskNotSupported`. If the number of masks is larger than
+    /// `GL_MAX_DRAW_BUFFERS`, drawing will return `TooManyColorMasks`. An empty slice is the
+    /// same as `None`.
+    pub color_masks: Option<&'a [(bool, bool, bool, bool)]>,
+
+    /// If `Some`, the colors are combined with the content of the framebuffer with a bitwise
+    /// operation instead of being written as they are.
+    ///
+    /// The operation is applied to the bits of the framebuffer's format, which means that
+    /// for example `Xor` can be used to draw a cursor that can be erased by drawing it a
+    /// second time. It has no effect on floating-point and sRGB attachments.
+    ///
+    /// Logic operations and blending are mutually exclusive. The draw function returns a
+    /// `LogicOpWithBlending` error if `blend` isn't the default value, and a
+    /// `LogicOpNotSupported` error on OpenGL ES, which doesn't support logic operations.
+    /// The default value is `None`.
+    pub color_logic_op: Option<LogicOp>,
+
     /// Width in pixels of the lines to draw when drawing lines.
     ///
     /// `None` means "don't care". Use this when you don't draw lines.
     pub line_width: Option<f32>,
 
+    /// If `Some`, the lines are drawn with a dashed pattern.
+    ///
+    /// The first value is the number of pixels that each bit of the pattern covers, and must
+    /// be between 1 and 256 or the draw function returns an `InvalidLineStippleFactor` error.
+    /// The second value is the pattern itself, starting with its least
+    /// significant bit. A bit set to 1 means that the corresponding pixels are drawn.
+    ///
+    /// Line stippling is only available on OpenGL compatibility profiles. The draw function
+    /// returns a `LineStippleNotSupported` error on core profiles and on OpenGL ES, where the
+    /// only way to draw dashed lines is to discard fragments in the fragment shader, for example
+    /// depending on the distance along the line. The default value is `None`.
+    pub line_stipple: Option<(i32, u16)>,
+
     /// Diameter in pixels of the points to draw when drawing points.
     ///
     /// `None` means "don't care". Use this when you don't draw points.
@@ -286,9 +497,28 @@ pub struct DrawParameters<'a> {
     /// After the vertex shader stage, the GPU will try to remove the faces that aren't facing
     /// the camera.
     ///
-    /// See the `BackfaceCullingMode` documentation for more infos.
+    /// See the `BackfaceCullingMode` documentation for more infos, and its
+    /// [rendering to a texture](enum.BackfaceCullingMode.html#rendering-to-a-texture) section
+    /// if your projection flips the Y axis when drawing to a texture.
+    ///
+    /// This value is ignored if `cull_face` is `Some`.
     pub backface_culling: BackfaceCullingMode,
 
+    /// Which winding the triangles that face the camera have. The default value is
+    /// `CounterClockwise`.
+    ///
+    /// This changes the value of `gl_FrontFacing` in the fragment shader, and which faces the
+    /// `cull_face` parameter refers to. The `backface_culling` parameter and the stencil
+    /// parameters are expressed in terms of winding, and aren't affected.
+    pub front_face: Winding,
+
+    /// Lower-level alternative to `backface_culling`. If `Some`, the GPU discards the given
+    /// faces, and `backface_culling` is ignored. The default value is `None`.
+    ///
+    /// For example two-sided materials can be drawn in two passes, the first one with
+    /// `Some(CullFace::Front)` and the second one with `Some(CullFace::Back)`.
+    pub cull_face: Option<CullFace>,
+
     /// How to render polygons. The default value is `Fill`.
     ///
     /// See the documentation of `PolygonMode` for more infos.
@@ -301,9 +531,26 @@ pub struct DrawParameters<'a> {
     /// creating the window.
     pub multisampling: bool,
 
-    /// Whether dithering is activated. Default value is `true`.
+    /// If specified, only the samples whose bit is set in this mask are written by the
+    /// fragments. Default is `None`, meaning that all the samples are written.
+    ///
+    /// This corresponds to `GL_SAMPLE_MASK` and is only useful with a multisampled target. It
+    /// can be used for example for stippled transparency or custom multisampling resolves.
+    ///
+    /// Requires OpenGL 3.2, OpenGL ES 3.1 or `GL_ARB_texture_multisample`, otherwise drawing
+    /// will return `SampleMaskNotSupported`.
+    pub sample_mask: Option<u32>,
+
+    /// Whether dithering is activated. Default value is `true`, which is also the default
+    /// value of OpenGL.
+    ///
+    /// Dithering will smoothen the transition between colors in your color buffer. It only
+    /// has a visible effect when the color buffer has a low number of bits per component, for
+    /// example with a 16-bits `RGB565` surface, where it can hide the banding of gradients at
+    /// the cost of some noise. Set this to `false` if you need exact and reproducible colors.
     ///
-    /// Dithering will smoothen the transition between colors in your color buffer.
+    /// The value is applied at each draw call. Note that the implementation is free to ignore
+    /// this setting, and that clearing a surface uses the value of the latest draw call.
     pub dithering: bool,
 
     /// The viewport to use when drawing.
@@ -317,6 +564,19 @@ pub struct DrawParameters<'a> {
     /// `None` means "use the whole surface".
     pub viewport: Option<Rect>,
 
+    /// Multiple viewports to use when drawing. Default is `None`.
+    ///
+    /// If specified, this takes precedence over `viewport`. The geometry shader of your program
+    /// can then choose in which viewport each primitive is drawn by writing to
+    /// `gl_ViewportIndex`. This is useful for example to render all the cascades of a shadow
+    /// map or both eyes of a VR headset in one draw call.
+    ///
+    /// Requires OpenGL 4.1 or `GL_ARB_viewport_array`, otherwise drawing will return
+    /// `ViewportArrayNotSupported`. If the number of viewports is larger than
+    /// `GL_MAX_VIEWPORTS`, drawing will return `TooManyViewports`. An empty slice is the same
+    /// as `None`.
+    pub viewports: Option<&'a [Rect]>,
+
     /// If specified, only pixels in this rect will be displayed. Default is `None`.
     ///
     /// This is different from a viewport. The image will stretch to fill the viewport, but
@@ -326,10 +586,12 @@ pub struct DrawParameters<'a> {
     /// If `false`, the pipeline will stop after the primitives generation stage. The default
     /// value is `true`.
     ///
-    /// If `false`, the fragment shader of your program won't be executed.
+    /// If `false`, the fragment shader of your program won't be executed and no pixel of the
+    /// target is modified. This corresponds to `GL_RASTERIZER_DISCARD`.
     ///
-    /// If `false`, drawing may return `TransformFeedbackNotSupported` if the backend doesn't
-    /// support this feature.
+    /// If `false`, drawing may return `RasterizerDiscardNotSupported` if the backend doesn't
+    /// support this feature. It requires OpenGL 3.0, OpenGL ES 3.0 or
+    /// `GL_EXT_transform_feedback`.
     ///
     /// This parameter may seem pointless, but it can be useful when you use transform
     /// feedback or if you just use your shaders to write to a buffer.
@@ -350,6 +612,13 @@ pub struct DrawParameters<'a> {
     pub transform_feedback_primitives_written_query:
                                     Option<&'a TransformFeedbackPrimitivesWrittenQuery>,
 
+    /// The counters of each of these queries are increased by the work done by the GPU for
+    /// this draw command. Default is an empty list.
+    ///
+    /// Each query measures a different `PipelineStatistic`. If two queries of the list
+    /// measure the same statistic, drawing will return `WrongQueryOperation`.
+    pub pipeline_statistics_queries: &'a [&'a PipelineStatisticsQuery],
+
     /// If set, the commands will only be executed if the specified query contains `true` or
     /// a number different than 0.
     pub condition: Option<ConditionalRendering<'a>>,
@@ -389,13 +658,51 @@ pub struct DrawParameters<'a> {
     /// If enabled, will split the index buffer (if any is used in the draw call)
     /// at the MAX value of the IndexType (u8::MAX, u16::MAX or u32::MAX) and start a new primitive
     /// of the same type ("primitive restarting"). Supported on > OpenGL 3.1 or OpenGL ES 3.0.
-    /// If the backend does not support GL_PRIMITIVE_RESTART_FIXED_INDEX, an Error
-    /// of type `FixedIndexRestartingNotSupported` will be returned.
+    /// If the backend supports neither GL_PRIMITIVE_RESTART_FIXED_INDEX nor GL_PRIMITIVE_RESTART,
+    /// an Error of type `FixedIndexRestartingNotSupported` will be returned.
+    ///
+    /// Before OpenGL 4.3, glium uses GL_PRIMITIVE_RESTART and sets the restart index to the MAX
+    /// value of the IndexType before each draw call. In both cases, the MAX value can't be used
+    /// to refer to a vertex. If you need that many vertices, use a larger IndexType.
+    ///
+    /// When the vertex sources contain more vertices than the MAX value, a warning is reported
+    /// through the debug output, as the last vertices can't be drawn with this index type.
+    /// The content of the index buffer itself isn't checked, as this would require reading it
+    /// back from the GPU at each draw call.
     pub primitive_restart_index: bool,
 
     /// If enabled, shifts the depth value of towards of away from the camera. This is useful for
     /// drawing decals and wireframes, for example.
     pub polygon_offset: PolygonOffset,
+
+    /// The outer and inner tessellation levels to use when the program has a tessellation
+    /// evaluation shader but no tessellation control shader.
+    ///
+    /// If a tessellation control shader is present, it writes the levels itself and this
+    /// field has no effect. The default value is `1.0` for every level, as in OpenGL.
+    ///
+    /// OpenGL ES always requires a tessellation control shader, so this parameter is ignored
+    /// if the backend doesn't support it.
+    pub tessellation_default_levels: TessellationLevels,
+}
+
+/// Tessellation levels used by the tessellation primitive generator.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TessellationLevels {
+    /// Corresponds to `gl_TessLevelOuter`.
+    pub outer: [f32; 4],
+    /// Corresponds to `gl_TessLevelInner`.
+    pub inner: [f32; 2],
+}
+
+impl Default for TessellationLevels {
+    #[inline]
+    fn default() -> TessellationLevels {
+        TessellationLevels {
+            outer: [1.0, 1.0, 1.0, 1.0],
+            inner: [1.0, 1.0],
+        }
+    }
 }
 
 /// Condition whether to render or not.
@@ -470,20 +777,28 @@ impl<'a> Default for DrawParameters<'a> {
             stencil: Default::default(),
             blend: Default::default(),
             color_mask: (true, true, true, true),
+            color_masks: None,
+            color_logic_op: None,
             line_width: None,
+            line_stipple: None,
             point_size: None,
             backface_culling: BackfaceCullingMode::CullingDisabled,
+            front_face: Winding::CounterClockwise,
+            cull_face: None,
             polygon_mode: PolygonMode::Fill,
             clip_planes_bitmask: 0,
             multisampling: true,
+            sample_mask: None,
             dithering: true,
             viewport: None,
+            viewports: None,
             scissor: None,
             draw_primitives: true,
             samples_passed_query: None,
             time_elapsed_query: None,
             primitives_generated_query: None,
             transform_feedback_primitives_written_query: None,
+            pipeline_statistics_queries: &[],
             condition: None,
             transform_feedback: None,
             smooth: None,
@@ -491,6 +806,7 @@ impl<'a> Default for DrawParameters<'a> {
             primitive_bounding_box: (-1.0 .. 1.0, -1.0 .. 1.0, -1.0 .. 1.0, -1.0 .. 1.0),
             primitive_restart_index: false,
             polygon_offset: Default::default(),
+            tessellation_default_levels: Default::default(),
         }
     }
 }
@@ -514,36 +830,86 @@ pub fn validate(context: &Context, params: &DrawParameters<'_>) -> Result<(), Dr
 
 #[doc(hidden)]
 pub fn sync(ctxt: &mut context::CommandContext<'_>, draw_parameters: &DrawParameters<'_>,
-            dimensions: (u32, u32), primitives_types: PrimitiveType) -> Result<(), DrawError>
+            dimensions: (u32, u32), primitives_types: PrimitiveType,
+            indices_type: Option<IndexType>) -> Result<(), DrawError>
 {
     depth::sync_depth(ctxt, &draw_parameters.depth)?;
-    stencil::sync_stencil(ctxt, &draw_parameters.stencil);
+    stencil::sync_stencil(ctxt, &draw_parameters.stencil, draw_parameters.front_face);
     blend::sync_blending(ctxt, draw_parameters.blend)?;
-    sync_color_mask(ctxt, draw_parameters.color_mask);
+    sync_color_mask(ctxt, draw_parameters.color_mask, draw_parameters.color_masks)?;
+    sync_color_logic_op(ctxt, draw_parameters.color_logic_op, &draw_parameters.blend)?;
     sync_line_width(ctxt, draw_parameters.line_width);
+    sync_line_stipple(ctxt, draw_parameters.line_stipple)?;
     sync_point_size(ctxt, draw_parameters.point_size);
-    sync_polygon_mode(ctxt, draw_parameters.backface_culling, draw_parameters.polygon_mode);
+    sync_polygon_mode(ctxt, draw_parameters.backface_culling, draw_parameters.front_face,
+                      draw_parameters.cull_face, draw_parameters.polygon_mode);
     sync_clip_planes_bitmask(ctxt, draw_parameters.clip_planes_bitmask)?;
     sync_multisampling(ctxt, draw_parameters.multisampling);
+    sync_sample_mask(ctxt, draw_parameters.sample_mask)?;
     sync_dithering(ctxt, draw_parameters.dithering);
-    sync_viewport_scissor(ctxt, draw_parameters.viewport, draw_parameters.scissor,
-                          dimensions);
+    sync_viewport_scissor(ctxt, draw_parameters.viewport, draw_parameters.viewports,
+                          draw_parameters.scissor, dimensions)?;
     sync_rasterizer_discard(ctxt, draw_parameters.draw_primitives)?;
     sync_queries(ctxt, draw_parameters.samples_passed_query,
                       draw_parameters.time_elapsed_query,
                       draw_parameters.primitives_generated_query,
                       draw_parameters.transform_feedback_primitives_written_query)?;
+    query::sync_pipeline_statistics_queries(ctxt, draw_parameters.pipeline_statistics_queries)?;
     sync_conditional_render(ctxt, draw_parameters.condition);
     sync_smooth(ctxt, draw_parameters.smooth, primitives_types)?;
     sync_provoking_vertex(ctxt, draw_parameters.provoking_vertex)?;
     sync_primitive_bounding_box(ctxt, &draw_parameters.primitive_bounding_box);
-    sync_primitive_restart_index(ctxt, draw_parameters.primitive_restart_index)?;
+    sync_primitive_restart_index(ctxt, draw_parameters.primitive_restart_index, indices_type)?;
     sync_polygon_offset(ctxt, draw_parameters.polygon_offset);
+    sync_tessellation_default_levels(ctxt, draw_parameters.tessellation_default_levels);
 
     Ok(())
 }
 
-fn sync_color_mask(ctxt: &mut context::CommandContext<'_>, mask: (bool, bool, bool, bool)) {
+fn sync_color_mask(ctxt: &mut context::CommandContext<'_>, mask: (bool, bool, bool, bool),
+                   masks: Option<&[(bool, bool, bool, bool)]>) -> Result<(), DrawError>
+{
+    if let Some(masks) = masks.filter(|m| !m.is_empty()) {
+        if !(ctxt.version >= &Version(Api::Gl, 3, 0)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 2))
+        {
+            return Err(DrawError::IndexedColorMaskNotSupported);
+        }
+
+        let max_draw_buffers = ctxt.capabilities.max_draw_buffers as usize;
+        if masks.len() > max_draw_buffers {
+            return Err(DrawError::TooManyColorMasks);
+        }
+
+        // `glColorMask` sets the masks of all the attachments at once
+        if let Some(mask) = ctxt.state.color_mask.take() {
+            ctxt.state.color_masks.clear();
+            ctxt.state.color_masks.extend(iter::repeat(mask).take(max_draw_buffers));
+        }
+
+        for index in 0 .. max_draw_buffers {
+            let mask = masks.get(index).cloned().unwrap_or((false, false, false, false));
+            let mask = (mask.0 as gl::types::GLboolean, mask.1 as gl::types::GLboolean,
+                        mask.2 as gl::types::GLboolean, mask.3 as gl::types::GLboolean);
+
+            if ctxt.state.color_masks.get(index) == Some(&mask) {
+                continue;
+            }
+
+            unsafe {
+                ctxt.gl.ColorMaski(index as gl::types::GLuint, mask.0, mask.1, mask.2, mask.3);
+            }
+
+            if index < ctxt.state.color_masks.len() {
+                ctxt.state.color_masks[index] = mask;
+            } else {
+                ctxt.state.color_masks.push(mask);
+            }
+        }
+
+        return Ok(());
+    }
+
     let mask = (
         if mask.0 { 1 } else { 0 },
         if mask.1 { 1 } else { 0 },
@@ -551,13 +917,53 @@ fn sync_color_mask(ctxt: &mut context::CommandContext<'_>, mask: (bool, bool, bo
         if mask.3 { 1 } else { 0 },
     );
 
-    if ctxt.state.color_mask != mask {
+    if ctxt.state.color_mask != Some(mask) {
         unsafe {
             ctxt.gl.ColorMask(mask.0, mask.1, mask.2, mask.3);
         }
 
-        ctxt.state.color_mask = mask;
+        ctxt.state.color_mask = Some(mask);
     }
+
+    Ok(())
+}
+
+fn sync_color_logic_op(ctxt: &mut context::CommandContext<'_>, logic_op: Option<LogicOp>,
+                       blend: &Blend) -> Result<(), DrawError>
+{
+    if let Some(logic_op) = logic_op {
+        if !(ctxt.version >= &Version(Api::Gl, 1, 1)) {
+            return Err(DrawError::LogicOpNotSupported);
+        }
+
+        if blend.color != BlendingFunction::AlwaysReplace ||
+           blend.alpha != BlendingFunction::AlwaysReplace
+        {
+            return Err(DrawError::LogicOpWithBlending);
+        }
+
+        let logic_op = logic_op.to_glenum();
+
+        unsafe {
+            if !ctxt.state.enabled_color_logic_op {
+                ctxt.gl.Enable(gl::COLOR_LOGIC_OP);
+                ctxt.state.enabled_color_logic_op = true;
+            }
+
+            if ctxt.state.logic_op != logic_op {
+                ctxt.gl.LogicOp(logic_op);
+                ctxt.state.logic_op = logic_op;
+            }
+        }
+
+    } else if ctxt.state.enabled_color_logic_op {
+        unsafe {
+            ctxt.gl.Disable(gl::COLOR_LOGIC_OP);
+            ctxt.state.enabled_color_logic_op = false;
+        }
+    }
+
+    Ok(())
 }
 
 fn sync_line_width(ctxt: &mut context::CommandContext<'_>, line_width: Option<f32>) {
@@ -571,6 +977,45 @@ fn sync_line_width(ctxt: &mut context::CommandContext<'_>, line_width: Option<f3
     }
 }
 
+fn sync_line_stipple(ctxt: &mut context::CommandContext<'_>, line_stipple: Option<(i32, u16)>)
+                     -> Result<(), DrawError>
+{
+    if let Some((factor, pattern)) = line_stipple {
+        // removed from OpenGL 3.1, except in compatibility profiles
+        if !(ctxt.version >= &Version(Api::Gl, 1, 0)) ||
+           (ctxt.version >= &Version(Api::Gl, 3, 1) &&
+            ctxt.capabilities.profile != Some(context::Profile::Compatibility) &&
+            !ctxt.extensions.gl_arb_compatibility)
+        {
+            return Err(DrawError::LineStippleNotSupported);
+        }
+
+        if factor < 1 || factor > 256 {
+            return Err(DrawError::InvalidLineStippleFactor);
+        }
+
+        unsafe {
+            if !ctxt.state.enabled_line_stipple {
+                ctxt.gl.Enable(gl::LINE_STIPPLE);
+                ctxt.state.enabled_line_stipple = true;
+            }
+
+            if ctxt.state.line_stipple != (factor, pattern) {
+                ctxt.gl.LineStipple(factor, pattern);
+                ctxt.state.line_stipple = (factor, pattern);
+            }
+        }
+
+    } else if ctxt.state.enabled_line_stipple {
+        unsafe {
+            ctxt.gl.Disable(gl::LINE_STIPPLE);
+            ctxt.state.enabled_line_stipple = false;
+        }
+    }
+
+    Ok(())
+}
+
 fn sync_point_size(ctxt: &mut context::CommandContext<'_>, point_size: Option<f32>) {
     if let Some(point_size) = point_size {
         if ctxt.state.point_size != point_size {
@@ -583,36 +1028,35 @@ fn sync_point_size(ctxt: &mut context::CommandContext<'_>, point_size: Option<f3
 }
 
 fn sync_polygon_mode(ctxt: &mut context::CommandContext<'_>, backface_culling: BackfaceCullingMode,
-                     polygon_mode: PolygonMode)
+                     front_face: Winding, cull_face: Option<CullFace>, polygon_mode: PolygonMode)
 {
+    // front face
+    unsafe {
+        let front_face = front_face.to_glenum();
+        if ctxt.state.front_face != front_face {
+            ctxt.gl.FrontFace(front_face);
+            ctxt.state.front_face = front_face;
+        }
+    }
+
     // back-face culling
-    // note: we never change the value of `glFrontFace`, whose default is GL_CCW
-    //  that's why `CullClockwise` uses `GL_BACK` for example
-    match backface_culling {
-        BackfaceCullingMode::CullingDisabled => unsafe {
+    let cull_face = cull_face.or_else(|| backface_culling.to_cull_face(front_face));
+    match cull_face {
+        None => unsafe {
             if ctxt.state.enabled_cull_face {
                 ctxt.gl.Disable(gl::CULL_FACE);
                 ctxt.state.enabled_cull_face = false;
             }
         },
-        BackfaceCullingMode::CullCounterClockwise => unsafe {
-            if !ctxt.state.enabled_cull_face {
-                ctxt.gl.Enable(gl::CULL_FACE);
-                ctxt.state.enabled_cull_face = true;
-            }
-            if ctxt.state.cull_face != gl::FRONT {
-                ctxt.gl.CullFace(gl::FRONT);
-                ctxt.state.cull_face = gl::FRONT;
-            }
-        },
-        BackfaceCullingMode::CullClockwise => unsafe {
+        Some(cull_face) => unsafe {
             if !ctxt.state.enabled_cull_face {
                 ctxt.gl.Enable(gl::CULL_FACE);
                 ctxt.state.enabled_cull_face = true;
             }
-            if ctxt.state.cull_face != gl::BACK {
-                ctxt.gl.CullFace(gl::BACK);
-                ctxt.state.cull_face = gl::BACK;
+            let cull_face = cull_face.to_glenum();
+            if ctxt.state.cull_face != cull_face {
+                ctxt.gl.CullFace(cull_face);
+                ctxt.state.cull_face = cull_face;
             }
         },
     }
@@ -630,8 +1074,7 @@ fn sync_polygon_mode(ctxt: &mut context::CommandContext<'_>, backface_culling: B
 fn sync_clip_planes_bitmask(ctxt: &mut context::CommandContext<'_>, clip_planes_bitmask: u32)
                             -> Result<(), DrawError> {
     unsafe {
-        let mut max_clip_planes: gl::types::GLint = 0;
-        ctxt.gl.GetIntegerv(gl::MAX_CLIP_DISTANCES, &mut max_clip_planes);
+        let max_clip_planes = ctxt.capabilities.max_clip_distances;
         for i in 0..32 {
             if clip_planes_bitmask & (1 << i) != ctxt.state.enabled_clip_planes & (1 << i) {
                 if clip_planes_bitmask & (1 << i) != 0 {
@@ -665,6 +1108,39 @@ fn sync_multisampling(ctxt: &mut context::CommandContext<'_>, multisampling: boo
     }
 }
 
+fn sync_sample_mask(ctxt: &mut context::CommandContext<'_>, sample_mask: Option<u32>)
+                    -> Result<(), DrawError>
+{
+    if let Some(mask) = sample_mask {
+        if !(ctxt.version >= &Version(Api::Gl, 3, 2)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 1)) &&
+           !ctxt.extensions.gl_arb_texture_multisample
+        {
+            return Err(DrawError::SampleMaskNotSupported);
+        }
+
+        unsafe {
+            if !ctxt.state.enabled_sample_mask {
+                ctxt.gl.Enable(gl::SAMPLE_MASK);
+                ctxt.state.enabled_sample_mask = true;
+            }
+
+            if ctxt.state.sample_mask != mask {
+                ctxt.gl.SampleMaski(0, mask);
+                ctxt.state.sample_mask = mask;
+            }
+        }
+
+    } else if ctxt.state.enabled_sample_mask {
+        unsafe {
+            ctxt.gl.Disable(gl::SAMPLE_MASK);
+            ctxt.state.enabled_sample_mask = false;
+        }
+    }
+
+    Ok(())
+}
+
 fn sync_dithering(ctxt: &mut context::CommandContext<'_>, dithering: bool) {
     if ctxt.state.enabled_dither != dithering {
         unsafe {
@@ -680,10 +1156,42 @@ fn sync_dithering(ctxt: &mut context::CommandContext<'_>, dithering: bool) {
 }
 
 fn sync_viewport_scissor(ctxt: &mut context::CommandContext<'_>, viewport: Option<Rect>,
-                         scissor: Option<Rect>, surface_dimensions: (u32, u32))
+                         viewports: Option<&[Rect]>, scissor: Option<Rect>,
+                         surface_dimensions: (u32, u32)) -> Result<(), DrawError>
 {
     // viewport
-    if let Some(viewport) = viewport {
+    if let Some(viewports) = viewports.filter(|v| !v.is_empty()) {
+        let max_viewports = match ctxt.capabilities.max_viewports {
+            Some(max) => max,
+            None => return Err(DrawError::ViewportArrayNotSupported),
+        };
+
+        if viewports.len() > max_viewports as usize {
+            return Err(DrawError::TooManyViewports);
+        }
+
+        let mut data = Vec::with_capacity(viewports.len() * 4);
+        for viewport in viewports {
+            if viewport.width > ctxt.capabilities.max_viewport_dims.0 as u32 ||
+               viewport.height > ctxt.capabilities.max_viewport_dims.1 as u32
+            {
+                return Err(DrawError::ViewportTooLarge);
+            }
+
+            data.push(viewport.left as gl::types::GLfloat);
+            data.push(viewport.bottom as gl::types::GLfloat);
+            data.push(viewport.width as gl::types::GLfloat);
+            data.push(viewport.height as gl::types::GLfloat);
+        }
+
+        unsafe {
+            ctxt.gl.ViewportArrayv(0, viewports.len() as gl::types::GLsizei, data.as_ptr());
+        }
+
+        // the cache only tracks the first viewport, which may or may not match
+        ctxt.state.viewport = None;
+
+    } else if let Some(viewport) = viewport {
         assert!(viewport.width <= ctxt.capabilities.max_viewport_dims.0 as u32,
                 "Viewport dimensions are too large");
         assert!(viewport.height <= ctxt.capabilities.max_viewport_dims.1 as u32,
@@ -738,13 +1246,15 @@ fn sync_viewport_scissor(ctxt: &mut context::CommandContext<'_>, viewport: Optio
             }
         }
     }
+
+    Ok(())
 }
 
 fn sync_rasterizer_discard(ctxt: &mut context::CommandContext<'_>, draw_primitives: bool)
                            -> Result<(), DrawError>
 {
     if ctxt.state.enabled_rasterizer_discard == draw_primitives {
-        if ctxt.version >= &Version(Api::Gl, 3, 0) {
+        if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.version >= &Version(Api::GlEs, 3, 0) {
             if draw_primitives {
                 unsafe { ctxt.gl.Disable(gl::RASTERIZER_DISCARD); }
                 ctxt.state.enabled_rasterizer_discard = false;
@@ -959,13 +1469,11 @@ fn sync_primitive_bounding_box(ctxt: &mut context::CommandContext<'_>,
 }
 
 fn sync_primitive_restart_index(ctxt: &mut context::CommandContext<'_>,
-                                enabled: bool)
+                                enabled: bool, indices_type: Option<IndexType>)
                                 -> Result<(), DrawError>
 {
-    // TODO: use GL_PRIMITIVE_RESTART (if possible) if
-    // GL_PRIMITIVE_RESTART_FIXED_INDEX is not supported
-    if ctxt.version >= &Version(Api::Gl, 3, 1)   || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
-    ctxt.extensions.gl_arb_es3_compatibility
+    if ctxt.version >= &Version(Api::Gl, 4, 3) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+       ctxt.extensions.gl_arb_es3_compatibility
     {
         if ctxt.state.enabled_primitive_fixed_restart != enabled {
             if enabled {
@@ -976,11 +1484,37 @@ fn sync_primitive_restart_index(ctxt: &mut context::CommandContext<'_>,
                 ctxt.state.enabled_primitive_fixed_restart = false;
             }
         }
+
+    } else if ctxt.version >= &Version(Api::Gl, 3, 1) {
+        // the fixed index isn't supported, so we emulate it by setting the restart index to the
+        // maximum value of the type of indices
+        if ctxt.state.enabled_primitive_restart != enabled {
+            if enabled {
+                unsafe { ctxt.gl.Enable(gl::PRIMITIVE_RESTART); }
+                ctxt.state.enabled_primitive_restart = true;
+            } else {
+                unsafe { ctxt.gl.Disable(gl::PRIMITIVE_RESTART); }
+                ctxt.state.enabled_primitive_restart = false;
+            }
+        }
+
+        if let (true, Some(indices_type)) = (enabled, indices_type) {
+            let index = match indices_type {
+                IndexType::U8 => 0xff,
+                IndexType::U16 => 0xffff,
+                IndexType::U32 => 0xffffffff,
+            };
+
+            if ctxt.state.primitive_restart_index != index {
+                unsafe { ctxt.gl.PrimitiveRestartIndex(index); }
+                ctxt.state.primitive_restart_index = index;
+            }
+        }
+
     } else if enabled {
         return Err(DrawError::FixedIndexRestartingNotSupported);
     }
 
-
     Ok(())
 }
 
@@ -1018,3 +1552,84 @@ fn sync_polygon_offset(ctxt: &mut context::CommandContext<'_>, offset: PolygonOf
     }
 }
 
+fn sync_tessellation_default_levels(ctxt: &mut context::CommandContext<'_>,
+                                    levels: TessellationLevels)
+{
+    if !(ctxt.version >= &Version(Api::Gl, 4, 0)) && !ctxt.extensions.gl_arb_tessellation_shader {
+        return;
+    }
+
+    unsafe {
+        if ctxt.state.patch_default_outer_level != levels.outer {
+            ctxt.gl.PatchParameterfv(gl::PATCH_DEFAULT_OUTER_LEVEL, levels.outer.as_ptr());
+            ctxt.state.patch_default_outer_level = levels.outer;
+        }
+
+        if ctxt.state.patch_default_inner_level != levels.inner {
+            ctxt.gl.PatchParameterfv(gl::PATCH_DEFAULT_INNER_LEVEL, levels.inner.as_ptr());
+            ctxt.state.patch_default_inner_level = levels.inner;
+        }
+    }
+}
+
+#[cfg(all(test, feature = "null_backend"))]
+mod tests {
+    use crate::backend::null::{build_context, build_program, NullBackend};
+    use crate::draw_parameters::{BackfaceCullingMode, Blend, Depth, DepthTest, DrawParameters};
+    use crate::index::{NoIndices, PrimitiveType};
+    use crate::gl;
+    use crate::uniforms::EmptyUniforms;
+    use crate::vertex::EmptyVertexAttributes;
+    use crate::{Frame, Surface};
+
+    #[test]
+    fn state_cache() {
+        let (backend, context) = build_context(NullBackend::new());
+        let program = build_program(&context);
+
+        let params = DrawParameters {
+            blend: Blend::alpha_blending(),
+            depth: Depth { test: DepthTest::IfLess, write: true, .. Default::default() },
+            backface_culling: BackfaceCullingMode::CullClockwise,
+            .. Default::default()
+        };
+
+        let mut frame = Frame::new(context.clone(), (800, 600));
+        frame.draw(EmptyVertexAttributes { len: 3 }, NoIndices(PrimitiveType::TrianglesList),
+                   &program, &EmptyUniforms, &params).unwrap();
+        assert!(backend.get_calls().contains(&"glBlendFuncSeparate"));
+
+        // drawing again with the same parameters only calls the draw function
+        backend.clear_calls();
+        frame.draw(EmptyVertexAttributes { len: 3 }, NoIndices(PrimitiveType::TrianglesList),
+                   &program, &EmptyUniforms, &params).unwrap();
+        assert_eq!(backend.get_calls(), vec!["glDrawArrays"]);
+
+        frame.finish().unwrap();
+    }
+
+    #[test]
+    fn color_masks_cache() {
+        let backend = NullBackend::new();
+        backend.set_integer(gl::MAX_DRAW_BUFFERS, &[4]);
+        let (backend, context) = build_context(backend);
+        let program = build_program(&context);
+
+        let masks = [(true, false, true, false), (true, true, true, true)];
+        let params = DrawParameters { color_masks: Some(&masks), .. Default::default() };
+
+        let mut frame = Frame::new(context.clone(), (800, 600));
+        frame.draw(EmptyVertexAttributes { len: 3 }, NoIndices(PrimitiveType::TrianglesList),
+                   &program, &EmptyUniforms, &params).unwrap();
+
+        // the second attachment already had the default mask
+        assert_eq!(backend.get_calls().iter().filter(|&&c| c == "glColorMaski").count(), 3);
+
+        backend.clear_calls();
+        frame.draw(EmptyVertexAttributes { len: 3 }, NoIndices(PrimitiveType::TrianglesList),
+                   &program, &EmptyUniforms, &params).unwrap();
+        assert_eq!(backend.get_calls(), vec!["glDrawArrays"]);
+
+        frame.finish().unwrap();
+    }
+}
@@ -39,6 +39,7 @@ pub enum QueryType {
     Timestamp,
     PrimitivesGenerated,
     TransformFeedbackPrimitivesWritten,
+    PipelineStatistics(PipelineStatistic),
 }
 
 impl ToGlEnum for QueryType {
@@ -54,6 +55,72 @@ impl ToGlEnum for QueryType {
             QueryType::TransformFeedbackPrimitivesWritten => {
                 gl::TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN
             },
+            QueryType::PipelineStatistics(statistic) => statistic.to_glenum(),
+        }
+    }
+}
+
+/// A counter of the GPU pipeline that can be measured with a `PipelineStatisticsQuery`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PipelineStatistic {
+    /// Number of vertices submitted by the draw commands.
+    VerticesSubmitted,
+    /// Number of primitives submitted by the draw commands.
+    PrimitivesSubmitted,
+    /// Number of times the vertex shader has been executed.
+    VertexShaderInvocations,
+    /// Number of patches processed by the tessellation control shader.
+    TessellationControlShaderPatches,
+    /// Number of times the tessellation evaluation shader has been executed.
+    TessellationEvaluationShaderInvocations,
+    /// Number of times the geometry shader has been executed.
+    GeometryShaderInvocations,
+    /// Number of primitives emitted by the geometry shader.
+    GeometryShaderPrimitivesEmitted,
+    /// Number of times the fragment shader has been executed.
+    FragmentShaderInvocations,
+    /// Number of primitives that reached the clipping stage.
+    ClippingInputPrimitives,
+    /// Number of primitives that came out of the clipping stage. This can be larger than the
+    /// number of input primitives if primitives have been split, or smaller if some have been
+    /// entirely clipped.
+    ClippingOutputPrimitives,
+}
+
+/// All the statistics, in the order of the indices used for `pipeline_statistics_queries`.
+const PIPELINE_STATISTICS: [PipelineStatistic; 10] = [
+    PipelineStatistic::VerticesSubmitted,
+    PipelineStatistic::PrimitivesSubmitted,
+    PipelineStatistic::VertexShaderInvocations,
+    PipelineStatistic::TessellationControlShaderPatches,
+    PipelineStatistic::TessellationEvaluationShaderInvocations,
+    PipelineStatistic::GeometryShaderInvocations,
+    PipelineStatistic::GeometryShaderPrimitivesEmitted,
+    PipelineStatistic::FragmentShaderInvocations,
+    PipelineStatistic::ClippingInputPrimitives,
+    PipelineStatistic::ClippingOutputPrimitives,
+];
+
+impl ToGlEnum for PipelineStatistic {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            PipelineStatistic::VerticesSubmitted => gl::VERTICES_SUBMITTED_ARB,
+            PipelineStatistic::PrimitivesSubmitted => gl::PRIMITIVES_SUBMITTED_ARB,
+            PipelineStatistic::VertexShaderInvocations => gl::VERTEX_SHADER_INVOCATIONS_ARB,
+            PipelineStatistic::TessellationControlShaderPatches => {
+                gl::TESS_CONTROL_SHADER_PATCHES_ARB
+            },
+            PipelineStatistic::TessellationEvaluationShaderInvocations => {
+                gl::TESS_EVALUATION_SHADER_INVOCATIONS_ARB
+            },
+            PipelineStatistic::GeometryShaderInvocations => gl::GEOMETRY_SHADER_INVOCATIONS,
+            PipelineStatistic::GeometryShaderPrimitivesEmitted => {
+                gl::GEOMETRY_SHADER_PRIMITIVES_EMITTED_ARB
+            },
+            PipelineStatistic::FragmentShaderInvocations => gl::FRAGMENT_SHADER_INVOCATIONS_ARB,
+            PipelineStatistic::ClippingInputPrimitives => gl::CLIPPING_INPUT_PRIMITIVES_ARB,
+            PipelineStatistic::ClippingOutputPrimitives => gl::CLIPPING_OUTPUT_PRIMITIVES_ARB,
         }
     }
 }
@@ -117,6 +184,9 @@ impl RawQuery {
                     QueryType::AnySamplesPassedConservative if
                             ctxt.extensions.gl_arb_es3_compatibility ||
                             ctxt.version >= &Version(Api:: Gl, 4, 3) => (),
+                    QueryType::PipelineStatistics(_) if
+                            ctxt.extensions.gl_arb_pipeline_statistics_query ||
+                            ctxt.version >= &Version(Api:: Gl, 4, 6) => (),
                     _ => return Err(QueryCreationError::NotSupported)
                 };
 
@@ -135,6 +205,8 @@ impl RawQuery {
                     QueryType::AnySamplesPassed if ctxt.extensions.gl_arb_occlusion_query2 => (),
                     QueryType::AnySamplesPassedConservative if ctxt.extensions.gl_arb_es3_compatibility => (),
                     QueryType::TimeElapsed if ctxt.extensions.gl_arb_timer_query => (),
+                    QueryType::PipelineStatistics(_) if
+                            ctxt.extensions.gl_arb_pipeline_statistics_query => (),
 
                     _ => return Err(QueryCreationError::NotSupported)
                 };
@@ -368,6 +440,13 @@ impl RawQuery {
             unsafe { raw_end_query(ctxt, gl::TIME_ELAPSED) };
             ctxt.state.time_elapsed_query = 0;
         }
+
+        for (index, statistic) in PIPELINE_STATISTICS.iter().enumerate() {
+            if ctxt.state.pipeline_statistics_queries[index] == self.id {
+                unsafe { raw_end_query(ctxt, statistic.to_glenum()) };
+                ctxt.state.pipeline_statistics_queries[index] = 0;
+            }
+        }
     }
 }
 
@@ -543,6 +622,26 @@ impl QueryExt for RawQuery {
                     ctxt.state.transform_feedback_primitives_written_query = self.id;
                 }
             },
+
+            QueryType::PipelineStatistics(statistic) => {
+                let index = PIPELINE_STATISTICS.iter().position(|&s| s == statistic).unwrap();
+
+                if ctxt.state.pipeline_statistics_queries[index] != self.id {
+                    if self.has_been_used.get() {
+                        return Err(DrawError::WrongQueryOperation);
+                    }
+
+                    unsafe {
+                        if ctxt.state.pipeline_statistics_queries[index] != 0 {
+                            raw_end_query(ctxt, statistic.to_glenum());
+                        }
+                        raw_begin_query(ctxt, statistic.to_glenum(), self.id);
+                    }
+
+                    self.has_been_used.set(true);
+                    ctxt.state.pipeline_statistics_queries[index] = self.id;
+                }
+            },
         };
 
         Ok(())
@@ -668,6 +767,33 @@ impl GlObject for RawQuery {
     }
 }
 
+/// Begins the given pipeline statistics queries, and ends the other ones that are active.
+pub fn sync_pipeline_statistics_queries(ctxt: &mut CommandContext<'_>,
+                                        queries: &[&PipelineStatisticsQuery])
+                                        -> Result<(), DrawError>
+{
+    for (index, statistic) in PIPELINE_STATISTICS.iter().enumerate() {
+        let mut requested = queries.iter().filter(|q| q.statistic == *statistic);
+
+        // only one query per statistic can be active at a time
+        if requested.clone().count() >= 2 {
+            return Err(DrawError::WrongQueryOperation);
+        }
+
+        match requested.next() {
+            Some(query) => query.query.begin_query(ctxt)?,
+            None => {
+                if ctxt.state.pipeline_statistics_queries[index] != 0 {
+                    ctxt.state.pipeline_statistics_queries[index] = 0;
+                    unsafe { raw_end_query(ctxt, statistic.to_glenum()); }
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
+
 /// Calls `glBeginQuery`.
 ///
 /// # Unsafe
@@ -924,3 +1050,39 @@ impl TransformFeedbackPrimitivesWrittenQuery {
 }
 
 impl_helper!(TransformFeedbackPrimitivesWrittenQuery, u32, get_u32);
+
+/// Query that allows you to measure one of the counters of the GPU pipeline, for example the
+/// number of times the fragment shader has been executed or the number of primitives that
+/// survived clipping.
+///
+/// This is mostly useful for debugging and profiling, for example to check that a geometry
+/// shader emits as many primitives as expected.
+///
+/// ## OpenGL
+///
+/// Requires OpenGL 4.6 or `GL_ARB_pipeline_statistics_query`. Not supported by OpenGL ES.
+#[derive(Debug)]
+pub struct PipelineStatisticsQuery {
+    query: RawQuery,
+    statistic: PipelineStatistic,
+}
+
+impl PipelineStatisticsQuery {
+    /// Builds a new query that measures `statistic`.
+    #[inline]
+    pub fn new<F: ?Sized>(facade: &F, statistic: PipelineStatistic)
+                          -> Result<PipelineStatisticsQuery, QueryCreationError>
+                          where F: Facade
+    {
+        RawQuery::new(facade, QueryType::PipelineStatistics(statistic))
+                                .map(|q| PipelineStatisticsQuery { query: q, statistic })
+    }
+
+    /// Returns the statistic that this query measures.
+    #[inline]
+    pub fn get_statistic(&self) -> PipelineStatistic {
+        self.statistic
+    }
+}
+
+impl_helper!(PipelineStatisticsQuery, u64, get_u64);
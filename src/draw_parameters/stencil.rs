@@ -1,6 +1,7 @@
 use crate::gl;
 use crate::context;
 use crate::ToGlEnum;
+use crate::draw_parameters::Winding;
 
 /// Describes the parameters that must be used for the stencil operations when drawing.
 #[derive(Copy, Clone, Debug)]
@@ -22,6 +23,9 @@ pub struct Stencil {
     /// Only relevant for faces that are clockwise on the target surface. Other faces, points and
     /// lines use `stencil_write_mask_counter_clockwise` instead.
     ///
+    /// This mask only applies to draws. `Surface::clear_stencil` always writes all the bits of
+    /// the stencil buffer, and `Surface::clear_stencil_masked` takes its own mask.
+    ///
     /// The default value is `0xffffffff`.
     pub write_mask_clockwise: u32,
 
@@ -220,7 +224,19 @@ impl ToGlEnum for StencilOperation {
     }
 }
 
-pub fn sync_stencil(ctxt: &mut context::CommandContext<'_>, params: &Stencil) {
+/// Turns a value for the clockwise faces and a value for the counterclockwise faces into a
+/// value for the back faces and a value for the front faces.
+///
+/// With the default `glFrontFace` of GL_CCW, the clockwise faces are the back faces.
+#[inline]
+fn back_and_front<T>(front_face: Winding, clockwise: T, counter_clockwise: T) -> (T, T) {
+    match front_face {
+        Winding::CounterClockwise => (clockwise, counter_clockwise),
+        Winding::Clockwise => (counter_clockwise, clockwise),
+    }
+}
+
+pub fn sync_stencil(ctxt: &mut context::CommandContext<'_>, params: &Stencil, front_face: Winding) {
     // checks if stencil operations can be disabled
     if params.test_clockwise == StencilTest::AlwaysPass &&
        params.test_counter_clockwise == StencilTest::AlwaysPass &&
@@ -273,57 +289,65 @@ pub fn sync_stencil(ctxt: &mut context::CommandContext<'_>, params: &Stencil) {
     let ref_cw = params.reference_value_clockwise;
     let ref_ccw = params.reference_value_counter_clockwise;
 
-    if (test_cw, ref_cw, read_mask_cw) == (test_ccw, ref_ccw, read_mask_ccw) {
-        if ctxt.state.stencil_func_back != (test_cw, ref_cw, read_mask_cw) ||
-           ctxt.state.stencil_func_front != (test_ccw, ref_ccw, read_mask_ccw)
+    let (func_back, func_front) = back_and_front(front_face,
+                                                 (test_cw, ref_cw, read_mask_cw),
+                                                 (test_ccw, ref_ccw, read_mask_ccw));
+
+    if func_back == func_front {
+        if ctxt.state.stencil_func_back != func_back ||
+           ctxt.state.stencil_func_front != func_front
         {
-            unsafe { ctxt.gl.StencilFunc(test_cw, ref_cw, read_mask_cw) };
-            ctxt.state.stencil_func_back = (test_cw, ref_cw, read_mask_cw);
-            ctxt.state.stencil_func_front = (test_ccw, ref_ccw, read_mask_ccw);
+            unsafe { ctxt.gl.StencilFunc(func_back.0, func_back.1, func_back.2) };
+            ctxt.state.stencil_func_back = func_back;
+            ctxt.state.stencil_func_front = func_front;
         }
 
     } else {
-        if ctxt.state.stencil_func_back != (test_cw, ref_cw, read_mask_cw) {
-            unsafe { ctxt.gl.StencilFuncSeparate(gl::BACK, test_cw, ref_cw, read_mask_cw) };
-            ctxt.state.stencil_func_back = (test_cw, ref_cw, read_mask_cw);
+        if ctxt.state.stencil_func_back != func_back {
+            unsafe { ctxt.gl.StencilFuncSeparate(gl::BACK, func_back.0, func_back.1, func_back.2) };
+            ctxt.state.stencil_func_back = func_back;
         }
 
-        if ctxt.state.stencil_func_front != (test_ccw, ref_ccw, read_mask_ccw) {
-            unsafe { ctxt.gl.StencilFuncSeparate(gl::FRONT, test_ccw, ref_ccw, read_mask_ccw) };
-            ctxt.state.stencil_func_front = (test_ccw, ref_ccw, read_mask_ccw);
+        if ctxt.state.stencil_func_front != func_front {
+            unsafe { ctxt.gl.StencilFuncSeparate(gl::FRONT, func_front.0, func_front.1,
+                                                 func_front.2) };
+            ctxt.state.stencil_func_front = func_front;
         }
     }
 
     // synchronizing the write mask
-    if params.write_mask_clockwise == params.write_mask_counter_clockwise {
-        if ctxt.state.stencil_mask_back != params.write_mask_clockwise ||
-           ctxt.state.stencil_mask_front != params.write_mask_clockwise
+    let (mask_back, mask_front) = back_and_front(front_face, params.write_mask_clockwise,
+                                                 params.write_mask_counter_clockwise);
+
+    if mask_back == mask_front {
+        if ctxt.state.stencil_mask_back != mask_back ||
+           ctxt.state.stencil_mask_front != mask_front
         {
-            unsafe { ctxt.gl.StencilMask(params.write_mask_clockwise) };
-            ctxt.state.stencil_mask_back = params.write_mask_clockwise;
-            ctxt.state.stencil_mask_front = params.write_mask_clockwise;
+            unsafe { ctxt.gl.StencilMask(mask_back) };
+            ctxt.state.stencil_mask_back = mask_back;
+            ctxt.state.stencil_mask_front = mask_front;
         }
 
     } else {
-        if ctxt.state.stencil_mask_back != params.write_mask_clockwise {
-            unsafe { ctxt.gl.StencilMaskSeparate(gl::BACK, params.write_mask_clockwise) };
-            ctxt.state.stencil_mask_back = params.write_mask_clockwise;
+        if ctxt.state.stencil_mask_back != mask_back {
+            unsafe { ctxt.gl.StencilMaskSeparate(gl::BACK, mask_back) };
+            ctxt.state.stencil_mask_back = mask_back;
         }
 
-        if ctxt.state.stencil_mask_front != params.write_mask_clockwise {
-            unsafe { ctxt.gl.StencilMaskSeparate(gl::FRONT, params.write_mask_clockwise) };
-            ctxt.state.stencil_mask_front = params.write_mask_clockwise;
+        if ctxt.state.stencil_mask_front != mask_front {
+            unsafe { ctxt.gl.StencilMaskSeparate(gl::FRONT, mask_front) };
+            ctxt.state.stencil_mask_front = mask_front;
         }
     }
 
     // synchronizing the operation
-    let op_back = (params.fail_operation_clockwise.to_glenum(),
-                   params.pass_depth_fail_operation_clockwise.to_glenum(),
-                   params.depth_pass_operation_clockwise.to_glenum());
-
-    let op_front = (params.fail_operation_counter_clockwise.to_glenum(),
-                    params.pass_depth_fail_operation_counter_clockwise.to_glenum(),
-                    params.depth_pass_operation_counter_clockwise.to_glenum());
+    let (op_back, op_front) = back_and_front(front_face,
+        (params.fail_operation_clockwise.to_glenum(),
+         params.pass_depth_fail_operation_clockwise.to_glenum(),
+         params.depth_pass_operation_clockwise.to_glenum()),
+        (params.fail_operation_counter_clockwise.to_glenum(),
+         params.pass_depth_fail_operation_counter_clockwise.to_glenum(),
+         params.depth_pass_operation_counter_clockwise.to_glenum()));
 
     if op_back == op_front {
         if ctxt.state.stencil_op_back != op_back || ctxt.state.stencil_op_front != op_front {
@@ -85,6 +85,16 @@ pub fn is_dimensions_mismatch_supported<C: ?Sized>(context: &C) -> bool where C:
     context.get_extensions().gl_arb_framebuffer_object
 }
 
+/// Returns true if the backend supports attaching whole array textures, cubemaps or 3D textures
+/// to a framebuffer.
+pub fn is_layered_supported<C: ?Sized>(context: &C) -> bool where C: CapabilitiesSource {
+    context.get_version() >= &Version(Api::Gl, 3, 2) ||
+    context.get_version() >= &Version(Api::GlEs, 3, 2) ||
+    context.get_extensions().gl_arb_direct_state_access ||
+    (context.get_extensions().gl_ext_direct_state_access &&
+     context.get_extensions().gl_ext_geometry_shader4)
+}
+
 /// Represents the attachments to use for an OpenGL framebuffer.
 #[derive(Clone)]
 pub enum FramebufferAttachments<'a> {
@@ -126,7 +136,7 @@ impl<'a> RegularAttachment<'a> {
 
 /// Describes a single layered framebuffer attachment.
 #[derive(Copy, Clone)]
-pub struct LayeredAttachment<'a>(TextureAnyMipmap<'a>);
+pub struct LayeredAttachment<'a>(pub TextureAnyMipmap<'a>);
 
 /// Depth and/or stencil attachment to use.
 #[derive(Copy, Clone)]
@@ -205,6 +215,7 @@ impl<'a> FramebufferAttachments<'a> {
                         layers,
                         depth_buffer_bits: None,
                         stencil_buffer_bits: None,
+                        integral_color_attachments: false,
                         marker: PhantomData,
                     })
 
@@ -222,6 +233,10 @@ impl<'a> FramebufferAttachments<'a> {
     {
         // TODO: make sure that all attachments are layered
 
+        if !is_layered_supported(context) {
+            return Err(ValidationError::LayeredFramebufferObjectsNotSupported);
+        }
+
         macro_rules! handle_tex {
             ($tex:ident, $dim:ident, $samples:ident, $num_bits:ident) => ({
                 $num_bits = Some($tex.get_texture().get_internal_format()
@@ -343,6 +358,9 @@ impl<'a> FramebufferAttachments<'a> {
             layers: None,       // FIXME: count layers
             depth_buffer_bits: depth_bits,
             stencil_buffer_bits: stencil_bits,
+            integral_color_attachments: colors.iter().any(|&(_, LayeredAttachment(ref a))| {
+                is_integral_kind(a.get_texture().kind())
+            }),
             marker: PhantomData,
         })
     }
@@ -529,6 +547,7 @@ impl<'a> FramebufferAttachments<'a> {
             layers: None,
             depth_buffer_bits: depth_bits,
             stencil_buffer_bits: stencil_bits,
+            integral_color_attachments: colors.iter().any(|&(_, ref a)| is_integral_kind(a.kind())),
             marker: PhantomData,
         })
     }
@@ -542,6 +561,7 @@ pub struct ValidatedAttachments<'a> {
     layers: Option<u32>,
     depth_buffer_bits: Option<u16>,
     stencil_buffer_bits: Option<u16>,
+    integral_color_attachments: bool,
     marker: PhantomData<&'a ()>,
 }
 
@@ -571,6 +591,18 @@ impl<'a> ValidatedAttachments<'a> {
     pub fn get_stencil_buffer_bits(&self) -> Option<u16> {
         self.stencil_buffer_bits
     }
+
+    /// Returns `true` if one of the color attachments is a signed or unsigned integer image.
+    /// Blending can't be used with these attachments.
+    #[inline]
+    pub fn has_integral_color_attachments(&self) -> bool {
+        self.integral_color_attachments
+    }
+}
+
+#[inline]
+fn is_integral_kind(kind: TextureKind) -> bool {
+    kind == TextureKind::Integral || kind == TextureKind::Unsigned
 }
 
 /// An error that can happen while validating attachments.
@@ -598,6 +630,13 @@ pub enum ValidationError {
         /// Number of attachments that were given.
         obtained: usize,
     },
+
+    /// You requested a layered framebuffer object, but they are not supported.
+    LayeredFramebufferObjectsNotSupported,
+
+    /// The attachments passed all the checks of glium, but the OpenGL implementation reports
+    /// that the framebuffer is incomplete.
+    Incomplete(FramebufferError),
 }
 
 impl fmt::Display for ValidationError {
@@ -614,17 +653,133 @@ impl fmt::Display for ValidationError {
                 "All attachments must have the same number of samples",
             TooManyColorAttachments {..} =>
                 "Backends only support a certain number of color attachments",
+            LayeredFramebufferObjectsNotSupported =>
+                "You requested a layered framebuffer object, but they are not supported",
+            Incomplete(_) =>
+                "The OpenGL implementation reports that the framebuffer is incomplete",
         };
         match self {
             TooManyColorAttachments{ ref maximum, ref obtained } =>
                 write!(fmt, "{}: found {}, maximum: {}", desc, obtained, maximum),
+            Incomplete(ref err) =>
+                write!(fmt, "{}: {}", desc, err),
             _ =>
                 fmt.write_str(desc),
         }
     }
 }
 
-impl Error for ValidationError {}
+impl Error for ValidationError {
+    #[inline]
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ValidationError::Incomplete(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Reason why a framebuffer is incomplete, as returned by `glCheckFramebufferStatus`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FramebufferError {
+    /// `GL_FRAMEBUFFER_UNDEFINED`. The default framebuffer doesn't exist.
+    Undefined,
+
+    /// `GL_FRAMEBUFFER_INCOMPLETE_ATTACHMENT`. One of the attachments isn't complete, for
+    /// example because its format can't be rendered to or because it has a width or a height
+    /// of zero.
+    IncompleteAttachment,
+
+    /// `GL_FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT`. The framebuffer doesn't have any
+    /// attachment.
+    MissingAttachment,
+
+    /// `GL_FRAMEBUFFER_INCOMPLETE_DIMENSIONS`. The attachments don't all have the same
+    /// dimensions, which some implementations require.
+    IncompleteDimensions,
+
+    /// `GL_FRAMEBUFFER_INCOMPLETE_FORMATS_EXT`. The color attachments don't all have the same
+    /// format, which some old implementations require.
+    IncompleteFormats,
+
+    /// `GL_FRAMEBUFFER_INCOMPLETE_DRAW_BUFFER`. One of the draw buffers refers to an attachment
+    /// point that doesn't have any attachment.
+    IncompleteDrawBuffer,
+
+    /// `GL_FRAMEBUFFER_INCOMPLETE_READ_BUFFER`. The read buffer refers to an attachment point
+    /// that doesn't have any attachment.
+    IncompleteReadBuffer,
+
+    /// `GL_FRAMEBUFFER_UNSUPPORTED`. This combination of formats of attachments isn't supported
+    /// by the implementation.
+    Unsupported,
+
+    /// `GL_FRAMEBUFFER_INCOMPLETE_MULTISAMPLE`. The attachments don't all have the same number
+    /// of samples, or don't all use fixed sample locations.
+    IncompleteMultisample,
+
+    /// `GL_FRAMEBUFFER_INCOMPLETE_LAYER_TARGETS`. Some attachments are layered and others are
+    /// not, or layered attachments don't have the same kind of texture.
+    IncompleteLayerTargets,
+
+    /// A status that glium doesn't know about.
+    Unknown(gl::types::GLenum),
+}
+
+impl FramebufferError {
+    /// Turns the value returned by `glCheckFramebufferStatus` into a result.
+    fn from_status(status: gl::types::GLenum) -> Result<(), FramebufferError> {
+        match status {
+            // `0` is returned if `glCheckFramebufferStatus` itself generates an error, in which
+            // case the error is reported by the usual means
+            0 | gl::FRAMEBUFFER_COMPLETE => Ok(()),
+            gl::FRAMEBUFFER_UNDEFINED => Err(FramebufferError::Undefined),
+            gl::FRAMEBUFFER_INCOMPLETE_ATTACHMENT => Err(FramebufferError::IncompleteAttachment),
+            gl::FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT => Err(FramebufferError::MissingAttachment),
+            gl::FRAMEBUFFER_INCOMPLETE_DIMENSIONS => Err(FramebufferError::IncompleteDimensions),
+            gl::FRAMEBUFFER_INCOMPLETE_FORMATS_EXT => Err(FramebufferError::IncompleteFormats),
+            gl::FRAMEBUFFER_INCOMPLETE_DRAW_BUFFER => Err(FramebufferError::IncompleteDrawBuffer),
+            gl::FRAMEBUFFER_INCOMPLETE_READ_BUFFER => Err(FramebufferError::IncompleteReadBuffer),
+            gl::FRAMEBUFFER_UNSUPPORTED => Err(FramebufferError::Unsupported),
+            gl::FRAMEBUFFER_INCOMPLETE_MULTISAMPLE => Err(FramebufferError::IncompleteMultisample),
+            gl::FRAMEBUFFER_INCOMPLETE_LAYER_TARGETS => Err(FramebufferError::IncompleteLayerTargets),
+            status => Err(FramebufferError::Unknown(status)),
+        }
+    }
+}
+
+impl fmt::Display for FramebufferError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::FramebufferError::*;
+        let desc = match *self {
+            Undefined =>
+                "The default framebuffer doesn't exist",
+            IncompleteAttachment =>
+                "One of the attachments is incomplete or has a format that can't be rendered to",
+            MissingAttachment =>
+                "The framebuffer doesn't have any attachment",
+            IncompleteDimensions =>
+                "The attachments don't all have the same dimensions",
+            IncompleteFormats =>
+                "The color attachments don't all have the same format",
+            IncompleteDrawBuffer =>
+                "One of the draw buffers refers to an attachment point without attachment",
+            IncompleteReadBuffer =>
+                "The read buffer refers to an attachment point without attachment",
+            Unsupported =>
+                "This combination of attachment formats isn't supported by the implementation",
+            IncompleteMultisample =>
+                "The attachments don't all have the same number of samples",
+            IncompleteLayerTargets =>
+                "The attachments aren't all layered, or don't have the same kind of texture",
+            Unknown(status) =>
+                return write!(fmt, "Unknown framebuffer status: 0x{:x}", status),
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for FramebufferError {}
 
 /// Data structure stored in the hashmap.
 ///
@@ -907,6 +1062,20 @@ impl FramebuffersContainer {
         }
     }
 
+    /// Builds the framebuffer object corresponding to the attachments if it doesn't exist yet,
+    /// and returns the result of `glCheckFramebufferStatus` for it.
+    ///
+    /// # Unsafety
+    ///
+    /// After calling this function, you **must** make sure to call `purge_texture`
+    /// and/or `purge_renderbuffer` when one of the attachment is destroyed.
+    pub fn check_status(ctxt: &mut CommandContext<'_>, attachments: &ValidatedAttachments<'_>)
+                        -> Result<(), FramebufferError>
+    {
+        FramebuffersContainer::get_framebuffer(ctxt, attachments);
+        ctxt.framebuffer_objects.framebuffers.borrow()[&attachments.raw].status
+    }
+
     ///
     /// # Unsafety
     ///
@@ -941,6 +1110,8 @@ impl Drop for FramebuffersContainer {
 struct FrameBufferObject {
     id: gl::types::GLuint,
     current_read_buffer: gl::types::GLenum,
+    /// Result of `glCheckFramebufferStatus` after the attachments have been attached.
+    status: Result<(), FramebufferError>,
 }
 
 impl FrameBufferObject {
@@ -1114,10 +1285,59 @@ impl FrameBufferObject {
             }
         }
 
+        // without any color attachment, the read buffer must be `GL_NONE` or the framebuffer is
+        // reported as incomplete by implementations that don't support `GL_ARB_ES2_compatibility`
+        let current_read_buffer = if raw_attachments.is_empty() {
+            if ctxt.version >= &Version(Api::Gl, 4, 5) ||
+               ctxt.extensions.gl_arb_direct_state_access
+            {
+                unsafe { ctxt.gl.NamedFramebufferReadBuffer(id, gl::NONE) };
+
+            } else if ctxt.version >= &Version(Api::Gl, 1, 0) ||
+                      ctxt.version >= &Version(Api::GlEs, 3, 0)
+            {
+                unsafe {
+                    bind_framebuffer(&mut ctxt, id, false, true);
+                    ctxt.gl.ReadBuffer(gl::NONE);
+                }
+            }
+
+            gl::NONE
+        } else {
+            gl::BACK
+        };
+
+        // checking the completeness
+        let status = unsafe {
+            if ctxt.version >= &Version(Api::Gl, 4, 5) ||
+                ctxt.extensions.gl_arb_direct_state_access
+            {
+                ctxt.gl.CheckNamedFramebufferStatus(id, gl::DRAW_FRAMEBUFFER)
+
+            } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                      ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+                      ctxt.extensions.gl_arb_framebuffer_object
+            {
+                bind_framebuffer(&mut ctxt, id, true, false);
+                ctxt.gl.CheckFramebufferStatus(gl::DRAW_FRAMEBUFFER)
+
+            } else if ctxt.version >= &Version(Api::GlEs, 2, 0) {
+                bind_framebuffer(&mut ctxt, id, true, false);
+                ctxt.gl.CheckFramebufferStatus(gl::FRAMEBUFFER)
+
+            } else if ctxt.extensions.gl_ext_framebuffer_object {
+                bind_framebuffer(&mut ctxt, id, true, false);
+                ctxt.gl.CheckFramebufferStatusEXT(gl::FRAMEBUFFER_EXT)
+
+            } else {
+                unreachable!();
+            }
+        };
 
         FrameBufferObject {
             id,
-            current_read_buffer: gl::BACK,
+            current_read_buffer,
+            status: FramebufferError::from_status(status),
         }
     }
 
@@ -1263,7 +1483,9 @@ unsafe fn attach(ctxt: &mut CommandContext<'_>, slot: gl::types::GLenum,
                         ctxt.gl.NamedFramebufferTextureEXT(id, slot, tex_id,
                                                            level as gl::types::GLint);
 
-                    } else if ctxt.version >= &Version(Api::Gl, 3, 2) {
+                    } else if ctxt.version >= &Version(Api::Gl, 3, 2) ||
+                              ctxt.version >= &Version(Api::GlEs, 3, 2)
+                    {
                         bind_framebuffer(ctxt, id, true, false);
                         ctxt.gl.FramebufferTexture(gl::DRAW_FRAMEBUFFER,
                                                    slot, tex_id, level as gl::types::GLint);
@@ -1407,7 +1629,9 @@ unsafe fn attach(ctxt: &mut CommandContext<'_>, slot: gl::types::GLenum,
                         ctxt.gl.NamedFramebufferTextureEXT(id, slot, tex_id,
                                                            level as gl::types::GLint);
 
-                    } else if ctxt.version >= &Version(Api::Gl, 3, 2) {
+                    } else if ctxt.version >= &Version(Api::Gl, 3, 2) ||
+                              ctxt.version >= &Version(Api::GlEs, 3, 2)
+                    {
                         bind_framebuffer(ctxt, id, true, false);
                         ctxt.gl.FramebufferTexture(gl::DRAW_FRAMEBUFFER,
                                                    slot, tex_id, level as gl::types::GLint);
@@ -1462,7 +1686,9 @@ unsafe fn attach(ctxt: &mut CommandContext<'_>, slot: gl::types::GLenum,
                         ctxt.gl.NamedFramebufferTextureEXT(id, slot, tex_id,
                                                            level as gl::types::GLint);
 
-                    } else if ctxt.version >= &Version(Api::Gl, 3, 2) {
+                    } else if ctxt.version >= &Version(Api::Gl, 3, 2) ||
+                              ctxt.version >= &Version(Api::GlEs, 3, 2)
+                    {
                         bind_framebuffer(ctxt, id, true, false);
                         ctxt.gl.FramebufferTexture(gl::DRAW_FRAMEBUFFER,
                                                    slot, tex_id, level as gl::types::GLint);
@@ -62,6 +62,13 @@ impl Surface for DefaultFramebuffer {
         ops::clear(&self.context, None, None, color, color_srgb, depth, stencil);
     }
 
+    #[inline]
+    fn clear_stencil_masked(&mut self, value: i32, mask: u32) {
+        // the stencil buffer is shared by all the color buffers of the default framebuffer, so
+        // contrary to the color clears this doesn't depend on `self.attachment`
+        ops::clear_stencil_masked(&self.context, None, None, value, mask);
+    }
+
     fn get_dimensions(&self) -> (u32, u32) {
         self.context.get_framebuffer_dimensions()
     }
@@ -86,24 +93,16 @@ impl Surface for DefaultFramebuffer {
             return Err(DrawError::NoDepthBuffer);
         }
 
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
-
         // TODO: wrong attachment
         ops::draw(&self.context, None, vertex_buffer, index_buffer.into(), program,
                   uniforms, draw_parameters, self.get_dimensions())
     }
 
+    #[inline]
+    fn execute(&mut self, list: &crate::command_list::CommandList<'_>) -> Result<(), DrawError> {
+        list.execute(&self.context, None, self.get_dimensions(), self.has_depth_buffer())
+    }
+
     #[inline]
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
                      filter: uniforms::MagnifySamplerFilter) where S: Surface
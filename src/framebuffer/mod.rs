@@ -35,6 +35,24 @@ let framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display, outp
 
 **Note**: depth-stencil attachments are not yet implemented.
 
+# Attaching a single layer
+
+Instead of a whole texture, you can attach a single face of a cubemap, a single layer of a
+texture array or a single slice of a 3D texture.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::MaybeUninit::uninit().assume_init() };
+# let cubemap: glium::texture::Cubemap = unsafe { ::std::mem::MaybeUninit::uninit().assume_init() };
+# let array: glium::texture::Texture2dArray = unsafe { ::std::mem::MaybeUninit::uninit().assume_init() };
+# let texture3d: glium::texture::Texture3d = unsafe { ::std::mem::MaybeUninit::uninit().assume_init() };
+use glium::texture::CubeLayer;
+use glium::framebuffer::SimpleFrameBuffer;
+
+let face = SimpleFrameBuffer::new(&display, cubemap.main_level().image(CubeLayer::PositiveX));
+let layer = SimpleFrameBuffer::new(&display, array.main_level().layer(2).unwrap());
+let slice = SimpleFrameBuffer::new(&display, texture3d.main_level().slice(5).unwrap());
+```
+
 # A note on restrictions
 
 Some restrictions apply when you use framebuffers:
@@ -63,13 +81,27 @@ You can check whether they are supported by calling `EmptyFrameBuffer::is_suppor
 
 # Layered framebuffers
 
-Not yet supported
+A `LayeredFrameBuffer` has whole texture arrays, cubemaps or 3D textures as attachments. The
+geometry shader chooses which layer each primitive is drawn to by writing to `gl_Layer`. This
+allows you, for example, to draw to the six faces of a cubemap in a single draw call.
+
+```no_run
+# let display: glium::Display = unsafe { ::std::mem::MaybeUninit::uninit().assume_init() };
+# let cubemap: glium::texture::Cubemap = unsafe { ::std::mem::MaybeUninit::uninit().assume_init() };
+let framebuffer = glium::framebuffer::LayeredFrameBuffer::new(&display, cubemap.main_level());
+// framebuffer.draw(...);    // the geometry shader writes to `gl_Layer`
+```
+
+You can check whether they are supported by calling `LayeredFrameBuffer::is_supported(&display)`.
 
 */
 use std::rc::Rc;
 use smallvec::SmallVec;
 
 use crate::texture::TextureAnyImage;
+use crate::texture::TextureAnyMipmap;
+use crate::texture::{CubeLayer, Dimensions, TextureKind};
+use crate::texture::pixel_buffer::PixelBuffer;
 
 use crate::backend::Facade;
 use crate::context::Context;
@@ -95,15 +127,18 @@ pub use self::render_buffer::{RenderBuffer, RenderBufferAny, DepthRenderBuffer};
 pub use self::render_buffer::{StencilRenderBuffer, DepthStencilRenderBuffer};
 pub use self::render_buffer::CreationError as RenderBufferCreationError;
 pub use crate::fbo::is_dimensions_mismatch_supported;
-pub use crate::fbo::ValidationError;
+pub use self::resolve::MultisampleResolve;
+pub use crate::fbo::{ValidationError, FramebufferError};
 
 mod default_fb;
 mod render_buffer;
+mod resolve;
 
 /// A framebuffer which has only one color attachment.
 pub struct SimpleFrameBuffer<'a> {
     context: Rc<Context>,
     attachments: fbo::ValidatedAttachments<'a>,
+    color_attachment: Option<fbo::RegularAttachment<'a>>,
 }
 
 impl<'a> SimpleFrameBuffer<'a> {
@@ -256,12 +291,112 @@ impl<'a> SimpleFrameBuffer<'a> {
         });
 
         let attachments = attachments.validate(facade)?;
+        fbo::FramebuffersContainer::check_status(&mut facade.get_context().make_current(),
+                                                 &attachments)
+            .map_err(ValidationError::Incomplete)?;
 
         Ok(SimpleFrameBuffer {
             context: facade.get_context().clone(),
             attachments,
+            color_attachment: color,
         })
     }
+
+    /// Reads the content of the color attachment and returns it as an RGBA image whose first
+    /// row is the top of the framebuffer.
+    ///
+    /// The content of the attachment is converted to 8 bits per component, whatever its
+    /// format is.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the framebuffer doesn't have a color attachment.
+    pub fn capture_image(&self) -> Result<crate::texture::CapturedImage, ops::ReadError> {
+        let attachment = self.color_attachment.as_ref()
+                             .expect("The framebuffer doesn't have a color attachment");
+
+        let mut ctxt = self.context.make_current();
+        let (width, height) = self.attachments.get_dimensions();
+        let rect = Rect { left: 0, bottom: 0, width, height };
+
+        let mut data: Vec<(u8, u8, u8, u8)> = Vec::with_capacity(0);
+        ops::read(&mut ctxt, attachment, &rect, &mut data, false)?;
+        Ok(crate::texture::CapturedImage::from_bottom_left(&data, width, height))
+    }
+
+    /// Reads the content of the color attachment into a buffer in video memory.
+    ///
+    /// Contrary to `capture_image`, this operation is done asynchronously and doesn't block.
+    /// Use `PixelBuffer::is_ready` to know when the content of the pixel buffer can be read
+    /// without waiting for the GPU.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the framebuffer doesn't have a color attachment.
+    pub fn read_to_pixel_buffer(&self) -> Result<PixelBuffer<(u8, u8, u8, u8)>, ops::ReadError> {
+        let attachment = self.color_attachment.as_ref()
+                             .expect("The framebuffer doesn't have a color attachment");
+
+        let (width, height) = self.attachments.get_dimensions();
+        let rect = Rect { left: 0, bottom: 0, width, height };
+        let pb = PixelBuffer::new_empty(&self.context, width as usize * height as usize);
+
+        let mut ctxt = self.context.make_current();
+        ops::read(&mut ctxt, attachment, &rect, &pb, false)?;
+        Ok(pb)
+    }
+
+    /// Clears the color attachment with `glClearBuffer`.
+    ///
+    /// Contrary to `clear_color`, this doesn't go through a conversion to floating-point values,
+    /// and the `_i` and `_u` variants can be used to clear integral attachments.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the framebuffer doesn't have a color attachment, or if the attachment isn't
+    /// a floating-point or normalized attachment.
+    #[inline]
+    pub fn clear_color_buffer(&mut self, color: [f32; 4]) {
+        self.clear_color_buffer_impl(TextureKind::Float, fbo::ClearBufferData::Float(color));
+    }
+
+    /// Clears the signed integral color attachment with `glClearBuffer`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the framebuffer doesn't have a color attachment, or if the attachment isn't
+    /// a signed integral attachment.
+    #[inline]
+    pub fn clear_color_buffer_i(&mut self, color: [i32; 4]) {
+        self.clear_color_buffer_impl(TextureKind::Integral, fbo::ClearBufferData::Integral(color));
+    }
+
+    /// Clears the unsigned integral color attachment with `glClearBuffer`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the framebuffer doesn't have a color attachment, or if the attachment isn't
+    /// an unsigned integral attachment.
+    #[inline]
+    pub fn clear_color_buffer_u(&mut self, color: [u32; 4]) {
+        self.clear_color_buffer_impl(TextureKind::Unsigned, fbo::ClearBufferData::Unsigned(color));
+    }
+
+    /// Clears the depth and stencil attachments with `glClearBuffer`.
+    ///
+    /// Only the attachments that exist are cleared.
+    #[inline]
+    pub fn clear_depth_stencil(&mut self, depth: f32, stencil: i32) {
+        ops::clear_buffer(&self.context, &self.attachments,
+                          fbo::ClearBufferData::DepthStencil(depth, stencil));
+    }
+
+    fn clear_color_buffer_impl(&mut self, kind: TextureKind, data: fbo::ClearBufferData) {
+        let attachment = self.color_attachment.as_ref()
+                             .expect("The framebuffer doesn't have a color attachment");
+        check_clear_kind(attachment, kind);
+        ops::clear_buffer(&self.context, &self.attachments, data);
+    }
 }
 
 impl<'a> Surface for SimpleFrameBuffer<'a> {
@@ -272,6 +407,11 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
         ops::clear(&self.context, Some(&self.attachments), rect, color, color_srgb, depth, stencil);
     }
 
+    #[inline]
+    fn clear_stencil_masked(&mut self, value: i32, mask: u32) {
+        ops::clear_stencil_masked(&self.context, Some(&self.attachments), None, value, mask);
+    }
+
     #[inline]
     fn get_dimensions(&self) -> (u32, u32) {
         self.attachments.get_dimensions()
@@ -298,23 +438,16 @@ impl<'a> Surface for SimpleFrameBuffer<'a> {
             return Err(DrawError::NoDepthBuffer);
         }
 
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
-
         ops::draw(&self.context, Some(&self.attachments), vb,
                   ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
     }
 
+    #[inline]
+    fn execute(&mut self, list: &crate::command_list::CommandList<'_>) -> Result<(), DrawError> {
+        list.execute(&self.context, Some(&self.attachments), self.get_dimensions(),
+                     self.has_depth_buffer())
+    }
+
     #[inline]
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
                      filter: uniforms::MagnifySamplerFilter) where S: Surface
@@ -356,6 +489,15 @@ impl<'a> FboAttachments for SimpleFrameBuffer<'a> {
     }
 }
 
+/// Panics if a color attachment can't be cleared with values of the given kind.
+fn check_clear_kind(attachment: &fbo::RegularAttachment<'_>, kind: TextureKind) {
+    let attachment_kind = attachment.kind();
+    if attachment_kind != kind {
+        panic!("Can't clear an attachment of kind {:?} with values of kind {:?}",
+               attachment_kind, kind);
+    }
+}
+
 /// This struct is useless for the moment.
 pub struct MultiOutputFrameBuffer<'a> {
     context: Rc<Context>,
@@ -503,6 +645,9 @@ impl<'a> MultiOutputFrameBuffer<'a> {
             colors: example_color,
             depth_stencil: depth_stencil_attachments,
         }).validate(facade)?;
+        fbo::FramebuffersContainer::check_status(&mut facade.get_context().make_current(),
+                                                 &example_attachments)
+            .map_err(ValidationError::Incomplete)?;
 
         Ok(MultiOutputFrameBuffer {
             context: facade.get_context().clone(),
@@ -512,6 +657,75 @@ impl<'a> MultiOutputFrameBuffer<'a> {
         })
     }
 
+    /// Clears the color attachment at position `index` with `glClearBuffer`.
+    ///
+    /// The index is the position of the attachment in the list that was passed when creating
+    /// the framebuffer. Contrary to `clear_color`, this allows clearing each attachment to a
+    /// different value, and the `_i` and `_u` variants can be used to clear integral attachments.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `index` is out of range, or if the attachment isn't a floating-point or
+    /// normalized attachment.
+    #[inline]
+    pub fn clear_color_buffer(&mut self, index: usize, color: [f32; 4]) {
+        self.clear_color_buffer_impl(index, TextureKind::Float, fbo::ClearBufferData::Float(color));
+    }
+
+    /// Clears the signed integral color attachment at position `index` with `glClearBuffer`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `index` is out of range, or if the attachment isn't a signed integral
+    /// attachment.
+    #[inline]
+    pub fn clear_color_buffer_i(&mut self, index: usize, color: [i32; 4]) {
+        self.clear_color_buffer_impl(index, TextureKind::Integral,
+                                     fbo::ClearBufferData::Integral(color));
+    }
+
+    /// Clears the unsigned integral color attachment at position `index` with `glClearBuffer`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `index` is out of range, or if the attachment isn't an unsigned integral
+    /// attachment.
+    #[inline]
+    pub fn clear_color_buffer_u(&mut self, index: usize, color: [u32; 4]) {
+        self.clear_color_buffer_impl(index, TextureKind::Unsigned,
+                                     fbo::ClearBufferData::Unsigned(color));
+    }
+
+    /// Clears the depth and stencil attachments with `glClearBuffer`.
+    ///
+    /// Only the attachments that exist are cleared.
+    #[inline]
+    pub fn clear_depth_stencil(&mut self, depth: f32, stencil: i32) {
+        ops::clear_buffer(&self.context, &self.example_attachments,
+                          fbo::ClearBufferData::DepthStencil(depth, stencil));
+    }
+
+    fn clear_color_buffer_impl(&mut self, index: usize, kind: TextureKind,
+                               data: fbo::ClearBufferData)
+    {
+        let attachment = match self.color_attachments.get(index) {
+            Some(&(_, ref attachment)) => *attachment,
+            None => panic!("The framebuffer doesn't have a color attachment at index {}", index),
+        };
+
+        check_clear_kind(&attachment, kind);
+
+        let mut colors = SmallVec::new();
+        colors.push((0, attachment));
+
+        let attachments = fbo::FramebufferAttachments::Regular(fbo::FramebufferSpecificAttachments {
+            colors,
+            depth_stencil: fbo::DepthStencilAttachments::None,
+        }).validate(&self.context).unwrap();
+
+        ops::clear_buffer(&self.context, &attachments, data);
+    }
+
     fn build_attachments(&self, program: &Program) -> fbo::ValidatedAttachments<'_> {
         let mut colors = SmallVec::new();
 
@@ -540,6 +754,12 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
                    color, color_srgb, depth, stencil);
     }
 
+    #[inline]
+    fn clear_stencil_masked(&mut self, value: i32, mask: u32) {
+        ops::clear_stencil_masked(&self.context, Some(&self.example_attachments), None,
+                                  value, mask);
+    }
+
     #[inline]
     fn get_dimensions(&self) -> (u32, u32) {
         self.example_attachments.get_dimensions()
@@ -566,19 +786,6 @@ impl<'a> Surface for MultiOutputFrameBuffer<'a> {
             return Err(DrawError::NoDepthBuffer);
         }
 
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
-
         ops::draw(&self.context, Some(&self.build_attachments(program)), vb,
                   ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
     }
@@ -693,6 +900,8 @@ impl<'a> EmptyFrameBuffer {
         };
 
         let attachments = attachments.validate(context)?;
+        fbo::FramebuffersContainer::check_status(&mut context.make_current(), &attachments)
+            .map_err(ValidationError::Incomplete)?;
 
         Ok(EmptyFrameBuffer {
             context: context.clone(),
@@ -709,6 +918,11 @@ impl Surface for EmptyFrameBuffer {
         ops::clear(&self.context, Some(&self.attachments), rect, color, color_srgb, depth, stencil);
     }
 
+    #[inline]
+    fn clear_stencil_masked(&mut self, value: i32, mask: u32) {
+        ops::clear_stencil_masked(&self.context, Some(&self.attachments), None, value, mask);
+    }
+
     #[inline]
     fn get_dimensions(&self) -> (u32, u32) {
         self.attachments.get_dimensions()
@@ -735,17 +949,234 @@ impl Surface for EmptyFrameBuffer {
             return Err(DrawError::NoDepthBuffer);
         }
 
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
+        ops::draw(&self.context, Some(&self.attachments), vb,
+                  ib.into(), program, uniforms, draw_parameters, self.get_dimensions())
+    }
+
+    #[inline]
+    fn execute(&mut self, list: &crate::command_list::CommandList<'_>) -> Result<(), DrawError> {
+        list.execute(&self.context, Some(&self.attachments), self.get_dimensions(),
+                     self.has_depth_buffer())
+    }
+
+    #[inline]
+    fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
+                     filter: uniforms::MagnifySamplerFilter) where S: Surface
+    {
+        unimplemented!()        // TODO:
+    }
+
+    #[inline]
+    fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
+                       filter: uniforms::MagnifySamplerFilter)
+    {
+        ops::blit(&self.context, None, self.get_attachments(),
+                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_from_simple_framebuffer(&self, source: &SimpleFrameBuffer<'_>,
+                                    source_rect: &Rect, target_rect: &BlitTarget,
+                                    filter: uniforms::MagnifySamplerFilter)
+    {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+    }
+
+    #[inline]
+    fn blit_from_multioutput_framebuffer(&self, source: &MultiOutputFrameBuffer<'_>,
+                                         source_rect: &Rect, target_rect: &BlitTarget,
+                                         filter: uniforms::MagnifySamplerFilter)
+    {
+        ops::blit(&self.context, source.get_attachments(), self.get_attachments(),
+                  gl::COLOR_BUFFER_BIT, source_rect, target_rect, filter.to_glenum())
+    }
+}
+
+impl FboAttachments for EmptyFrameBuffer {
+    #[inline]
+    fn get_attachments(&self) -> Option<&fbo::ValidatedAttachments<'_>> {
+        Some(&self.attachments)
+    }
+}
+
+/// A framebuffer whose attachments are whole texture arrays, cubemaps or 3D textures.
+///
+/// The layer that each primitive is drawn to is selected by writing to `gl_Layer` in the
+/// geometry shader. Primitives are drawn to the first layer if `gl_Layer` is not written.
+///
+/// Blitting from a layered framebuffer reads its first layer.
+pub struct LayeredFrameBuffer<'a> {
+    context: Rc<Context>,
+    attachments: fbo::ValidatedAttachments<'a>,
+    color: Option<TextureAnyMipmap<'a>>,
+    depth: Option<TextureAnyMipmap<'a>>,
+}
+
+impl<'a> LayeredFrameBuffer<'a> {
+    /// Returns true if layered framebuffers are supported by the backend.
+    #[inline]
+    pub fn is_supported<C: ?Sized>(context: &C) -> bool where C: CapabilitiesSource {
+        fbo::is_layered_supported(context)
+    }
+
+    /// Creates a `LayeredFrameBuffer` with a single color attachment and no depth
+    /// nor stencil buffer.
+    #[inline]
+    pub fn new<F: ?Sized, C>(facade: &F, color: C) -> Result<LayeredFrameBuffer<'a>, ValidationError>
+                     where C: ToLayeredColorAttachment<'a>, F: Facade
+    {
+        LayeredFrameBuffer::new_impl(facade, Some(color.to_layered_color_attachment()), None)
+    }
+
+    /// Creates a `LayeredFrameBuffer` with a single color attachment and a depth
+    /// buffer, but no stencil buffer.
+    #[inline]
+    pub fn with_depth_buffer<F: ?Sized, C, D>(facade: &F, color: C, depth: D)
+                                      -> Result<LayeredFrameBuffer<'a>, ValidationError>
+                                      where C: ToLayeredColorAttachment<'a>,
+                                            D: ToLayeredDepthAttachment<'a>, F: Facade
+    {
+        LayeredFrameBuffer::new_impl(facade, Some(color.to_layered_color_attachment()),
+                                     Some(depth.to_layered_depth_attachment()))
+    }
+
+    /// Creates a `LayeredFrameBuffer` with a depth buffer and no color attachment.
+    ///
+    /// This is typically used to render the shadow cubemap of a point light.
+    #[inline]
+    pub fn depth_only<F: ?Sized, D>(facade: &F, depth: D)
+                            -> Result<LayeredFrameBuffer<'a>, ValidationError>
+        where D: ToLayeredDepthAttachment<'a>, F: Facade
+    {
+        LayeredFrameBuffer::new_impl(facade, None, Some(depth.to_layered_depth_attachment()))
+    }
+
+    fn new_impl<F: ?Sized>(facade: &F, color: Option<LayeredColorAttachment<'a>>,
+                   depth: Option<LayeredDepthAttachment<'a>>)
+                   -> Result<LayeredFrameBuffer<'a>, ValidationError> where F: Facade
+    {
+        let color_mipmap = color.as_ref().map(|&LayeredColorAttachment(color)| color);
+        let depth_mipmap = depth.as_ref().map(|&LayeredDepthAttachment(depth)| depth);
+
+        let attachments = fbo::FramebufferAttachments::Layered(fbo::FramebufferSpecificAttachments {
+            colors: if let Some(LayeredColorAttachment(color)) = color {
+                let mut v = SmallVec::new(); v.push((0, fbo::LayeredAttachment(color))); v
+            } else {
+                SmallVec::new()
+            },
+            depth_stencil: if let Some(LayeredDepthAttachment(depth)) = depth {
+                fbo::DepthStencilAttachments::DepthAttachment(fbo::LayeredAttachment(depth))
+            } else {
+                fbo::DepthStencilAttachments::None
             }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
+        });
+
+        let attachments = attachments.validate(facade)?;
+        fbo::FramebuffersContainer::check_status(&mut facade.get_context().make_current(),
+                                                 &attachments)
+            .map_err(ValidationError::Incomplete)?;
+
+        Ok(LayeredFrameBuffer {
+            context: facade.get_context().clone(),
+            attachments,
+            color: color_mipmap,
+            depth: depth_mipmap,
+        })
+    }
+
+    /// Clears a single layer of the attachments, leaving the other layers untouched.
+    ///
+    /// Contrary to `clear`, which clears every layer, this only clears the layer whose index is
+    /// `layer`. For example this can be used to clear only the cascades of a shadow map array
+    /// that are going to be re-rendered. The layers of cubemaps are numbered like `gl_Layer`:
+    /// the index is `6 * index_in_the_array + face`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if `layer` is out of range.
+    pub fn clear_layer(&mut self, layer: u32, color: Option<(f32, f32, f32, f32)>,
+                       depth: Option<f32>)
+    {
+        let color = color.and_then(|value| self.color.map(|mipmap| (layer_image(mipmap, layer), value)));
+        let depth = depth.and_then(|value| self.depth.map(|mipmap| (layer_image(mipmap, layer), value)));
+
+        let attachments = fbo::FramebufferAttachments::Regular(fbo::FramebufferSpecificAttachments {
+            colors: if let Some((image, _)) = color {
+                let mut v = SmallVec::new(); v.push((0, fbo::RegularAttachment::Texture(image))); v
+            } else {
+                SmallVec::new()
+            },
+            depth_stencil: if let Some((image, _)) = depth {
+                fbo::DepthStencilAttachments::DepthAttachment(fbo::RegularAttachment::Texture(image))
+            } else {
+                fbo::DepthStencilAttachments::None
             }
+        });
+
+        let attachments = attachments.validate(&self.context).unwrap();
+        ops::clear(&self.context, Some(&attachments), None, color.map(|(_, value)| value), false,
+                   depth.map(|(_, value)| value), None);
+    }
+}
+
+/// Returns the image of a mipmap that corresponds to the layer `layer` of a layered framebuffer.
+fn layer_image(mipmap: TextureAnyMipmap<'_>, layer: u32) -> TextureAnyImage<'_> {
+    let (layer, cube_layer) = match mipmap.get_texture().get_texture_type() {
+        Dimensions::Cubemap { .. } | Dimensions::CubemapArray { .. } => {
+            let face = match layer % 6 {
+                0 => CubeLayer::PositiveX,
+                1 => CubeLayer::NegativeX,
+                2 => CubeLayer::PositiveY,
+                3 => CubeLayer::NegativeY,
+                4 => CubeLayer::PositiveZ,
+                _ => CubeLayer::NegativeZ,
+            };
+            (layer / 6, Some(face))
+        },
+        _ => (layer, None),
+    };
+
+    mipmap.layer(layer).expect("Layer out of range").into_image(cube_layer).unwrap()
+}
+
+impl<'a> Surface for LayeredFrameBuffer<'a> {
+    #[inline]
+    fn clear(&mut self, rect: Option<&Rect>, color: Option<(f32, f32, f32, f32)>, color_srgb: bool,
+             depth: Option<f32>, stencil: Option<i32>)
+    {
+        ops::clear(&self.context, Some(&self.attachments), rect, color, color_srgb, depth, stencil);
+    }
+
+    #[inline]
+    fn clear_stencil_masked(&mut self, value: i32, mask: u32) {
+        ops::clear_stencil_masked(&self.context, Some(&self.attachments), None, value, mask);
+    }
+
+    #[inline]
+    fn get_dimensions(&self) -> (u32, u32) {
+        self.attachments.get_dimensions()
+    }
+
+    #[inline]
+    fn get_depth_buffer_bits(&self) -> Option<u16> {
+        self.attachments.get_depth_buffer_bits()
+    }
+
+    #[inline]
+    fn get_stencil_buffer_bits(&self) -> Option<u16> {
+        self.attachments.get_stencil_buffer_bits()
+    }
+
+    fn draw<'b, 'v, V, I, U>(&mut self, vb: V, ib: I, program: &crate::Program,
+        uniforms: &U, draw_parameters: &crate::DrawParameters<'_>) -> Result<(), DrawError>
+        where I: Into<crate::index::IndicesSource<'b>>, U: crate::uniforms::Uniforms,
+        V: crate::vertex::MultiVerticesSource<'v>
+    {
+        if !self.has_depth_buffer() && (draw_parameters.depth.test.requires_depth_buffer() ||
+                        draw_parameters.depth.write)
+        {
+            return Err(DrawError::NoDepthBuffer);
         }
 
         ops::draw(&self.context, Some(&self.attachments), vb,
@@ -753,10 +1184,24 @@ impl Surface for EmptyFrameBuffer {
     }
 
     #[inline]
+    fn execute(&mut self, list: &crate::command_list::CommandList<'_>) -> Result<(), DrawError> {
+        list.execute(&self.context, Some(&self.attachments), self.get_dimensions(),
+                     self.has_depth_buffer())
+    }
+
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
                      filter: uniforms::MagnifySamplerFilter) where S: Surface
     {
-        unimplemented!()        // TODO:
+        // like OpenGL does with layered framebuffers, only the first layer is read
+        let color = match self.color {
+            Some(color) => color,
+            None => return,
+        };
+
+        let source = ColorAttachment::Texture(layer_image(color, 0));
+        let source = SimpleFrameBuffer::new(&self.context, source)
+                                        .expect("a layer of a valid attachment is valid");
+        target.blit_from_simple_framebuffer(&source, source_rect, target_rect, filter)
     }
 
     #[inline]
@@ -786,7 +1231,7 @@ impl Surface for EmptyFrameBuffer {
     }
 }
 
-impl FboAttachments for EmptyFrameBuffer {
+impl<'a> FboAttachments for LayeredFrameBuffer<'a> {
     #[inline]
     fn get_attachments(&self) -> Option<&fbo::ValidatedAttachments<'_>> {
         Some(&self.attachments)
@@ -880,3 +1325,57 @@ impl<'a> ToDepthStencilAttachment<'a> for DepthStencilAttachment<'a> {
         self
     }
 }
+
+/// Describes a layered attachment for a color buffer.
+#[derive(Copy, Clone)]
+pub struct LayeredColorAttachment<'a>(TextureAnyMipmap<'a>);
+
+impl<'a> LayeredColorAttachment<'a> {
+    /// Builds a layered color attachment from a mipmap level of a texture.
+    ///
+    /// The texture should be a texture array, a cubemap or a 3D texture.
+    #[inline]
+    pub fn new(mipmap: TextureAnyMipmap<'a>) -> LayeredColorAttachment<'a> {
+        LayeredColorAttachment(mipmap)
+    }
+}
+
+/// Trait for objects that can be used as layered color attachments.
+pub trait ToLayeredColorAttachment<'a> {
+    /// Builds the `LayeredColorAttachment`.
+    fn to_layered_color_attachment(self) -> LayeredColorAttachment<'a>;
+}
+
+impl<'a> ToLayeredColorAttachment<'a> for LayeredColorAttachment<'a> {
+    #[inline]
+    fn to_layered_color_attachment(self) -> LayeredColorAttachment<'a> {
+        self
+    }
+}
+
+/// Describes a layered attachment for a depth buffer.
+#[derive(Copy, Clone)]
+pub struct LayeredDepthAttachment<'a>(TextureAnyMipmap<'a>);
+
+impl<'a> LayeredDepthAttachment<'a> {
+    /// Builds a layered depth attachment from a mipmap level of a depth texture.
+    ///
+    /// The texture should be a texture array, a cubemap or a 3D texture.
+    #[inline]
+    pub fn new(mipmap: TextureAnyMipmap<'a>) -> LayeredDepthAttachment<'a> {
+        LayeredDepthAttachment(mipmap)
+    }
+}
+
+/// Trait for objects that can be used as layered depth attachments.
+pub trait ToLayeredDepthAttachment<'a> {
+    /// Builds the `LayeredDepthAttachment`.
+    fn to_layered_depth_attachment(self) -> LayeredDepthAttachment<'a>;
+}
+
+impl<'a> ToLayeredDepthAttachment<'a> for LayeredDepthAttachment<'a> {
+    #[inline]
+    fn to_layered_depth_attachment(self) -> LayeredDepthAttachment<'a> {
+        self
+    }
+}
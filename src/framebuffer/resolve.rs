@@ -0,0 +1,140 @@
+//! Resolving multisampled textures with a custom shader.
+
+use crate::backend::Facade;
+use crate::index::{NoIndices, PrimitiveType};
+use crate::texture::Texture2dMultisample;
+use crate::uniforms::{EmptyUniforms, Uniforms, UniformValue, AsUniformValue};
+use crate::vertex::EmptyVertexAttributes;
+
+use crate::DrawError;
+use crate::Program;
+use crate::ProgramCreationError;
+use crate::Surface;
+
+/// A program that resolves multisampled textures with a custom fragment shader.
+///
+/// The usual way to resolve a multisampled texture is to blit it to a single-sample surface.
+/// The driver then averages the samples of each pixel, which is not always what you want. For
+/// example HDR renderers usually get better results by tonemapping the samples before averaging
+/// them.
+///
+/// A `MultisampleResolve` draws a triangle that covers the whole target and runs a fragment
+/// shader that you provide for each pixel. This shader has access to two uniforms:
+///
+///  - `source`, a `sampler2DMS` that contains the texture to resolve.
+///  - `samples`, an `int` that contains the number of samples of the texture.
+///
+/// Since the triangle covers the target, `ivec2(gl_FragCoord.xy)` gives the coordinates of the
+/// texel to read with `texelFetch`. This means that the target should have the same dimensions
+/// as the texture.
+///
+/// ```no_run
+/// # let display: glium::Display = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// # let multisampled: glium::texture::Texture2dMultisample = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// # let texture: glium::Texture2d = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// let resolve = glium::framebuffer::MultisampleResolve::new(&display, "
+///     #version 150
+///
+///     uniform sampler2DMS source;
+///     uniform int samples;
+///
+///     out vec4 color;
+///
+///     void main() {
+///         ivec2 coords = ivec2(gl_FragCoord.xy);
+///
+///         vec4 sum = vec4(0.0);
+///         for (int i = 0; i < samples; ++i) {
+///             vec4 value = texelFetch(source, coords, i);
+///             sum += value / (1.0 + max(value.r, max(value.g, value.b)));
+///         }
+///
+///         color = sum / float(samples);
+///     }
+/// ").unwrap();
+///
+/// resolve.resolve(&multisampled, &mut texture.as_surface()).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct MultisampleResolve {
+    program: Program,
+}
+
+impl MultisampleResolve {
+    /// Builds the program from the source code of its fragment shader.
+    ///
+    /// The vertex shader is provided by glium and uses the same `#version` as the fragment
+    /// shader. `sampler2DMS` requires at least GLSL 1.50 or GLSL ES 3.10.
+    pub fn new<F: ?Sized>(facade: &F, fragment_shader: &str)
+                          -> Result<MultisampleResolve, ProgramCreationError> where F: Facade
+    {
+        let version = fragment_shader.lines()
+                                     .map(|line| line.trim())
+                                     .find(|line| line.starts_with("#version"))
+                                     .unwrap_or("#version 150");
+
+        let vertex_shader = format!("{}
+
+            void main() {{
+                vec2 position = vec2(float((gl_VertexID & 1) << 2) - 1.0,
+                                     float((gl_VertexID & 2) << 1) - 1.0);
+                gl_Position = vec4(position, 0.0, 1.0);
+            }}
+        ", version);
+
+        let program = Program::from_source(facade, &vertex_shader, fragment_shader, None)?;
+
+        Ok(MultisampleResolve {
+            program,
+        })
+    }
+
+    /// Returns the program that is used to resolve the textures.
+    #[inline]
+    pub fn get_program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Resolves `source` into `target`.
+    #[inline]
+    pub fn resolve<S: ?Sized>(&self, source: &Texture2dMultisample, target: &mut S)
+                              -> Result<(), DrawError> where S: Surface
+    {
+        self.resolve_with_uniforms(source, target, &EmptyUniforms)
+    }
+
+    /// Resolves `source` into `target`, and passes additional uniforms to the fragment shader.
+    ///
+    /// This can be used for example to pass the exposure to a tonemapping shader.
+    pub fn resolve_with_uniforms<S: ?Sized, U>(&self, source: &Texture2dMultisample,
+                                               target: &mut S, uniforms: &U)
+                                               -> Result<(), DrawError>
+                                               where S: Surface, U: Uniforms
+    {
+        let uniforms = ResolveUniforms {
+            source,
+            samples: source.samples() as i32,
+            rest: uniforms,
+        };
+
+        target.draw(EmptyVertexAttributes { len: 3 },
+                    NoIndices(PrimitiveType::TrianglesList), &self.program, &uniforms,
+                    &Default::default())
+    }
+}
+
+/// The uniforms passed to the program, in addition to the uniforms of the user.
+struct ResolveUniforms<'a, U> {
+    source: &'a Texture2dMultisample,
+    samples: i32,
+    rest: &'a U,
+}
+
+impl<'b, U> Uniforms for ResolveUniforms<'b, U> where U: Uniforms {
+    #[inline]
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        output("source", self.source.as_uniform_value());
+        output("samples", UniformValue::SignedInt(self.samples));
+        self.rest.visit_values(output);
+    }
+}
@@ -1039,6 +1039,55 @@ pub enum CompressedFormat {
     S3tcDxt3Alpha,
     /// S3TC DXT5, see https://www.opengl.org/wiki/S3_Texture_Compression.
     S3tcDxt5Alpha,
+
+    /// ETC2 with three components. ETC1 data can be uploaded with this format as well.
+    ///
+    /// ETC2 and EAC are available on OpenGL ES 3.0 and OpenGL 4.3.
+    Etc2Rgb8,
+    /// ETC2 with three components and 1-bit alpha.
+    Etc2Rgb8PunchthroughAlpha1,
+    /// ETC2 with four components.
+    Etc2Rgba8,
+    /// EAC with one unsigned component.
+    EacR11,
+    /// EAC with one signed component.
+    EacSignedR11,
+    /// EAC with two unsigned components.
+    EacRg11,
+    /// EAC with two signed components.
+    EacSignedRg11,
+
+    /// ASTC with blocks of 4x4 texels.
+    ///
+    /// ASTC is mainly available on mobile GPUs, see
+    /// https://www.khronos.org/opengl/wiki/ASTC_Texture_Compression.
+    Astc4x4,
+    /// ASTC with blocks of 5x4 texels.
+    Astc5x4,
+    /// ASTC with blocks of 5x5 texels.
+    Astc5x5,
+    /// ASTC with blocks of 6x5 texels.
+    Astc6x5,
+    /// ASTC with blocks of 6x6 texels.
+    Astc6x6,
+    /// ASTC with blocks of 8x5 texels.
+    Astc8x5,
+    /// ASTC with blocks of 8x6 texels.
+    Astc8x6,
+    /// ASTC with blocks of 8x8 texels.
+    Astc8x8,
+    /// ASTC with blocks of 10x5 texels.
+    Astc10x5,
+    /// ASTC with blocks of 10x6 texels.
+    Astc10x6,
+    /// ASTC with blocks of 10x8 texels.
+    Astc10x8,
+    /// ASTC with blocks of 10x10 texels.
+    Astc10x10,
+    /// ASTC with blocks of 12x10 texels.
+    Astc12x10,
+    /// ASTC with blocks of 12x12 texels.
+    Astc12x12,
 }
 
 impl CompressedFormat {
@@ -1057,6 +1106,27 @@ impl CompressedFormat {
             CompressedFormat::S3tcDxt1Alpha,
             CompressedFormat::S3tcDxt3Alpha,
             CompressedFormat::S3tcDxt5Alpha,
+            CompressedFormat::Etc2Rgb8,
+            CompressedFormat::Etc2Rgb8PunchthroughAlpha1,
+            CompressedFormat::Etc2Rgba8,
+            CompressedFormat::EacR11,
+            CompressedFormat::EacSignedR11,
+            CompressedFormat::EacRg11,
+            CompressedFormat::EacSignedRg11,
+            CompressedFormat::Astc4x4,
+            CompressedFormat::Astc5x4,
+            CompressedFormat::Astc5x5,
+            CompressedFormat::Astc6x5,
+            CompressedFormat::Astc6x6,
+            CompressedFormat::Astc8x5,
+            CompressedFormat::Astc8x6,
+            CompressedFormat::Astc8x8,
+            CompressedFormat::Astc10x5,
+            CompressedFormat::Astc10x6,
+            CompressedFormat::Astc10x8,
+            CompressedFormat::Astc10x10,
+            CompressedFormat::Astc12x10,
+            CompressedFormat::Astc12x12,
         ]
     }
 
@@ -1066,6 +1136,29 @@ impl CompressedFormat {
         TextureFormat::CompressedFormat(self)
     }
 
+    /// Returns the width and height in texels of the blocks of this format.
+    ///
+    /// All the formats use blocks of 4x4 texels, except for ASTC.
+    pub fn get_block_dimensions(&self) -> (u32, u32) {
+        match *self {
+            CompressedFormat::Astc4x4 => (4, 4),
+            CompressedFormat::Astc5x4 => (5, 4),
+            CompressedFormat::Astc5x5 => (5, 5),
+            CompressedFormat::Astc6x5 => (6, 5),
+            CompressedFormat::Astc6x6 => (6, 6),
+            CompressedFormat::Astc8x5 => (8, 5),
+            CompressedFormat::Astc8x6 => (8, 6),
+            CompressedFormat::Astc8x8 => (8, 8),
+            CompressedFormat::Astc10x5 => (10, 5),
+            CompressedFormat::Astc10x6 => (10, 6),
+            CompressedFormat::Astc10x8 => (10, 8),
+            CompressedFormat::Astc10x10 => (10, 10),
+            CompressedFormat::Astc12x10 => (12, 10),
+            CompressedFormat::Astc12x12 => (12, 12),
+            _ => (4, 4),
+        }
+    }
+
     /// Returns true if this format is supported by the backend.
     pub fn is_supported<C: ?Sized>(&self, context: &C) -> bool where C: CapabilitiesSource {
         let version = context.get_version();
@@ -1105,6 +1198,33 @@ impl CompressedFormat {
             &CompressedFormat::S3tcDxt5Alpha => {
                 extensions.gl_ext_texture_compression_s3tc
             },
+            &CompressedFormat::Etc2Rgb8 |
+            &CompressedFormat::Etc2Rgb8PunchthroughAlpha1 |
+            &CompressedFormat::Etc2Rgba8 |
+            &CompressedFormat::EacR11 |
+            &CompressedFormat::EacSignedR11 |
+            &CompressedFormat::EacRg11 |
+            &CompressedFormat::EacSignedRg11 => {
+                version >= &Version(Api::Gl, 4, 3) || version >= &Version(Api::GlEs, 3, 0) ||
+                extensions.gl_arb_es3_compatibility
+            },
+            &CompressedFormat::Astc4x4 |
+            &CompressedFormat::Astc5x4 |
+            &CompressedFormat::Astc5x5 |
+            &CompressedFormat::Astc6x5 |
+            &CompressedFormat::Astc6x6 |
+            &CompressedFormat::Astc8x5 |
+            &CompressedFormat::Astc8x6 |
+            &CompressedFormat::Astc8x8 |
+            &CompressedFormat::Astc10x5 |
+            &CompressedFormat::Astc10x6 |
+            &CompressedFormat::Astc10x8 |
+            &CompressedFormat::Astc10x10 |
+            &CompressedFormat::Astc12x10 |
+            &CompressedFormat::Astc12x12 => {
+                version >= &Version(Api::GlEs, 3, 2) ||
+                extensions.gl_khr_texture_compression_astc_ldr
+            },
         }
     }
 
@@ -1121,6 +1241,28 @@ impl CompressedFormat {
             &CompressedFormat::S3tcDxt1Alpha => gl::COMPRESSED_RGBA_S3TC_DXT1_EXT,
             &CompressedFormat::S3tcDxt3Alpha => gl::COMPRESSED_RGBA_S3TC_DXT3_EXT,
             &CompressedFormat::S3tcDxt5Alpha => gl::COMPRESSED_RGBA_S3TC_DXT5_EXT,
+            &CompressedFormat::Etc2Rgb8 => gl::COMPRESSED_RGB8_ETC2,
+            &CompressedFormat::Etc2Rgb8PunchthroughAlpha1 =>
+                gl::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2,
+            &CompressedFormat::Etc2Rgba8 => gl::COMPRESSED_RGBA8_ETC2_EAC,
+            &CompressedFormat::EacR11 => gl::COMPRESSED_R11_EAC,
+            &CompressedFormat::EacSignedR11 => gl::COMPRESSED_SIGNED_R11_EAC,
+            &CompressedFormat::EacRg11 => gl::COMPRESSED_RG11_EAC,
+            &CompressedFormat::EacSignedRg11 => gl::COMPRESSED_SIGNED_RG11_EAC,
+            &CompressedFormat::Astc4x4 => gl::COMPRESSED_RGBA_ASTC_4x4,
+            &CompressedFormat::Astc5x4 => gl::COMPRESSED_RGBA_ASTC_5x4,
+            &CompressedFormat::Astc5x5 => gl::COMPRESSED_RGBA_ASTC_5x5,
+            &CompressedFormat::Astc6x5 => gl::COMPRESSED_RGBA_ASTC_6x5,
+            &CompressedFormat::Astc6x6 => gl::COMPRESSED_RGBA_ASTC_6x6,
+            &CompressedFormat::Astc8x5 => gl::COMPRESSED_RGBA_ASTC_8x5,
+            &CompressedFormat::Astc8x6 => gl::COMPRESSED_RGBA_ASTC_8x6,
+            &CompressedFormat::Astc8x8 => gl::COMPRESSED_RGBA_ASTC_8x8,
+            &CompressedFormat::Astc10x5 => gl::COMPRESSED_RGBA_ASTC_10x5,
+            &CompressedFormat::Astc10x6 => gl::COMPRESSED_RGBA_ASTC_10x6,
+            &CompressedFormat::Astc10x8 => gl::COMPRESSED_RGBA_ASTC_10x8,
+            &CompressedFormat::Astc10x10 => gl::COMPRESSED_RGBA_ASTC_10x10,
+            &CompressedFormat::Astc12x10 => gl::COMPRESSED_RGBA_ASTC_12x10,
+            &CompressedFormat::Astc12x12 => gl::COMPRESSED_RGBA_ASTC_12x12,
         }
     }
 }
@@ -1135,6 +1277,12 @@ pub enum CompressedSrgbFormat {
     S3tcDxt1Alpha,
     S3tcDxt3Alpha,
     S3tcDxt5Alpha,
+    /// ETC2 in the sRGB color space, without alpha.
+    Etc2,
+    /// ETC2 in the sRGB color space, with 1-bit alpha.
+    Etc2PunchthroughAlpha1,
+    /// ETC2 in the sRGB color space, with an EAC-compressed alpha component.
+    Etc2Alpha8,
 }
 
 impl CompressedSrgbFormat {
@@ -1147,6 +1295,9 @@ impl CompressedSrgbFormat {
             CompressedSrgbFormat::S3tcDxt1Alpha,
             CompressedSrgbFormat::S3tcDxt3Alpha,
             CompressedSrgbFormat::S3tcDxt5Alpha,
+            CompressedSrgbFormat::Etc2,
+            CompressedSrgbFormat::Etc2PunchthroughAlpha1,
+            CompressedSrgbFormat::Etc2Alpha8,
         ]
     }
 
@@ -1177,6 +1328,12 @@ impl CompressedSrgbFormat {
             &CompressedSrgbFormat::S3tcDxt5Alpha => {
                 extensions.gl_ext_texture_compression_s3tc && extensions.gl_ext_texture_srgb
             },
+            &CompressedSrgbFormat::Etc2 |
+            &CompressedSrgbFormat::Etc2PunchthroughAlpha1 |
+            &CompressedSrgbFormat::Etc2Alpha8 => {
+                version >= &Version(Api::Gl, 4, 3) || version >= &Version(Api::GlEs, 3, 0) ||
+                extensions.gl_arb_es3_compatibility
+            },
         }
     }
 
@@ -1187,6 +1344,10 @@ impl CompressedSrgbFormat {
             &CompressedSrgbFormat::S3tcDxt1Alpha => gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT,
             &CompressedSrgbFormat::S3tcDxt3Alpha => gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT,
             &CompressedSrgbFormat::S3tcDxt5Alpha => gl::COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT,
+            &CompressedSrgbFormat::Etc2 => gl::COMPRESSED_SRGB8_ETC2,
+            &CompressedSrgbFormat::Etc2PunchthroughAlpha1 =>
+                gl::COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2,
+            &CompressedSrgbFormat::Etc2Alpha8 => gl::COMPRESSED_SRGB8_ALPHA8_ETC2_EAC,
         }
     }
 }
@@ -1258,8 +1419,10 @@ impl DepthFormat {
 }
 
 /// List of formats available for depth-stencil textures.
-// TODO: If OpenGL 4.3 or ARB_stencil_texturing is not available, then depth/stencil
-//       textures are treated by samplers exactly like depth-only textures
+///
+/// By default, depth-stencil textures are sampled like depth textures. With OpenGL 4.3,
+/// OpenGL ES 3.1 or `GL_ARB_stencil_texturing`, their stencil component can be sampled instead.
+/// See `TextureAny::set_depth_stencil_texture_mode`.
 #[allow(missing_docs)]
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum DepthStencilFormat {
@@ -1517,7 +1680,8 @@ impl ClientFormatAny {
     ///
     /// ## Panic
     ///
-    /// Panics if the dimensions are invalid for this format.
+    /// Panics if the dimensions are invalid for this format, or if the size doesn't fit in a
+    /// `usize`.
     pub fn get_buffer_size(&self, width: u32, height: Option<u32>,
                            depth: Option<u32>, array_size: Option<u32>) -> usize {
         match *self {
@@ -1575,6 +1739,60 @@ impl ClientFormatAny {
                                             depth.unwrap_or(1) as usize * array_size.unwrap_or(1) as usize;
                 uncompressed_bit_size / 4   // Apply 4:1 compression ratio
             },
+
+            // 8 or 16 bytes per 4x4 block
+            ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgb8) |
+            ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgb8PunchthroughAlpha1) |
+            ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgba8) |
+            ClientFormatAny::CompressedFormat(CompressedFormat::EacR11) |
+            ClientFormatAny::CompressedFormat(CompressedFormat::EacSignedR11) |
+            ClientFormatAny::CompressedFormat(CompressedFormat::EacRg11) |
+            ClientFormatAny::CompressedFormat(CompressedFormat::EacSignedRg11) |
+            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2) |
+            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2PunchthroughAlpha1) |
+            ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Alpha8) => {
+                let block_size = match *self {
+                    ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgba8) |
+                    ClientFormatAny::CompressedFormat(CompressedFormat::EacRg11) |
+                    ClientFormatAny::CompressedFormat(CompressedFormat::EacSignedRg11) |
+                    ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Alpha8) => 16,
+                    _ => 8,
+                };
+
+                let height = height.expect("ETC2 and EAC textures must have 2 dimensions");
+                if depth.is_some() {
+                    panic!("ETC2 and EAC textures are 2 dimension only.")
+                }
+
+                // like ASTC, the dimensions don't need to be multiples of 4
+                compressed_buffer_size(block_size, (4, 4), width, height, array_size)
+            },
+
+            // 16 bytes per block, whose dimensions depend on the format
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc4x4) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc5x4) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc5x5) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc6x5) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc6x6) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc8x5) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc8x6) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc8x8) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc10x5) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc10x6) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc10x8) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc10x10) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc12x10) |
+            ClientFormatAny::CompressedFormat(format @ CompressedFormat::Astc12x12) => {
+                let (block_width, block_height) = format.get_block_dimensions();
+                let height = height.expect("ASTC textures must have 2 dimensions");
+                if depth.is_some() {
+                    panic!("ASTC textures are 2 dimension only.")
+                }
+
+                // contrary to the other formats, the dimensions don't need to be multiples of
+                // the dimensions of the blocks
+                compressed_buffer_size(16, (block_width, block_height), width, height, array_size)
+            },
         }
     }
 
@@ -1605,11 +1823,55 @@ impl ClientFormatAny {
             gl::COMPRESSED_SIGNED_RED_RGTC1 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatI)),
             gl::COMPRESSED_RG_RGTC2 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatUU)),
             gl::COMPRESSED_SIGNED_RG_RGTC2 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::RgtcFormatII)),
+            gl::COMPRESSED_RGB8_ETC2 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgb8)),
+            gl::COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgb8PunchthroughAlpha1)),
+            gl::COMPRESSED_RGBA8_ETC2_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Etc2Rgba8)),
+            gl::COMPRESSED_R11_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::EacR11)),
+            gl::COMPRESSED_SIGNED_R11_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::EacSignedR11)),
+            gl::COMPRESSED_RG11_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::EacRg11)),
+            gl::COMPRESSED_SIGNED_RG11_EAC => Some(ClientFormatAny::CompressedFormat(CompressedFormat::EacSignedRg11)),
+            gl::COMPRESSED_SRGB8_ETC2 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2)),
+            gl::COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2 => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2PunchthroughAlpha1)),
+            gl::COMPRESSED_SRGB8_ALPHA8_ETC2_EAC => Some(ClientFormatAny::CompressedSrgbFormat(CompressedSrgbFormat::Etc2Alpha8)),
+            gl::COMPRESSED_RGBA_ASTC_4x4 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc4x4)),
+            gl::COMPRESSED_RGBA_ASTC_5x4 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc5x4)),
+            gl::COMPRESSED_RGBA_ASTC_5x5 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc5x5)),
+            gl::COMPRESSED_RGBA_ASTC_6x5 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc6x5)),
+            gl::COMPRESSED_RGBA_ASTC_6x6 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc6x6)),
+            gl::COMPRESSED_RGBA_ASTC_8x5 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc8x5)),
+            gl::COMPRESSED_RGBA_ASTC_8x6 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc8x6)),
+            gl::COMPRESSED_RGBA_ASTC_8x8 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc8x8)),
+            gl::COMPRESSED_RGBA_ASTC_10x5 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc10x5)),
+            gl::COMPRESSED_RGBA_ASTC_10x6 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc10x6)),
+            gl::COMPRESSED_RGBA_ASTC_10x8 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc10x8)),
+            gl::COMPRESSED_RGBA_ASTC_10x10 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc10x10)),
+            gl::COMPRESSED_RGBA_ASTC_12x10 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc12x10)),
+            gl::COMPRESSED_RGBA_ASTC_12x12 => Some(ClientFormatAny::CompressedFormat(CompressedFormat::Astc12x12)),
             _ => None,
         }
     }
 }
 
+/// Returns the size in bytes of an image made of blocks of `block_size` bytes that each cover
+/// `block_dimensions` texels. The dimensions of the image don't need to be multiples of the
+/// dimensions of the blocks.
+///
+/// ## Panic
+///
+/// Panics if the size doesn't fit in a `usize`.
+fn compressed_buffer_size(block_size: usize, block_dimensions: (u32, u32), width: u32,
+                          height: u32, array_size: Option<u32>) -> usize
+{
+    let (block_width, block_height) = block_dimensions;
+    let blocks_x = width / block_width + if width % block_width != 0 { 1 } else { 0 };
+    let blocks_y = height / block_height + if height % block_height != 0 { 1 } else { 0 };
+
+    block_size.checked_mul(blocks_x as usize)
+              .and_then(|size| size.checked_mul(blocks_y as usize))
+              .and_then(|size| size.checked_mul(array_size.unwrap_or(1) as usize))
+              .expect("The size of the compressed texture overflows")
+}
+
 /// Type of request.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum RequestType {
@@ -1633,6 +1895,141 @@ impl RequestType {
     }
 }
 
+/// Amount of memory used by the texels of a texture format.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FormatMemorySize {
+    /// Each texel uses this number of bits.
+    Bits(usize),
+
+    /// The texels are compressed by blocks.
+    Blocks {
+        /// Width of a block in texels.
+        width: u32,
+        /// Height of a block in texels.
+        height: u32,
+        /// Number of bytes of a block.
+        bytes: usize,
+    },
+}
+
+/// Returns the amount of memory used by the texels of a format, without querying the backend.
+///
+/// Specific requests are computed from the format. The other requests are computed from the
+/// internal format that has been chosen for them by `format_request_to_glenum`. Returns `None`
+/// if the amount of memory can't be known, for example for generic compressed formats.
+pub fn format_memory_size(format: TextureFormatRequest, internal_format: gl::types::GLenum)
+                          -> Option<FormatMemorySize>
+{
+    let bits = match format {
+        TextureFormatRequest::Specific(TextureFormat::UncompressedFloat(f)) => match f {
+            UncompressedFloatFormat::U8 | UncompressedFloatFormat::I8 |
+            UncompressedFloatFormat::U3U3U2 | UncompressedFloatFormat::U2U2U2U2 => 8,
+            UncompressedFloatFormat::U4U4U4 => 12,
+            UncompressedFloatFormat::U5U5U5 => 15,
+            UncompressedFloatFormat::U16 | UncompressedFloatFormat::I16 |
+            UncompressedFloatFormat::U8U8 | UncompressedFloatFormat::I8I8 |
+            UncompressedFloatFormat::U4U4U4U4 | UncompressedFloatFormat::U5U5U5U1 |
+            UncompressedFloatFormat::F16 => 16,
+            UncompressedFloatFormat::U8U8U8 | UncompressedFloatFormat::I8I8I8 => 24,
+            UncompressedFloatFormat::U10U10U10 => 30,
+            UncompressedFloatFormat::U16U16 | UncompressedFloatFormat::I16I16 |
+            UncompressedFloatFormat::U8U8U8U8 | UncompressedFloatFormat::I8I8I8I8 |
+            UncompressedFloatFormat::U10U10U10U2 | UncompressedFloatFormat::F16F16 |
+            UncompressedFloatFormat::F32 | UncompressedFloatFormat::F11F11F10 |
+            UncompressedFloatFormat::F9F9F9 => 32,
+            UncompressedFloatFormat::U12U12U12 => 36,
+            UncompressedFloatFormat::U16U16U16 | UncompressedFloatFormat::I16I16I16 |
+            UncompressedFloatFormat::U12U12U12U12 | UncompressedFloatFormat::F16F16F16 => 48,
+            UncompressedFloatFormat::U16U16U16U16 | UncompressedFloatFormat::I16I16I16I16 |
+            UncompressedFloatFormat::F16F16F16F16 | UncompressedFloatFormat::F32F32 => 64,
+            UncompressedFloatFormat::F32F32F32 => 96,
+            UncompressedFloatFormat::F32F32F32F32 => 128,
+        },
+
+        TextureFormatRequest::Specific(TextureFormat::UncompressedIntegral(f)) => match f {
+            UncompressedIntFormat::I8 => 8,
+            UncompressedIntFormat::I16 | UncompressedIntFormat::I8I8 => 16,
+            UncompressedIntFormat::I8I8I8 => 24,
+            UncompressedIntFormat::I32 | UncompressedIntFormat::I16I16 |
+            UncompressedIntFormat::I8I8I8I8 => 32,
+            UncompressedIntFormat::I16I16I16 => 48,
+            UncompressedIntFormat::I32I32 | UncompressedIntFormat::I16I16I16I16 => 64,
+            UncompressedIntFormat::I32I32I32 => 96,
+            UncompressedIntFormat::I32I32I32I32 => 128,
+        },
+
+        TextureFormatRequest::Specific(TextureFormat::UncompressedUnsigned(f)) => match f {
+            UncompressedUintFormat::U8 => 8,
+            UncompressedUintFormat::U16 | UncompressedUintFormat::U8U8 => 16,
+            UncompressedUintFormat::U8U8U8 => 24,
+            UncompressedUintFormat::U32 | UncompressedUintFormat::U16U16 |
+            UncompressedUintFormat::U8U8U8U8 | UncompressedUintFormat::U10U10U10U2 => 32,
+            UncompressedUintFormat::U16U16U16 => 48,
+            UncompressedUintFormat::U32U32 | UncompressedUintFormat::U16U16U16U16 => 64,
+            UncompressedUintFormat::U32U32U32 => 96,
+            UncompressedUintFormat::U32U32U32U32 => 128,
+        },
+
+        TextureFormatRequest::Specific(TextureFormat::Srgb(f)) => match f {
+            SrgbFormat::U8U8U8 => 24,
+            SrgbFormat::U8U8U8U8 => 32,
+        },
+
+        TextureFormatRequest::Specific(TextureFormat::CompressedFormat(f)) => {
+            let (width, height) = f.get_block_dimensions();
+            let bytes = match f {
+                CompressedFormat::RgtcFormatU | CompressedFormat::RgtcFormatI |
+                CompressedFormat::S3tcDxt1NoAlpha | CompressedFormat::S3tcDxt1Alpha |
+                CompressedFormat::Etc2Rgb8 | CompressedFormat::Etc2Rgb8PunchthroughAlpha1 |
+                CompressedFormat::EacR11 | CompressedFormat::EacSignedR11 => 8,
+                _ => 16,
+            };
+            return Some(FormatMemorySize::Blocks { width, height, bytes });
+        },
+
+        TextureFormatRequest::Specific(TextureFormat::CompressedSrgbFormat(f)) => {
+            let bytes = match f {
+                CompressedSrgbFormat::S3tcDxt1NoAlpha | CompressedSrgbFormat::S3tcDxt1Alpha |
+                CompressedSrgbFormat::Etc2 | CompressedSrgbFormat::Etc2PunchthroughAlpha1 => 8,
+                _ => 16,
+            };
+            return Some(FormatMemorySize::Blocks { width: 4, height: 4, bytes });
+        },
+
+        TextureFormatRequest::Specific(TextureFormat::DepthFormat(f)) => match f {
+            DepthFormat::I16 => 16,
+            DepthFormat::I24 => 24,
+            DepthFormat::I32 | DepthFormat::F32 => 32,
+        },
+
+        TextureFormatRequest::Specific(TextureFormat::StencilFormat(f)) => match f {
+            StencilFormat::I1 => 1,
+            StencilFormat::I4 => 4,
+            StencilFormat::I8 => 8,
+            StencilFormat::I16 => 16,
+        },
+
+        TextureFormatRequest::Specific(TextureFormat::DepthStencilFormat(f)) => match f {
+            DepthStencilFormat::I24I8 => 32,
+            DepthStencilFormat::F32I8 => 40,
+        },
+
+        _ => match internal_format {
+            gl::R8 | gl::RED | gl::LUMINANCE => 8,
+            gl::RG8 | gl::RG | gl::LUMINANCE_ALPHA | gl::RGB565 | gl::RGB5_A1 => 16,
+            gl::RGB8 | gl::RGB | gl::SRGB8 | gl::DEPTH_COMPONENT24 => 24,
+            gl::RGBA8 | gl::RGBA | gl::SRGB8_ALPHA8 | gl::R32I | gl::R32UI |
+            gl::DEPTH24_STENCIL8 | gl::DEPTH_STENCIL => 32,
+            gl::RG32I | gl::RG32UI => 64,
+            gl::RGB32I | gl::RGB32UI => 96,
+            gl::RGBA32I | gl::RGBA32UI => 128,
+            _ => return None,
+        },
+    };
+
+    Some(FormatMemorySize::Bits(bits))
+}
+
 /// Checks that the texture format is supported and compatible with the client format.
 ///
 /// Returns two `GLenum`s. The first one can be unsized and is suitable for the internal format
@@ -1696,6 +2093,15 @@ pub fn format_request_to_glenum(context: &Context, format: TextureFormatRequest,
 
             } else if version >= &Version(Api::GlEs, 2, 0) {
                 match (rq_ty, size) {
+                    // without `GL_EXT_texture_rg`, luminance textures are the only way to have
+                    // one or two channels ; they are sampled as `(L, L, L, 1)` and `(L, L, L, A)`
+                    // instead of `(R, 0, 0, 1)` and `(R, G, 0, 1)`
+                    (RequestType::TexImage(_), Some(1)) if extensions.gl_ext_texture_rg => gl::RED_EXT,
+                    (RequestType::TexImage(_), Some(1)) => gl::LUMINANCE,
+                    (RequestType::TexImage(_), Some(2)) if extensions.gl_ext_texture_rg => gl::RG_EXT,
+                    (RequestType::TexImage(_), Some(2)) => gl::LUMINANCE_ALPHA,
+                    (_, Some(1)) if extensions.gl_ext_texture_rg => gl::R8_EXT,
+                    (_, Some(2)) if extensions.gl_ext_texture_rg => gl::RG8_EXT,
                     (RequestType::TexImage(_), Some(3)) => gl::RGB,
                     (_, Some(3)) => {
                         if extensions.gl_oes_rgb8_rgba8 {
@@ -1726,6 +2132,29 @@ pub fn format_request_to_glenum(context: &Context, format: TextureFormatRequest,
         TextureFormatRequest::Specific(TextureFormat::UncompressedFloat(format)) => {
             if format.is_supported(context) {
                 format.to_glenum()
+
+            } else if version >= &Version(Api::GlEs, 2, 0) &&
+                      !(version >= &Version(Api::GlEs, 3, 0))
+            {
+                // OpenGL ES 2 doesn't support sized internal formats in `glTexImage2D`, but
+                // the unsized formats are equivalent if the client data has the same components
+                let (unsized_format, components) = match (rq_ty, format) {
+                    (RequestType::TexImage(_), UncompressedFloatFormat::U8)
+                        if extensions.gl_ext_texture_rg => (gl::RED_EXT, 1),
+                    (RequestType::TexImage(_), UncompressedFloatFormat::U8U8)
+                        if extensions.gl_ext_texture_rg => (gl::RG_EXT, 2),
+                    (RequestType::TexImage(_), UncompressedFloatFormat::U8U8U8) => (gl::RGB, 3),
+                    (RequestType::TexImage(_), UncompressedFloatFormat::U8U8U8U8) => (gl::RGBA, 4),
+                    _ => return Err(FormatNotSupportedError),
+                };
+
+                match rq_ty.get_client_format() {
+                    None => unsized_format,
+                    Some(ClientFormatAny::ClientFormat(client))
+                        if client.get_num_components() == components => unsized_format,
+                    Some(_) => return Err(FormatNotSupportedError),
+                }
+
             } else {
                 return Err(FormatNotSupportedError);
             }
@@ -2252,6 +2681,22 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
         },
     };
 
+    // without `GL_EXT_texture_rg`, OpenGL ES 2 textures with one or two channels are luminance
+    // textures, see `format_request_to_glenum`
+    let version = context.get_opengl_version();
+    let value = if version >= &Version(Api::GlEs, 2, 0) &&
+                   !(version >= &Version(Api::GlEs, 3, 0)) &&
+                   !context.get_extensions().gl_ext_texture_rg
+    {
+        value.map(|(format, ty)| match format {
+            gl::RED => (gl::LUMINANCE, ty),
+            gl::RG => (gl::LUMINANCE_ALPHA, ty),
+            format => (format, ty),
+        })
+    } else {
+        value
+    };
+
     if inverted {
         value.and_then(|(format, ty)| {
             let format = match format {
@@ -2266,3 +2711,83 @@ pub fn client_format_to_glenum(context: &Context, client: ClientFormatAny,
         value
     }
 }
+
+#[cfg(all(test, feature = "null_backend"))]
+mod tests {
+    use crate::backend::null::{build_context, NullBackend};
+    use crate::gl;
+    use crate::texture::{Texture2d, TextureCreationError, UncompressedFloatFormat, MipmapsOption};
+
+    #[test]
+    fn es2_texture_formats() {
+        let (backend, context) = build_context(NullBackend::with_version("OpenGL ES 2.0",
+                                                                         "OpenGL ES GLSL ES 1.00",
+                                                                         &[]));
+
+        // OpenGL ES 2 doesn't have sized internal formats nor immutable storage
+        Texture2d::empty_with_format(&context, UncompressedFloatFormat::U8U8U8U8,
+                                     MipmapsOption::NoMipmap, 16, 16).unwrap();
+
+        assert!(!backend.get_calls().contains(&"glTexStorage2D"));
+        assert_eq!(backend.get_texture_formats(), vec![gl::RGBA]);
+    }
+
+    #[test]
+    fn texture_storage() {
+        let (backend, context) = build_context(NullBackend::with_version("4.2.0", "4.20", &[]));
+
+        Texture2d::empty_with_format(&context, UncompressedFloatFormat::U8U8U8U8,
+                                     MipmapsOption::NoMipmap, 16, 16).unwrap();
+
+        assert!(backend.get_calls().contains(&"glTexStorage2D"));
+        assert_eq!(backend.get_texture_formats(), vec![gl::RGBA8]);
+    }
+
+    #[test]
+    fn es2_sized_formats_fallback() {
+        // OpenGL ES 2 only accepts the unsized equivalents of the sized formats
+        for &(version, glsl_version, expected) in [
+            ("OpenGL ES 2.0", "OpenGL ES GLSL ES 1.00", [gl::RGB, gl::RGBA]),
+            ("OpenGL ES 3.0", "OpenGL ES GLSL ES 3.00", [gl::RGB8, gl::RGBA8]),
+        ].iter() {
+            let (backend, context) = build_context(NullBackend::with_version(version,
+                                                                             glsl_version, &[]));
+
+            Texture2d::with_format(&context, vec![vec![(0u8, 0u8, 0u8); 4]; 4],
+                                   UncompressedFloatFormat::U8U8U8,
+                                   MipmapsOption::NoMipmap).unwrap();
+            Texture2d::with_format(&context, vec![vec![(0u8, 0u8, 0u8, 0u8); 4]; 4],
+                                   UncompressedFloatFormat::U8U8U8U8,
+                                   MipmapsOption::NoMipmap).unwrap();
+
+            assert_eq!(backend.get_texture_formats(), expected.to_vec());
+        }
+    }
+
+    #[test]
+    fn es2_sized_formats_not_supported() {
+        let (backend, context) = build_context(NullBackend::with_version("OpenGL ES 2.0",
+                                                                         "OpenGL ES GLSL ES 1.00",
+                                                                         &[]));
+
+        // formats without an unsized equivalent
+        for &format in [UncompressedFloatFormat::U8, UncompressedFloatFormat::U16U16U16U16,
+                        UncompressedFloatFormat::F32F32F32F32].iter()
+        {
+            match Texture2d::empty_with_format(&context, format, MipmapsOption::NoMipmap, 4, 4) {
+                Err(TextureCreationError::FormatNotSupported) => (),
+                r => panic!("{:?}", r.map(|_| ())),
+            }
+        }
+
+        // the client data must have the same number of components as the unsized format
+        match Texture2d::with_format(&context, vec![vec![(0u8, 0u8, 0u8, 0u8); 4]; 4],
+                                     UncompressedFloatFormat::U8U8U8, MipmapsOption::NoMipmap)
+        {
+            Err(TextureCreationError::FormatNotSupported) => (),
+            r => panic!("{:?}", r.map(|_| ())),
+        }
+
+        assert!(backend.get_texture_formats().is_empty());
+    }
+}
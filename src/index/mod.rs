@@ -22,6 +22,30 @@ There are two ways to specify the indices that must be used:
  - `NoIndices`, in which case the vertices will be used in the order in which they are in the
    vertex buffer.
 
+## Drawing a part of a buffer
+
+Several meshes can be packed in the same index buffer, for example to draw each of them with
+a different material. Use `slice` to draw only the indices of one of them. The range is in
+number of indices, and `slice` returns `None` if it is out of the buffer.
+
+```no_run
+# use glium::Surface;
+# let display: glium::Display = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+# let vertex_buffer: glium::VertexBuffer<u8> = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+# let index_buffer: glium::IndexBuffer<u16> = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+# let program: glium::Program = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+# let mut frame = display.draw();
+let (first_index, index_count) = (36, 120);
+let submesh = index_buffer.slice(first_index .. first_index + index_count).unwrap();
+frame.draw(&vertex_buffer, &submesh, &program, &glium::uniforms::EmptyUniforms,
+           &Default::default()).unwrap();
+```
+
+If the vertices of each mesh are packed as well, slice the vertex buffer too. The indices are
+then relative to the start of the vertex buffer slice. Glium uses the `glDraw*BaseVertex`
+functions when they are available, so that drawing different parts of the same buffers
+doesn't require changing the bindings.
+
 ## Multidraw indirect
 
 In addition to indices, you can also use **multidraw indirect** rendering.
@@ -38,6 +62,7 @@ use crate::version::Version;
 use std::mem;
 
 use crate::buffer::BufferAnySlice;
+use crate::vertex::TransformFeedbackStream;
 
 pub use self::buffer::{IndexBuffer, IndexBufferSlice, IndexBufferAny};
 pub use self::buffer::CreationError as BufferCreationError;
@@ -86,6 +111,15 @@ pub enum IndicesSource<'a> {
         /// Type of primitives contained in the vertex source.
         primitives: PrimitiveType,
     },
+
+    /// Don't use indices, and draw as many vertices as a transform feedback session has
+    /// written to the vertices source.
+    TransformFeedback {
+        /// The stream that holds the number of vertices.
+        stream: &'a TransformFeedbackStream,
+        /// Type of primitives contained in the vertex source.
+        primitives: PrimitiveType,
+    },
 }
 
 impl<'a> IndicesSource<'a> {
@@ -97,6 +131,17 @@ impl<'a> IndicesSource<'a> {
             &IndicesSource::MultidrawArray { primitives, .. } => primitives,
             &IndicesSource::MultidrawElement { primitives, .. } => primitives,
             &IndicesSource::NoIndices { primitives } => primitives,
+            &IndicesSource::TransformFeedback { primitives, .. } => primitives,
+        }
+    }
+
+    /// Returns the type of the indices, or `None` if this source doesn't use indices.
+    #[inline]
+    pub fn get_indices_type(&self) -> Option<IndexType> {
+        match self {
+            &IndicesSource::IndexBuffer { data_type, .. } => Some(data_type),
+            &IndicesSource::MultidrawElement { data_type, .. } => Some(data_type),
+            _ => None,
         }
     }
 }
@@ -117,7 +162,7 @@ pub enum PrimitiveType {
     /// chunk represents the line.
     ///
     /// Adjacency information doesn't do anything per-se, but is passed to the geometry shader if
-    /// there is any.
+    /// there is any. Without a geometry shader the adjacent vertices are simply ignored.
     /// The first vertex represents the vertex adjacent to the second vertex. The fourth vertex
     /// represents the vertex adjacent to the third vertex.
     LinesListAdjacency,
@@ -132,7 +177,7 @@ pub enum PrimitiveType {
     /// that represent the vertices adjacent to the first and last ones.
     ///
     /// Adjacency information doesn't do anything per-se, but is passed to the geometry shader if
-    /// there is any.
+    /// there is any. Without a geometry shader the adjacent vertices are simply ignored.
     LineStripAdjacency,
 
     /// Each vertex forms a line with the next vertex. The last vertex form a line with the first
@@ -152,7 +197,7 @@ pub enum PrimitiveType {
     /// clockwise or counter-clockwise. See `BackfaceCulling` for more infos.
     ///
     /// Adjacency information doesn't do anything per-se, but is passed to the geometry shader if
-    /// there is any.
+    /// there is any. Without a geometry shader the adjacent vertices are simply ignored.
     /// The second vertex represents the vertex adjacent to the first and third vertices. The
     /// fourth vertex represents the vertex adjacent to the third and fifth vertices. The sixth
     /// vertex represents the vertex adjacent to the first and fifth vertices.
@@ -172,7 +217,7 @@ pub enum PrimitiveType {
     ///
     /// Each uneven vertex is adjacent to the previous and next ones.
     /// Adjacency information doesn't do anything per-se, but is passed to the geometry shader if
-    /// there is any.
+    /// there is any. Without a geometry shader the adjacent vertices are simply ignored.
     TriangleStripAdjacency,
 
     /// Starting at the second vertex, each vertex forms a triangle with the next and the first
@@ -186,6 +231,8 @@ pub enum PrimitiveType {
     ///
     /// This primitives type can only be used in conjunction with a tessellation shader. The
     /// tessellation shader will indicate how each patch will be divided into lines or triangles.
+    /// Drawing patches with a program that has no tessellation evaluation shader returns a
+    /// `PatchesWithoutTessellation` error.
     Patches {
         /// Number of vertices per patch.
         vertices_per_patch: u16,
@@ -7,6 +7,7 @@ use std::os::raw;
 use crate::backend::Facade;
 use crate::buffer::{BufferCreationError, BufferType, BufferMode, Buffer};
 use crate::buffer::{BufferSlice, BufferMutSlice};
+use crate::utils::range::RangeArgument;
 use crate::index::{IndicesSource, PrimitiveType, IndexBuffer, Index};
 
 /// Represents an element in a list of draw commands.
@@ -112,6 +113,23 @@ impl DrawCommandsNoIndicesBuffer {
             primitives,
         }
     }
+
+    /// Same as `with_primitive_type`, but only uses the commands within `range`. For example
+    /// `slice_with_primitive_type(3 .. 4, ...)` only executes the fourth command.
+    ///
+    /// Returns `None` if out of range.
+    #[inline]
+    pub fn slice_with_primitive_type<R>(&self, range: R, primitives: PrimitiveType)
+                                        -> Option<IndicesSource<'_>>
+                                        where R: RangeArgument<usize>
+    {
+        self.buffer.slice(range).map(|buffer| {
+            IndicesSource::MultidrawArray {
+                buffer: buffer.as_slice_any(),
+                primitives,
+            }
+        })
+    }
 }
 
 impl Deref for DrawCommandsNoIndicesBuffer {
@@ -217,6 +235,25 @@ impl DrawCommandsIndicesBuffer {
             primitives: index_buffer.get_primitives_type(),
         }
     }
+
+    /// Same as `with_index_buffer`, but only uses the commands within `range`. For example
+    /// `slice_with_index_buffer(3 .. 4, ...)` only executes the fourth command.
+    ///
+    /// Returns `None` if out of range.
+    #[inline]
+    pub fn slice_with_index_buffer<'a, R, T>(&'a self, range: R, index_buffer: &'a IndexBuffer<T>)
+                                             -> Option<IndicesSource<'a>>
+                                             where R: RangeArgument<usize>, T: Index
+    {
+        self.buffer.slice(range).map(|commands| {
+            IndicesSource::MultidrawElement {
+                commands: commands.as_slice_any(),
+                indices: index_buffer.as_slice_any(),
+                data_type: index_buffer.get_indices_type(),
+                primitives: index_buffer.get_primitives_type(),
+            }
+        })
+    }
 }
 
 impl Deref for DrawCommandsIndicesBuffer {
@@ -37,6 +37,28 @@ meaning that you can't pass it to another thread.
 The display has ownership of both the window and context, and also provides some methods related to
 domains such as events handling.
 
+# Threads
+
+OpenGL contexts can only be used from the thread where they are current. Because of this, the
+display and all the objects that are created from it (buffers, textures, programs, etc.) hold
+a reference-counted pointer to the context and don't implement `Send` and `Sync` either. Trying
+to move them to another thread is rejected at compile-time:
+
+```compile_fail
+# let display: glium::Display = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+let texture = glium::texture::Texture2d::empty(&display, 256, 256).unwrap();
+
+std::thread::spawn(move || {
+    // error: `Rc<Context>` cannot be sent between threads safely
+    drop(texture);
+});
+```
+
+If you want to load assets in the background, do the expensive work (reading files, decoding
+images, building meshes) on another thread and send the resulting data, for example a
+`texture::RawImage2d` or a `Vec` of vertices, to the thread that owns the display in order to
+upload it.
+
 # Overview
 
 OpenGL is similar to a drawing software: you draw something, then draw over it, then over it
@@ -102,7 +124,9 @@ extern crate lazy_static;
 
 #[cfg(feature = "glutin")]
 pub use crate::backend::glutin::glutin;
-pub use crate::context::Profile;
+pub use crate::context::{BackbufferFormat, Hint, HintMode, HintNotSupportedError, Profile};
+pub use crate::context::ClampColorNotSupportedError;
+pub use crate::context::{InternalFormatInfo, InternalFormatTarget, RegionBarrier};
 pub use crate::draw_parameters::{Blend, BlendingFunction, LinearBlendingFactor, BackfaceCullingMode};
 pub use crate::draw_parameters::{Depth, DepthTest, PolygonMode, DrawParameters, StencilTest, StencilOperation};
 pub use crate::draw_parameters::{Smooth};
@@ -132,6 +156,7 @@ mod macros;
 
 pub mod backend;
 pub mod buffer;
+pub mod command_list;
 pub mod debug;
 pub mod draw_parameters;
 pub mod framebuffer;
@@ -322,6 +347,10 @@ trait ProgramExt {
     fn get_atomic_counters(&self) -> &HashMap<String, program::UniformBlock, BuildHasherDefault<FnvHasher>>;
 
     fn get_subroutine_data(&self) -> &program::SubroutineData;
+
+    /// Calls `glValidateProgram` against the current state and returns the info log if the
+    /// validation failed.
+    fn validate_program(&self, ctxt: &mut context::CommandContext<'_>) -> Result<(), String>;
 }
 
 /// Internal trait for queries.
@@ -394,6 +423,12 @@ trait UniformsExt {
     /// Will replace texture and buffer bind points.
     fn bind_uniforms<'a, P>(&'a self, _: &mut CommandContext<'_>, _: &P, _: &mut Vec<buffer::Inserter<'a>>)
                             -> Result<(), DrawError> where P: ProgramExt;
+
+    /// Sets the regular uniform values of a program, without binding anything.
+    ///
+    /// Returns an error if a texture, a buffer or a subroutine is encountered.
+    fn set_persistent_uniforms<P>(&self, _: &mut CommandContext<'_>, _: &P)
+                                  -> Result<(), DrawError> where P: ProgramExt;
 }
 
 
@@ -682,7 +717,7 @@ pub struct BlitTarget {
 ///
 /// This is finally the step where colors are being written. The `color_mask` parameter allow you
 /// to specify whether each color component (red, green, blue and alpha) is written to the color
-/// buffer.
+/// buffer. The `color_masks` parameter does the same separately for each color attachment.
 ///
 pub trait Surface {
     /// Clears some attachments of the target.
@@ -705,10 +740,28 @@ pub trait Surface {
     }
 
     /// Clears the stencil attachment of the target.
+    ///
+    /// All the bits of the stencil buffer are cleared, whatever the stencil write masks of the
+    /// previous draws. Use `clear_stencil_masked` to clear only some bits.
     fn clear_stencil(&mut self, value: i32) {
         self.clear(None, None, false, None, Some(value));
     }
 
+    /// Clears the bits of the stencil attachment of the target that are set in `mask`, and
+    /// leaves the other bits untouched.
+    ///
+    /// This allows multiple techniques to share the same stencil buffer, each of them using
+    /// its own bits.
+    ///
+    /// The default implementation can only clear all the bits at once, and panics if `mask`
+    /// doesn't contain all the bits of the stencil buffer. The surfaces of glium override it.
+    fn clear_stencil_masked(&mut self, value: i32, mask: u32) {
+        let bits = self.get_stencil_buffer_bits().unwrap_or(0) as u32;
+        let all_bits = if bits >= 32 { !0 } else { (1 << bits) - 1 };
+        assert!(mask & all_bits == all_bits, "This surface doesn't support masked stencil clears");
+        self.clear_stencil(value);
+    }
+
     /// Clears the color and depth attachments of the target.
     fn clear_color_and_depth(&mut self, color: (f32, f32, f32, f32), depth: f32) {
         self.clear(None, Some(color), false, Some(depth), None);
@@ -778,6 +831,48 @@ pub trait Surface {
         V: vertex::MultiVerticesSource<'b>, I: Into<index::IndicesSource<'a>>,
         U: uniforms::Uniforms;
 
+    /// Draws the geometry twice: once filled with `uniforms`, then as a wireframe with
+    /// `wireframe_uniforms` on top of it.
+    ///
+    /// The filled triangles are drawn with a polygon offset that pushes them slightly away from
+    /// the camera, so that the lines of the wireframe pass the depth test instead of fighting
+    /// with the triangles they belong to. The wireframe is drawn with `PolygonMode::Line` and
+    /// the given `line_width`. All other parameters are taken from `draw_parameters` for both
+    /// draws, and their `polygon_mode`, `polygon_offset` and `line_width` are ignored.
+    ///
+    /// The same program is used for both draws. Use the uniforms to give the wireframe a
+    /// different color, for example.
+    ///
+    /// Like any use of `PolygonMode::Line`, this requires desktop OpenGL.
+    fn draw_solid_with_wireframe<'a, 'b, V, I, U, W>(&mut self, vertices: V, indices: I,
+        program: &Program, uniforms: &U, wireframe_uniforms: &W,
+        draw_parameters: &DrawParameters<'_>, line_width: f32) -> Result<(), DrawError> where
+        V: vertex::MultiVerticesSource<'b> + Clone, I: Into<index::IndicesSource<'a>>,
+        U: uniforms::Uniforms, W: uniforms::Uniforms, Self: Sized
+    {
+        let indices = indices.into();
+
+        let solid = DrawParameters {
+            polygon_mode: PolygonMode::Fill,
+            polygon_offset: crate::draw_parameters::PolygonOffset {
+                factor: 1.0,
+                units: 1.0,
+                fill: true,
+                .. Default::default()
+            },
+            .. draw_parameters.clone()
+        };
+        self.draw(vertices.clone(), indices.clone(), program, uniforms, &solid)?;
+
+        let wireframe = DrawParameters {
+            polygon_mode: PolygonMode::Line,
+            polygon_offset: Default::default(),
+            line_width: Some(line_width),
+            .. draw_parameters.clone()
+        };
+        self.draw(vertices, indices, program, wireframe_uniforms, &wireframe)
+    }
+
     /// Blits from the default framebuffer.
     fn blit_from_frame(&self, source_rect: &Rect, target_rect: &BlitTarget,
                        filter: uniforms::MagnifySamplerFilter);
@@ -826,6 +921,15 @@ pub trait Surface {
         let target_rect = BlitTarget { left: 0, bottom: 0, width: target_dim.0 as i32, height: target_dim.1 as i32 };
         self.blit_color(&src_rect, target, &target_rect, filter)
     }
+
+    /// Executes the draw commands of a `CommandList` on this surface, in the order in which
+    /// they have been recorded. See the `command_list` module.
+    ///
+    /// The default implementation calls `draw` for each command. The surfaces of glium instead
+    /// submit the whole list while the context is current.
+    fn execute(&mut self, list: &command_list::CommandList<'_>) -> Result<(), DrawError> {
+        list.draw_each(self)
+    }
 }
 
 /// Private trait for framebuffer-like objects that provide attachments.
@@ -842,17 +946,48 @@ pub enum DrawError {
 
     /// The type of a vertex attribute in the vertices source doesn't match what the
     /// program requires.
-    AttributeTypeMismatch,
+    AttributeTypeMismatch {
+        /// Name of the attribute.
+        name: String,
+        /// The type declared in the program.
+        expected: vertex::AttributeType,
+        /// The type declared in the vertex format.
+        obtained: vertex::AttributeType,
+    },
 
     /// One of the attributes required by the program is missing from the vertex format.
     ///
     /// Note that it is perfectly valid to have an attribute in the vertex format that is
     /// not used by the program.
-    AttributeMissing,
+    AttributeMissing {
+        /// Name of the attribute.
+        name: String,
+    },
+
+    /// The type of a 64 bits vertex attribute is not supported by the backend.
+    AttributeTypeNotSupported {
+        /// Name of the attribute.
+        name: String,
+        /// The type declared in the vertex format.
+        ty: vertex::AttributeType,
+    },
 
     /// The viewport's dimensions are not supported by the backend.
     ViewportTooLarge,
 
+    /// Trying to use multiple viewports, but viewport arrays are not supported by the backend.
+    ViewportArrayNotSupported,
+
+    /// The number of viewports is larger than `GL_MAX_VIEWPORTS`.
+    TooManyViewports,
+
+    /// Trying to use per-attachment color masks, but `glColorMaski` is not supported by the
+    /// backend.
+    IndexedColorMaskNotSupported,
+
+    /// The number of color masks is larger than `GL_MAX_DRAW_BUFFERS`.
+    TooManyColorMasks,
+
     /// The depth range is outside of the `(0, 1)` range.
     InvalidDepthRange,
 
@@ -876,6 +1011,20 @@ pub enum DrawError {
         name: String,
     },
 
+    /// A uniform of the program has never been assigned a value. Only returned if enabled with
+    /// `Context::set_unset_uniforms_check`.
+    UniformNotSet {
+        /// Name of the uniform.
+        name: String,
+    },
+
+    /// Tried to pass a texture, a buffer or a subroutine to `Program::set_persistent_uniforms`.
+    /// Only regular values stay set between draw calls.
+    UniformNotPersistent {
+        /// Name of the uniform you are trying to set.
+        name: String,
+    },
+
     /// The layout of the content of the uniform buffer does not match the layout of the block.
     UniformBlockLayoutMismatch {
         /// Name of the block you are trying to bind.
@@ -884,6 +1033,18 @@ pub enum DrawError {
         err: uniforms::LayoutMismatchError,
     },
 
+    /// The buffer bound to a uniform or shader storage block doesn't start at a multiple of the
+    /// offset alignment required by the backend. Use `UniformBuffer::block_range` to build
+    /// aligned ranges.
+    BlockOffsetNotAligned {
+        /// Name of the block you are trying to bind.
+        name: String,
+        /// The offset in bytes of the buffer.
+        offset: usize,
+        /// The required alignment in bytes.
+        alignment: usize,
+    },
+
     /// Tried to bind a subroutine uniform like a regular uniform value.
     SubroutineUniformToValue {
         /// Name of the uniform you are trying to bind.
@@ -918,9 +1079,40 @@ pub enum DrawError {
     /// Using a program which contains tessellation shaders, but without submitting patches.
     TessellationWithoutPatches,
 
+    /// Submitting patches, but the program doesn't contain a tessellation evaluation shader.
+    PatchesWithoutTessellation,
+
+    /// The type of primitives being drawn doesn't match the input type declared by the geometry
+    /// shader of the program.
+    GeometryShaderInputMismatch {
+        /// The primitives that the geometry shader expects.
+        expected: program::InputPrimitives,
+        /// The primitives that were submitted.
+        obtained: index::PrimitiveType,
+    },
+
+    /// The program failed to validate against the current state. Only checked if enabled with
+    /// `Context::set_program_validation`.
+    ProgramValidationFailed {
+        /// The validation log of the program.
+        log: String,
+    },
+
+    /// Trying to draw with a buffer of draw commands, but indirect drawing is not supported by
+    /// the backend.
+    IndirectDrawNotSupported,
+
     /// Trying to use a sampler, but they are not supported by the backend.
     SamplersNotSupported,
 
+    /// Trying to use `SamplerWrapFunction::BorderClamp` or a border color, but this is not
+    /// supported by the backend.
+    BorderClampNotSupported,
+
+    /// Trying to disable the sRGB decoding of a sampler, but `GL_EXT_texture_sRGB_decode` is not
+    /// supported by the backend.
+    SrgbDecodeNotSupported,
+
     /// When you use instancing, all vertices sources must have the same size.
     InstancesCountMismatch,
 
@@ -930,6 +1122,10 @@ pub enum DrawError {
     /// You requested not to draw primitives, but this is not supported by the backend.
     TransformFeedbackNotSupported,
 
+    /// Trying to draw multiple instances of a `TransformFeedbackStream`, but this is not
+    /// supported by the backend.
+    TransformFeedbackInstancingNotSupported,
+
     /// See the documentation of the `draw_parameters` module for infos.
     WrongQueryOperation,
 
@@ -942,17 +1138,41 @@ pub enum DrawError {
     /// Discarding rasterizer output isn't supported by the backend.
     RasterizerDiscardNotSupported,
 
+    /// Trying to use a sample mask, but this is not supported by the backend.
+    SampleMaskNotSupported,
+
     /// Depth clamping isn't supported by the backend.
     DepthClampNotSupported,
 
     /// One of the blending parameters is not supported by the backend.
     BlendingParameterNotSupported,
 
+    /// Line stippling is not supported by the backend.
+    LineStippleNotSupported,
+
+    /// The line stipple factor is outside of the `[1, 256]` range.
+    InvalidLineStippleFactor,
+
+    /// Logic operations are not supported by the backend.
+    LogicOpNotSupported,
+
+    /// A logic operation has been requested while blending is enabled.
+    LogicOpWithBlending,
+
+    /// Blending has been requested while drawing to a signed or unsigned integer color
+    /// attachment.
+    BlendingWithIntegralAttachment,
+
     /// Restarting indices (multiple objects per draw call) is not supported by the backend.
     FixedIndexRestartingNotSupported,
 
     /// Tried to enable a clip plane that does not exist.
     ClipPlaneIndexOutOfBounds,
+
+    /// The OpenGL context has been lost and needs to be recreated.
+    ///
+    /// See the documentation of `SwapBuffersError::ContextLost` for more infos.
+    ContextLost,
 }
 
 impl Error for DrawError {
@@ -972,12 +1192,22 @@ impl fmt::Display for DrawError {
         let desc = match self {
             NoDepthBuffer =>
                 "A depth function has been requested but no depth buffer is available",
-            AttributeTypeMismatch =>
+            AttributeTypeMismatch { .. } =>
                 "The type of a vertex attribute in the vertices source doesn't match what the program requires",
-            AttributeMissing =>
+            AttributeMissing { .. } =>
                 "One of the attributes required by the program is missing from the vertex format",
+            AttributeTypeNotSupported { .. } =>
+                "The type of a 64 bits vertex attribute is not supported by the backend",
             ViewportTooLarge =>
                 "The viewport's dimensions are not supported by the backend",
+            ViewportArrayNotSupported =>
+                "Trying to use multiple viewports, but viewport arrays are not supported by the backend",
+            TooManyViewports =>
+                "The number of viewports is larger than what the backend supports",
+            IndexedColorMaskNotSupported =>
+                "Trying to use per-attachment color masks, but they are not supported by the backend",
+            TooManyColorMasks =>
+                "The number of color masks is larger than what the backend supports",
             InvalidDepthRange =>
                 "The depth range is outside of the `(0, 1)` range",
             UniformTypeMismatch { .. } =>
@@ -986,8 +1216,14 @@ impl fmt::Display for DrawError {
                 "Tried to bind a uniform buffer to a single uniform value",
             UniformValueToBlock { .. } =>
                 "Tried to bind a single uniform value to a uniform block",
+            UniformNotSet { .. } =>
+                "A uniform of the program has never been assigned a value",
+            UniformNotPersistent { .. } =>
+                "Only regular uniform values can be set persistently",
             UniformBlockLayoutMismatch { .. } =>
                 "The layout of the content of the uniform buffer does not match the layout of the block",
+            BlockOffsetNotAligned { .. } =>
+                "The offset of the buffer bound to a block isn't a multiple of the required alignment",
             SubroutineUniformToValue { .. } =>
                 "Tried to bind a subroutine uniform like a regular uniform value",
             SubroutineUniformMissing { .. } =>
@@ -1000,14 +1236,28 @@ impl fmt::Display for DrawError {
                 "Trying to use tessellation, but this is not supported by the underlying hardware",
             TessellationWithoutPatches =>
                 "Using a program which contains tessellation shaders, but without submitting patches",
+            PatchesWithoutTessellation =>
+                "Submitting patches, but the program doesn't contain a tessellation evaluation shader",
+            GeometryShaderInputMismatch { .. } =>
+                "The type of primitives doesn't match the input of the geometry shader",
+            ProgramValidationFailed { .. } =>
+                "The program failed to validate against the current state",
+            IndirectDrawNotSupported =>
+                "Trying to draw with a buffer of draw commands, but indirect drawing is not supported",
             SamplersNotSupported => "
                 Trying to use a sampler, but they are not supported by the backend",
+            BorderClampNotSupported =>
+                "Trying to use a border color, but this is not supported by the backend",
+            SrgbDecodeNotSupported =>
+                "Trying to disable the sRGB decoding of a sampler, but this is not supported by the backend",
             InstancesCountMismatch =>
                 "When you use instancing, all vertices sources must have the same size",
             VerticesSourcesLengthMismatch =>
                 "If you don't use indices, then all vertices sources must have the same size",
             TransformFeedbackNotSupported =>
                 "Requested not to draw primitives, but this is not supported by the backend",
+            TransformFeedbackInstancingNotSupported =>
+                "Trying to draw multiple instances of a transform feedback stream, but this is not supported by the backend",
             WrongQueryOperation =>
                 "Wrong query operation",
             SmoothingNotSupported =>
@@ -1016,14 +1266,28 @@ impl fmt::Display for DrawError {
                 "Trying to set the provoking vertex, but this is not supported by the backend",
             RasterizerDiscardNotSupported =>
                 "Discarding rasterizer output is not supported by the backend",
+            SampleMaskNotSupported =>
+                "Trying to use a sample mask, but this is not supported by the backend",
             DepthClampNotSupported =>
                 "The depth clamp mode is not supported by the backend",
             BlendingParameterNotSupported =>
                 "One the blending parameters is not supported by the backend",
+            LineStippleNotSupported =>
+                "Line stippling is not supported by the backend",
+            InvalidLineStippleFactor =>
+                "The line stipple factor is outside of the `[1, 256]` range",
+            LogicOpNotSupported =>
+                "Logic operations are not supported by the backend",
+            LogicOpWithBlending =>
+                "Logic operations and blending can't be enabled at the same time",
+            BlendingWithIntegralAttachment =>
+                "Blending can't be enabled when drawing to an integer color attachment",
             FixedIndexRestartingNotSupported =>
                 "Restarting indices (multiple objects per draw call) is not supported by the backend",
             ClipPlaneIndexOutOfBounds =>
-                "Tried to enable a clip plane that does not exist."
+                "Tried to enable a clip plane that does not exist.",
+            ContextLost =>
+                "The OpenGL context has been lost and needs to be recreated",
         };
         match self {
             UniformTypeMismatch { name, expected } =>
@@ -1048,6 +1312,20 @@ impl fmt::Display for DrawError {
                     desc,
                     name,
                 ),
+            UniformNotSet { name } =>
+                write!(
+                    fmt,
+                    "{}: {}",
+                    desc,
+                    name,
+                ),
+            UniformNotPersistent { name } =>
+                write!(
+                    fmt,
+                    "{}: {}",
+                    desc,
+                    name,
+                ),
             UniformBlockLayoutMismatch { name, err } =>
                 write!(
                     fmt,
@@ -1056,6 +1334,54 @@ impl fmt::Display for DrawError {
                     name,
                     err,
                 ),
+            BlockOffsetNotAligned { name, offset, alignment } =>
+                write!(
+                    fmt,
+                    "{}: {}, offset: {}, alignment: {}",
+                    desc,
+                    name,
+                    offset,
+                    alignment,
+                ),
+            ProgramValidationFailed { log } =>
+                write!(
+                    fmt,
+                    "{}: {}",
+                    desc,
+                    log,
+                ),
+            AttributeTypeMismatch { name, expected, obtained } =>
+                write!(
+                    fmt,
+                    "{}: {}, expected: {:?}, got: {:?}",
+                    desc,
+                    name,
+                    expected,
+                    obtained,
+                ),
+            AttributeMissing { name } =>
+                write!(
+                    fmt,
+                    "{}: {}",
+                    desc,
+                    name,
+                ),
+            AttributeTypeNotSupported { name, ty } =>
+                write!(
+                    fmt,
+                    "{}: {}, type: {:?}",
+                    desc,
+                    name,
+                    ty,
+                ),
+            GeometryShaderInputMismatch { expected, obtained } =>
+                write!(
+                    fmt,
+                    "{}, expected: {:?}, got: {:?}",
+                    desc,
+                    expected,
+                    obtained,
+                ),
             _ =>
                 fmt.write_str(desc),
         }
@@ -1085,6 +1411,18 @@ pub enum SwapBuffersError {
 
 impl Error for SwapBuffersError {}
 
+/// Error that happens when the backend can't change the swap interval.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SwapIntervalNotSupportedError;
+
+impl Error for SwapIntervalNotSupportedError {}
+
+impl fmt::Display for SwapIntervalNotSupportedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        fmt.write_str("The backend doesn't support changing the swap interval")
+    }
+}
+
 impl fmt::Display for SwapBuffersError {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         use self::SwapBuffersError::*;
@@ -1141,6 +1479,66 @@ impl Frame {
         self.destroyed = true;
         self.context.swap_buffers()
     }
+
+    /// Reads the content of the back buffer and returns it as an RGBA image whose first row
+    /// is the top of the frame.
+    ///
+    /// The content of the back buffer is converted to 8 bits per component, whatever its
+    /// format is. Use `Display::read_front_buffer` if you want the raw data, starting from the
+    /// bottom-left hand corner.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # use glium::Surface;
+    /// # let display: glium::Display = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// let mut target = display.draw();
+    /// target.clear_color(0.0, 0.0, 1.0, 1.0);
+    /// let image = target.capture_image().unwrap();
+    /// assert_eq!(image.data.len(), (image.width * image.height * 4) as usize);
+    /// target.finish().unwrap();
+    /// ```
+    pub fn capture_image(&self) -> Result<texture::CapturedImage, ReadError> {
+        let mut ctxt = self.context.make_current();
+        let (width, height) = self.dimensions;
+        let rect = Rect { left: 0, bottom: 0, width, height };
+
+        let mut data: Vec<(u8, u8, u8, u8)> = Vec::with_capacity(0);
+        let read_buffer = self.back_buffer(&ctxt);
+        ops::read(&mut ctxt, ops::Source::DefaultFramebuffer(read_buffer), &rect,
+                  &mut data, false)?;
+        Ok(texture::CapturedImage::from_bottom_left(&data, width, height))
+    }
+
+    /// Reads the content of the back buffer into a buffer in video memory.
+    ///
+    /// Contrary to `capture_image`, this operation is done asynchronously and doesn't block.
+    /// Use `PixelBuffer::is_ready` to know when the content of the pixel buffer can be read
+    /// without waiting for the GPU.
+    pub fn read_to_pixel_buffer(&self)
+                                -> Result<texture::pixel_buffer::PixelBuffer<(u8, u8, u8, u8)>,
+                                          ReadError>
+    {
+        let (width, height) = self.dimensions;
+        let rect = Rect { left: 0, bottom: 0, width, height };
+        let pb = texture::pixel_buffer::PixelBuffer::new_empty(&self.context,
+                                                               width as usize * height as usize);
+
+        let mut ctxt = self.context.make_current();
+        let read_buffer = self.back_buffer(&ctxt);
+        ops::read(&mut ctxt, ops::Source::DefaultFramebuffer(read_buffer), &rect,
+                  &pb, false)?;
+        Ok(pb)
+    }
+
+    /// Returns the value to pass to `glReadBuffer` to read from the back buffer.
+    fn back_buffer(&self, ctxt: &CommandContext<'_>) -> gl::types::GLenum {
+        if ctxt.version >= &Version(Api::Gl, 1, 0) {
+            gl::BACK_LEFT
+        } else {
+            gl::BACK
+        }
+    }
 }
 
 impl Surface for Frame {
@@ -1151,6 +1549,11 @@ impl Surface for Frame {
         ops::clear(&self.context, None, rect, color, color_srgb, depth, stencil);
     }
 
+    #[inline]
+    fn clear_stencil_masked(&mut self, value: i32, mask: u32) {
+        ops::clear_stencil_masked(&self.context, None, None, value, mask);
+    }
+
     fn get_dimensions(&self) -> (u32, u32) {
         self.dimensions
     }
@@ -1175,23 +1578,16 @@ impl Surface for Frame {
             return Err(DrawError::NoDepthBuffer);
         }
 
-        if let Some(viewport) = draw_parameters.viewport {
-            if viewport.width > self.context.capabilities().max_viewport_dims.0
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-            if viewport.height > self.context.capabilities().max_viewport_dims.1
-                    as u32
-            {
-                return Err(DrawError::ViewportTooLarge);
-            }
-        }
-
         ops::draw(&self.context, None, vertex_buffer, index_buffer.into(), program,
                   uniforms, draw_parameters, (self.dimensions.0 as u32, self.dimensions.1 as u32))
     }
 
+    #[inline]
+    fn execute(&mut self, list: &command_list::CommandList<'_>) -> Result<(), DrawError> {
+        list.execute(&self.context, None, (self.dimensions.0 as u32, self.dimensions.1 as u32),
+                     self.has_depth_buffer())
+    }
+
     #[inline]
     fn blit_color<S>(&self, source_rect: &Rect, target: &S, target_rect: &BlitTarget,
                      filter: uniforms::MagnifySamplerFilter) where S: Surface
@@ -1255,6 +1651,69 @@ impl fmt::Display for IncompatibleOpenGl {
 
 impl Error for IncompatibleOpenGl {}
 
+/// An error reported by `glGetError`.
+///
+/// See `Context::get_error`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GlError {
+    /// `GL_INVALID_ENUM`. An unacceptable value has been passed for an enumerated argument.
+    InvalidEnum,
+    /// `GL_INVALID_VALUE`. A numeric argument is out of range.
+    InvalidValue,
+    /// `GL_INVALID_OPERATION`. The operation is not allowed in the current state.
+    InvalidOperation,
+    /// `GL_INVALID_FRAMEBUFFER_OPERATION`. The framebuffer object is not complete.
+    InvalidFramebufferOperation,
+    /// `GL_OUT_OF_MEMORY`. There is not enough memory left to execute the command.
+    OutOfMemory,
+    /// `GL_STACK_UNDERFLOW`. An operation would have caused an internal stack to underflow.
+    StackUnderflow,
+    /// `GL_STACK_OVERFLOW`. An operation would have caused an internal stack to overflow.
+    StackOverflow,
+    /// `GL_CONTEXT_LOST`. The context has been lost, for example because of a GPU reset.
+    ContextLost,
+    /// A value that glium doesn't know about.
+    Unknown(gl::types::GLenum),
+}
+
+impl GlError {
+    /// Builds a `GlError` from the value returned by `glGetError`.
+    fn from_gl_enum(value: gl::types::GLenum) -> Option<GlError> {
+        match value {
+            gl::NO_ERROR => None,
+            gl::INVALID_ENUM => Some(GlError::InvalidEnum),
+            gl::INVALID_VALUE => Some(GlError::InvalidValue),
+            gl::INVALID_OPERATION => Some(GlError::InvalidOperation),
+            gl::INVALID_FRAMEBUFFER_OPERATION => Some(GlError::InvalidFramebufferOperation),
+            gl::OUT_OF_MEMORY => Some(GlError::OutOfMemory),
+            gl::STACK_UNDERFLOW => Some(GlError::StackUnderflow),
+            gl::STACK_OVERFLOW => Some(GlError::StackOverflow),
+            gl::CONTEXT_LOST => Some(GlError::ContextLost),
+            value => Some(GlError::Unknown(value)),
+        }
+    }
+}
+
+impl fmt::Display for GlError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let desc = match *self {
+            GlError::InvalidEnum => "GL_INVALID_ENUM",
+            GlError::InvalidValue => "GL_INVALID_VALUE",
+            GlError::InvalidOperation => "GL_INVALID_OPERATION",
+            GlError::InvalidFramebufferOperation => "GL_INVALID_FRAMEBUFFER_OPERATION",
+            GlError::OutOfMemory => "GL_OUT_OF_MEMORY",
+            GlError::StackUnderflow => "GL_STACK_UNDERFLOW",
+            GlError::StackOverflow => "GL_STACK_OVERFLOW",
+            GlError::ContextLost => "GL_CONTEXT_LOST",
+            GlError::Unknown(value) =>
+                return write!(fmt, "Unknown glGetError return value: 0x{:x}", value),
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for GlError {}
+
 #[allow(dead_code)]
 #[inline]
 fn get_gl_error(ctxt: &mut context::CommandContext<'_>) -> Option<&'static str> {
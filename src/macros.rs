@@ -425,6 +425,10 @@ macro_rules! implement_uniform_block {
 ///
 /// Returns a `glium::program::ProgramChooserCreationError`.
 ///
+/// In addition to the source code of the shaders, each entry accepts `outputs_srgb: bool`,
+/// `point_size: bool` and `geometry_shader_parameters: Option<GeometryShaderParameters>`. See
+/// the fields of `ProgramCreationInput::SourceCode`.
+///
 /// ## Example
 ///
 /// ```ignore       // TODO: no_run instead
@@ -521,11 +525,13 @@ macro_rules! program {
             let __fragment_shader: &str = "";
             let __outputs_srgb: bool = false;
             let __uses_point_size: bool = false;
+            let __geometry_shader_parameters:
+                Option<$crate::program::GeometryShaderParameters> = None;
 
             $(
                 program!(_program_ty $ty, $src, __vertex_shader, __tessellation_control_shader,
                          __tessellation_evaluation_shader, __geometry_shader, __fragment_shader,
-                         __outputs_srgb, __uses_point_size);
+                         __outputs_srgb, __uses_point_size, __geometry_shader_parameters);
             )+
 
             let input = $crate::program::ProgramCreationInput::SourceCode {
@@ -537,6 +543,8 @@ macro_rules! program {
                 transform_feedback_varyings: None,
                 outputs_srgb: __outputs_srgb,
                 uses_point_size: __uses_point_size,
+                geometry_shader_parameters: __geometry_shader_parameters,
+                frag_data_locations: &[],
             };
 
             $crate::program::Program::new($context, input)
@@ -551,34 +559,38 @@ macro_rules! program {
         program!(_inner, $context, $vers, {$($ty:$src),+} $($rest)*);
     );
 
-    (_program_ty vertex, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident) => (
+    (_program_ty vertex, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident, $gsp:ident) => (
         let $vs = $src;
     );
 
-    (_program_ty tessellation_control, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident) => (
+    (_program_ty tessellation_control, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident, $gsp:ident) => (
         let $tcs = Some($src);
     );
 
-    (_program_ty tessellation_evaluation, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident) => (
+    (_program_ty tessellation_evaluation, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident, $gsp:ident) => (
         let $tes = Some($src);
     );
 
-    (_program_ty geometry, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident) => (
+    (_program_ty geometry, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident, $gsp:ident) => (
         let $gs = Some($src);
     );
 
-    (_program_ty fragment, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident) => (
+    (_program_ty fragment, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident, $gsp:ident) => (
         let $fs = $src;
     );
 
-    (_program_ty point_size, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident) => (
+    (_program_ty point_size, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident, $gsp:ident) => (
         let $ps = $src;
     );
 
-    (_program_ty outputs_srgb, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident) => (
+    (_program_ty outputs_srgb, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident, $gsp:ident) => (
         let $srgb = $src;
     );
 
+    (_program_ty geometry_shader_parameters, $src:expr, $vs:ident, $tcs:ident, $tes:ident, $gs:ident, $fs:ident, $srgb:ident, $ps:ident, $gsp:ident) => (
+        let $gsp = $src;
+    );
+
     (_parse_num_gl $num:expr) => (
         if $num == 100 {
             $crate::Version($crate::Api::GlEs, 1, 0)
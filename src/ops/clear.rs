@@ -1,5 +1,6 @@
-use crate::fbo::{self, ValidatedAttachments};
+use crate::fbo::{self, ClearBufferData, ValidatedAttachments};
 
+use crate::context::CommandContext;
 use crate::context::Context;
 use crate::ContextExt;
 use crate::Rect;
@@ -18,53 +19,7 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
 {
     unsafe {
         let mut ctxt = context.make_current();
-
-        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, framebuffer);
-        fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false);
-
-        if ctxt.state.enabled_rasterizer_discard {
-            ctxt.gl.Disable(gl::RASTERIZER_DISCARD);
-            ctxt.state.enabled_rasterizer_discard = false;
-        }
-
-        if ctxt.state.color_mask != (1, 1, 1, 1) {
-            ctxt.state.color_mask = (1, 1, 1, 1);
-            ctxt.gl.ColorMask(1, 1, 1, 1);
-        }
-
-        if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_framebuffer_srgb ||
-           ctxt.extensions.gl_ext_framebuffer_srgb || ctxt.extensions.gl_ext_srgb_write_control
-        {
-            if !color_srgb && !ctxt.state.enabled_framebuffer_srgb {
-                ctxt.gl.Enable(gl::FRAMEBUFFER_SRGB);
-                ctxt.state.enabled_framebuffer_srgb = true;
-
-            } else if color_srgb && ctxt.state.enabled_framebuffer_srgb {
-                ctxt.gl.Disable(gl::FRAMEBUFFER_SRGB);
-                ctxt.state.enabled_framebuffer_srgb = false;
-            }
-        }
-
-        TimeElapsedQuery::end_conditional_render(&mut ctxt);
-
-        if let Some(rect) = rect {
-            let rect = (rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
-                        rect.width as gl::types::GLsizei, rect.height as gl::types::GLsizei);
-
-            if ctxt.state.scissor != Some(rect) {
-                ctxt.gl.Scissor(rect.0, rect.1, rect.2, rect.3);
-                ctxt.state.scissor = Some(rect);
-            }
-
-            if !ctxt.state.enabled_scissor_test {
-                ctxt.gl.Enable(gl::SCISSOR_TEST);
-                ctxt.state.enabled_scissor_test = true;
-            }
-
-        } else if ctxt.state.enabled_scissor_test {
-            ctxt.gl.Disable(gl::SCISSOR_TEST);
-            ctxt.state.enabled_scissor_test = false;
-        }
+        prepare(&mut ctxt, framebuffer, rect, color_srgb);
 
         let mut flags = 0;
 
@@ -112,8 +67,158 @@ pub fn clear(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
                 ctxt.gl.ClearStencil(stencil);
                 ctxt.state.clear_stencil = stencil;
             }
+
+            set_stencil_mask(&mut ctxt, !0);
         }
 
         ctxt.gl.Clear(flags);
     }
 }
+
+/// Clears the bits of the stencil attachment of a framebuffer that are set in `mask`, leaving
+/// the other bits untouched.
+pub fn clear_stencil_masked(context: &Context, framebuffer: Option<&ValidatedAttachments<'_>>,
+                            rect: Option<&Rect>, value: i32, mask: u32)
+{
+    unsafe {
+        let mut ctxt = context.make_current();
+        prepare(&mut ctxt, framebuffer, rect, false);
+
+        if ctxt.state.clear_stencil != value {
+            ctxt.gl.ClearStencil(value);
+            ctxt.state.clear_stencil = value;
+        }
+
+        set_stencil_mask(&mut ctxt, mask);
+        ctxt.gl.Clear(gl::STENCIL_BUFFER_BIT);
+    }
+}
+
+/// Clears the first color attachment, or the depth and/or stencil attachments, of a framebuffer
+/// with `glClearBuffer*`.
+///
+/// Contrary to `clear`, this can be used with integral attachments. The data must match the
+/// kind of attachment.
+pub fn clear_buffer(context: &Context, framebuffer: &ValidatedAttachments<'_>,
+                    data: ClearBufferData)
+{
+    unsafe {
+        let mut ctxt = context.make_current();
+
+        // glClearBuffer is not available, but integral attachments can't exist either
+        if !(ctxt.version >= &Version(Api::Gl, 3, 0)) && !(ctxt.version >= &Version(Api::GlEs, 3, 0)) {
+            drop(ctxt);
+            return match data {
+                ClearBufferData::Float(c) => clear(context, Some(framebuffer), None,
+                                                   Some((c[0], c[1], c[2], c[3])), false,
+                                                   None, None),
+                ClearBufferData::Depth(d) => clear(context, Some(framebuffer), None, None, false,
+                                                   Some(d), None),
+                ClearBufferData::Stencil(s) => clear(context, Some(framebuffer), None, None, false,
+                                                     None, Some(s)),
+                ClearBufferData::DepthStencil(d, s) => clear(context, Some(framebuffer), None,
+                                                             None, false, Some(d), Some(s)),
+                ClearBufferData::Integral(_) | ClearBufferData::Unsigned(_) => unreachable!(),
+            };
+        }
+
+        prepare(&mut ctxt, Some(framebuffer), None, false);
+
+        match data {
+            ClearBufferData::Float(data) => {
+                ctxt.gl.ClearBufferfv(gl::COLOR, 0, data.as_ptr());
+            },
+            ClearBufferData::Integral(data) => {
+                ctxt.gl.ClearBufferiv(gl::COLOR, 0, data.as_ptr());
+            },
+            ClearBufferData::Unsigned(data) => {
+                ctxt.gl.ClearBufferuiv(gl::COLOR, 0, data.as_ptr());
+            },
+            ClearBufferData::Depth(depth) => {
+                if !ctxt.state.depth_mask {
+                    ctxt.gl.DepthMask(gl::TRUE);
+                    ctxt.state.depth_mask = true;
+                }
+
+                ctxt.gl.ClearBufferfv(gl::DEPTH, 0, &depth);
+            },
+            ClearBufferData::Stencil(stencil) => {
+                set_stencil_mask(&mut ctxt, !0);
+                ctxt.gl.ClearBufferiv(gl::STENCIL, 0, &stencil);
+            },
+            ClearBufferData::DepthStencil(depth, stencil) => {
+                if !ctxt.state.depth_mask {
+                    ctxt.gl.DepthMask(gl::TRUE);
+                    ctxt.state.depth_mask = true;
+                }
+
+                set_stencil_mask(&mut ctxt, !0);
+                ctxt.gl.ClearBufferfi(gl::DEPTH_STENCIL, 0, depth, stencil);
+            },
+        }
+    }
+}
+
+/// Sets the stencil write mask that determines which bits of the stencil buffer are cleared.
+///
+/// Clearing is affected by the write mask of the front faces, which is otherwise left to the
+/// value of the latest draw.
+unsafe fn set_stencil_mask(ctxt: &mut CommandContext<'_>, mask: u32) {
+    if ctxt.state.stencil_mask_front != mask || ctxt.state.stencil_mask_back != mask {
+        ctxt.gl.StencilMask(mask);
+        ctxt.state.stencil_mask_front = mask;
+        ctxt.state.stencil_mask_back = mask;
+    }
+}
+
+/// Binds the framebuffer and sets the state that affects clearing.
+unsafe fn prepare(ctxt: &mut CommandContext<'_>, framebuffer: Option<&ValidatedAttachments<'_>>,
+                  rect: Option<&Rect>, color_srgb: bool)
+{
+    let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(ctxt, framebuffer);
+    fbo::bind_framebuffer(ctxt, fbo_id, true, false);
+
+    if ctxt.state.enabled_rasterizer_discard {
+        ctxt.gl.Disable(gl::RASTERIZER_DISCARD);
+        ctxt.state.enabled_rasterizer_discard = false;
+    }
+
+    if ctxt.state.color_mask != Some((1, 1, 1, 1)) {
+        ctxt.state.color_mask = Some((1, 1, 1, 1));
+        ctxt.gl.ColorMask(1, 1, 1, 1);
+    }
+
+    if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.extensions.gl_arb_framebuffer_srgb ||
+       ctxt.extensions.gl_ext_framebuffer_srgb || ctxt.extensions.gl_ext_srgb_write_control
+    {
+        if !color_srgb && !ctxt.state.enabled_framebuffer_srgb {
+            ctxt.gl.Enable(gl::FRAMEBUFFER_SRGB);
+            ctxt.state.enabled_framebuffer_srgb = true;
+
+        } else if color_srgb && ctxt.state.enabled_framebuffer_srgb {
+            ctxt.gl.Disable(gl::FRAMEBUFFER_SRGB);
+            ctxt.state.enabled_framebuffer_srgb = false;
+        }
+    }
+
+    TimeElapsedQuery::end_conditional_render(ctxt);
+
+    if let Some(rect) = rect {
+        let rect = (rect.left as gl::types::GLint, rect.bottom as gl::types::GLint,
+                    rect.width as gl::types::GLsizei, rect.height as gl::types::GLsizei);
+
+        if ctxt.state.scissor != Some(rect) {
+            ctxt.gl.Scissor(rect.0, rect.1, rect.2, rect.3);
+            ctxt.state.scissor = Some(rect);
+        }
+
+        if !ctxt.state.enabled_scissor_test {
+            ctxt.gl.Enable(gl::SCISSOR_TEST);
+            ctxt.state.enabled_scissor_test = true;
+        }
+
+    } else if ctxt.state.enabled_scissor_test {
+        ctxt.gl.Disable(gl::SCISSOR_TEST);
+        ctxt.state.enabled_scissor_test = false;
+    }
+}
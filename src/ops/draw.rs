@@ -1,6 +1,7 @@
 use std::ptr;
 
 use crate::BufferExt;
+use crate::CapabilitiesSource;
 use crate::BufferSliceExt;
 use crate::ProgramExt;
 use crate::DrawError;
@@ -8,17 +9,19 @@ use crate::UniformsExt;
 
 use crate::context::Context;
 use crate::ContextExt;
+use crate::GlObject;
 use crate::TransformFeedbackSessionExt;
 
 use crate::fbo::{self, ValidatedAttachments};
 
 use crate::uniforms::Uniforms;
 use crate::{Program, ToGlEnum};
+use crate::program::InputPrimitives;
 use crate::index::{self, IndicesSource};
-use crate::vertex::{MultiVerticesSource, VerticesSource, TransformFeedbackSession};
+use crate::vertex::{AttributeType, MultiVerticesSource, VerticesSource, TransformFeedbackSession, VertexFormat};
 use crate::vertex_array_object::VertexAttributesSystem;
 
-use crate::draw_parameters::DrawParameters;
+use crate::draw_parameters::{BlendingFunction, DrawParameters};
 
 use crate::{gl, context, draw_parameters};
 use crate::version::Version;
@@ -31,10 +34,28 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                       dimensions: (u32, u32)) -> Result<(), DrawError>
                       where U: Uniforms, V: MultiVerticesSource<'a>
 {
-    // this contains the list of fences that will need to be fulfilled after the draw command
-    // has started
-    let mut fences = Vec::with_capacity(0);
+    let vertices_per_patch = validate(context, &indices, program, draw_parameters)?;
+    validate_framebuffer(framebuffer, draw_parameters)?;
 
+    // starting the state changes
+    let mut ctxt = context.make_current();
+
+    // drawing with a lost context has no effect, so we report it instead
+    if ctxt.state.lost_context {
+        return Err(DrawError::ContextLost);
+    }
+
+    draw_with_context(&mut ctxt, context, framebuffer, vertex_buffers.iter(), indices, program,
+                      uniforms, draw_parameters, dimensions, vertices_per_patch)
+}
+
+/// Checks the parts of a draw command that only depend on the context, the program, the
+/// primitives and the draw parameters.
+///
+/// Returns the number of vertices per patch if tessellation is used.
+pub fn validate(context: &Context, indices: &IndicesSource<'_>, program: &Program,
+                draw_parameters: &DrawParameters<'_>) -> Result<Option<u16>, DrawError>
+{
     // handling tessellation
     let vertices_per_patch = match indices.get_primitives_type() {
         index::PrimitiveType::Patches { vertices_per_patch } => {
@@ -46,35 +67,93 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                 return Err(DrawError::TessellationNotSupported);
             }
 
-            // TODO: programs created from binaries have the wrong value
-            // for `has_tessellation_shaders`
-            /*if !program.has_tessellation_shaders() {    // TODO:
-                panic!("Default tessellation level is not supported yet");
-            }*/
+            if !program.has_tessellation_evaluation_shader() {
+                return Err(DrawError::PatchesWithoutTessellation);
+            }
 
             Some(vertices_per_patch)
         },
         _ => {
-            // TODO: programs created from binaries have the wrong value
-            // for `has_tessellation_shaders`
-            /*if program.has_tessellation_shaders() {
+            if program.has_tessellation_shaders() {
                 return Err(DrawError::TessellationWithoutPatches);
-            }*/
+            }
 
             None
         },
     };
 
-    // starting the state changes
-    let mut ctxt = context.make_current();
+    // checking the primitives against the input of the geometry shader ; when tessellation is
+    // used the geometry shader receives the output of the tessellation evaluation stage instead
+    if vertices_per_patch.is_none() {
+        if let Some(expected) = program.get_geometry_input_primitives() {
+            let obtained = indices.get_primitives_type();
+            if !geometry_input_matches(expected, obtained) {
+                return Err(DrawError::GeometryShaderInputMismatch { expected, obtained });
+            }
+        }
+    }
+
+    // handling indirect draws
+    match *indices {
+        IndicesSource::MultidrawArray { .. } | IndicesSource::MultidrawElement { .. } => {
+            if !is_indirect_draw_supported(context) {
+                return Err(DrawError::IndirectDrawNotSupported);
+            }
+        },
+        _ => ()
+    };
+
+    if let Some(viewport) = draw_parameters.viewport {
+        let max_viewport_dims = context.capabilities().max_viewport_dims;
+        if viewport.width > max_viewport_dims.0 as u32 ||
+           viewport.height > max_viewport_dims.1 as u32
+        {
+            return Err(DrawError::ViewportTooLarge);
+        }
+    }
+
+    Ok(vertices_per_patch)
+}
+
+/// Checks the draw parameters against the attachments of the framebuffer.
+pub fn validate_framebuffer(framebuffer: Option<&ValidatedAttachments<'_>>,
+                            draw_parameters: &DrawParameters<'_>) -> Result<(), DrawError>
+{
+    // blending is not allowed on integer color attachments
+    if let Some(framebuffer) = framebuffer {
+        if framebuffer.has_integral_color_attachments() &&
+           (draw_parameters.blend.color != BlendingFunction::AlwaysReplace ||
+            draw_parameters.blend.alpha != BlendingFunction::AlwaysReplace)
+        {
+            return Err(DrawError::BlendingWithIntegralAttachment);
+        }
+    }
+
+    Ok(())
+}
+
+/// Draws with a context that has already been made current. The draw command must have been
+/// checked with `validate` and `validate_framebuffer`, which returned `vertices_per_patch`.
+pub fn draw_with_context<'a, U, V>(ctxt: &mut context::CommandContext<'_>, context: &Context,
+                                   framebuffer: Option<&ValidatedAttachments<'_>>,
+                                   vertex_buffers: V, indices: IndicesSource<'_>,
+                                   program: &Program, uniforms: &U,
+                                   draw_parameters: &DrawParameters<'_>, dimensions: (u32, u32),
+                                   vertices_per_patch: Option<u16>) -> Result<(), DrawError>
+                                   where U: Uniforms, V: Iterator<Item = VerticesSource<'a>>
+{
+    // this contains the list of fences that will need to be fulfilled after the draw command
+    // has started
+    let mut fences = Vec::with_capacity(0);
 
     // handling vertices source
-    let (vertices_count, instances_count, base_vertex) = {
+    let (vertices_count, instances_count, base_vertex, base_instance) = {
         let index_buffer = match indices {
             IndicesSource::IndexBuffer { buffer, .. } => Some(buffer),
             IndicesSource::MultidrawArray { .. } => None,
             IndicesSource::MultidrawElement { indices, .. } => Some(indices),
             IndicesSource::NoIndices { .. } => None,
+            IndicesSource::TransformFeedback { .. } => None,
         };
 
         // determining whether we can use the `base_vertex` variants for drawing
@@ -82,21 +161,33 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
             IndicesSource::MultidrawArray { .. } => false,
             IndicesSource::MultidrawElement { .. } => false,
             IndicesSource::NoIndices { .. } => true,
+            IndicesSource::TransformFeedback { .. } => false,
             _ => ctxt.version >= &Version(Api::Gl, 3, 2) ||
                  ctxt.version >= &Version(Api::GlEs, 3, 2) ||
                  ctxt.extensions.gl_arb_draw_elements_base_vertex ||
                  ctxt.extensions.gl_oes_draw_elements_base_vertex
         };
 
+        // determining whether we can use the `base_instance` variants for drawing
+        let use_base_instance = match indices {
+            IndicesSource::MultidrawArray { .. } => false,
+            IndicesSource::MultidrawElement { .. } => false,
+            IndicesSource::TransformFeedback { .. } => false,
+            _ => ctxt.version >= &Version(Api::Gl, 4, 2) ||
+                 ctxt.extensions.gl_arb_base_instance
+        };
+
         // object that is used to build the bindings
-        let mut binder = VertexAttributesSystem::start(&mut ctxt, program, index_buffer,
-                                                       use_base_vertex);
+        let mut binder = VertexAttributesSystem::start(ctxt, program, index_buffer,
+                                                       use_base_vertex, use_base_instance);
         // number of vertices in the vertices sources, or `None` if there is a mismatch
         let mut vertices_count: Option<usize> = None;
         // number of instances to draw
         let mut instances_count: Option<usize> = None;
+        // formats of the vertex buffers, used to check the attributes of the program
+        let mut formats = Vec::new();
 
-        for src in vertex_buffers.iter() {
+        for src in vertex_buffers {
             match src {
                 VerticesSource::VertexBuffer(buffer, format, per_instance) => {
                     // TODO: assert!(buffer.get_elements_size() == total_size(format));
@@ -105,6 +196,8 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                         fences.push(fence);
                     }
 
+                    formats.push(format);
+
                     binder = binder.add(&buffer, format, if per_instance { Some(1) } else { None });
                 },
                 _ => {}
@@ -153,30 +246,62 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
             }
         }
 
-        (vertices_count, instances_count, binder.bind().unwrap_or(0))
+        check_vertex_attributes(context, program, &formats)?;
+
+        let (base_vertex, base_instance) = binder.bind();
+        (vertices_count, instances_count, base_vertex.unwrap_or(0), base_instance.unwrap_or(0))
     };
 
+    // the maximum value of the index type restarts the primitive, so it can't be used to
+    // refer to a vertex
+    if let (true, Some(indices_type), Some(count)) = (draw_parameters.primitive_restart_index,
+                                                      indices.get_indices_type(),
+                                                      vertices_count)
+    {
+        let restart_index = match indices_type {
+            index::IndexType::U8 => 0xff,
+            index::IndexType::U16 => 0xffff,
+            index::IndexType::U32 => 0xffffffff,
+        };
+
+        if count > restart_index {
+            context::debug_warning(ctxt, "The primitive restart index also refers to a \
+                                           vertex of the vertex buffers, this vertex can't \
+                                           be drawn");
+        }
+    }
+
     // binding the FBO to draw upon
     {
-        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(&mut ctxt, framebuffer);
-        unsafe { fbo::bind_framebuffer(&mut ctxt, fbo_id, true, false) };
+        let fbo_id = fbo::FramebuffersContainer::get_framebuffer_for_drawing(ctxt, framebuffer);
+        unsafe { fbo::bind_framebuffer(ctxt, fbo_id, true, false) };
     };
 
     // binding the program and uniforms
-    program.use_program(&mut ctxt);
-    uniforms.bind_uniforms(&mut ctxt, program, &mut fences)?;
+    program.use_program(ctxt);
+    uniforms.bind_uniforms(ctxt, program, &mut fences)?;
+    context.check_unset_uniforms(program)?;
 
     // sync-ing draw_parameters
     unsafe {
-        draw_parameters::sync(&mut ctxt, draw_parameters, dimensions, indices.get_primitives_type())?;
-        sync_vertices_per_patch(&mut ctxt, vertices_per_patch);
+        draw_parameters::sync(ctxt, draw_parameters, dimensions, indices.get_primitives_type(),
+                              indices.get_indices_type())?;
+        sync_vertices_per_patch(ctxt, vertices_per_patch);
 
         // TODO: make sure that the program is the right one
         // TODO: changing the current transform feedback requires pausing/unbinding before changing the program
         if let Some(ref tf) = draw_parameters.transform_feedback {
-            tf.bind(&mut ctxt, indices.get_primitives_type());
+            tf.bind(ctxt, indices.get_primitives_type());
         } else {
-            TransformFeedbackSession::unbind(&mut ctxt);
+            TransformFeedbackSession::unbind(ctxt);
+        }
+    }
+
+    // validating the program against the state that has just been set up ; this is expensive,
+    // so it is only done if the user asked for it
+    if context.is_program_validation_enabled() {
+        if let Err(log) = program.validate_program(ctxt) {
+            return Err(DrawError::ProgramValidationFailed { log });
         }
     }
 
@@ -193,7 +318,18 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                 }
 
                 unsafe {
-                    if let Some(instances_count) = instances_count {
+                    if let (Some(instances_count), true) = (instances_count, base_instance != 0) {
+                        // `base_instance` is only non-zero if the backend supports it
+                        ctxt.gl.DrawElementsInstancedBaseVertexBaseInstance(primitives.to_glenum(),
+                                                                buffer.get_elements_count() as
+                                                                gl::types::GLsizei,
+                                                                data_type.to_glenum(),
+                                                                ptr as *const _,
+                                                                instances_count as
+                                                                gl::types::GLsizei,
+                                                                base_vertex, base_instance);
+
+                    } else if let Some(instances_count) = instances_count {
                         if base_vertex != 0 {
                             if ctxt.version >= &Version(Api::Gl, 3, 2) ||
                                ctxt.version >= &Version(Api::GlEs, 3, 2) ||
@@ -273,10 +409,28 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                 }
 
                 unsafe {
-                    buffer.prepare_and_bind_for_draw_indirect(&mut ctxt);
-                    ctxt.gl.MultiDrawArraysIndirect(primitives.to_glenum(), ptr as *const _,
-                                                    buffer.get_elements_count() as gl::types::GLsizei,
-                                                    0);
+                    buffer.prepare_and_bind_for_draw_indirect(ctxt);
+
+                    if ctxt.version >= &Version(Api::Gl, 4, 3) ||
+                       ctxt.extensions.gl_arb_multi_draw_indirect
+                    {
+                        ctxt.gl.MultiDrawArraysIndirect(primitives.to_glenum(), ptr as *const _,
+                                                        buffer.get_elements_count() as gl::types::GLsizei,
+                                                        0);
+
+                    } else if ctxt.extensions.gl_ext_multi_draw_indirect {
+                        ctxt.gl.MultiDrawArraysIndirectEXT(primitives.to_glenum(), ptr as *const _,
+                                                           buffer.get_elements_count() as gl::types::GLsizei,
+                                                           0);
+
+                    } else {
+                        // only single indirect draws are supported, so we submit the commands
+                        // one by one
+                        for num in 0 .. buffer.get_elements_count() {
+                            let ptr = ptr.add(num * buffer.get_elements_size());
+                            ctxt.gl.DrawArraysIndirect(primitives.to_glenum(), ptr as *const _);
+                        }
+                    }
                 }
             },
 
@@ -293,12 +447,34 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                 }
 
                 unsafe {
-                    commands.prepare_and_bind_for_draw_indirect(&mut ctxt);
+                    commands.prepare_and_bind_for_draw_indirect(ctxt);
                     debug_assert_eq!(base_vertex, 0);       // enforced earlier in this function
-                    ctxt.gl.MultiDrawElementsIndirect(primitives.to_glenum(), data_type.to_glenum(),
-                                                      cmd_ptr as *const _,
-                                                      commands.get_elements_count() as gl::types::GLsizei,
-                                                      0);
+
+                    if ctxt.version >= &Version(Api::Gl, 4, 3) ||
+                       ctxt.extensions.gl_arb_multi_draw_indirect
+                    {
+                        ctxt.gl.MultiDrawElementsIndirect(primitives.to_glenum(), data_type.to_glenum(),
+                                                          cmd_ptr as *const _,
+                                                          commands.get_elements_count() as gl::types::GLsizei,
+                                                          0);
+
+                    } else if ctxt.extensions.gl_ext_multi_draw_indirect {
+                        ctxt.gl.MultiDrawElementsIndirectEXT(primitives.to_glenum(),
+                                                             data_type.to_glenum(),
+                                                             cmd_ptr as *const _,
+                                                             commands.get_elements_count() as gl::types::GLsizei,
+                                                             0);
+
+                    } else {
+                        // only single indirect draws are supported, so we submit the commands
+                        // one by one
+                        for num in 0 .. commands.get_elements_count() {
+                            let cmd_ptr = cmd_ptr.add(num * commands.get_elements_size());
+                            ctxt.gl.DrawElementsIndirect(primitives.to_glenum(),
+                                                         data_type.to_glenum(),
+                                                         cmd_ptr as *const _);
+                        }
+                    }
                 }
             },
 
@@ -309,7 +485,14 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                 };
 
                 unsafe {
-                    if let Some(instances_count) = instances_count {
+                    if let (Some(instances_count), true) = (instances_count, base_instance != 0) {
+                        // `base_instance` is only non-zero if the backend supports it
+                        ctxt.gl.DrawArraysInstancedBaseInstance(primitives.to_glenum(), base_vertex,
+                                                                vertices_count as gl::types::GLsizei,
+                                                                instances_count as gl::types::GLsizei,
+                                                                base_instance);
+
+                    } else if let Some(instances_count) = instances_count {
                         ctxt.gl.DrawArraysInstanced(primitives.to_glenum(), base_vertex,
                                                     vertices_count as gl::types::GLsizei,
                                                     instances_count as gl::types::GLsizei);
@@ -319,6 +502,25 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
                     }
                 }
             },
+
+            &IndicesSource::TransformFeedback { stream, primitives } => {
+                unsafe {
+                    if let Some(instances_count) = instances_count {
+                        if !(ctxt.version >= &Version(Api::Gl, 4, 2)) &&
+                           !ctxt.extensions.gl_arb_transform_feedback_instanced
+                        {
+                            return Err(DrawError::TransformFeedbackInstancingNotSupported);
+                        }
+
+                        ctxt.gl.DrawTransformFeedbackInstanced(primitives.to_glenum(),
+                                                               stream.get_id(),
+                                                               instances_count as
+                                                               gl::types::GLsizei);
+                    } else {
+                        ctxt.gl.DrawTransformFeedback(primitives.to_glenum(), stream.get_id());
+                    }
+                }
+            },
         };
     };
 
@@ -326,12 +528,19 @@ pub fn draw<'a, U, V>(context: &Context, framebuffer: Option<&ValidatedAttachmen
 
     // fulfilling the fences
     for fence in fences.into_iter() {
-        fence.insert(&mut ctxt);
+        fence.insert(ctxt);
     }
 
     Ok(())
 }
 
+/// Returns true if the backend supports drawing with a buffer of draw commands.
+fn is_indirect_draw_supported<C: ?Sized>(context: &C) -> bool where C: CapabilitiesSource {
+    context.get_version() >= &Version(Api::Gl, 4, 0) ||
+    context.get_version() >= &Version(Api::GlEs, 3, 1) ||
+    context.get_extensions().gl_arb_draw_indirect
+}
+
 unsafe fn sync_vertices_per_patch(ctxt: &mut context::CommandContext<'_>, vertices_per_patch: Option<u16>) {
     if let Some(vertices_per_patch) = vertices_per_patch {
         let vertices_per_patch = vertices_per_patch as gl::types::GLint;
@@ -341,3 +550,100 @@ unsafe fn sync_vertices_per_patch(ctxt: &mut context::CommandContext<'_>, vertic
         }
     }
 }
+
+/// Returns true if `primitives` can be fed to a geometry shader whose input is `input`.
+fn geometry_input_matches(input: InputPrimitives, primitives: index::PrimitiveType) -> bool {
+    use crate::index::PrimitiveType::*;
+
+    match (input, primitives) {
+        (InputPrimitives::Points, Points) => true,
+        (InputPrimitives::Lines, LinesList) |
+        (InputPrimitives::Lines, LineStrip) |
+        (InputPrimitives::Lines, LineLoop) => true,
+        (InputPrimitives::LinesAdjacency, LinesListAdjacency) |
+        (InputPrimitives::LinesAdjacency, LineStripAdjacency) => true,
+        (InputPrimitives::Triangles, TrianglesList) |
+        (InputPrimitives::Triangles, TriangleStrip) |
+        (InputPrimitives::Triangles, TriangleFan) => true,
+        (InputPrimitives::TrianglesAdjacency, TrianglesListAdjacency) |
+        (InputPrimitives::TrianglesAdjacency, TriangleStripAdjacency) => true,
+        _ => false,
+    }
+}
+
+/// Checks that the attributes of the program are all found in the vertex formats and that
+/// their types match.
+fn check_vertex_attributes(context: &Context, program: &Program, formats: &[&VertexFormat])
+                           -> Result<(), DrawError>
+{
+    for format in formats {
+        for &(ref name, _, ty, normalize) in format.iter() {
+            let attribute = match program.get_attribute(name) {
+                Some(a) => a,
+                None => continue
+            };
+
+            // integral shader inputs must be fed with integers that aren't converted to floats
+            let integral_mismatch = attribute.ty.is_integral() &&
+                                    (!ty.is_integral() || normalize);
+
+            // `int` inputs must be fed with signed integers and `uint` inputs with unsigned ones
+            let sign_mismatch = attribute.ty.is_integral() && ty.is_integral() &&
+                                is_unsigned(attribute.ty) != is_unsigned(ty);
+
+            // 64 bits shader inputs can only be fed with the exact same type, and 64 bits data
+            // can only be fed to 64 bits inputs
+            let long_mismatch = (is_64bit(attribute.ty) && (ty != attribute.ty || normalize)) ||
+                                (is_64bit(ty) && !is_64bit(attribute.ty));
+
+            if ty.get_num_components() != attribute.ty.get_num_components() ||
+                attribute.size != 1 || integral_mismatch || sign_mismatch || long_mismatch
+            {
+                return Err(DrawError::AttributeTypeMismatch {
+                    name: name.to_string(),
+                    expected: attribute.ty,
+                    obtained: ty,
+                });
+            }
+
+            // 64 bits attributes are bound with `glVertexAttribLPointer`, which isn't available
+            // everywhere
+            if is_64bit(ty) && !ty.is_supported(context) {
+                return Err(DrawError::AttributeTypeNotSupported {
+                    name: name.to_string(),
+                    ty,
+                });
+            }
+        }
+    }
+
+    for (name, _) in program.attributes() {
+        if !formats.iter().any(|format| format.iter().any(|&(ref n, _, _, _)| n == name)) {
+            return Err(DrawError::AttributeMissing { name: name.clone() });
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns true if the attribute type is made of `f64`s, `i64`s or `u64`s, which are bound with
+/// `glVertexAttribLPointer`.
+fn is_64bit(ty: AttributeType) -> bool {
+    match ty {
+        AttributeType::I64 | AttributeType::I64I64 | AttributeType::I64I64I64 |
+        AttributeType::I64I64I64I64 | AttributeType::U64 | AttributeType::U64U64 |
+        AttributeType::U64U64U64 | AttributeType::U64U64U64U64 => true,
+        _ => ty.is_double(),
+    }
+}
+
+/// Returns true if the attribute type is made of unsigned integers.
+fn is_unsigned(ty: AttributeType) -> bool {
+    match ty {
+        AttributeType::U8 | AttributeType::U8U8 | AttributeType::U8U8U8 |
+        AttributeType::U8U8U8U8 | AttributeType::U16 | AttributeType::U16U16 |
+        AttributeType::U16U16U16 | AttributeType::U16U16U16U16 | AttributeType::U32 |
+        AttributeType::U32U32 | AttributeType::U32U32U32 | AttributeType::U32U32U32U32 => true,
+        _ => false,
+    }
+}
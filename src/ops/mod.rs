@@ -1,6 +1,6 @@
 pub use self::blit::blit;
-pub use self::clear::clear;
-pub use self::draw::draw;
+pub use self::clear::{clear, clear_buffer, clear_stencil_masked};
+pub use self::draw::{draw, draw_with_context, validate as validate_draw, validate_framebuffer};
 pub use self::read::{read, ReadError, Source, Destination};
 
 mod blit;
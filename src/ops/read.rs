@@ -111,15 +111,6 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext<'_>, source: S, rect: &Re
 
     let pixels_to_read = rect.width * rect.height;
 
-    // checking that the output format is supported
-    // OpenGL supported everything, while OpenGL ES only supports U8U8U8U8 plus an additional
-    // implementation-defined format
-    if ctxt.version >= &Version(Api::GlEs, 2, 0) && output_pixel_format != ClientFormat::U8U8U8U8 {
-        // TODO: GLES is guaranteed to support GL_RGBA and an implementation-defined format
-        //       queried with GL_IMPLEMENTATION_COLOR_READ_FORMAT. We only handle GL_RGBA.
-        return Err(ReadError::OutputFormatNotSupported);
-    }
-
     // handling clamping
     if ctxt.version >= &Version(Api::Gl, 3, 0) {
         unsafe {
@@ -174,6 +165,24 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext<'_>, source: S, rect: &Re
         },
     };
 
+    // checking that the output format is supported
+    // OpenGL supported everything, while OpenGL ES only supports U8U8U8U8 (or U32U32U32U32 and
+    // I32I32I32I32 for integral attachments) plus an additional implementation-defined format
+    if ctxt.version >= &Version(Api::GlEs, 2, 0) {
+        // TODO: GLES is guaranteed to support an implementation-defined format queried with
+        //       GL_IMPLEMENTATION_COLOR_READ_FORMAT. We only handle the guaranteed formats.
+        let supported = if integer {
+            output_pixel_format == ClientFormat::U32U32U32U32 ||
+            output_pixel_format == ClientFormat::I32I32I32I32
+        } else {
+            output_pixel_format == ClientFormat::U8U8U8U8
+        };
+
+        if !supported {
+            return Err(ReadError::OutputFormatNotSupported);
+        }
+    }
+
     // OpenGL ES doesn't support reading from depth, stencil or depth-stencil attachments by default
     if ctxt.version >= &Version(Api::GlEs, 2, 0) {
         match read_src_type {
@@ -248,7 +257,7 @@ pub fn read<'a, S, D, T>(mut ctxt: &mut CommandContext<'_>, source: S, rect: &Re
                                    rect.height as gl::types::GLsizei, format, gltype,
                                    ptr::null_mut());
 
-                crate::pixel_buffer::store_infos(pixel_buffer, (rect.width, rect.height));
+                pixel_buffer.finish_read(ctxt, (rect.width, rect.height));
             }
         }
     };
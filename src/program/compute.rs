@@ -16,7 +16,7 @@ use crate::ProgramExt;
 use crate::Handle;
 use crate::RawUniformValue;
 
-use crate::program::{COMPILER_GLOBAL_LOCK, ProgramCreationError, Binary, GetBinaryError};
+use crate::program::{lock_compiler, ProgramCreationError, Binary, GetBinaryError};
 
 use crate::program::reflection::{Uniform, UniformBlock};
 use crate::program::reflection::{ShaderStage, SubroutineData};
@@ -44,12 +44,13 @@ impl ComputeShader {
     pub fn from_source<F: ?Sized>(facade: &F, src: &str) -> Result<ComputeShader, ProgramCreationError>
                           where F: Facade
     {
-        let _lock = COMPILER_GLOBAL_LOCK.lock();
+        let _lock = lock_compiler(facade);
 
         let shader = build_shader(facade, gl::COMPUTE_SHADER, src)?;
 
         Ok(ComputeShader {
-            raw: RawProgram::from_shaders(facade, &[shader], false, false, false, None)?
+            raw: RawProgram::from_shaders(facade, &[shader], false, false, false, None, None,
+                                          &[])?
         })
     }
 
@@ -58,7 +59,7 @@ impl ComputeShader {
     pub fn from_binary<F: ?Sized>(facade: &F, data: Binary) -> Result<ComputeShader, ProgramCreationError>
                           where F: Facade
     {
-        let _lock = COMPILER_GLOBAL_LOCK.lock();
+        let _lock = lock_compiler(facade);
 
         Ok(ComputeShader {
             raw: RawProgram::from_binary(facade, data)?
@@ -226,6 +227,11 @@ impl ProgramExt for ComputeShader {
     fn get_subroutine_data(&self) -> &SubroutineData {
         self.raw.get_subroutine_data()
     }
+
+    #[inline]
+    fn validate_program(&self, ctxt: &mut CommandContext<'_>) -> Result<(), String> {
+        self.raw.validate_program(ctxt)
+    }
 }
 
 /// Represents a compute shader command waiting to be dispatched.
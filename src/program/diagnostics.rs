@@ -0,0 +1,223 @@
+/// Severity of a message found in the info log of a shader or a program.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    /// The message describes an error.
+    Error,
+    /// The message describes a warning.
+    Warning,
+    /// The message gives additional information about a previous message.
+    Note,
+}
+
+/// A message found in the info log of a shader or a program.
+///
+/// The format of the info log is not specified by OpenGL and each driver uses its own. Glium
+/// understands the formats `0(12) : error C0000: ...` (used by NVIDIA) and
+/// `0:12(5): error: ...` or `ERROR: 0:12: ...` (used by most other drivers). Lines that can't
+/// be parsed are kept as they are, without any location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderDiagnostic {
+    /// Severity of the message.
+    pub severity: DiagnosticSeverity,
+    /// The line in the source code the message refers to, if known.
+    pub line: Option<u32>,
+    /// The column in the source code the message refers to, if known.
+    pub column: Option<u32>,
+    /// The message itself, without the location and severity.
+    pub message: String,
+}
+
+/// Parses the info log of a shader or a program into a list of diagnostics.
+pub fn parse_info_log(log: &str) -> Vec<ShaderDiagnostic> {
+    log.lines()
+       .map(|line| line.trim())
+       .filter(|line| !line.is_empty())
+       .map(parse_line)
+       .collect()
+}
+
+/// Turns the bytes filled by `glGetShaderInfoLog` or `glGetProgramInfoLog` into a string.
+///
+/// Some drivers (notably some versions of Mesa with geometry shaders) report a length that
+/// includes the terminating NUL, so any trailing NUL is removed. If the log is empty, a
+/// placeholder message is returned instead.
+pub(crate) fn decode_info_log(mut log: Vec<u8>) -> String {
+    while log.last() == Some(&0) {
+        log.pop();
+    }
+
+    if log.is_empty() {
+        return "<no log provided by driver>".to_owned();
+    }
+
+    String::from_utf8_lossy(&log).into_owned()
+}
+
+fn parse_line(line: &str) -> ShaderDiagnostic {
+    // some drivers put the severity before the location
+    let (mut severity, rest) = match strip_severity(line) {
+        Some((severity, rest)) => (Some(severity), rest),
+        None => (None, line),
+    };
+
+    let (line_num, column, rest) = match parse_location(rest) {
+        Some(location) => location,
+        None => {
+            let severity = severity.unwrap_or_else(|| guess_severity(line));
+            return ShaderDiagnostic {
+                severity,
+                line: None,
+                column: None,
+                message: rest.to_owned(),
+            };
+        },
+    };
+
+    // others put the severity after the location, optionally followed by an error code
+    let mut rest = rest.trim_start_matches(|c: char| c == ':' || c.is_whitespace());
+    if severity.is_none() {
+        if let Some((s, r)) = strip_severity(rest) {
+            severity = Some(s);
+            rest = r;
+        }
+    }
+
+    ShaderDiagnostic {
+        severity: severity.unwrap_or_else(|| guess_severity(line)),
+        line: Some(line_num),
+        column,
+        message: rest.to_owned(),
+    }
+}
+
+/// Strips a leading `error`, `warning`, `note` or `info` word, an optional error code and the
+/// following `:`.
+fn strip_severity(s: &str) -> Option<(DiagnosticSeverity, &str)> {
+    let word_len = s.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(s.len());
+    let severity = match &s[.. word_len].to_ascii_lowercase()[..] {
+        "error" => DiagnosticSeverity::Error,
+        "warning" => DiagnosticSeverity::Warning,
+        "note" | "info" => DiagnosticSeverity::Note,
+        _ => return None,
+    };
+
+    let rest = &s[word_len ..];
+    let colon = rest.find(':')?;
+    let code = rest[.. colon].trim();
+    if !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    Some((severity, rest[colon + 1 ..].trim_start()))
+}
+
+/// Parses `0(12)`, `0:12`, `0:12(5)` or `0:12:5`, and returns the line, the column and what
+/// follows.
+fn parse_location(s: &str) -> Option<(u32, Option<u32>, &str)> {
+    let (_source_string, rest) = parse_number(s)?;
+
+    if let Some(rest) = rest.strip_prefix('(') {
+        let (line, rest) = parse_number(rest)?;
+        let rest = rest.strip_prefix(')')?;
+        return Some((line, None, rest));
+    }
+
+    let rest = rest.strip_prefix(':')?;
+    let (line, rest) = parse_number(rest)?;
+
+    if let Some(after) = rest.strip_prefix('(') {
+        if let Some((column, after)) = parse_number(after) {
+            if let Some(after) = after.strip_prefix(')') {
+                return Some((line, Some(column), after));
+            }
+        }
+    }
+
+    if let Some(after) = rest.strip_prefix(':') {
+        if let Some((column, after)) = parse_number(after) {
+            if after.starts_with(':') {
+                return Some((line, Some(column), after));
+            }
+        }
+    }
+
+    Some((line, None, rest))
+}
+
+fn parse_number(s: &str) -> Option<(u32, &str)> {
+    let len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let value = s[.. len].parse().ok()?;
+    Some((value, &s[len ..]))
+}
+
+fn guess_severity(line: &str) -> DiagnosticSeverity {
+    if line.to_ascii_lowercase().contains("warning") {
+        DiagnosticSeverity::Warning
+    } else {
+        DiagnosticSeverity::Error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_info_log, parse_info_log, DiagnosticSeverity, ShaderDiagnostic};
+
+    fn diag(severity: DiagnosticSeverity, line: Option<u32>, column: Option<u32>, message: &str)
+            -> ShaderDiagnostic
+    {
+        ShaderDiagnostic { severity, line, column, message: message.to_owned() }
+    }
+
+    #[test]
+    fn nvidia() {
+        let log = "0(12) : error C0000: syntax error, unexpected '}'\n\
+                   0(3) : warning C7050: \"foo\" might be used before being initialized\n";
+
+        assert_eq!(parse_info_log(log), vec![
+            diag(DiagnosticSeverity::Error, Some(12), None, "syntax error, unexpected '}'"),
+            diag(DiagnosticSeverity::Warning, Some(3), None,
+                 "\"foo\" might be used before being initialized"),
+        ]);
+    }
+
+    #[test]
+    fn mesa() {
+        let log = "0:7(13): error: `foo' undeclared\n0:9(2): warning: unused variable";
+
+        assert_eq!(parse_info_log(log), vec![
+            diag(DiagnosticSeverity::Error, Some(7), Some(13), "`foo' undeclared"),
+            diag(DiagnosticSeverity::Warning, Some(9), Some(2), "unused variable"),
+        ]);
+    }
+
+    #[test]
+    fn severity_first() {
+        let log = "ERROR: 0:4: 'bar' : undeclared identifier\nERROR: 1 compilation errors.";
+
+        assert_eq!(parse_info_log(log), vec![
+            diag(DiagnosticSeverity::Error, Some(4), None, "'bar' : undeclared identifier"),
+            diag(DiagnosticSeverity::Error, None, None, "1 compilation errors."),
+        ]);
+    }
+
+    #[test]
+    fn line_and_column() {
+        assert_eq!(parse_info_log("0:5:10: error: oops"), vec![
+            diag(DiagnosticSeverity::Error, Some(5), Some(10), "oops"),
+        ]);
+    }
+
+    #[test]
+    fn unknown_format() {
+        assert_eq!(parse_info_log("Vertex shader failed to compile\n\n"), vec![
+            diag(DiagnosticSeverity::Error, None, None, "Vertex shader failed to compile"),
+        ]);
+    }
+
+    #[test]
+    fn info_log_trailing_nul() {
+        assert_eq!(decode_info_log(b"0:1(1): error: oops\n\0".to_vec()), "0:1(1): error: oops\n");
+        assert_eq!(decode_info_log(b"\0".to_vec()), "<no log provided by driver>");
+        assert_eq!(decode_info_log(Vec::new()), "<no log provided by driver>");
+    }
+}
@@ -2,24 +2,30 @@
 
 use std::fmt;
 use std::error::Error;
-use std::sync::Mutex;
+use std::sync::{Mutex, MutexGuard};
 use crate::CapabilitiesSource;
+use crate::ToGlEnum;
 
 use crate::gl;
+use crate::index::PrimitiveType;
 use crate::version::Api;
 use crate::version::Version;
 
 pub use self::compute::{ComputeShader, ComputeCommand};
-pub use self::program::Program;
-pub use self::reflection::{Uniform, UniformBlock, BlockLayout, OutputPrimitives};
+pub use self::diagnostics::{ShaderDiagnostic, DiagnosticSeverity, parse_info_log};
+pub use self::program::{Program, PendingProgram};
+pub use self::reflection::{Uniform, UniformBlock, BlockLayout, BlockMember, OutputPrimitives, InputPrimitives};
 pub use self::reflection::{Attribute, TransformFeedbackVarying, TransformFeedbackBuffer, TransformFeedbackMode};
 pub use self::reflection::{ShaderStage, SubroutineData, SubroutineUniform};
+pub use self::source::prepend_header;
 
 mod compute;
+mod diagnostics;
 mod program;
 mod raw;
 mod reflection;
 mod shader;
+mod source;
 mod uniforms_storage;
 mod binary_header;
 
@@ -62,6 +68,28 @@ lazy_static! {
     static ref COMPILER_GLOBAL_LOCK: Mutex<()> = Mutex::new(());
 }
 
+/// Returns true if the backend can compile shaders in the background with
+/// `GL_ARB_parallel_shader_compile` or `GL_KHR_parallel_shader_compile`.
+#[inline]
+pub fn is_parallel_shader_compile_supported<C: ?Sized>(ctxt: &C) -> bool
+    where C: CapabilitiesSource
+{
+    ctxt.get_extensions().gl_arb_parallel_shader_compile ||
+        ctxt.get_extensions().gl_khr_parallel_shader_compile
+}
+
+// Locks `COMPILER_GLOBAL_LOCK`, except if the driver supports parallel compilation, in which case
+// shaders can safely be compiled from multiple threads at once.
+fn lock_compiler<C: ?Sized>(ctxt: &C) -> Option<MutexGuard<'static, ()>>
+    where C: CapabilitiesSource
+{
+    if is_parallel_shader_compile_supported(ctxt) {
+        None
+    } else {
+        Some(COMPILER_GLOBAL_LOCK.lock().unwrap_or_else(|err| err.into_inner()))
+    }
+}
+
 /// Used in ProgramCreationError::CompilationError to explain which shader stage failed compilation 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ShaderType {
@@ -108,6 +136,90 @@ impl ShaderType {
     }
 }
 
+/// A precision qualifier of the variables of a shader.
+///
+/// See `Context::get_shader_precision_format`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PrecisionType {
+    /// `lowp float`.
+    LowFloat,
+    /// `mediump float`.
+    MediumFloat,
+    /// `highp float`.
+    HighFloat,
+    /// `lowp int`.
+    LowInt,
+    /// `mediump int`.
+    MediumInt,
+    /// `highp int`.
+    HighInt,
+}
+
+impl PrecisionType {
+    /// Returns true if this is one of the floating-point types.
+    #[inline]
+    pub fn is_float(&self) -> bool {
+        match *self {
+            PrecisionType::LowFloat | PrecisionType::MediumFloat |
+            PrecisionType::HighFloat => true,
+            PrecisionType::LowInt | PrecisionType::MediumInt | PrecisionType::HighInt => false,
+        }
+    }
+}
+
+impl ToGlEnum for PrecisionType {
+    #[inline]
+    fn to_glenum(&self) -> gl::types::GLenum {
+        match *self {
+            PrecisionType::LowFloat => gl::LOW_FLOAT,
+            PrecisionType::MediumFloat => gl::MEDIUM_FLOAT,
+            PrecisionType::HighFloat => gl::HIGH_FLOAT,
+            PrecisionType::LowInt => gl::LOW_INT,
+            PrecisionType::MediumInt => gl::MEDIUM_INT,
+            PrecisionType::HighInt => gl::HIGH_INT,
+        }
+    }
+}
+
+/// The range and the precision of the values of a `PrecisionType` in a shader.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PrecisionFormat {
+    /// Log2 of the absolute value of the smallest negative value that can be represented.
+    pub range_min: i32,
+
+    /// Log2 of the absolute value of the largest positive value that can be represented.
+    pub range_max: i32,
+
+    /// Number of bits of precision of floating-point values, or `0` for integers.
+    pub precision: i32,
+}
+
+impl PrecisionFormat {
+    /// The format of IEEE 754 single-precision floats, which desktop OpenGL always uses.
+    pub const FULL_FLOAT: PrecisionFormat = PrecisionFormat {
+        range_min: 127,
+        range_max: 127,
+        precision: 23,
+    };
+
+    /// The format of 32 bits integers, which desktop OpenGL always uses.
+    pub const FULL_INT: PrecisionFormat = PrecisionFormat {
+        range_min: 31,
+        range_max: 30,
+        precision: 0,
+    };
+
+    /// Returns false if the implementation doesn't support this precision in this kind of
+    /// shader.
+    ///
+    /// OpenGL ES doesn't require `highp` in fragment shaders, in which case the range and the
+    /// precision are all `0`.
+    #[inline]
+    pub fn is_supported(&self) -> bool {
+        self.range_min != 0 || self.range_max != 0 || self.precision != 0
+    }
+}
+
 /// Error that can be triggered when creating a `Program`.
 #[derive(Clone, Debug)]
 pub enum ProgramCreationError {
@@ -133,8 +245,22 @@ pub enum ProgramCreationError {
     /// supported by the backend.
     PointSizeNotSupported,
 
+    /// You have requested explicit locations for the outputs of the fragment shader, but
+    /// `glBindFragDataLocation` is not supported by the backend.
+    FragDataLocationNotSupported,
+
     /// The glium-specific binary header was not found or is corrupt.
     BinaryHeaderError,
+
+    /// The source code of the fragment shader is empty, but the backend requires programs to
+    /// have a fragment shader.
+    ///
+    /// This is always the case with OpenGL ES.
+    FragmentShaderRequired,
+
+    /// The `GeometryShaderParameters` use a type of primitives that geometry shaders can't
+    /// receive or emit, like `Patches` or `Quads`.
+    InvalidGeometryShaderParameters,
 }
 
 impl fmt::Display for ProgramCreationError {
@@ -161,8 +287,14 @@ impl fmt::Display for ProgramCreationError {
                 "Transform feedback is not supported by the backend.",
             PointSizeNotSupported =>
                 "Point size is not supported by the backend.",
+            FragDataLocationNotSupported =>
+                "Binding the outputs of the fragment shader is not supported by the backend.",
             BinaryHeaderError =>
                 "The glium-specific binary header was not found or is corrupt.",
+            FragmentShaderRequired =>
+                "The backend requires programs to have a fragment shader.",
+            InvalidGeometryShaderParameters =>
+                "The geometry shader parameters use an invalid type of primitives.",
         };
         match *self {
             CompilationError(ref s, _) =>
@@ -175,6 +307,20 @@ impl fmt::Display for ProgramCreationError {
     }
 }
 
+impl ProgramCreationError {
+    /// Parses the info log of a `CompilationError` or a `LinkingError` into a list of
+    /// diagnostics. Returns an empty list for the other errors.
+    ///
+    /// See `ShaderDiagnostic` for the formats that are understood.
+    pub fn diagnostics(&self) -> Vec<ShaderDiagnostic> {
+        match *self {
+            ProgramCreationError::CompilationError(ref log, _) => parse_info_log(log),
+            ProgramCreationError::LinkingError(ref log) => parse_info_log(log),
+            _ => Vec::new(),
+        }
+    }
+}
+
 impl Error for ProgramCreationError {}
 
 /// Error type that is returned by the `program!` macro.
@@ -254,6 +400,9 @@ pub enum ProgramCreationInput<'a> {
         geometry_shader: Option<&'a str>,
 
         /// Source code of the fragment shader.
+        ///
+        /// An empty string means that the program doesn't have a fragment shader. See
+        /// `Program::from_source`.
         fragment_shader: &'a str,
 
         /// The list of variables and mode to use for transform feedback.
@@ -271,6 +420,21 @@ pub enum ProgramCreationInput<'a> {
 
         /// Whether the shader uses point size.
         uses_point_size: bool,
+
+        /// Parameters of the geometry shader to pass to the linker.
+        ///
+        /// This is only used if the backend supports geometry shaders through
+        /// `GL_ARB_geometry_shader4` and not natively. Otherwise these parameters are read from
+        /// the `layout` qualifiers of the geometry shader and this field is ignored.
+        geometry_shader_parameters: Option<GeometryShaderParameters>,
+
+        /// Locations to assign to the outputs of the fragment shader before linking.
+        ///
+        /// Each entry is the name of an output and the index of the color attachment it is
+        /// written to. Outputs that aren't in the list are assigned a location by the linker,
+        /// and `layout(location = ...)` qualifiers in the source code take precedence. Pass an
+        /// empty slice if you don't need this.
+        frag_data_locations: &'a [(String, u32)],
     },
 
     /// Use a precompiled binary.
@@ -300,7 +464,7 @@ pub struct SourceCode<'a> {
     /// Source code of the optional geometry shader.
     pub geometry_shader: Option<&'a str>,
 
-    /// Source code of the fragment shader.
+    /// Source code of the fragment shader, or an empty string for no fragment shader.
     pub fragment_shader: &'a str,
 }
 
@@ -319,10 +483,32 @@ impl<'a> From<SourceCode<'a>> for ProgramCreationInput<'a> {
             transform_feedback_varyings: None,
             outputs_srgb: false,
             uses_point_size: false,
+            geometry_shader_parameters: None,
+            frag_data_locations: &[],
         }
     }
 }
 
+/// Parameters of a geometry shader that have to be set before linking with
+/// `GL_ARB_geometry_shader4`, as the GLSL extension has no `layout` qualifier to declare them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GeometryShaderParameters {
+    /// The type of primitives that the geometry shader receives.
+    ///
+    /// Only the kind of primitive is taken into account. For example `TriangleStrip` is
+    /// equivalent to `TrianglesList`. `Patches` can't be used with a geometry shader.
+    pub input_type: PrimitiveType,
+
+    /// The type of primitives that the geometry shader emits.
+    ///
+    /// Geometry shaders always output points, line strips or triangle strips. `Quads` can't be
+    /// used.
+    pub output_type: OutputPrimitives,
+
+    /// The maximum number of vertices that the geometry shader emits per invocation.
+    pub vertices_out: u32,
+}
+
 /// Represents the compiled binary data of a program.
 pub struct Binary {
     /// An implementation-defined format.
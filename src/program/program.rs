@@ -5,36 +5,60 @@ use crate::version::Version;
 use crate::version::Api;
 
 use crate::backend::Facade;
+use crate::context::Context;
 use crate::CapabilitiesSource;
 
+use std::cell::RefCell;
 use std::fmt;
+use std::rc::Rc;
 use std::collections::hash_map::{self, HashMap};
+use std::collections::HashSet;
 use std::hash::BuildHasherDefault;
 
 use fnv::FnvHasher;
 
 use crate::GlObject;
 use crate::ProgramExt;
+use crate::ContextExt;
 use crate::Handle;
 use crate::RawUniformValue;
+use crate::DrawError;
+use crate::UniformsExt;
+use crate::uniforms::Uniforms;
 
-use crate::program::{COMPILER_GLOBAL_LOCK, ProgramCreationInput, ProgramCreationError, ShaderType, Binary};
-use crate::program::GetBinaryError;
+use crate::program::{is_binary_supported, lock_compiler, ProgramCreationInput, ProgramCreationError, ShaderType, Binary};
+use crate::program::{GetBinaryError, GeometryShaderParameters, SourceCode};
 
-use crate::program::reflection::{Uniform, UniformBlock, OutputPrimitives};
-use crate::program::reflection::{Attribute, TransformFeedbackBuffer};
+use crate::program::reflection::{Uniform, UniformBlock, BlockMember, OutputPrimitives, InputPrimitives};
+use crate::program::reflection::{Attribute, TransformFeedbackBuffer, TransformFeedbackMode};
 use crate::program::reflection::{SubroutineData, ShaderStage, SubroutineUniform};
-use crate::program::shader::build_shader;
+use crate::program::shader::{Shader, start_shader, check_shader, is_shader_compiled};
 
 use crate::program::raw::RawProgram;
 
 use crate::vertex::VertexFormat;
+use crate::index::PrimitiveType;
 
 /// A combination of shaders linked together.
 pub struct Program {
-    raw: RawProgram,
+    // the programs that have been linked, the last one being the current one ; the previous
+    // ones have been destroyed by `reload` and are only kept so that the references returned
+    // by the getters stay valid
+    raws: RefCell<Vec<Rc<RawProgram>>>,
     outputs_srgb: bool,
     uses_point_size: bool,
+    link_parameters: LinkParameters,
+    // names of the uniforms passed to `set_persistent_uniforms`
+    persistent_uniforms: RefCell<HashSet<String>>,
+}
+
+/// Parameters of `ProgramCreationInput::SourceCode` that are kept in order to link the program
+/// again in `reload`.
+#[derive(Default)]
+struct LinkParameters {
+    transform_feedback_varyings: Option<(Vec<String>, TransformFeedbackMode)>,
+    geometry_shader_parameters: Option<GeometryShaderParameters>,
+    frag_data_locations: Vec<(String, u32)>,
 }
 
 impl Program {
@@ -44,77 +68,183 @@ impl Program {
     {
         let input = input.into();
 
-        let (raw, outputs_srgb, uses_point_size) = match input {
-            ProgramCreationInput::SourceCode { vertex_shader, tessellation_control_shader,
-                                               tessellation_evaluation_shader, geometry_shader,
-                                               fragment_shader, transform_feedback_varyings,
-                                               outputs_srgb, uses_point_size } =>
-            {
-                let mut has_geometry_shader = false;
-                let mut has_tessellation_control_shader = false;
-                let mut has_tessellation_evaluation_shader = false;
-
-                let mut shaders = vec![
-                    (vertex_shader, ShaderType::Vertex),
-                    (fragment_shader, ShaderType::Fragment)
-                ];
-
-                if let Some(gs) = geometry_shader {
-                    shaders.push((gs, ShaderType::Geometry));
-                    has_geometry_shader = true;
-                }
-
-                if let Some(ts) = tessellation_control_shader {
-                    shaders.push((ts, ShaderType::TesselationControl));
-                    has_tessellation_control_shader = true;
-                }
-
-                if let Some(ts) = tessellation_evaluation_shader {
-                    shaders.push((ts, ShaderType::TesselationEvaluation));
-                    has_tessellation_evaluation_shader = true;
-                }
-
-                // TODO: move somewhere else
-                if transform_feedback_varyings.is_some() &&
-                    !(facade.get_context().get_version() >= &Version(Api::Gl, 3, 0)) &&
-                    !facade.get_context().get_extensions().gl_ext_transform_feedback
-                {
-                    return Err(ProgramCreationError::TransformFeedbackNotSupported);
-                }
+        match input {
+            input @ ProgramCreationInput::SourceCode { .. } => {
+                let _lock = lock_compiler(facade);
+                CompilingProgram::start(facade, input)?.finish(facade)
+            },
 
+            ProgramCreationInput::Binary { data, outputs_srgb, uses_point_size } => {
                 if uses_point_size && !(facade.get_context().get_version() >= &Version(Api::Gl, 3, 0)) {
                     return Err(ProgramCreationError::PointSizeNotSupported);
                 }
 
-                let _lock = COMPILER_GLOBAL_LOCK.lock();
+                Ok(Program::from_raw(RawProgram::from_binary(facade, data)?, outputs_srgb,
+                                     uses_point_size, LinkParameters::default()))
+            },
+        }
+    }
 
-                let shaders_store = {
-                    let mut shaders_store = Vec::new();
-                    for (src, ty) in shaders.into_iter() {
-                        shaders_store.push(build_shader(facade, ty.to_opengl_type(), src)?);
-                    }
-                    shaders_store
-                };
+    /// Starts building a new program, without waiting for the shaders to be compiled.
+    ///
+    /// Call `PendingProgram::poll` regularly, for example once per frame, in order to get the
+    /// program once it is ready. This allows compiling many programs, for example behind a
+    /// loading screen, without blocking on each of them.
+    ///
+    /// The compilation only happens in the background if the backend supports
+    /// `GL_ARB_parallel_shader_compile` or `GL_KHR_parallel_shader_compile` (see
+    /// `is_parallel_shader_compile_supported`). Otherwise the shaders are compiled immediately and
+    /// the first call to `poll` returns the program.
+    ///
+    /// Errors that can be detected before compiling, such as unsupported shader types, are
+    /// returned immediately. Compilation and linking errors are returned by `poll`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let display: glium::Display = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// # let vertex_source = ""; let fragment_source = "";
+    /// let mut pending = glium::Program::new_async(&display,
+    ///     glium::program::ProgramCreationInput::SourceCode {
+    ///         vertex_shader: vertex_source,
+    ///         fragment_shader: fragment_source,
+    ///         geometry_shader: None,
+    ///         tessellation_control_shader: None,
+    ///         tessellation_evaluation_shader: None,
+    ///         transform_feedback_varyings: None,
+    ///         outputs_srgb: false,
+    ///         uses_point_size: false,
+    ///         geometry_shader_parameters: None,
+    ///         frag_data_locations: &[],
+    ///     }).unwrap();
+    ///
+    /// let program = loop {
+    ///     if let Some(program) = pending.poll() {
+    ///         break program.unwrap();
+    ///     }
+    ///
+    ///     // draw the loading screen
+    /// };
+    /// ```
+    pub fn new_async<'a, F: ?Sized, I>(facade: &F, input: I)
+                                       -> Result<PendingProgram, ProgramCreationError>
+                                       where I: Into<ProgramCreationInput<'a>>, F: Facade
+    {
+        let input = input.into();
 
-                (RawProgram::from_shaders(facade, &shaders_store, has_geometry_shader,
-                                               has_tessellation_control_shader, has_tessellation_evaluation_shader,
-                                               transform_feedback_varyings)?,
-                 outputs_srgb, uses_point_size)
+        let state = match input {
+            input @ ProgramCreationInput::SourceCode { .. } => {
+                let _lock = lock_compiler(facade);
+                PendingProgramState::Compiling(CompilingProgram::start(facade, input)?)
             },
+            input @ ProgramCreationInput::Binary { .. } => {
+                PendingProgramState::Done(Program::new(facade, input))
+            },
+        };
 
-            ProgramCreationInput::Binary { data, outputs_srgb, uses_point_size } => {
-                if uses_point_size && !(facade.get_context().get_version() >= &Version(Api::Gl, 3, 0)) {
-                    return Err(ProgramCreationError::PointSizeNotSupported);
-                }
+        Ok(PendingProgram {
+            context: facade.get_context().clone(),
+            state: Some(state),
+        })
+    }
 
-                (RawProgram::from_binary(facade, data)?, outputs_srgb, uses_point_size)
-            },
+    /// Recompiles and relinks the program from a new source, in place.
+    ///
+    /// This is useful in order to reload shaders while the application is running. On success,
+    /// the old OpenGL program is destroyed and this `Program` uses the new one from now on. On
+    /// failure, the error is returned and the program is left untouched and can still be used.
+    ///
+    /// The other parameters that the program has been created with, like the transform feedback
+    /// varyings or whether it outputs sRGB, are kept. The uniforms that have been passed to
+    /// `set_persistent_uniforms` keep their value if the new program has a uniform with the same
+    /// name and type. Programs that have been created from a binary are linked without transform
+    /// feedback varyings, geometry shader parameters and fragment output locations.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # let display: glium::Display = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// # let program: glium::Program = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// # let vertex_source = ""; let fragment_source = "";
+    /// let source = glium::program::SourceCode {
+    ///     vertex_shader: vertex_source,
+    ///     tessellation_control_shader: None,
+    ///     tessellation_evaluation_shader: None,
+    ///     geometry_shader: None,
+    ///     fragment_shader: fragment_source,
+    /// };
+    ///
+    /// if let Err(err) = program.reload(source) {
+    ///     println!("Failed to reload the program: {}", err);
+    /// }
+    /// ```
+    pub fn reload(&self, source: SourceCode<'_>) -> Result<(), ProgramCreationError> {
+        let context = self.raw().get_context().clone();
+
+        let input = ProgramCreationInput::SourceCode {
+            vertex_shader: source.vertex_shader,
+            tessellation_control_shader: source.tessellation_control_shader,
+            tessellation_evaluation_shader: source.tessellation_evaluation_shader,
+            geometry_shader: source.geometry_shader,
+            fragment_shader: source.fragment_shader,
+            transform_feedback_varyings: self.link_parameters.transform_feedback_varyings.clone(),
+            outputs_srgb: self.outputs_srgb,
+            uses_point_size: self.uses_point_size,
+            geometry_shader_parameters: self.link_parameters.geometry_shader_parameters,
+            frag_data_locations: &self.link_parameters.frag_data_locations,
         };
-        Ok(Program {
-            raw,
+
+        let new_raw = {
+            let _lock = lock_compiler(&context);
+            CompilingProgram::start(&context, input)?.link(&context)?
+        };
+
+        let mut raws = self.raws.borrow_mut();
+
+        {
+            let old_raw = raws.last().unwrap();
+            let mut ctxt = context.make_current();
+            new_raw.use_program(&mut ctxt);
+
+            for name in self.persistent_uniforms.borrow().iter() {
+                let (old_uniform, new_uniform) = match (old_raw.get_uniform(name),
+                                                        new_raw.get_uniform(name))
+                {
+                    (Some(old), Some(new)) if old.ty == new.ty => (old, new),
+                    _ => continue,
+                };
+
+                if let Some(value) = old_raw.get_uniform_value(old_uniform.location) {
+                    new_raw.set_uniform(&mut ctxt, new_uniform.location, &value);
+                }
+            }
+        }
+
+        raws.last().unwrap().destroy();
+        raws.push(Rc::new(new_raw));
+        Ok(())
+    }
+
+    /// Builds a program around a linked `RawProgram`.
+    fn from_raw(raw: RawProgram, outputs_srgb: bool, uses_point_size: bool,
+                link_parameters: LinkParameters) -> Program
+    {
+        Program {
+            raws: RefCell::new(vec![Rc::new(raw)]),
             outputs_srgb,
             uses_point_size,
-        })
+            link_parameters,
+            persistent_uniforms: RefCell::new(HashSet::new()),
+        }
+    }
+
+    /// Returns the program that is currently used.
+    #[inline]
+    fn raw(&self) -> &RawProgram {
+        let raw: *const RawProgram = &**self.raws.borrow().last().unwrap();
+
+        // the programs are only dropped with `self`, and moving an `Rc` doesn't move its content
+        unsafe { &*raw }
     }
 
     /// Builds a new program from GLSL source code.
@@ -127,6 +257,13 @@ impl Program {
     /// - `fragment_shader`: Source code of the fragment shader.
     /// - `geometry_shader`: Source code of the geometry shader.
     ///
+    /// The fragment shader can be an empty string, in which case the program doesn't have
+    /// one. This is useful for programs that only write to the depth buffer, like depth
+    /// prepasses and shadow maps, or whose output is captured with transform feedback and
+    /// `draw_primitives` disabled. Drawing with such a program leaves the color buffers in an
+    /// undefined state. OpenGL ES requires programs to have a fragment shader, and
+    /// `FragmentShaderRequired` is returned there.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -150,6 +287,8 @@ impl Program {
             transform_feedback_varyings: None,
             outputs_srgb: false,
             uses_point_size: false,
+            geometry_shader_parameters: None,
+            frag_data_locations: &[],
         })
     }
 
@@ -159,7 +298,80 @@ impl Program {
     /// the source code every time.
     #[inline]
     pub fn get_binary(&self) -> Result<Binary, GetBinaryError> {
-        self.raw.get_binary()
+        self.raw().get_binary()
+    }
+
+    /// Checks whether the program can be executed given the current OpenGL state, by calling
+    /// `glValidateProgram`. On failure, returns the validation log of the program.
+    ///
+    /// Note that the uniforms and textures of the program are only bound when drawing, so
+    /// the state being validated is the one left by the previous draw command. After calling
+    /// `Context::set_program_validation(true)`, glium automatically validates the program before
+    /// each draw command and returns `DrawError::ProgramValidationFailed` on failure.
+    ///
+    /// Validating a program is slow and should only be used for debugging purposes.
+    #[inline]
+    pub fn validate(&self) -> Result<(), String> {
+        self.raw().validate()
+    }
+
+    /// Asks the driver to finish preparing the program, so that the first draw command that
+    /// uses it doesn't stutter.
+    ///
+    /// Many drivers only finish compiling a program the first time it is bound, which can cause
+    /// a noticeable hitch in the middle of the application. This function binds the program,
+    /// validates it and retrieves its binary if possible, which is enough to force the
+    /// compilation on most drivers. Call it at loading time, for example right after creating
+    /// the program.
+    ///
+    /// Whether this has any effect is driver-dependent. Some drivers also recompile the program
+    /// when it is used with a different state (the blending function or the format of the
+    /// framebuffer, for example), which this function can't predict.
+    pub fn warm_up(&self) {
+        {
+            let mut ctxt = self.raw().get_context().make_current();
+            self.raw().use_program(&mut ctxt);
+        }
+
+        let _ = self.raw().validate();
+
+        if is_binary_supported(self.raw().get_context()) {
+            let _ = self.raw().get_binary();
+        }
+    }
+
+    /// Sets the value of some uniforms of the program, which stay set until they are changed.
+    ///
+    /// The values of the uniforms are stored in the program itself, and a draw call only
+    /// updates the uniforms that it is passed. Values that are the same for many draw calls,
+    /// like the camera matrices or the current time, can therefore be set once with this
+    /// function and then omitted from the uniforms passed when drawing.
+    ///
+    /// Only regular values can be set this way. Textures, buffers and subroutines are bound
+    /// to shared binding points which are reassigned at each draw call, and passing one of them
+    /// returns `DrawError::UniformNotPersistent`. Uniforms that don't exist in the program
+    /// are ignored.
+    ///
+    /// ```no_run
+    /// # #[macro_use] extern crate glium;
+    /// # fn main() {
+    /// # let program: glium::Program = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// program.set_persistent_uniforms(&uniform! {
+    ///     view: [[1.0, 0.0, 0.0, 0.0], [0.0, 1.0, 0.0, 0.0], [0.0, 0.0, 1.0, 0.0], [0.0, 0.0, 0.0, 1.0f32]],
+    ///     time: 12.5f32,
+    /// }).unwrap();
+    /// # }
+    /// ```
+    pub fn set_persistent_uniforms<U>(&self, uniforms: &U) -> Result<(), DrawError>
+                                      where U: Uniforms
+    {
+        let mut ctxt = self.raw().get_context().make_current();
+        self.use_program(&mut ctxt);
+        uniforms.set_persistent_uniforms(&mut ctxt, self)?;
+
+        let mut names = self.persistent_uniforms.borrow_mut();
+        uniforms.visit_values(|name, _| { names.insert(name.to_owned()); });
+        Ok(())
     }
 
     /// Returns the *location* of an output fragment, if it exists.
@@ -175,13 +387,72 @@ impl Program {
     ///
     #[inline]
     pub fn get_frag_data_location(&self, name: &str) -> Option<u32> {
-        self.raw.get_frag_data_location(name)
+        self.raw().get_frag_data_location(name)
     }
 
     /// Returns informations about a uniform variable, if it exists.
     #[inline]
     pub fn get_uniform(&self, name: &str) -> Option<&Uniform> {
-        self.raw.get_uniform(name)
+        self.raw().get_uniform(name)
+    }
+
+    /// Returns the number of elements of a uniform array, if it exists.
+    ///
+    /// This works for arrays of basic types like `uniform vec3 positions[8];` as well as for
+    /// arrays of structs like `uniform Light lights[8];`. The length is deduced from the active
+    /// uniforms of the program, therefore the elements at the end of the array that aren't used
+    /// by the shaders may have been optimized away by the driver and are not counted.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # let program: glium::Program = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// if let Some(len) = program.get_uniform_array_len("lights") {
+    ///     println!("The program uses {} lights", len);
+    /// }
+    /// ```
+    pub fn get_uniform_array_len(&self, name: &str) -> Option<usize> {
+        self.raw().uniforms()
+                .filter_map(|(uniform_name, _)| {
+                    let index = uniform_name.strip_prefix(name)?.strip_prefix('[')?;
+                    let end = index.find(']')?;
+                    index[.. end].parse::<usize>().ok()
+                })
+                .max()
+                .map(|index| index + 1)
+    }
+
+    /// Reads the current value of a uniform from the program object, which is the value that
+    /// the GPU uses.
+    ///
+    /// This is mostly useful for debugging, for example to display the values of the uniforms
+    /// of a program. The values of vectors are returned in order, and the values of matrices are
+    /// returned column by column. For arrays, only the first element is returned. Integers and
+    /// booleans are converted to floats.
+    ///
+    /// Returns `None` if the uniform doesn't exist, or if its type is a double or a 64-bits
+    /// integer. This function needs to synchronize with the GPU, and shouldn't be called at
+    /// each frame.
+    #[inline]
+    pub fn get_uniform_f32(&self, name: &str) -> Option<Vec<f32>> {
+        self.raw().get_uniform_f32(name)
+    }
+
+    /// Same as `get_uniform_f32`, but returns signed integers.
+    ///
+    /// For samplers and images, the value is the texture or image unit that the uniform uses.
+    #[inline]
+    pub fn get_uniform_i32(&self, name: &str) -> Option<Vec<i32>> {
+        self.raw().get_uniform_i32(name)
+    }
+
+    /// Same as `get_uniform_f32`, but returns unsigned integers.
+    ///
+    /// Also returns `None` if the backend doesn't support OpenGL 3.0, OpenGL ES 3.0 or
+    /// `GL_EXT_gpu_shader4`.
+    #[inline]
+    pub fn get_uniform_u32(&self, name: &str) -> Option<Vec<u32>> {
+        self.raw().get_uniform_u32(name)
     }
 
     /// Returns an iterator to the list of uniforms.
@@ -196,7 +467,19 @@ impl Program {
     /// ```
     #[inline]
     pub fn uniforms(&self) -> hash_map::Iter<'_, String, Uniform> {
-        self.raw.uniforms()
+        self.raw().uniforms()
+    }
+
+    /// Returns the name of a uniform of the program that has never been assigned a value and
+    /// that isn't in `allowed`.
+    ///
+    /// The members of uniform blocks don't have a location and are ignored, since their value
+    /// comes from the buffer bound to the block.
+    pub(crate) fn find_unset_uniform(&self, allowed: &HashSet<String>) -> Option<&str> {
+        self.raw().uniforms()
+                .find(|&(name, uniform)| uniform.location >= 0 && !allowed.contains(name) &&
+                                         !self.raw().is_uniform_set(uniform.location))
+                .map(|(name, _)| &name[..])
     }
 
     /// Returns a list of uniform blocks.
@@ -212,13 +495,35 @@ impl Program {
     #[inline]
     pub fn get_uniform_blocks(&self)
                               -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
-        self.raw.get_uniform_blocks()
+        self.raw().get_uniform_blocks()
+    }
+
+    /// Returns the members of a uniform block with their offset, array stride and matrix
+    /// stride, as reported by the OpenGL implementation.
+    ///
+    /// This is useful to check that the layout of the Rust struct that you put in a
+    /// `UniformBuffer` matches the layout of the block, which is easy to get wrong with the
+    /// padding rules of `std140`. The members are sorted by offset.
+    ///
+    /// Returns `None` if the block doesn't exist.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # let program: glium::Program = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// for member in program.get_uniform_block_members("MyBlock").unwrap() {
+    ///     println!("{} at offset {}", member.name, member.offset);
+    /// }
+    /// ```
+    #[inline]
+    pub fn get_uniform_block_members(&self, name: &str) -> Option<Vec<BlockMember>> {
+        self.raw().get_uniform_block_members(name)
     }
 
     /// Returns the list of transform feedback varyings.
     #[inline]
     pub fn get_transform_feedback_buffers(&self) -> &[TransformFeedbackBuffer] {
-        self.raw.get_transform_feedback_buffers()
+        self.raw().get_transform_feedback_buffers()
     }
 
     /// True if the transform feedback output of this program matches the specified `VertexFormat`
@@ -227,7 +532,7 @@ impl Program {
     /// The `stride` is the number of bytes between two vertices.
     #[inline]
     pub fn transform_feedback_matches(&self, format: &VertexFormat, stride: usize) -> bool {
-        self.raw.transform_feedback_matches(format, stride)
+        self.raw().transform_feedback_matches(format, stride)
     }
 
     /// Returns the type of geometry that transform feedback would generate, or `None` if it
@@ -237,37 +542,48 @@ impl Program {
     /// contain either a geometry shader or a tessellation evaluation shader, returns `None`.
     #[inline]
     pub fn get_output_primitives(&self) -> Option<OutputPrimitives> {
-        self.raw.get_output_primitives()
+        self.raw().get_output_primitives()
+    }
+
+    /// Returns the type of primitives that the geometry shader expects as input, or `None` if
+    /// the program doesn't contain a geometry shader or if the implementation reported an
+    /// unknown type.
+    ///
+    /// This corresponds to `GL_GEOMETRY_INPUT_TYPE`, or `GL_GEOMETRY_INPUT_TYPE_ARB` with
+    /// `GL_ARB_geometry_shader4`.
+    #[inline]
+    pub fn get_geometry_input_primitives(&self) -> Option<InputPrimitives> {
+        self.raw().get_geometry_input_primitives()
     }
 
     /// Returns true if the program contains a tessellation stage.
     #[inline]
     pub fn has_tessellation_shaders(&self) -> bool {
-        self.raw.has_tessellation_shaders()
+        self.raw().has_tessellation_shaders()
     }
 
     /// Returns true if the program contains a tessellation control stage.
     #[inline]
     pub fn has_tessellation_control_shader(&self) -> bool {
-        self.raw.has_tessellation_control_shader()
+        self.raw().has_tessellation_control_shader()
     }
 
     /// Returns true if the program contains a tessellation evaluation stage.
     #[inline]
     pub fn has_tessellation_evaluation_shader(&self) -> bool {
-        self.raw.has_tessellation_evaluation_shader()
+        self.raw().has_tessellation_evaluation_shader()
     }
 
     /// Returns true if the program contains a geometry shader.
     #[inline]
     pub fn has_geometry_shader(&self) -> bool {
-        self.raw.has_geometry_shader()
+        self.raw().has_geometry_shader()
     }
 
     /// Returns informations about an attribute, if it exists.
     #[inline]
     pub fn get_attribute(&self, name: &str) -> Option<&Attribute> {
-        self.raw.get_attribute(name)
+        self.raw().get_attribute(name)
     }
 
     /// Returns an iterator to the list of attributes.
@@ -282,7 +598,7 @@ impl Program {
     /// ```
     #[inline]
     pub fn attributes(&self) -> hash_map::Iter<'_, String, Attribute> {
-        self.raw.attributes()
+        self.raw().attributes()
     }
 
     /// Returns true if the program has been configured to output sRGB instead of RGB.
@@ -304,7 +620,7 @@ impl Program {
     #[inline]
     pub fn get_shader_storage_blocks(&self)
             -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
-        self.raw.get_shader_storage_blocks()
+        self.raw().get_shader_storage_blocks()
     }
 
     /// Returns the list of shader storage blocks.
@@ -320,7 +636,7 @@ impl Program {
     #[inline]
     pub fn get_atomic_counters(&self)
             -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
-        self.raw.get_atomic_counters()
+        self.raw().get_atomic_counters()
     }
 
     /// Returns the subroutine uniforms of this program.
@@ -338,7 +654,30 @@ impl Program {
     #[inline]
     pub fn get_subroutine_uniforms(&self)
             -> &HashMap<(String, ShaderStage), SubroutineUniform, BuildHasherDefault<FnvHasher>> {
-        &self.raw.get_subroutine_data().subroutine_uniforms
+        &self.raw().get_subroutine_data().subroutine_uniforms
+    }
+
+    /// Returns the index of the subroutine with the given name in the given shader stage.
+    ///
+    /// Returns `None` if no subroutine uniform of this stage is compatible with a subroutine of
+    /// this name.
+    ///
+    /// Note that you don't need this index to select a subroutine when drawing. Instead, pass
+    /// a `(name, stage)` tuple as the value of the subroutine uniform.
+    ///
+    /// ## Example
+    ///
+    /// ```no_run
+    /// # let program: glium::Program = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// use glium::program::ShaderStage;
+    /// let index = program.get_subroutine_index(ShaderStage::Fragment, "ColorRed");
+    /// ```
+    pub fn get_subroutine_index(&self, stage: ShaderStage, name: &str) -> Option<u32> {
+        self.raw().get_subroutine_data().subroutine_uniforms.iter()
+            .filter(|&(&(_, uniform_stage), _)| uniform_stage == stage)
+            .flat_map(|(_, uniform)| uniform.compatible_subroutines.iter())
+            .find(|subroutine| subroutine.name == name)
+            .map(|subroutine| subroutine.index)
     }
 
     /// Returns true if the program has been configured to use the `gl_PointSize` variable.
@@ -351,10 +690,213 @@ impl Program {
     }
 }
 
+/// A program whose shaders are still being compiled.
+///
+/// Created with `Program::new_async`.
+pub struct PendingProgram {
+    context: Rc<Context>,
+    state: Option<PendingProgramState>,
+}
+
+enum PendingProgramState {
+    Compiling(CompilingProgram),
+    Done(Result<Program, ProgramCreationError>),
+}
+
+impl PendingProgram {
+    /// Returns the program if the compilation is over, or `None` if it is still in progress.
+    ///
+    /// # Panic
+    ///
+    /// The program is only returned once. Panics if this function is called again after it has
+    /// returned `Some`.
+    pub fn poll(&mut self) -> Option<Result<Program, ProgramCreationError>> {
+        match self.state.take().expect("The program has already been returned by `poll`") {
+            PendingProgramState::Compiling(compiling) => {
+                if !compiling.is_compiled() {
+                    self.state = Some(PendingProgramState::Compiling(compiling));
+                    return None;
+                }
+
+                let _lock = lock_compiler(&self.context);
+                Some(compiling.finish(&self.context))
+            },
+            PendingProgramState::Done(result) => Some(result),
+        }
+    }
+}
+
+impl fmt::Debug for PendingProgram {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        let ready = match self.state {
+            Some(PendingProgramState::Compiling(ref compiling)) => compiling.is_compiled(),
+            _ => true,
+        };
+
+        formatter.debug_struct("PendingProgram").field("ready", &ready).finish()
+    }
+}
+
+/// Shaders whose compilation has been started, and everything needed to link them.
+struct CompilingProgram {
+    shaders: Vec<Shader>,
+    has_geometry_shader: bool,
+    has_tessellation_control_shader: bool,
+    has_tessellation_evaluation_shader: bool,
+    transform_feedback_varyings: Option<(Vec<String>, TransformFeedbackMode)>,
+    geometry_shader_parameters: Option<GeometryShaderParameters>,
+    frag_data_locations: Vec<(String, u32)>,
+    outputs_srgb: bool,
+    uses_point_size: bool,
+}
+
+impl CompilingProgram {
+    /// Checks the parameters and starts compiling the shaders.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the input isn't `ProgramCreationInput::SourceCode`.
+    fn start<F: ?Sized>(facade: &F, input: ProgramCreationInput<'_>)
+                        -> Result<CompilingProgram, ProgramCreationError> where F: Facade
+    {
+        let (vertex_shader, tessellation_control_shader, tessellation_evaluation_shader,
+             geometry_shader, fragment_shader, transform_feedback_varyings, outputs_srgb,
+             uses_point_size, geometry_shader_parameters, frag_data_locations) = match input
+        {
+            ProgramCreationInput::SourceCode { vertex_shader, tessellation_control_shader,
+                                               tessellation_evaluation_shader, geometry_shader,
+                                               fragment_shader, transform_feedback_varyings,
+                                               outputs_srgb, uses_point_size,
+                                               geometry_shader_parameters,
+                                               frag_data_locations } =>
+            {
+                (vertex_shader, tessellation_control_shader, tessellation_evaluation_shader,
+                 geometry_shader, fragment_shader, transform_feedback_varyings, outputs_srgb,
+                 uses_point_size, geometry_shader_parameters, frag_data_locations)
+            },
+            ProgramCreationInput::Binary { .. } => unreachable!(),
+        };
+
+        let mut has_geometry_shader = false;
+        let mut has_tessellation_control_shader = false;
+        let mut has_tessellation_evaluation_shader = false;
+
+        let mut shaders = vec![(vertex_shader, ShaderType::Vertex)];
+
+        // an empty fragment shader means that there is no fragment shader, which only desktop
+        // OpenGL allows
+        if !fragment_shader.trim().is_empty() {
+            shaders.push((fragment_shader, ShaderType::Fragment));
+        } else if facade.get_context().get_version().0 == Api::GlEs {
+            return Err(ProgramCreationError::FragmentShaderRequired);
+        }
+
+        if let Some(gs) = geometry_shader {
+            shaders.push((gs, ShaderType::Geometry));
+            has_geometry_shader = true;
+        }
+
+        if let Some(ts) = tessellation_control_shader {
+            shaders.push((ts, ShaderType::TesselationControl));
+            has_tessellation_control_shader = true;
+        }
+
+        if let Some(ts) = tessellation_evaluation_shader {
+            shaders.push((ts, ShaderType::TesselationEvaluation));
+            has_tessellation_evaluation_shader = true;
+        }
+
+        // TODO: move somewhere else
+        if transform_feedback_varyings.is_some() &&
+            !(facade.get_context().get_version() >= &Version(Api::Gl, 3, 0)) &&
+            !facade.get_context().get_extensions().gl_ext_transform_feedback
+        {
+            return Err(ProgramCreationError::TransformFeedbackNotSupported);
+        }
+
+        if uses_point_size && !(facade.get_context().get_version() >= &Version(Api::Gl, 3, 0)) {
+            return Err(ProgramCreationError::PointSizeNotSupported);
+        }
+
+        if let Some(ref params) = geometry_shader_parameters {
+            if let PrimitiveType::Patches { .. } = params.input_type {
+                return Err(ProgramCreationError::InvalidGeometryShaderParameters);
+            }
+
+            if params.output_type == OutputPrimitives::Quads {
+                return Err(ProgramCreationError::InvalidGeometryShaderParameters);
+            }
+        }
+
+        if !frag_data_locations.is_empty() &&
+            !(facade.get_context().get_version() >= &Version(Api::Gl, 3, 0)) &&
+            !facade.get_context().get_extensions().gl_ext_gpu_shader4
+        {
+            return Err(ProgramCreationError::FragDataLocationNotSupported);
+        }
+
+        // all the shaders are submitted before checking any of them, so that drivers
+        // that support parallel compilation can compile them at the same time
+        let mut shaders_store = Vec::with_capacity(shaders.len());
+        for (src, ty) in shaders.into_iter() {
+            shaders_store.push(start_shader(facade, ty.to_opengl_type(), src)?);
+        }
+
+        Ok(CompilingProgram {
+            shaders: shaders_store,
+            has_geometry_shader,
+            has_tessellation_control_shader,
+            has_tessellation_evaluation_shader,
+            transform_feedback_varyings,
+            geometry_shader_parameters,
+            frag_data_locations: frag_data_locations.to_vec(),
+            outputs_srgb,
+            uses_point_size,
+        })
+    }
+
+    /// Returns true if all the shaders have finished compiling.
+    fn is_compiled(&self) -> bool {
+        self.shaders.iter().all(is_shader_compiled)
+    }
+
+    /// Waits for the shaders to be compiled, checks them and links them.
+    fn link<F: ?Sized>(&mut self, facade: &F) -> Result<RawProgram, ProgramCreationError>
+                       where F: Facade
+    {
+        let mut shaders_store = Vec::with_capacity(self.shaders.len());
+        for shader in self.shaders.drain(..) {
+            shaders_store.push(check_shader(shader)?);
+        }
+
+        RawProgram::from_shaders(facade, &shaders_store, self.has_geometry_shader,
+                                 self.has_tessellation_control_shader,
+                                 self.has_tessellation_evaluation_shader,
+                                 self.transform_feedback_varyings.clone(),
+                                 self.geometry_shader_parameters,
+                                 &self.frag_data_locations)
+    }
+
+    /// Waits for the shaders to be compiled, checks them and links the program.
+    fn finish<F: ?Sized>(mut self, facade: &F) -> Result<Program, ProgramCreationError>
+                         where F: Facade
+    {
+        let raw = self.link(facade)?;
+
+        let link_parameters = LinkParameters {
+            transform_feedback_varyings: self.transform_feedback_varyings,
+            geometry_shader_parameters: self.geometry_shader_parameters,
+            frag_data_locations: self.frag_data_locations,
+        };
+
+        Ok(Program::from_raw(raw, self.outputs_srgb, self.uses_point_size, link_parameters))
+    }
+}
+
 impl fmt::Debug for Program {
     #[inline]
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
-        write!(formatter, "{:?}", self.raw)
+        write!(formatter, "{:?}", self.raw())
     }
 }
 
@@ -363,7 +905,7 @@ impl GlObject for Program {
 
     #[inline]
     fn get_id(&self) -> Handle {
-        self.raw.get_id()
+        self.raw().get_id()
     }
 }
 
@@ -387,21 +929,21 @@ impl ProgramExt for Program {
             }
         }
 
-        self.raw.use_program(ctxt)
+        self.raw().use_program(ctxt)
     }
 
     #[inline]
     fn set_uniform(&self, ctxt: &mut CommandContext<'_>, uniform_location: gl::types::GLint,
                    value: &RawUniformValue)
     {
-        self.raw.set_uniform(ctxt, uniform_location, value)
+        self.raw().set_uniform(ctxt, uniform_location, value)
     }
 
     #[inline]
     fn set_uniform_block_binding(&self, ctxt: &mut CommandContext<'_>, block_location: gl::types::GLuint,
                                  value: gl::types::GLuint)
     {
-        self.raw.set_uniform_block_binding(ctxt, block_location, value)
+        self.raw().set_uniform_block_binding(ctxt, block_location, value)
     }
 
     #[inline]
@@ -409,7 +951,7 @@ impl ProgramExt for Program {
                                         block_location: gl::types::GLuint,
                                         value: gl::types::GLuint)
     {
-        self.raw.set_shader_storage_block_binding(ctxt, block_location, value)
+        self.raw().set_shader_storage_block_binding(ctxt, block_location, value)
     }
 
     #[inline]
@@ -417,33 +959,112 @@ impl ProgramExt for Program {
                                          stage: ShaderStage,
                                          indices: &[gl::types::GLuint])
     {
-        self.raw.set_subroutine_uniforms_for_stage(ctxt, stage, indices);
+        self.raw().set_subroutine_uniforms_for_stage(ctxt, stage, indices);
     }
 
     #[inline]
     fn get_uniform(&self, name: &str) -> Option<&Uniform> {
-        self.raw.get_uniform(name)
+        self.raw().get_uniform(name)
     }
 
     #[inline]
     fn get_uniform_blocks(&self) -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
-        self.raw.get_uniform_blocks()
+        self.raw().get_uniform_blocks()
     }
 
     #[inline]
     fn get_shader_storage_blocks(&self)
                                  -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
-        self.raw.get_shader_storage_blocks()
+        self.raw().get_shader_storage_blocks()
     }
 
     #[inline]
     fn get_atomic_counters(&self)
                                  -> &HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>> {
-        self.raw.get_atomic_counters()
+        self.raw().get_atomic_counters()
     }
 
     #[inline]
     fn get_subroutine_data(&self) -> &SubroutineData {
-        self.raw.get_subroutine_data()
+        self.raw().get_subroutine_data()
+    }
+
+    #[inline]
+    fn validate_program(&self, ctxt: &mut CommandContext<'_>) -> Result<(), String> {
+        self.raw().validate_program(ctxt)
+    }
+}
+
+#[cfg(all(test, feature = "null_backend"))]
+mod tests {
+    use crate::backend::null::{build_context, build_program, NullBackend};
+    use crate::gl;
+    use crate::program::{Program, SourceCode};
+    use crate::uniforms::{UniformType, UniformsStorage};
+
+    #[test]
+    #[should_panic(expected = "already been returned")]
+    fn poll_after_done() {
+        let (_backend, context) = build_context(NullBackend::new());
+        let mut pending = Program::new_async(&context, SourceCode {
+            vertex_shader: "
+                #version 100
+                void main() {
+                    gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+                }
+            ",
+            tessellation_control_shader: None,
+            tessellation_evaluation_shader: None,
+            geometry_shader: None,
+            fragment_shader: "
+                #version 100
+                void main() {
+                    gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+                }
+            ",
+        }).unwrap();
+
+        // without parallel compilation, the program is ready immediately
+        pending.poll().unwrap().unwrap();
+        pending.poll();
+    }
+
+    #[test]
+    fn reload_keeps_persistent_uniforms() {
+        let backend = NullBackend::new();
+        backend.add_uniform("time", gl::FLOAT);
+
+        let (backend, context) = build_context(backend);
+        let program = build_program(&context);
+        program.set_persistent_uniforms(&UniformsStorage::new("time", 2.0f32)).unwrap();
+        let uniform = program.get_uniform("time").unwrap();
+
+        backend.clear_calls();
+        program.reload(SourceCode {
+            vertex_shader: "
+                #version 100
+                void main() {
+                    gl_Position = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+            tessellation_control_shader: None,
+            tessellation_evaluation_shader: None,
+            geometry_shader: None,
+            fragment_shader: "
+                #version 100
+                uniform float time;
+                void main() {
+                    gl_FragColor = vec4(time, 0.0, 0.0, 1.0);
+                }
+            ",
+        }).unwrap();
+
+        let calls = backend.get_calls();
+        assert!(calls.contains(&"glUniform1f"));
+        assert!(calls.contains(&"glDeleteProgram"));
+
+        // the references obtained before reloading stay valid
+        assert_eq!(uniform.ty, UniformType::Float);
+        assert!(program.get_uniform("time").is_some());
     }
 }
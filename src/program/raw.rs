@@ -12,7 +12,7 @@ use crate::UniformsExt;
 use std::{ffi, fmt};
 use std::collections::hash_map::{self, HashMap};
 use std::rc::Rc;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::os::raw;
 use std::hash::BuildHasherDefault;
 
@@ -23,6 +23,8 @@ use crate::GlObject;
 use crate::ProgramExt;
 use crate::Handle;
 use crate::RawUniformValue;
+use crate::uniforms::UniformType;
+use crate::index::PrimitiveType;
 
 use crate::QueryExt;
 use crate::draw_parameters::TimeElapsedQuery;
@@ -31,18 +33,20 @@ use crate::buffer::BufferSlice;
 use crate::BufferExt;
 use crate::BufferSliceExt;
 
-use crate::program::{ProgramCreationError, Binary, GetBinaryError};
+use crate::program::{ProgramCreationError, Binary, GetBinaryError, GeometryShaderParameters};
 use crate::program::uniforms_storage::UniformsStorage;
 
 use crate::program::compute::ComputeCommand;
-use crate::program::reflection::{Uniform, UniformBlock, OutputPrimitives};
+use crate::program::reflection::{Uniform, UniformBlock, BlockMember, OutputPrimitives, InputPrimitives};
 use crate::program::reflection::{Attribute, TransformFeedbackMode, TransformFeedbackBuffer};
 use crate::program::reflection::{SubroutineData, ShaderStage};
 use crate::program::reflection::{reflect_uniforms, reflect_attributes, reflect_uniform_blocks};
 use crate::program::reflection::{reflect_transform_feedback, reflect_geometry_output_type};
 use crate::program::reflection::{reflect_tess_eval_output_type, reflect_shader_storage_blocks};
-use crate::program::reflection::{reflect_subroutine_data};
+use crate::program::reflection::{reflect_subroutine_data, reflect_geometry_input_type};
+use crate::program::reflection::reflect_uniform_block_members;
 use crate::program::shader::Shader;
+use crate::program::diagnostics::decode_info_log;
 use crate::program::binary_header::{attach_glium_header, process_glium_header};
 
 use crate::uniforms::Uniforms;
@@ -54,6 +58,7 @@ use crate::vertex_array_object::VertexAttributesSystem;
 pub struct RawProgram {
     context: Rc<Context>,
     id: Handle,
+    destroyed: Cell<bool>,
     uniform_values: UniformsStorage,
     uniforms: HashMap<String, Uniform, BuildHasherDefault<FnvHasher>>,
     uniform_blocks: HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
@@ -64,6 +69,7 @@ pub struct RawProgram {
     ssbos: HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
     atomic_counters: HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>,
     output_primitives: Option<OutputPrimitives>,
+    geometry_input_primitives: Option<InputPrimitives>,
     has_geometry_shader: bool,
     has_tessellation_control_shader: bool,
     has_tessellation_evaluation_shader: bool,
@@ -75,7 +81,9 @@ impl RawProgram {
     pub fn from_shaders<'a, F: ?Sized, I>(facade: &'a F, shaders: I, has_geometry_shader: bool,
                                   has_tessellation_control_shader: bool,
                                   has_tessellation_evaluation_shader: bool,
-                                  transform_feedback: Option<(Vec<String>, TransformFeedbackMode)>)
+                                  transform_feedback: Option<(Vec<String>, TransformFeedbackMode)>,
+                                  geometry_shader_parameters: Option<GeometryShaderParameters>,
+                                  frag_data_locations: &[(String, u32)])
                                   -> Result<RawProgram, ProgramCreationError>
                                   where F: Facade, I: IntoIterator<Item = &'a Shader>
     {
@@ -139,6 +147,61 @@ impl RawProgram {
                 }
             }
 
+            // geometry shader parameters, only needed with `GL_ARB_geometry_shader4`
+            if has_geometry_shader && !(ctxt.version >= &Version(Api::Gl, 3, 2)) &&
+               !(ctxt.version >= &Version(Api::GlEs, 3, 2)) &&
+               ctxt.extensions.gl_arb_geometry_shader4
+            {
+                if let (Handle::Id(id), Some(params)) = (id, geometry_shader_parameters) {
+                    let input_type = match params.input_type {
+                        PrimitiveType::Points => gl::POINTS,
+                        PrimitiveType::LinesList | PrimitiveType::LineStrip |
+                        PrimitiveType::LineLoop => gl::LINES,
+                        PrimitiveType::LinesListAdjacency |
+                        PrimitiveType::LineStripAdjacency => gl::LINES_ADJACENCY_ARB,
+                        PrimitiveType::TrianglesList | PrimitiveType::TriangleStrip |
+                        PrimitiveType::TriangleFan => gl::TRIANGLES,
+                        PrimitiveType::TrianglesListAdjacency |
+                        PrimitiveType::TriangleStripAdjacency => gl::TRIANGLES_ADJACENCY_ARB,
+                        PrimitiveType::Patches { .. } => unreachable!(),   // checked in the frontend
+                    };
+
+                    let output_type = match params.output_type {
+                        OutputPrimitives::Points => gl::POINTS,
+                        OutputPrimitives::Lines => gl::LINE_STRIP,
+                        OutputPrimitives::Triangles => gl::TRIANGLE_STRIP,
+                        OutputPrimitives::Quads => unreachable!(),    // checked in the frontend
+                    };
+
+                    ctxt.gl.ProgramParameteriARB(id, gl::GEOMETRY_INPUT_TYPE_ARB,
+                                                 input_type as gl::types::GLint);
+                    ctxt.gl.ProgramParameteriARB(id, gl::GEOMETRY_OUTPUT_TYPE_ARB,
+                                                 output_type as gl::types::GLint);
+                    ctxt.gl.ProgramParameteriARB(id, gl::GEOMETRY_VERTICES_OUT_ARB,
+                                                 params.vertices_out as gl::types::GLint);
+                }
+            }
+
+            // locations of the fragment shader outputs
+            if !frag_data_locations.is_empty() {
+                let id = match id {
+                    Handle::Id(id) => id,
+                    Handle::Handle(_) => unreachable!()     // the API requires OpenGL 2.0
+                };
+
+                for &(ref name, location) in frag_data_locations.iter() {
+                    let name = ffi::CString::new(name.as_bytes()).unwrap();
+
+                    if ctxt.version >= &Version(Api::Gl, 3, 0) {
+                        ctxt.gl.BindFragDataLocation(id, location, name.as_ptr());
+                    } else if ctxt.extensions.gl_ext_gpu_shader4 {
+                        ctxt.gl.BindFragDataLocationEXT(id, location, name.as_ptr());
+                    } else {
+                        unreachable!();     // has been checked in the frontend
+                    }
+                }
+            }
+
             // linking
             {
                 ctxt.report_debug_output_errors.set(false);
@@ -183,9 +246,16 @@ impl RawProgram {
             None
         };
 
+        let geometry_input_primitives = if has_geometry_shader {
+            unsafe { reflect_geometry_input_type(&mut ctxt, id) }
+        } else {
+            None
+        };
+
         Ok(RawProgram {
             context: facade.get_context().clone(),
             id,
+            destroyed: Cell::new(false),
             uniforms,
             uniform_values: UniformsStorage::new(),
             uniform_blocks: blocks,
@@ -196,6 +266,7 @@ impl RawProgram {
             ssbos,
             atomic_counters,
             output_primitives,
+            geometry_input_primitives,
             has_geometry_shader,
             has_tessellation_control_shader,
             has_tessellation_evaluation_shader,
@@ -255,9 +326,16 @@ impl RawProgram {
             None
         };
 
+        let geometry_input_primitives = if has_geometry_shader {
+            unsafe { reflect_geometry_input_type(&mut ctxt, id) }
+        } else {
+            None
+        };
+
         Ok(RawProgram {
             context: facade.get_context().clone(),
             id,
+            destroyed: Cell::new(false),
             uniforms,
             uniform_values: UniformsStorage::new(),
             uniform_blocks: blocks,
@@ -268,6 +346,7 @@ impl RawProgram {
             ssbos,
             atomic_counters,
             output_primitives,
+            geometry_input_primitives,
             has_geometry_shader,
             has_tessellation_control_shader,
             has_tessellation_evaluation_shader,
@@ -316,6 +395,20 @@ impl RawProgram {
         }
     }
 
+    /// Returns the context this program belongs to.
+    #[inline]
+    pub fn get_context(&self) -> &Rc<Context> {
+        &self.context
+    }
+
+    /// Checks whether the program can be executed given the current OpenGL state.
+    ///
+    /// On failure, returns the validation log of the program.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut ctxt = self.context.make_current();
+        self.validate_program(&mut ctxt)
+    }
+
     /// Returns the *location* of an output fragment, if it exists.
     ///
     /// The *location* is low-level information that is used internally by glium.
@@ -382,6 +475,165 @@ impl RawProgram {
         self.uniforms.iter()
     }
 
+    /// Reads the current value of a uniform from the program object, as floats.
+    ///
+    /// See `Program::get_uniform_f32`.
+    pub fn get_uniform_f32(&self, name: &str) -> Option<Vec<f32>> {
+        let (location, components) = self.uniform_location_and_components(name)?;
+        let mut result = vec![0.0; components];
+
+        let ctxt = self.context.make_current();
+        unsafe {
+            match self.id {
+                Handle::Id(id) => ctxt.gl.GetUniformfv(id, location, result.as_mut_ptr()),
+                Handle::Handle(id) => ctxt.gl.GetUniformfvARB(id, location, result.as_mut_ptr()),
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Reads the current value of a uniform from the program object, as signed integers.
+    ///
+    /// See `Program::get_uniform_i32`.
+    pub fn get_uniform_i32(&self, name: &str) -> Option<Vec<i32>> {
+        let (location, components) = self.uniform_location_and_components(name)?;
+        let mut result = vec![0; components];
+
+        let ctxt = self.context.make_current();
+        unsafe {
+            match self.id {
+                Handle::Id(id) => ctxt.gl.GetUniformiv(id, location, result.as_mut_ptr()),
+                Handle::Handle(id) => ctxt.gl.GetUniformivARB(id, location, result.as_mut_ptr()),
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Reads the current value of a uniform from the program object, as unsigned integers.
+    ///
+    /// See `Program::get_uniform_u32`.
+    pub fn get_uniform_u32(&self, name: &str) -> Option<Vec<u32>> {
+        let (location, components) = self.uniform_location_and_components(name)?;
+        let mut result = vec![0; components];
+
+        let ctxt = self.context.make_current();
+        unsafe {
+            match self.id {
+                Handle::Id(id) if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+                                  ctxt.version >= &Version(Api::GlEs, 3, 0) =>
+                {
+                    ctxt.gl.GetUniformuiv(id, location, result.as_mut_ptr())
+                },
+                Handle::Id(id) if ctxt.extensions.gl_ext_gpu_shader4 => {
+                    ctxt.gl.GetUniformuivEXT(id, location, result.as_mut_ptr())
+                },
+                _ => return None,
+            }
+        }
+
+        Some(result)
+    }
+
+    /// Returns the location of a uniform and the number of values that `glGetUniform*` writes
+    /// for it, or `None` if the uniform doesn't exist or can't be read this way.
+    ///
+    /// Members of uniform blocks don't have a location and can't be read with `glGetUniform*`.
+    fn uniform_location_and_components(&self, name: &str) -> Option<(gl::types::GLint, usize)> {
+        let uniform = self.uniforms.get(name)?;
+        if uniform.location < 0 {
+            return None;
+        }
+
+        let components = match uniform.ty {
+            UniformType::Float | UniformType::Int | UniformType::UnsignedInt |
+            UniformType::Bool => 1,
+            UniformType::FloatVec2 | UniformType::IntVec2 | UniformType::UnsignedIntVec2 |
+            UniformType::BoolVec2 => 2,
+            UniformType::FloatVec3 | UniformType::IntVec3 | UniformType::UnsignedIntVec3 |
+            UniformType::BoolVec3 => 3,
+            UniformType::FloatVec4 | UniformType::IntVec4 | UniformType::UnsignedIntVec4 |
+            UniformType::BoolVec4 | UniformType::FloatMat2 => 4,
+            UniformType::FloatMat2x3 | UniformType::FloatMat3x2 => 6,
+            UniformType::FloatMat2x4 | UniformType::FloatMat4x2 => 8,
+            UniformType::FloatMat3 => 9,
+            UniformType::FloatMat3x4 | UniformType::FloatMat4x3 => 12,
+            UniformType::FloatMat4 => 16,
+
+            // these would require `glGetUniformdv` or `glGetUniformi64vARB`
+            UniformType::Double | UniformType::DoubleVec2 | UniformType::DoubleVec3 |
+            UniformType::DoubleVec4 | UniformType::DoubleMat2 | UniformType::DoubleMat3 |
+            UniformType::DoubleMat4 | UniformType::DoubleMat2x3 | UniformType::DoubleMat2x4 |
+            UniformType::DoubleMat3x2 | UniformType::DoubleMat3x4 | UniformType::DoubleMat4x2 |
+            UniformType::DoubleMat4x3 | UniformType::Int64 | UniformType::Int64Vec2 |
+            UniformType::Int64Vec3 | UniformType::Int64Vec4 | UniformType::UnsignedInt64 |
+            UniformType::UnsignedInt64Vec2 | UniformType::UnsignedInt64Vec3 |
+            UniformType::UnsignedInt64Vec4 => return None,
+
+            // samplers and images, whose value is the texture or image unit
+            _ => 1,
+        };
+
+        Some((uniform.location, components))
+    }
+
+    /// Returns true if a value has been assigned to the uniform at this location, either during
+    /// a draw call or with `set_persistent_uniforms`.
+    #[inline]
+    pub fn is_uniform_set(&self, location: gl::types::GLint) -> bool {
+        self.uniform_values.is_uniform_value_set(location)
+    }
+
+    /// Returns the latest value assigned to the uniform at this location, either during a draw
+    /// call or with `set_persistent_uniforms`.
+    #[inline]
+    pub fn get_uniform_value(&self, location: gl::types::GLint) -> Option<RawUniformValue> {
+        self.uniform_values.get_uniform_value(location)
+    }
+
+    /// Destroys the OpenGL program. The reflection data stays available, but the program must
+    /// not be used for drawing anymore.
+    ///
+    /// Does nothing if the program has already been destroyed.
+    pub fn destroy(&self) {
+        if self.destroyed.replace(true) {
+            return;
+        }
+
+        let mut ctxt = self.context.make_current();
+
+        // removing VAOs which contain this program
+        VertexAttributesSystem::purge_program(&mut ctxt, self.id);
+
+        // sending the destroy command
+        unsafe {
+            match self.id {
+                Handle::Id(id) => {
+                    assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                            ctxt.version >= &Version(Api::GlEs, 2, 0));
+
+                    if ctxt.state.program == Handle::Id(id) {
+                        ctxt.gl.UseProgram(0);
+                        ctxt.state.program = Handle::Id(0);
+                    }
+
+                    ctxt.gl.DeleteProgram(id);
+                },
+                Handle::Handle(id) => {
+                    assert!(ctxt.extensions.gl_arb_shader_objects);
+
+                    if ctxt.state.program == Handle::Handle(id) {
+                        ctxt.gl.UseProgramObjectARB(0 as gl::types::GLhandleARB);
+                        ctxt.state.program = Handle::Handle(0 as gl::types::GLhandleARB);
+                    }
+
+                    ctxt.gl.DeleteObjectARB(id);
+                }
+            }
+        }
+    }
+
     /// Returns a list of uniform blocks.
     ///
     /// ## Example
@@ -398,6 +650,18 @@ impl RawProgram {
         &self.uniform_blocks
     }
 
+    /// Queries the members of a uniform block.
+    ///
+    /// See `Program::get_uniform_block_members`.
+    pub fn get_uniform_block_members(&self, name: &str) -> Option<Vec<BlockMember>> {
+        let block = self.uniform_blocks.get(name)?;
+
+        let mut ctxt = self.context.make_current();
+        Some(unsafe {
+            reflect_uniform_block_members(&mut ctxt, self.id, block.id as gl::types::GLuint)
+        })
+    }
+
     /// Returns the list of transform feedback varyings.
     #[inline]
     pub fn get_transform_feedback_buffers(&self) -> &[TransformFeedbackBuffer] {
@@ -451,6 +715,15 @@ impl RawProgram {
         self.output_primitives
     }
 
+    /// Returns the type of primitives that the geometry shader expects as input, or `None` if
+    /// the program doesn't contain a geometry shader.
+    ///
+    /// This corresponds to `GL_GEOMETRY_INPUT_TYPE`.
+    #[inline]
+    pub fn get_geometry_input_primitives(&self) -> Option<InputPrimitives> {
+        self.geometry_input_primitives
+    }
+
     /// Returns true if the program contains a tessellation stage.
     #[inline]
     pub fn has_tessellation_shaders(&self) -> bool {
@@ -701,44 +974,42 @@ impl ProgramExt for RawProgram {
     fn get_subroutine_data(&self) -> &SubroutineData {
         &self.subroutine_data
     }
-}
 
-impl Drop for RawProgram {
-    fn drop(&mut self) {
-        let mut ctxt = self.context.make_current();
-
-        // removing VAOs which contain this program
-        VertexAttributesSystem::purge_program(&mut ctxt, self.id);
-
-        // sending the destroy command
+    fn validate_program(&self, ctxt: &mut CommandContext<'_>) -> Result<(), String> {
         unsafe {
+            let mut status: gl::types::GLint = 0;
+
             match self.id {
                 Handle::Id(id) => {
                     assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
                             ctxt.version >= &Version(Api::GlEs, 2, 0));
-
-                    if ctxt.state.program == Handle::Id(id) {
-                        ctxt.gl.UseProgram(0);
-                        ctxt.state.program = Handle::Id(0);
-                    }
-
-                    ctxt.gl.DeleteProgram(id);
+                    ctxt.gl.ValidateProgram(id);
+                    ctxt.gl.GetProgramiv(id, gl::VALIDATE_STATUS, &mut status);
                 },
                 Handle::Handle(id) => {
                     assert!(ctxt.extensions.gl_arb_shader_objects);
-
-                    if ctxt.state.program == Handle::Handle(id) {
-                        ctxt.gl.UseProgramObjectARB(0 as gl::types::GLhandleARB);
-                        ctxt.state.program = Handle::Handle(0 as gl::types::GLhandleARB);
-                    }
-
-                    ctxt.gl.DeleteObjectARB(id);
+                    ctxt.gl.ValidateProgramARB(id);
+                    ctxt.gl.GetObjectParameterivARB(id, gl::OBJECT_VALIDATE_STATUS_ARB,
+                                                    &mut status);
                 }
             }
+
+            if status != 0 {
+                return Ok(());
+            }
+
+            Err(get_program_info_log(ctxt, self.id))
         }
     }
 }
 
+impl Drop for RawProgram {
+    #[inline]
+    fn drop(&mut self) {
+        self.destroy();
+    }
+}
+
 /// Builds an empty program from within the GL context.
 unsafe fn create_program(ctxt: &mut CommandContext<'_>) -> Handle {
     let id = if ctxt.version >= &Version(Api::Gl, 2, 0) ||
@@ -792,42 +1063,50 @@ unsafe fn check_program_link_errors(ctxt: &mut CommandContext<'_>, id: Handle)
             }
         };
 
-        let mut error_log_size: gl::types::GLint = 0;
+        return Err(LinkingError(get_program_info_log(ctxt, id)));
+    }
 
-        match id {
-            Handle::Id(id) => {
-                assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
-                    ctxt.version >= &Version(Api::GlEs, 2, 0));
-                ctxt.gl.GetProgramiv(id, gl::INFO_LOG_LENGTH, &mut error_log_size);
-            },
-            Handle::Handle(id) => {
-                assert!(ctxt.extensions.gl_arb_shader_objects);
-                ctxt.gl.GetObjectParameterivARB(id, gl::OBJECT_INFO_LOG_LENGTH_ARB,
-                                                &mut error_log_size);
-            }
-        }
+    Ok(())
+}
 
-        let mut error_log: Vec<u8> = Vec::with_capacity(error_log_size as usize);
+/// Reads the info log of a program.
+unsafe fn get_program_info_log(ctxt: &mut CommandContext<'_>, id: Handle) -> String {
+    let mut error_log_size: gl::types::GLint = 0;
 
-        match id {
-            Handle::Id(id) => {
-                assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
-                    ctxt.version >= &Version(Api::GlEs, 2, 0));
-                ctxt.gl.GetProgramInfoLog(id, error_log_size, &mut error_log_size,
-                                          error_log.as_mut_ptr() as *mut gl::types::GLchar);
-            },
-            Handle::Handle(id) => {
-                assert!(ctxt.extensions.gl_arb_shader_objects);
-                ctxt.gl.GetInfoLogARB(id, error_log_size, &mut error_log_size,
-                                      error_log.as_mut_ptr() as *mut gl::types::GLchar);
-            }
+    match id {
+        Handle::Id(id) => {
+            assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                ctxt.version >= &Version(Api::GlEs, 2, 0));
+            ctxt.gl.GetProgramiv(id, gl::INFO_LOG_LENGTH, &mut error_log_size);
+        },
+        Handle::Handle(id) => {
+            assert!(ctxt.extensions.gl_arb_shader_objects);
+            ctxt.gl.GetObjectParameterivARB(id, gl::OBJECT_INFO_LOG_LENGTH_ARB,
+                                            &mut error_log_size);
         }
+    }
 
-        error_log.set_len(error_log_size as usize);
+    // some drivers return 0 or even -1 when there is no log
+    let mut error_log_size = error_log_size.max(0);
+    let mut error_log: Vec<u8> = Vec::with_capacity(error_log_size as usize);
 
-        let msg = String::from_utf8(error_log).unwrap();
-        return Err(LinkingError(msg));
+    match id {
+        Handle::Id(id) => {
+            assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                ctxt.version >= &Version(Api::GlEs, 2, 0));
+            ctxt.gl.GetProgramInfoLog(id, error_log_size, &mut error_log_size,
+                                      error_log.as_mut_ptr() as *mut gl::types::GLchar);
+        },
+        Handle::Handle(id) => {
+            assert!(ctxt.extensions.gl_arb_shader_objects);
+            ctxt.gl.GetInfoLogARB(id, error_log_size, &mut error_log_size,
+                                  error_log.as_mut_ptr() as *mut gl::types::GLchar);
+        }
     }
 
-    Ok(())
+    // some drivers report more bytes than what the buffer can hold
+    let error_log_size = (error_log_size.max(0) as usize).min(error_log.capacity());
+    error_log.set_len(error_log_size);
+
+    decode_info_log(error_log)
 }
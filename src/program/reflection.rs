@@ -53,6 +53,38 @@ pub struct UniformBlock {
     pub layout: BlockLayout,
 }
 
+/// Information about a member of a uniform block, as reported by the OpenGL implementation.
+///
+/// This can be used to check that the layout of a Rust struct matches the layout that the
+/// program expects, for example when the block uses the `std140` layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockMember {
+    /// Name of the member, as it would be passed to `glGetUniformIndices`.
+    ///
+    /// Members of nested structs have names like `block_member.struct_member`, and arrays
+    /// have names ending with `[0]`.
+    pub name: String,
+
+    /// Type of the member. For arrays, this is the type of each element.
+    pub ty: UniformType,
+
+    /// Offset of this member in bytes from the start of the block.
+    pub offset: usize,
+
+    /// If it is an array, the number of elements.
+    pub size: Option<usize>,
+
+    /// If it is an array, the number of bytes between the start of two consecutive elements.
+    pub array_stride: Option<usize>,
+
+    /// If it is a matrix, the number of bytes between the start of two consecutive columns, or
+    /// two consecutive rows if `row_major` is true.
+    pub matrix_stride: Option<usize>,
+
+    /// True if it is a matrix that is stored row by row.
+    pub row_major: bool,
+}
+
 /// Layout of a shader storage buffer or a uniform buffer.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BlockLayout {
@@ -175,6 +207,21 @@ pub enum OutputPrimitives {
     Quads,
 }
 
+/// Type of primitives that a geometry shader expects as input.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InputPrimitives {
+    /// Points.
+    Points,
+    /// Lines.
+    Lines,
+    /// Lines with adjacency information.
+    LinesAdjacency,
+    /// Triangles.
+    Triangles,
+    /// Triangles with adjacency information.
+    TrianglesAdjacency,
+}
+
 /// Returns a list of uniforms and a list of atomic counters of a program.
 pub unsafe fn reflect_uniforms(ctxt: &mut CommandContext<'_>, program: Handle)
                                -> (HashMap<String, Uniform, BuildHasherDefault<FnvHasher>>, HashMap<String, UniformBlock, BuildHasherDefault<FnvHasher>>)
@@ -306,7 +353,8 @@ pub unsafe fn reflect_uniforms(ctxt: &mut CommandContext<'_>, program: Handle)
         }
 
         // We've got an array, first get the base of the name
-        let name_base = uniform.0.split('[').next().unwrap();
+        // only the last `[0]` is removed, as the array can be part of an array of structs
+        let name_base = &uniform.0[.. uniform.0.len() - 3];
         let uniform_base = uniform.1;
 
         // Go over all the elements in the array
@@ -528,6 +576,74 @@ pub unsafe fn reflect_uniform_blocks(ctxt: &mut CommandContext<'_>, program: Han
     blocks
 }
 
+/// Queries the list of members of a uniform block, sorted by offset.
+pub unsafe fn reflect_uniform_block_members(ctxt: &mut CommandContext<'_>, program: Handle,
+                                            block_id: gl::types::GLuint) -> Vec<BlockMember>
+{
+    let program = match program {
+        Handle::Id(id) => id,
+        _ => unreachable!()
+    };
+
+    // number of members
+    let mut num_members: gl::types::GLint = 0;
+    ctxt.gl.GetActiveUniformBlockiv(program, block_id, gl::UNIFORM_BLOCK_ACTIVE_UNIFORMS,
+                                    &mut num_members);
+
+    if num_members == 0 {
+        return Vec::new();
+    }
+
+    // indices of the members
+    let mut members_indices = vec![0; num_members as usize];
+    ctxt.gl.GetActiveUniformBlockiv(program, block_id, gl::UNIFORM_BLOCK_ACTIVE_UNIFORM_INDICES,
+                                    members_indices.as_mut_ptr() as *mut gl::types::GLint);
+
+    let query = |pname: gl::types::GLenum| {
+        let mut values = vec![0; num_members as usize];
+        ctxt.gl.GetActiveUniformsiv(program, num_members, members_indices.as_ptr(), pname,
+                                    values.as_mut_ptr());
+        values
+    };
+
+    let offsets = query(gl::UNIFORM_OFFSET);
+    let types = query(gl::UNIFORM_TYPE);
+    let sizes = query(gl::UNIFORM_SIZE);
+    let array_strides = query(gl::UNIFORM_ARRAY_STRIDE);
+    let matrix_strides = query(gl::UNIFORM_MATRIX_STRIDE);
+    let row_major = query(gl::UNIFORM_IS_ROW_MAJOR);
+    let name_lens = query(gl::UNIFORM_NAME_LENGTH);
+
+    let mut members = (0 .. num_members as usize).map(|i| {
+        let name = {
+            let mut name_tmp: Vec<u8> = Vec::with_capacity(1 + name_lens[i] as usize);
+            let mut name_len_tmp = name_lens[i];
+            ctxt.gl.GetActiveUniformName(program, members_indices[i], name_lens[i],
+                                         &mut name_len_tmp,
+                                         name_tmp.as_mut_ptr() as *mut gl::types::GLchar);
+            name_tmp.set_len(name_len_tmp as usize);
+            String::from_utf8(name_tmp).unwrap()
+        };
+
+        // the array stride is also reported for arrays of one element
+        let is_array = sizes[i] > 1 || name.ends_with(']');
+
+        BlockMember {
+            name,
+            ty: glenum_to_uniform_type(types[i] as gl::types::GLenum),
+            offset: offsets[i] as usize,
+            size: if is_array { Some(sizes[i] as usize) } else { None },
+            array_stride: if is_array { Some(array_strides[i] as usize) } else { None },
+            matrix_stride: if matrix_strides[i] > 0 { Some(matrix_strides[i] as usize) }
+                           else { None },
+            row_major: row_major[i] != 0,
+        }
+    }).collect::<Vec<_>>();
+
+    members.sort_by_key(|m| m.offset);
+    members
+}
+
 pub unsafe fn reflect_transform_feedback(ctxt: &mut CommandContext<'_>, program: Handle)
                                          -> Vec<TransformFeedbackBuffer>
 {
@@ -689,6 +805,56 @@ pub unsafe fn reflect_geometry_output_type(ctxt: &mut CommandContext<'_>, progra
     }
 }
 
+/// Obtains the type of data that the geometry shader stage expects as input.
+///
+/// # Unsafety
+///
+/// - `program` must be a valid handle to a program.
+/// - The program **must** contain a geometry shader.
+///
+/// Returns `None` if the query fails or if the implementation returns an unknown value.
+pub unsafe fn reflect_geometry_input_type(ctxt: &mut CommandContext<'_>, program: Handle)
+                                          -> Option<InputPrimitives>
+{
+    // OpenGL 3.2, OpenGL ES 3.2, `GL_EXT_geometry_shader` and `GL_OES_geometry_shader` link
+    // the input type from the layout qualifier and share the same enum ; with
+    // `GL_ARB_geometry_shader4` and `GL_EXT_geometry_shader4`, the input type has been set with
+    // `glProgramParameteri` and must be queried with another enum
+    let pname = if ctxt.version >= &Version(Api::Gl, 3, 2) ||
+                   ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+                   ctxt.extensions.gl_ext_geometry_shader ||
+                   ctxt.extensions.gl_oes_geometry_shader
+    {
+        gl::GEOMETRY_INPUT_TYPE
+    } else {
+        gl::GEOMETRY_INPUT_TYPE_ARB
+    };
+
+    // `GL_POINTS` is 0, so the value must not be left at 0 if the query fails
+    let mut value = -1;
+
+    match program {
+        Handle::Id(program) => {
+            assert!(ctxt.version >= &Version(Api::Gl, 2, 0) ||
+                    ctxt.version >= &Version(Api::GlEs, 2, 0));
+            ctxt.gl.GetProgramiv(program, pname, &mut value);
+        },
+        Handle::Handle(program) => {
+            assert!(ctxt.extensions.gl_arb_vertex_shader);
+            ctxt.gl.GetObjectParameterivARB(program, pname, &mut value);
+        }
+    };
+
+    match value as gl::types::GLenum {
+        gl::POINTS => Some(InputPrimitives::Points),
+        gl::LINES => Some(InputPrimitives::Lines),
+        gl::LINES_ADJACENCY => Some(InputPrimitives::LinesAdjacency),
+        gl::TRIANGLES => Some(InputPrimitives::Triangles),
+        gl::TRIANGLES_ADJACENCY => Some(InputPrimitives::TrianglesAdjacency),
+        _ => None,
+    }
+}
+
 /// Obtains the type of data that the tessellation evaluation shader stage outputs.
 ///
 /// # Unsafety
@@ -956,6 +1122,14 @@ fn glenum_to_uniform_type(ty: gl::types::GLenum) -> UniformType {
         gl::UNSIGNED_INT_VEC2 => UniformType::UnsignedIntVec2,
         gl::UNSIGNED_INT_VEC3 => UniformType::UnsignedIntVec3,
         gl::UNSIGNED_INT_VEC4 => UniformType::UnsignedIntVec4,
+        gl::INT64_ARB => UniformType::Int64,
+        gl::INT64_VEC2_ARB => UniformType::Int64Vec2,
+        gl::INT64_VEC3_ARB => UniformType::Int64Vec3,
+        gl::INT64_VEC4_ARB => UniformType::Int64Vec4,
+        gl::UNSIGNED_INT64_ARB => UniformType::UnsignedInt64,
+        gl::UNSIGNED_INT64_VEC2_ARB => UniformType::UnsignedInt64Vec2,
+        gl::UNSIGNED_INT64_VEC3_ARB => UniformType::UnsignedInt64Vec3,
+        gl::UNSIGNED_INT64_VEC4_ARB => UniformType::UnsignedInt64Vec4,
         gl::BOOL => UniformType::Bool,
         gl::BOOL_VEC2 => UniformType::BoolVec2,
         gl::BOOL_VEC3 => UniformType::BoolVec3,
@@ -14,11 +14,13 @@ use crate::GlObject;
 use crate::Handle;
 
 use crate::program::{ProgramCreationError, ShaderType};
+use crate::program::diagnostics::decode_info_log;
 
 /// A single, compiled but unlinked, shader.
 pub struct Shader {
     context: Rc<Context>,
     id: Handle,
+    shader_type: gl::types::GLenum,
 }
 
 impl GlObject for Shader {
@@ -51,8 +53,21 @@ impl Drop for Shader {
 }
 
 /// Builds an individual shader.
+#[inline]
 pub fn build_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum, source_code: &str)
                        -> Result<Shader, ProgramCreationError> where F: Facade
+{
+    let shader = start_shader(facade, shader_type, source_code)?;
+    check_shader(shader)
+}
+
+/// Creates a shader and asks the driver to compile it, without waiting for the result.
+///
+/// Use `check_shader` to know whether the compilation succeeded. If the driver supports
+/// `GL_ARB_parallel_shader_compile`, the compilation happens in the background in the
+/// meantime.
+pub fn start_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum, source_code: &str)
+                       -> Result<Shader, ProgramCreationError> where F: Facade
 {
     unsafe {
         let mut ctxt = facade.get_context().make_current();
@@ -112,6 +127,51 @@ pub fn build_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum, sourc
             ctxt.report_debug_output_errors.set(true);
         }
 
+        Ok(Shader {
+            context: facade.get_context().clone(),
+            id,
+            shader_type,
+        })
+    }
+}
+
+/// Returns true if the compilation of a shader started with `start_shader` is over, in other
+/// words if `check_shader` won't block.
+///
+/// Always returns true if `GL_ARB_parallel_shader_compile` isn't supported.
+pub fn is_shader_compiled(shader: &Shader) -> bool {
+    let ctxt = shader.context.make_current();
+
+    let id = match shader.id {
+        Handle::Id(id) => id,
+        Handle::Handle(_) => return true,
+    };
+
+    if !ctxt.extensions.gl_khr_parallel_shader_compile &&
+       !ctxt.extensions.gl_arb_parallel_shader_compile
+    {
+        return true;
+    }
+
+    unsafe {
+        let mut completed: gl::types::GLint = 0;
+        // `COMPLETION_STATUS_ARB` and `COMPLETION_STATUS_KHR` have the same value
+        ctxt.gl.GetShaderiv(id, gl::COMPLETION_STATUS_ARB, &mut completed);
+        completed != 0
+    }
+}
+
+/// Waits for the compilation of a shader started with `start_shader` to be over, and returns
+/// an error if it failed.
+///
+/// Reading the compile status blocks until the compilation is over. Use `is_shader_compiled`
+/// beforehand in order to avoid blocking.
+pub fn check_shader(shader: Shader) -> Result<Shader, ProgramCreationError> {
+    unsafe {
+        let ctxt = shader.context.make_current();
+        let id = shader.id;
+        let shader_type = shader.shader_type;
+
         // checking compilation success by reading a flag on the shader
         let compilation_success = {
             let mut compilation_success: gl::types::GLint = 0;
@@ -131,10 +191,8 @@ pub fn build_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum, sourc
         };
 
         if compilation_success == 1 {
-            Ok(Shader {
-                context: facade.get_context().clone(),
-                id
-            })
+            drop(ctxt);
+            Ok(shader)
 
         } else {
             // compilation error
@@ -153,6 +211,8 @@ pub fn build_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum, sourc
                 }
             }
 
+            // some drivers return 0 or even -1 when there is no log
+            let mut error_log_size = error_log_size.max(0);
             let mut error_log: Vec<u8> = Vec::with_capacity(error_log_size as usize);
 
             match id {
@@ -169,15 +229,12 @@ pub fn build_shader<F: ?Sized>(facade: &F, shader_type: gl::types::GLenum, sourc
                 }
             }
 
-            error_log.set_len(error_log_size as usize);
+            // some drivers report more bytes than what the buffer can hold
+            let error_log_size = (error_log_size.max(0) as usize).min(error_log.capacity());
+            error_log.set_len(error_log_size);
 
-            match String::from_utf8(error_log) {
-                Ok(msg) => Err(ProgramCreationError::CompilationError(msg, ShaderType::from_opengl_type(shader_type))),
-                Err(_) => Err(
-                    ProgramCreationError::CompilationError("Could not convert the log \
-                                                            message to UTF-8".to_owned(), ShaderType::from_opengl_type(shader_type))
-                ),
-            }
+            let msg = decode_info_log(error_log);
+            Err(ProgramCreationError::CompilationError(msg, ShaderType::from_opengl_type(shader_type)))
         }
     }
 }
@@ -220,3 +277,38 @@ pub fn check_shader_type_compatibility<C: ?Sized>(ctxt: &C, shader_type: gl::typ
 
     true
 }
+
+#[cfg(all(test, feature = "null_backend"))]
+mod tests {
+    use crate::backend::null::{build_context, build_program, NullBackend};
+
+    #[test]
+    fn core_shaders() {
+        let (backend, context) = build_context(NullBackend::with_version("OpenGL ES 2.0",
+                                                                         "OpenGL ES GLSL ES 1.00",
+                                                                         &[]));
+
+        backend.clear_calls();
+        build_program(&context);
+
+        let calls = backend.get_calls();
+        assert_eq!(calls.iter().filter(|&&c| c == "glCreateShader").count(), 2);
+        assert!(!calls.contains(&"glCreateShaderObjectARB"));
+    }
+
+    #[test]
+    fn arb_shader_objects() {
+        let (backend, context) = build_context(NullBackend::with_version("1.5.0", "1.10", &[
+            "GL_ARB_vertex_buffer_object", "GL_ARB_map_buffer_range", "GL_ARB_shader_objects",
+            "GL_ARB_vertex_shader", "GL_ARB_fragment_shader", "GL_EXT_framebuffer_object",
+            "GL_EXT_framebuffer_blit", "GL_ARB_ES2_compatibility"
+        ]));
+
+        backend.clear_calls();
+        build_program(&context);
+
+        let calls = backend.get_calls();
+        assert_eq!(calls.iter().filter(|&&c| c == "glCreateShaderObjectARB").count(), 2);
+        assert!(!calls.contains(&"glCreateShader"));
+    }
+}
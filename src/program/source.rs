@@ -0,0 +1,152 @@
+/// Inserts `header` at the start of the GLSL `source`, while keeping the line numbers reported
+/// by the compiler identical to the line numbers of `source`.
+///
+/// This is useful to share `#define`s or common functions between multiple shaders without
+/// making the line numbers of the compilation errors point at the wrong lines.
+///
+/// A `#version` directive must be the first thing in a shader, therefore if `source` starts
+/// with one, possibly after some comments, the header is inserted right after it. The header is followed by a `#line`
+/// directive that resets the line number, taking into account the fact that its meaning
+/// differs between GLSL versions.
+///
+/// # Example
+///
+/// ```
+/// let source = glium::program::prepend_header("#define COLOR vec4(1.0)\n", "
+/// #version 110
+/// void main() { gl_FragColor = COLOR; }
+/// ");
+///
+/// assert!(source.contains("#define COLOR"));
+/// ```
+pub fn prepend_header(header: &str, source: &str) -> String {
+    let mut result = String::with_capacity(header.len() + source.len() + 16);
+
+    // finding the `#version` directive, which can only be preceded by whitespace and comments
+    let mut version = None;
+    let mut offset = 0;
+    let mut in_comment = false;
+    for (num, line) in source.split_inclusive('\n').enumerate() {
+        let code = strip_comments(line, &mut in_comment);
+        let trimmed = code.trim();
+
+        if trimmed.is_empty() {
+            offset += line.len();
+            continue;
+        }
+
+        if let Some(rest) = trimmed.strip_prefix('#') {
+            if let Some(rest) = rest.trim_start().strip_prefix("version") {
+                version = Some((num as u32 + 1, rest.trim().to_owned()));
+                offset += line.len();
+            }
+        }
+
+        break;
+    }
+
+    let (body, next_line, new_semantics) = match version {
+        Some((line, directive)) => {
+            result.push_str(&source[.. offset]);
+            if !result.ends_with('\n') {
+                result.push('\n');
+            }
+
+            let mut words = directive.split_whitespace();
+            let number: u32 = words.next().and_then(|n| n.parse().ok()).unwrap_or(110);
+            let es = words.next() == Some("es");
+            (&source[offset ..], line + 1, number >= 330 || es)
+        },
+        None => (source, 1, false),
+    };
+
+    result.push_str(header);
+    if !header.is_empty() && !header.ends_with('\n') {
+        result.push('\n');
+    }
+
+    // before GLSL 3.30, `#line n` means that the next line is line `n + 1`
+    let line = if new_semantics { next_line } else { next_line - 1 };
+    result.push_str(&format!("#line {} 0\n", line));
+    result.push_str(body);
+    result
+}
+
+/// Returns `line` without its comments. `in_comment` indicates whether the line starts inside a
+/// `/* */` comment, and is updated to indicate whether the next one does.
+fn strip_comments(line: &str, in_comment: &mut bool) -> String {
+    let mut result = String::with_capacity(line.len());
+    let mut rest = line;
+
+    loop {
+        if *in_comment {
+            match rest.find("*/") {
+                Some(end) => {
+                    rest = &rest[end + 2 ..];
+                    *in_comment = false;
+                    // a comment is equivalent to a space
+                    result.push(' ');
+                },
+                None => return result,
+            }
+        }
+
+        let line_comment = rest.find("//");
+        let block_comment = rest.find("/*");
+
+        match (line_comment, block_comment) {
+            (Some(l), Some(b)) if b < l => {
+                result.push_str(&rest[.. b]);
+                rest = &rest[b + 2 ..];
+                *in_comment = true;
+            },
+            (None, Some(b)) => {
+                result.push_str(&rest[.. b]);
+                rest = &rest[b + 2 ..];
+                *in_comment = true;
+            },
+            (Some(l), _) => {
+                result.push_str(&rest[.. l]);
+                return result;
+            },
+            (None, None) => {
+                result.push_str(rest);
+                return result;
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::prepend_header;
+
+    #[test]
+    fn without_version() {
+        assert_eq!(prepend_header("#define A 1\n", "void main() {}\n"),
+                   "#define A 1\n#line 0 0\nvoid main() {}\n");
+    }
+
+    #[test]
+    fn old_version() {
+        assert_eq!(prepend_header("#define A 1", "\n#version 110\nvoid main() {}\n"),
+                   "\n#version 110\n#define A 1\n#line 2 0\nvoid main() {}\n");
+    }
+
+    #[test]
+    fn new_version() {
+        assert_eq!(prepend_header("#define A 1\n", "#version 330 core\nvoid main() {}\n"),
+                   "#version 330 core\n#define A 1\n#line 2 0\nvoid main() {}\n");
+        assert_eq!(prepend_header("#define A 1\n", "#version 300 es\nvoid main() {}\n"),
+                   "#version 300 es\n#define A 1\n#line 2 0\nvoid main() {}\n");
+    }
+
+    #[test]
+    fn comments_before_version() {
+        assert_eq!(prepend_header("#define A 1\n", "// shader\n#version 330\nvoid main() {}\n"),
+                   "// shader\n#version 330\n#define A 1\n#line 3 0\nvoid main() {}\n");
+        assert_eq!(prepend_header("#define A 1\n",
+                                  "/* multi\nline */ #version 110 // old\nvoid main() {}\n"),
+                   "/* multi\nline */ #version 110 // old\n#define A 1\n#line 2 0\nvoid main() {}\n");
+    }
+}
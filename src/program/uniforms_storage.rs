@@ -34,6 +34,18 @@ impl UniformsStorage {
         }
     }
 
+    /// Returns true if a value has been set for this uniform location.
+    #[inline]
+    pub fn is_uniform_value_set(&self, location: gl::types::GLint) -> bool {
+        self.values.borrow().get(&location).map_or(false, |v| v.is_some())
+    }
+
+    /// Returns the value stored for this uniform location.
+    #[inline]
+    pub fn get_uniform_value(&self, location: gl::types::GLint) -> Option<RawUniformValue> {
+        self.values.borrow().get(&location).cloned().and_then(|v| v)
+    }
+
     /// Compares `value` with the value stored in this object. If the values differ, updates
     /// the storage and calls `glUniform`.
     pub fn set_uniform_value(&self, ctxt: &mut CommandContext<'_>, program: Handle,
@@ -395,3 +407,59 @@ impl UniformsStorage {
         }
     }
 }
+
+#[cfg(all(test, feature = "null_backend"))]
+mod tests {
+    use crate::backend::null::{build_context, build_program, NullBackend};
+    use crate::gl;
+    use crate::index::{NoIndices, PrimitiveType};
+    use crate::uniforms::{UniformValue, Uniforms};
+    use crate::vertex::EmptyVertexAttributes;
+    use crate::{Frame, Surface};
+
+    /// Uniforms named `u0`, `u1`, etc. with the given values.
+    struct FloatUniforms(Vec<f32>);
+
+    impl Uniforms for FloatUniforms {
+        fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+            for (i, &value) in self.0.iter().enumerate() {
+                output(&format!("u{}", i), UniformValue::Float(value));
+            }
+        }
+    }
+
+    #[test]
+    fn values_cache() {
+        let backend = NullBackend::new();
+        for i in 0 .. 20 {
+            backend.add_uniform(&format!("u{}", i), gl::FLOAT);
+        }
+
+        let (backend, context) = build_context(backend);
+        let program = build_program(&context);
+
+        let mut uniforms = FloatUniforms(vec![1.0; 20]);
+        let uniform_calls = |backend: &NullBackend| {
+            backend.get_calls().iter().filter(|&&c| c == "glUniform1f").count()
+        };
+
+        let mut frame = Frame::new(context.clone(), (800, 600));
+        frame.draw(EmptyVertexAttributes { len: 3 }, NoIndices(PrimitiveType::TrianglesList),
+                   &program, &uniforms, &Default::default()).unwrap();
+        assert_eq!(uniform_calls(&backend), 20);
+
+        // only the values that have changed since the previous draw are uploaded
+        backend.clear_calls();
+        frame.draw(EmptyVertexAttributes { len: 3 }, NoIndices(PrimitiveType::TrianglesList),
+                   &program, &uniforms, &Default::default()).unwrap();
+        assert_eq!(uniform_calls(&backend), 0);
+
+        backend.clear_calls();
+        uniforms.0[7] = 2.0;
+        frame.draw(EmptyVertexAttributes { len: 3 }, NoIndices(PrimitiveType::TrianglesList),
+                   &program, &uniforms, &Default::default()).unwrap();
+        assert_eq!(uniform_calls(&backend), 1);
+
+        frame.finish().unwrap();
+    }
+}
@@ -1,6 +1,6 @@
 use crate::DrawError;
 
-use crate::uniforms::SamplerBehavior;
+use crate::uniforms::{SamplerBehavior, SamplerWrapFunction};
 
 use crate::gl;
 use crate::context::CommandContext;
@@ -57,6 +57,20 @@ impl SamplerObject {
 
                 ctxt.gl.SamplerParameterf(sampler, gl::TEXTURE_MAX_ANISOTROPY_EXT, value);
             }
+
+            if behavior.lod_bias != 0.0 && ctxt.version >= &Version(Api::Gl, 1, 4) {
+                ctxt.gl.SamplerParameterf(sampler, gl::TEXTURE_LOD_BIAS, behavior.lod_bias);
+            }
+
+            if let Some((r, g, b, a)) = behavior.border_color {
+                ctxt.gl.SamplerParameterfv(sampler, gl::TEXTURE_BORDER_COLOR,
+                                           [r, g, b, a].as_ptr());
+            }
+
+            if !behavior.srgb_decode {
+                ctxt.gl.SamplerParameteri(sampler, gl::TEXTURE_SRGB_DECODE_EXT,
+                                          gl::SKIP_DECODE_EXT as gl::types::GLint);
+            }
         }
 
         SamplerObject {
@@ -102,6 +116,21 @@ pub fn get_sampler(ctxt: &mut CommandContext<'_>, behavior: &SamplerBehavior)
         return Err(DrawError::SamplersNotSupported);
     }
 
+    if !is_border_clamp_supported(ctxt) {
+        let wrap = behavior.wrap_function;
+        if behavior.border_color.is_some() ||
+           wrap.0 == SamplerWrapFunction::BorderClamp ||
+           wrap.1 == SamplerWrapFunction::BorderClamp ||
+           wrap.2 == SamplerWrapFunction::BorderClamp
+        {
+            return Err(DrawError::BorderClampNotSupported);
+        }
+    }
+
+    if !behavior.srgb_decode && !ctxt.extensions.gl_ext_texture_srgb_decode {
+        return Err(DrawError::SrgbDecodeNotSupported);
+    }
+
     // looking for an existing sampler
     match ctxt.samplers.get(behavior) {
         Some(obj) => return Ok(obj.get_id()),
@@ -114,3 +143,9 @@ pub fn get_sampler(ctxt: &mut CommandContext<'_>, behavior: &SamplerBehavior)
     ctxt.samplers.insert(*behavior, sampler);
     Ok(id)
 }
+
+/// Returns true if `GL_CLAMP_TO_BORDER` and `GL_TEXTURE_BORDER_COLOR` are supported.
+fn is_border_clamp_supported(ctxt: &CommandContext<'_>) -> bool {
+    ctxt.version >= &Version(Api::Gl, 1, 3) || ctxt.version >= &Version(Api::GlEs, 3, 2) ||
+    ctxt.extensions.gl_ext_texture_border_clamp || ctxt.extensions.gl_oes_texture_border_clamp
+}
@@ -130,6 +130,38 @@ pub unsafe fn wait_linear_sync_fence_and_drop(mut fence: LinearSyncFence,
     delete_fence(ctxt, fence);
 }
 
+/// Returns true if the fence is signaled, without blocking.
+///
+/// The commands queue is flushed, so that the fence eventually becomes signaled.
+#[inline]
+pub unsafe fn is_linear_sync_fence_signaled(ctxt: &mut CommandContext<'_>,
+                                            fence: &LinearSyncFence) -> bool
+{
+    wait_linear_sync_fence_timeout(ctxt, fence, 0)
+}
+
+/// Waits for the fence to be signaled for at most `timeout` nanoseconds. Returns true if the
+/// fence is signaled.
+///
+/// The commands queue is flushed, so that the fence eventually becomes signaled.
+pub unsafe fn wait_linear_sync_fence_timeout(ctxt: &mut CommandContext<'_>,
+                                             fence: &LinearSyncFence, timeout: u64) -> bool
+{
+    let fence = fence.id.unwrap();
+
+    let result = if ctxt.version >= &Version(Api::Gl, 3, 2) ||
+                    ctxt.version >= &Version(Api::GlEs, 3, 0) || ctxt.extensions.gl_arb_sync
+    {
+        ctxt.gl.ClientWaitSync(fence, gl::SYNC_FLUSH_COMMANDS_BIT, timeout)
+    } else if ctxt.extensions.gl_apple_sync {
+        ctxt.gl.ClientWaitSyncAPPLE(fence, gl::SYNC_FLUSH_COMMANDS_BIT_APPLE, timeout)
+    } else {
+        unreachable!();
+    };
+
+    result == gl::ALREADY_SIGNALED || result == gl::CONDITION_SATISFIED
+}
+
 /// Destroys a fence, from within the commands context.
 #[inline]
 pub unsafe fn destroy_linear_sync_fence(ctxt: &mut CommandContext<'_>, mut fence: LinearSyncFence) {
@@ -3,8 +3,9 @@ use crate::GlObject;
 
 use crate::backend::Facade;
 use crate::version::Version;
-use crate::context::Context;
+use crate::context::{self, Context};
 use crate::context::CommandContext;
+use crate::context::deletion::{self, DeferredObject};
 use crate::CapabilitiesSource;
 use crate::ContextExt;
 use crate::TextureExt;
@@ -12,9 +13,9 @@ use crate::TextureMipmapExt;
 use crate::version::Api;
 use crate::Rect;
 
-use crate::image_format::{self, TextureFormatRequest, ClientFormatAny};
+use crate::image_format::{self, TextureFormatRequest, ClientFormatAny, FormatMemorySize};
 use crate::texture::Texture2dDataSink;
-use crate::texture::TextureKind;
+use crate::texture::{TextureKind, DepthStencilTextureMode};
 use crate::texture::{MipmapsOption, TextureFormat, TextureCreationError, CubeLayer};
 use crate::texture::{get_format, InternalFormat, GetFormatError};
 use crate::texture::pixel::PixelValue;
@@ -28,6 +29,7 @@ use crate::BufferExt;
 use crate::BufferSliceExt;
 
 use std::cmp;
+use std::error::Error;
 use std::fmt;
 use std::mem;
 use std::ptr;
@@ -65,6 +67,9 @@ pub struct TextureAny {
     /// been checked yet. The inner Result is Err if the format has been checked but is unknown.
     actual_format: Cell<Option<Result<InternalFormat, GetFormatError>>>,
 
+    /// Amount of memory used by the texels of the format, if known.
+    memory_format: Option<FormatMemorySize>,
+
     /// Type and dimensions of the texture.
     ty: Dimensions,
 
@@ -74,7 +79,11 @@ pub struct TextureAny {
     generate_mipmaps: bool,
 
     /// Is this texture owned by us? If not, we won't clean it up on drop.
-    owned: bool
+    owned: bool,
+
+    /// Number of bytes that have been added to the memory usage of the context for this
+    /// texture.
+    tracked_memory: usize,
 }
 
 fn extract_dimensions(ty: Dimensions)
@@ -108,9 +117,36 @@ fn get_bind_point(ty: Dimensions) -> gl::types::GLenum {
     }
 }
 
-unsafe fn generate_mipmaps(ctxt: &CommandContext<'_>,
+/// Returns true if textures can be modified with the `GL_ARB_direct_state_access` functions,
+/// without being bound.
+#[inline]
+fn is_dsa_supported(ctxt: &CommandContext<'_>) -> bool {
+    ctxt.version >= &Version(Api::Gl, 4, 5) || ctxt.extensions.gl_arb_direct_state_access
+}
+
+/// Sets a parameter of a texture.
+///
+/// If `dsa` is false, the texture must be bound to `bind_point`.
+#[inline]
+unsafe fn tex_parameter(ctxt: &CommandContext<'_>, dsa: bool, id: gl::types::GLuint,
+                        bind_point: gl::types::GLenum, pname: gl::types::GLenum,
+                        value: gl::types::GLint)
+{
+    if dsa {
+        ctxt.gl.TextureParameteri(id, pname, value);
+    } else {
+        ctxt.gl.TexParameteri(bind_point, pname, value);
+    }
+}
+
+/// Generates the mipmaps of a texture.
+///
+/// If `dsa` is false, the texture must be bound to `bind_point`.
+unsafe fn generate_mipmaps(ctxt: &CommandContext<'_>, dsa: bool, id: gl::types::GLuint,
                            bind_point: gl::types::GLenum) {
-    if ctxt.version >= &Version(Api::Gl, 3, 0) ||
+    if dsa {
+        ctxt.gl.GenerateTextureMipmap(id);
+    } else if ctxt.version >= &Version(Api::Gl, 3, 0) ||
        ctxt.version >= &Version(Api::GlEs, 2, 0)
     {
         ctxt.gl.GenerateMipmap(bind_point);
@@ -162,11 +198,44 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
         return Err(TextureCreationError::DimensionsNotSupported);
     }
 
+    // OpenGL ES 2 supports non-power-of-two textures, but only without mipmaps and with
+    // `GL_CLAMP_TO_EDGE` wrapping ; automatically generated mipmaps are dropped with a warning,
+    // while explicitly allocated mipmaps are an error
+    let npot_restricted = {
+        let ctxt = facade.get_context();
+        ctxt.get_version().0 == Api::GlEs && ctxt.get_version() < &Version(Api::GlEs, 3, 0) &&
+            !ctxt.get_extensions().gl_oes_texture_npot &&
+            (!width.is_power_of_two() || !height.unwrap_or(1).is_power_of_two() ||
+             !depth.unwrap_or(1).is_power_of_two())
+    };
+
+    let mipmaps = match mipmaps {
+        MipmapsOption::AutoGeneratedMipmaps | MipmapsOption::AutoGeneratedMipmapsMax(_)
+            if npot_restricted =>
+        {
+            let ctxt = facade.get_context().make_current();
+            context::debug_warning(&ctxt, "Mipmaps are not supported for non-power-of-two \
+                                           textures by this backend, the texture is created \
+                                           without mipmaps");
+            MipmapsOption::NoMipmap
+        },
+        MipmapsOption::EmptyMipmaps if npot_restricted => {
+            return Err(TextureCreationError::DimensionsNotSupported);
+        },
+        MipmapsOption::EmptyMipmapsMax(levels) if npot_restricted && levels > 0 => {
+            return Err(TextureCreationError::DimensionsNotSupported);
+        },
+        m => m,
+    };
+
+    let wrap_mode = if npot_restricted { gl::CLAMP_TO_EDGE } else { gl::REPEAT };
+
     let should_generate_mipmaps = mipmaps.should_generate();
     let texture_levels = mipmaps.num_levels(width, height, depth) as gl::types::GLsizei;
 
     let teximg_internal_format = image_format::format_request_to_glenum(facade.get_context(), format, image_format::RequestType::TexImage(data.as_ref().map(|&(c, _)| c)))?;
     let storage_internal_format = image_format::format_request_to_glenum(facade.get_context(), format, image_format::RequestType::TexStorage).ok();
+    let memory_format = image_format::format_memory_size(format, storage_internal_format.unwrap_or(teximg_internal_format));
 
     let (client_format, client_type) = match (&data, format) {
         (&Some((client_format, _)), f) => image_format::client_format_to_glenum(facade.get_context(), client_format, f, false)?,
@@ -174,6 +243,10 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
         (&None, TextureFormatRequest::Specific(TextureFormat::DepthFormat(_))) => (gl::DEPTH_COMPONENT, gl::FLOAT),
         (&None, TextureFormatRequest::AnyDepthStencil) => (gl::DEPTH_STENCIL, gl::UNSIGNED_INT_24_8),
         (&None, TextureFormatRequest::Specific(TextureFormat::DepthStencilFormat(_))) => (gl::DEPTH_STENCIL, gl::UNSIGNED_INT_24_8),
+        // on OpenGL ES 2 the client format must be the same as the unsized internal format
+        (&None, _) if teximg_internal_format == gl::RGB => (gl::RGB, gl::UNSIGNED_BYTE),
+        (&None, _) if teximg_internal_format == gl::RED_EXT => (gl::RED_EXT, gl::UNSIGNED_BYTE),
+        (&None, _) if teximg_internal_format == gl::RG_EXT => (gl::RG_EXT, gl::UNSIGNED_BYTE),
         (&None, _) => (gl::RGBA, gl::UNSIGNED_BYTE),
     };
 
@@ -206,20 +279,33 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
             ctxt.gl.PixelStorei(gl::UNPACK_ALIGNMENT, 1);
         }
 
+        set_unpack_row_length(&mut ctxt, 0);
+
         BufferAny::unbind_pixel_unpack(&mut ctxt);
 
-        let id: gl::types::GLuint = 0;
-        ctxt.gl.GenTextures(1, mem::transmute(&id));
+        // with direct state access the texture is initialized without being bound, which
+        // leaves the texture units untouched
+        // this path always uses `glTextureStorage*`, and OpenGL 4.3 is required for the
+        // multisample variants
+        let dsa = storage_internal_format.is_some() &&
+                  (ctxt.version >= &Version(Api::Gl, 4, 5) ||
+                   (ctxt.extensions.gl_arb_direct_state_access &&
+                    ctxt.version >= &Version(Api::Gl, 4, 3)));
 
-        {
+        let mut id: gl::types::GLuint = 0;
+
+        if dsa {
+            ctxt.gl.CreateTextures(bind_point, 1, &mut id);
+        } else {
+            ctxt.gl.GenTextures(1, &mut id);
             ctxt.gl.BindTexture(bind_point, id);
             let act = ctxt.state.active_texture as usize;
             ctxt.state.texture_units[act].texture = id;
         }
 
         if !is_multisampled {
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_S, gl::REPEAT as i32);
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MAG_FILTER, filtering as i32);
+            tex_parameter(&ctxt, dsa, id, bind_point, gl::TEXTURE_WRAP_S, wrap_mode as i32);
+            tex_parameter(&ctxt, dsa, id, bind_point, gl::TEXTURE_MAG_FILTER, filtering as i32);
         }
 
         match ty {
@@ -227,7 +313,7 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
             Dimensions::Texture2dMultisample { .. } => (),
             Dimensions::Texture2dMultisampleArray { .. } => (),
             _ => {
-                ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_T, gl::REPEAT as i32);
+                tex_parameter(&ctxt, dsa, id, bind_point, gl::TEXTURE_WRAP_T, wrap_mode as i32);
             },
         };
 
@@ -236,23 +322,23 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
             Dimensions::Texture2d { .. } => (),
             Dimensions::Texture2dMultisample { .. } => (),
             _ => {
-                ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_WRAP_R, gl::REPEAT as i32);
+                tex_parameter(&ctxt, dsa, id, bind_point, gl::TEXTURE_WRAP_R, wrap_mode as i32);
             },
         };
 
         if has_mipmaps {
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MIN_FILTER,
-                                  mipmap_filtering as i32);
+            tex_parameter(&ctxt, dsa, id, bind_point, gl::TEXTURE_MIN_FILTER,
+                          mipmap_filtering as i32);
         } else if !is_multisampled {
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MIN_FILTER,
-                                  filtering as i32);
+            tex_parameter(&ctxt, dsa, id, bind_point, gl::TEXTURE_MIN_FILTER,
+                          filtering as i32);
         }
 
         if !has_mipmaps && (ctxt.version >= &Version(Api::Gl, 1, 2) ||
                             ctxt.version >= &Version(Api::GlEs, 3, 0))
         {
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_BASE_LEVEL, 0);
-            ctxt.gl.TexParameteri(bind_point, gl::TEXTURE_MAX_LEVEL, 0);
+            tex_parameter(&ctxt, dsa, id, bind_point, gl::TEXTURE_BASE_LEVEL, 0);
+            tex_parameter(&ctxt, dsa, id, bind_point, gl::TEXTURE_MAX_LEVEL, 0);
         }
 
         if bind_point == gl::TEXTURE_3D || bind_point == gl::TEXTURE_2D_ARRAY ||
@@ -275,7 +361,23 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
                 a => a
             };
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            if dsa {
+                ctxt.gl.TextureStorage3D(id, texture_levels,
+                                         storage_internal_format.unwrap() as gl::types::GLenum,
+                                         width, height, depth);
+
+                if !data_raw.is_null() {
+                    if is_client_compressed {
+                        ctxt.gl.CompressedTextureSubImage3D(id, 0, 0, 0, 0, width, height, depth,
+                                                            teximg_internal_format as u32,
+                                                            data_bufsize as i32, data_raw);
+                    } else {
+                        ctxt.gl.TextureSubImage3D(id, 0, 0, 0, 0, width, height, depth,
+                                                  client_format, client_type, data_raw);
+                    }
+                }
+
+            } else if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
                 ctxt.gl.TexStorage3D(bind_point, texture_levels,
                                      storage_internal_format.unwrap() as gl::types::GLenum,
                                      width, height, depth);
@@ -315,7 +417,23 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
                 a => a
             };
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            if dsa {
+                ctxt.gl.TextureStorage2D(id, texture_levels,
+                                         storage_internal_format.unwrap() as gl::types::GLenum,
+                                         width, height);
+
+                if !data_raw.is_null() {
+                    if is_client_compressed {
+                        ctxt.gl.CompressedTextureSubImage2D(id, 0, 0, 0, width, height,
+                                                            teximg_internal_format as u32,
+                                                            data_bufsize as i32, data_raw);
+                    } else {
+                        ctxt.gl.TextureSubImage2D(id, 0, 0, 0, width, height, client_format,
+                                                  client_type, data_raw);
+                    }
+                }
+
+            } else if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
                 ctxt.gl.TexStorage2D(bind_point, texture_levels,
                                      storage_internal_format.unwrap() as gl::types::GLenum,
                                      width, height);
@@ -352,7 +470,12 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
                 a => a
             };
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            if dsa {
+                ctxt.gl.TextureStorage2DMultisample(id, samples.unwrap() as gl::types::GLsizei,
+                                                    storage_internal_format.unwrap() as gl::types::GLenum,
+                                                    width, height, gl::TRUE);
+
+            } else if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
                 ctxt.gl.TexStorage2DMultisample(gl::TEXTURE_2D_MULTISAMPLE,
                                                 samples.unwrap() as gl::types::GLsizei,
                                                 storage_internal_format.unwrap() as gl::types::GLenum,
@@ -381,7 +504,13 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
                 a => a
             };
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            if dsa {
+                ctxt.gl.TextureStorage3DMultisample(id, samples.unwrap() as gl::types::GLsizei,
+                                                    storage_internal_format.unwrap() as gl::types::GLenum,
+                                                    width, height, array_size.unwrap() as gl::types::GLsizei,
+                                                    gl::TRUE);
+
+            } else if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
                 ctxt.gl.TexStorage3DMultisample(gl::TEXTURE_2D_MULTISAMPLE_ARRAY,
                                                 samples.unwrap() as gl::types::GLsizei,
                                                 storage_internal_format.unwrap() as gl::types::GLenum,
@@ -407,7 +536,23 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
                 a => a
             };
 
-            if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
+            if dsa {
+                ctxt.gl.TextureStorage1D(id, texture_levels,
+                                         storage_internal_format.unwrap() as gl::types::GLenum,
+                                         width);
+
+                if !data_raw.is_null() {
+                    if is_client_compressed {
+                        ctxt.gl.CompressedTextureSubImage1D(id, 0, 0, width,
+                                                            teximg_internal_format as u32,
+                                                            data_bufsize as i32, data_raw);
+                    } else {
+                        ctxt.gl.TextureSubImage1D(id, 0, 0, width, client_format,
+                                                  client_type, data_raw);
+                    }
+                }
+
+            } else if storage_internal_format.is_some() && (ctxt.version >= &Version(Api::Gl, 4, 2) || ctxt.extensions.gl_arb_texture_storage) {
                 ctxt.gl.TexStorage1D(bind_point, texture_levels,
                                      storage_internal_format.unwrap() as gl::types::GLenum,
                                      width);
@@ -437,22 +582,31 @@ pub fn new_texture<'a, F: ?Sized, P>(facade: &F, format: TextureFormatRequest,
 
         // only generate mipmaps for color textures
         if should_generate_mipmaps {
-            generate_mipmaps(&ctxt, bind_point);
+            generate_mipmaps(&ctxt, dsa, id, bind_point);
         }
 
         id
     };
 
-    Ok(TextureAny {
+    let mut texture = TextureAny {
         context: facade.get_context().clone(),
         id,
         requested_format: format,
         actual_format: Cell::new(None),
+        memory_format,
         ty,
         levels: texture_levels as u32,
         generate_mipmaps: should_generate_mipmaps,
-        owned: true
-    })
+        owned: true,
+        tracked_memory: 0,
+    };
+
+    if let Some(size) = texture.memory_size() {
+        ctxt.allocated_memory.set(ctxt.allocated_memory.get() + size);
+        texture.tracked_memory = size;
+    }
+
+    Ok(texture)
 }
 
 /// Builds a new texture reference from an existing, externally created OpenGL texture.
@@ -470,18 +624,26 @@ pub unsafe fn from_id<F: Facade + ?Sized>(facade: &F,
     let mipmap_levels = mipmaps.num_levels(width, height, depth);
     let should_generate_mipmaps = mipmaps.should_generate();
     if should_generate_mipmaps {
-        let ctxt = facade.get_context().make_current();
-        generate_mipmaps(&ctxt, get_bind_point(ty));
+        let mut ctxt = facade.get_context().make_current();
+        let dsa = is_dsa_supported(&ctxt);
+        if !dsa {
+            ctxt.gl.BindTexture(get_bind_point(ty), id);
+            let act = ctxt.state.active_texture as usize;
+            ctxt.state.texture_units[act].texture = id;
+        }
+        generate_mipmaps(&ctxt, dsa, id, get_bind_point(ty));
     }
     TextureAny {
         context: facade.get_context().clone(),
         id,
         requested_format: format,
         actual_format: Cell::new(None),
+        memory_format: image_format::format_memory_size(format, gl::NONE),
         ty,
         levels: mipmap_levels,
         generate_mipmaps: should_generate_mipmaps,
-        owned
+        owned,
+        tracked_memory: 0,
     }
 }
 
@@ -636,6 +798,40 @@ impl TextureAny {
         }
     }
 
+    /// Returns an estimate of the number of bytes of video memory used by this texture, including
+    /// all its mipmap levels, layers and samples.
+    ///
+    /// The estimate is computed from the dimensions of the texture and from the format that was
+    /// requested when creating it, without querying the backend. Returns `None` if the amount of
+    /// memory used by the format is unknown, for example for generic compressed formats.
+    pub fn memory_size(&self) -> Option<usize> {
+        let memory_format = self.memory_format?;
+
+        let (width, height, depth, array_size, samples) = extract_dimensions(self.ty);
+        let faces = match self.ty {
+            Dimensions::Cubemap { .. } => 6,
+            _ => 1,
+        };
+        let layers = array_size.unwrap_or(1) as usize * faces * samples.unwrap_or(1) as usize;
+
+        let mut bits = 0;
+        for level in 0 .. self.levels {
+            let w = cmp::max(1, width >> level);
+            let h = height.map(|h| cmp::max(1, h >> level)).unwrap_or(1);
+            let d = depth.map(|d| cmp::max(1, d >> level)).unwrap_or(1) as usize;
+
+            bits += match memory_format {
+                FormatMemorySize::Bits(texel_bits) => w as usize * h as usize * d * texel_bits,
+                FormatMemorySize::Blocks { width: bw, height: bh, bytes } => {
+                    let blocks = ((w + bw - 1) / bw) as usize * ((h + bh - 1) / bh) as usize;
+                    blocks * d * bytes * 8
+                },
+            };
+        }
+
+        Some(bits * layers / 8)
+    }
+
     /// Determines the number of depth and stencil bits in the format of this texture.
     pub fn get_depth_stencil_bits(&self) -> (u16, u16) {
         unsafe {
@@ -680,15 +876,175 @@ impl TextureAny {
         })
     }
 
-    /// Binds this texture and generates mipmaps.
+    /// Restricts the mipmap levels that can be accessed when sampling this texture to the range
+    /// `base_level ..= max_level`. This corresponds to `GL_TEXTURE_BASE_LEVEL` and
+    /// `GL_TEXTURE_MAX_LEVEL`.
+    ///
+    /// This is typically used when streaming textures, in order to sample only the low
+    /// resolution mipmaps while the high resolution ones are still being uploaded.
+    ///
+    /// Returns an error if the backend doesn't support it (OpenGL ES 2).
+    ///
+    /// # Panic
+    ///
+    /// Panics if `base_level` is superior to `max_level`, or if `max_level` is out of range.
+    pub fn set_mipmap_range(&self, base_level: u32, max_level: u32)
+                            -> Result<(), MipmapRangeNotSupportedError>
+    {
+        assert!(base_level <= max_level);
+        assert!(max_level < self.levels);
+
+        let mut ctxt = self.context.make_current();
+        if !(ctxt.version >= &Version(Api::Gl, 1, 2)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 0))
+        {
+            return Err(MipmapRangeNotSupportedError);
+        }
+
+        let dsa = self.bind_unless_dsa(&mut ctxt);
+        unsafe {
+            tex_parameter(&ctxt, dsa, self.id, self.get_bind_point(), gl::TEXTURE_BASE_LEVEL,
+                          base_level as gl::types::GLint);
+            tex_parameter(&ctxt, dsa, self.id, self.get_bind_point(), gl::TEXTURE_MAX_LEVEL,
+                          max_level as gl::types::GLint);
+        }
+
+        Ok(())
+    }
+
+    /// Chooses whether sampling this depth-stencil texture reads its depth component or its
+    /// stencil component. This corresponds to `GL_DEPTH_STENCIL_TEXTURE_MODE`.
+    ///
+    /// Stencil-only textures don't need this, as they always return their stencil value.
+    ///
+    /// Returns an error if the texture isn't a depth-stencil texture, or if the backend doesn't
+    /// support it (OpenGL 4.3, OpenGL ES 3.1 or `GL_ARB_stencil_texturing` are required).
+    pub fn set_depth_stencil_texture_mode(&self, mode: DepthStencilTextureMode)
+                                          -> Result<(), DepthStencilTextureModeError>
+    {
+        if self.kind() != TextureKind::DepthStencil {
+            return Err(DepthStencilTextureModeError::NotDepthStencil);
+        }
+
+        let mut ctxt = self.context.make_current();
+        if !(ctxt.version >= &Version(Api::Gl, 4, 3)) &&
+           !(ctxt.version >= &Version(Api::GlEs, 3, 1)) &&
+           !ctxt.extensions.gl_arb_stencil_texturing
+        {
+            return Err(DepthStencilTextureModeError::NotSupported);
+        }
+
+        let value = match mode {
+            DepthStencilTextureMode::Depth => gl::DEPTH_COMPONENT,
+            DepthStencilTextureMode::Stencil => gl::STENCIL_INDEX,
+        };
+
+        let dsa = self.bind_unless_dsa(&mut ctxt);
+        unsafe {
+            tex_parameter(&ctxt, dsa, self.id, self.get_bind_point(),
+                          gl::DEPTH_STENCIL_TEXTURE_MODE, value as gl::types::GLint);
+        }
+
+        Ok(())
+    }
+
+    /// Chooses whether the texels of this sRGB texture are converted to linear RGB when the
+    /// texture is sampled. This corresponds to `GL_TEXTURE_SRGB_DECODE_EXT`.
+    ///
+    /// sRGB textures are decoded by default. Disabling the decoding returns the raw values that
+    /// are stored in the texture, which is useful for example for data that has been stored as
+    /// sRGB by mistake. This has no effect on textures that aren't in sRGB.
+    ///
+    /// This value is ignored when the texture is sampled with a `Sampler`. Use
+    /// `Sampler::srgb_decode` instead in that case.
+    ///
+    /// Returns an error if `GL_EXT_texture_sRGB_decode` isn't supported.
+    pub fn set_srgb_decode(&self, decode: bool) -> Result<(), SrgbDecodeNotSupportedError> {
+        let mut ctxt = self.context.make_current();
+        if !ctxt.extensions.gl_ext_texture_srgb_decode {
+            return Err(SrgbDecodeNotSupportedError);
+        }
+
+        let value = if decode { gl::DECODE_EXT } else { gl::SKIP_DECODE_EXT };
+
+        let dsa = self.bind_unless_dsa(&mut ctxt);
+        unsafe {
+            tex_parameter(&ctxt, dsa, self.id, self.get_bind_point(),
+                          gl::TEXTURE_SRGB_DECODE_EXT, value as gl::types::GLint);
+        }
+
+        Ok(())
+    }
+
+    /// Generates mipmaps. The texture is bound first if direct state access isn't supported.
     #[inline]
     pub unsafe fn generate_mipmaps(&self) {
         let mut ctxt = self.context.make_current();
-        self.bind_to_current(&mut ctxt);
-        generate_mipmaps(&ctxt, self.get_bind_point());
+        let dsa = self.bind_unless_dsa(&mut ctxt);
+        generate_mipmaps(&ctxt, dsa, self.id, self.get_bind_point());
+    }
+
+    /// Binds this texture to the active texture unit, unless it can be modified with direct
+    /// state access. Returns true if direct state access is used.
+    #[inline]
+    fn bind_unless_dsa(&self, ctxt: &mut CommandContext<'_>) -> bool {
+        if is_dsa_supported(ctxt) {
+            true
+        } else {
+            self.bind_to_current(ctxt);
+            false
+        }
+    }
+}
+
+/// Restricting the range of mipmap levels of a texture is not supported (OpenGL ES 2).
+#[derive(Debug, Copy, Clone)]
+pub struct MipmapRangeNotSupportedError;
+
+impl fmt::Display for MipmapRangeNotSupportedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Restricting the range of mipmap levels is not supported by the backend")
     }
 }
 
+impl Error for MipmapRangeNotSupportedError {}
+
+/// `GL_EXT_texture_sRGB_decode` is not supported.
+#[derive(Debug, Copy, Clone)]
+pub struct SrgbDecodeNotSupportedError;
+
+impl fmt::Display for SrgbDecodeNotSupportedError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("Controlling the sRGB decoding of textures is not supported")
+    }
+}
+
+impl Error for SrgbDecodeNotSupportedError {}
+
+/// Error that can happen when choosing the component sampled from a depth-stencil texture.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DepthStencilTextureModeError {
+    /// The texture isn't a depth-stencil texture.
+    NotDepthStencil,
+
+    /// Sampling the stencil component of depth-stencil textures is not supported by the
+    /// backend.
+    NotSupported,
+}
+
+impl fmt::Display for DepthStencilTextureModeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::DepthStencilTextureModeError::*;
+        let desc = match *self {
+            NotDepthStencil => "The texture isn't a depth-stencil texture",
+            NotSupported => "Sampling the stencil component of depth-stencil textures is not supported",
+        };
+        fmt.write_str(desc)
+    }
+}
+
+impl Error for DepthStencilTextureModeError {}
+
 impl TextureExt for TextureAny {
     #[inline]
     fn get_texture_id(&self) -> gl::types::GLuint {
@@ -743,15 +1099,39 @@ impl Drop for TextureAny {
         // removing FBOs which contain this texture
         fbo::FramebuffersContainer::purge_texture(&mut ctxt, self.id);
 
-        // resetting the bindings
-        for tex_unit in ctxt.state.texture_units.iter_mut() {
-            if tex_unit.texture == self.id {
-                tex_unit.texture = 0;
+        ctxt.allocated_memory.set(ctxt.allocated_memory.get() - self.tracked_memory);
+
+        if self.owned {
+            unsafe {
+                if !deletion::defer(&mut ctxt, DeferredObject::Texture(self.id)) {
+                    destroy_texture(&mut ctxt, self.id);
+                }
             }
+
+        } else {
+            reset_texture_bindings(&mut ctxt, self.id);
         }
+    }
+}
 
-        if self.owned {
-            unsafe { ctxt.gl.DeleteTextures(1, [ self.id ].as_ptr()); }
+/// Destroys a texture and removes it from the state of the context.
+pub unsafe fn destroy_texture(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint) {
+    reset_texture_bindings(ctxt, id);
+    ctxt.gl.DeleteTextures(1, [ id ].as_ptr());
+}
+
+/// Sets `GL_UNPACK_ROW_LENGTH`, unless it already has the requested value.
+fn set_unpack_row_length(ctxt: &mut CommandContext<'_>, row_length: gl::types::GLint) {
+    if ctxt.state.pixel_store_unpack_row_length != row_length {
+        ctxt.state.pixel_store_unpack_row_length = row_length;
+        unsafe { ctxt.gl.PixelStorei(gl::UNPACK_ROW_LENGTH, row_length); }
+    }
+}
+
+fn reset_texture_bindings(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint) {
+    for tex_unit in ctxt.state.texture_units.iter_mut() {
+        if tex_unit.texture == id {
+            tex_unit.texture = 0;
         }
     }
 }
@@ -940,7 +1320,7 @@ impl<'a> TextureAnyMipmap<'a> {
                                            y: Range<u32>, z: Range<u32>)
                                            where P: PixelValue
     {
-        self.raw_upload_from_pixel_buffer_impl(source, x, y, z, false);
+        self.raw_upload_from_pixel_buffer_impl(source, x, y, z, 0, false);
     }
 
     /// Uploads data to the texture from a buffer. The R, G and B components are flipped.
@@ -954,11 +1334,33 @@ impl<'a> TextureAnyMipmap<'a> {
                                                     x: Range<u32>, y: Range<u32>, z: Range<u32>)
                                                     where P: PixelValue
     {
-        self.raw_upload_from_pixel_buffer_impl(source, x, y, z, true);
+        self.raw_upload_from_pixel_buffer_impl(source, x, y, z, 0, true);
+    }
+
+    /// Uploads data to the texture from a buffer whose rows are `row_length` pixels long.
+    ///
+    /// This allows uploading a sub-rectangle of a larger image that is stored in the buffer.
+    /// Only the first `x.end - x.start` pixels of each row are read. A `row_length` of 0 means
+    /// that the rows are tightly packed, like `raw_upload_from_pixel_buffer` does.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the offsets and dimensions are outside the boundaries of the texture. Panics
+    /// if the buffer is not big enough to hold the data. Panics if `row_length` is not 0 and is
+    /// smaller than the width of the uploaded region, or if the backend doesn't support
+    /// `GL_UNPACK_ROW_LENGTH` (OpenGL ES 2 without `GL_EXT_unpack_subimage`).
+    #[inline]
+    pub fn raw_upload_from_pixel_buffer_with_row_length<P>(&self, source: BufferSlice<'_, [P]>,
+                                                           x: Range<u32>, y: Range<u32>,
+                                                           z: Range<u32>, row_length: u32)
+                                                           where P: PixelValue
+    {
+        self.raw_upload_from_pixel_buffer_impl(source, x, y, z, row_length, false);
     }
 
     fn raw_upload_from_pixel_buffer_impl<P>(&self, source: BufferSlice<'_, [P]>, x: Range<u32>,
-                                            y: Range<u32>, z: Range<u32>, inverted: bool)
+                                            y: Range<u32>, z: Range<u32>, row_length: u32,
+                                            inverted: bool)
                                             where P: PixelValue
     {
         let tex_dim = self.get_mipmap_dimensions();
@@ -973,7 +1375,14 @@ impl<'a> TextureAnyMipmap<'a> {
         let height = y.end - y.start;
         let depth = z.end - z.start;
 
-        if source.len() < (width * height * depth) as usize {
+        let required_len = if row_length == 0 {
+            width * height * depth
+        } else {
+            assert!(row_length >= width);
+            row_length * (height * depth - 1) + width
+        };
+
+        if source.len() < required_len as usize {
             panic!("Buffer is too small");
         }
 
@@ -984,6 +1393,21 @@ impl<'a> TextureAnyMipmap<'a> {
 
         let mut ctxt = self.texture.context.make_current();
 
+        // rows of pixels are tightly packed in the buffer, whatever their size
+        if ctxt.state.pixel_store_unpack_alignment != 1 {
+            ctxt.state.pixel_store_unpack_alignment = 1;
+            unsafe { ctxt.gl.PixelStorei(gl::UNPACK_ALIGNMENT, 1); }
+        }
+
+        if row_length != 0 && !(ctxt.version >= &Version(Api::Gl, 1, 0) ||
+                                ctxt.version >= &Version(Api::GlEs, 3, 0) ||
+                                ctxt.extensions.gl_ext_unpack_subimage)
+        {
+            panic!("Setting the row length of uploads is not supported by the backend");
+        }
+
+        set_unpack_row_length(&mut ctxt, row_length as gl::types::GLint);
+
         // binds the pixel buffer
         source.prepare_and_bind_for_pixel_unpack(&mut ctxt);
 
@@ -1141,10 +1565,10 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
 
         let (is_client_compressed, data_bufsize) = (format.is_compressed(),
                                                     format.get_buffer_size(width, height, depth, None));
-        let regen_mipmaps = regen_mipmaps && self.texture.levels >= 2 &&
+        // the mipmaps are only generated from the main level, so writing to another level
+        // doesn't regenerate them
+        let regen_mipmaps = regen_mipmaps && level == 0 && self.texture.levels >= 2 &&
                             self.texture.generate_mipmaps && !is_client_compressed;
-
-        assert!(!regen_mipmaps || level == 0);  // when regen_mipmaps is true, level must be 0!
         assert!(x_offset <= self.width);
         assert!(y_offset <= self.height.unwrap_or(1));
         assert!(z_offset <= self.depth.unwrap_or(1));
@@ -1170,8 +1594,11 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
                 ctxt.gl.PixelStorei(gl::UNPACK_ALIGNMENT, 1);
             }
 
+            set_unpack_row_length(&mut ctxt, 0);
+
             BufferAny::unbind_pixel_unpack(&mut ctxt);
-            let bind_point = self.texture.bind_to_current(&mut ctxt);
+            let dsa = self.texture.bind_unless_dsa(&mut ctxt);
+            let bind_point = self.texture.get_bind_point();
 
             if bind_point == gl::TEXTURE_3D || bind_point == gl::TEXTURE_2D_ARRAY {
                 unimplemented!();
@@ -1179,7 +1606,24 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
             } else if bind_point == gl::TEXTURE_2D || bind_point == gl::TEXTURE_1D_ARRAY {
                 assert!(z_offset == 0);
                 // FIXME should glTexImage be used here somewhere or glTexSubImage does it just fine?
-                if is_client_compressed {
+                if dsa && is_client_compressed {
+                    ctxt.gl.CompressedTextureSubImage2D(id, level as gl::types::GLint,
+                                                        x_offset as gl::types::GLint,
+                                                        y_offset as gl::types::GLint,
+                                                        width as gl::types::GLsizei,
+                                                        height.unwrap_or(1) as gl::types::GLsizei,
+                                                        client_format,
+                                                        data_bufsize as gl::types::GLsizei,
+                                                        data.as_ptr() as *const _);
+                } else if dsa {
+                    ctxt.gl.TextureSubImage2D(id, level as gl::types::GLint,
+                                              x_offset as gl::types::GLint,
+                                              y_offset as gl::types::GLint,
+                                              width as gl::types::GLsizei,
+                                              height.unwrap_or(1) as gl::types::GLsizei,
+                                              client_format, client_type,
+                                              data.as_ptr() as *const _);
+                } else if is_client_compressed {
                     ctxt.gl.CompressedTexSubImage2D(bind_point, level as gl::types::GLint,
                                                     x_offset as gl::types::GLint,
                                                     y_offset as gl::types::GLint,
@@ -1207,11 +1651,7 @@ impl<'t> TextureMipmapExt for TextureAnyMipmap<'t> {
 
             // regenerate mipmaps if there are some
             if regen_mipmaps {
-                if ctxt.version >= &Version(Api::Gl, 3, 0) {
-                    ctxt.gl.GenerateMipmap(bind_point);
-                } else {
-                    ctxt.gl.GenerateMipmapEXT(bind_point);
-                }
+                generate_mipmaps(&ctxt, dsa, id, bind_point);
             }
 
             Ok(())
@@ -1455,6 +1895,75 @@ impl<'a> TextureAnyImage<'a> {
             .unwrap();
     }
 
+    /// Uploads compressed data that covers the whole image. Only faces of cubemaps can be
+    /// written this way for now.
+    ///
+    /// Returns `Err` if the format of the data isn't supported or doesn't match the texture.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if this image isn't the face of a cubemap.
+    /// - Panics if `format` isn't a compressed format.
+    /// - Panics if the size of `data` doesn't match the dimensions of the image.
+    ///
+    pub fn raw_upload_compressed_data(&self, data: &[u8], format: ClientFormatAny)
+                                      -> Result<(), ()>
+    {
+        let cube_layer = match (self.texture.ty, self.cube_layer) {
+            (Dimensions::Cubemap { .. }, Some(layer)) => layer,
+            _ => panic!("Only the faces of cubemaps can be written"),
+        };
+
+        assert!(format.is_compressed());
+
+        let height = self.height.unwrap_or(1);
+        let data_bufsize = format.get_buffer_size(self.width, Some(height), None, None);
+        if data.len() != data_bufsize {
+            panic!("Texture data size mismatch");
+        }
+
+        let (client_format, _) = image_format::client_format_to_glenum(&self.texture.context,
+                                                                       format,
+                                                                       self.texture.requested_format,
+                                                                       false).map_err(|_| ())?;
+
+        let mut ctxt = self.texture.context.make_current();
+
+        unsafe {
+            if ctxt.state.pixel_store_unpack_alignment != 1 {
+                ctxt.state.pixel_store_unpack_alignment = 1;
+                ctxt.gl.PixelStorei(gl::UNPACK_ALIGNMENT, 1);
+            }
+
+            set_unpack_row_length(&mut ctxt, 0);
+
+            BufferAny::unbind_pixel_unpack(&mut ctxt);
+            let face = cube_layer.get_layer_index() as gl::types::GLuint;
+
+            // with direct state access, the faces of a cubemap are the layers of a 3D texture
+            if self.texture.bind_unless_dsa(&mut ctxt) {
+                ctxt.gl.CompressedTextureSubImage3D(self.texture.id,
+                                                    self.level as gl::types::GLint,
+                                                    0, 0, face as gl::types::GLint,
+                                                    self.width as gl::types::GLsizei,
+                                                    height as gl::types::GLsizei, 1,
+                                                    client_format,
+                                                    data_bufsize as gl::types::GLsizei,
+                                                    data.as_ptr() as *const _);
+            } else {
+                ctxt.gl.CompressedTexSubImage2D(gl::TEXTURE_CUBE_MAP_POSITIVE_X + face,
+                                                self.level as gl::types::GLint, 0, 0,
+                                                self.width as gl::types::GLsizei,
+                                                height as gl::types::GLsizei,
+                                                client_format,
+                                                data_bufsize as gl::types::GLsizei,
+                                                data.as_ptr() as *const _);
+            }
+        }
+
+        Ok(())
+    }
+
     /// Clears the content of the texture to a specific value.
     ///
     /// # Panic
@@ -1472,3 +1981,25 @@ impl<'a> TextureAnyImage<'a> {
         }
     }
 }
+
+#[cfg(all(test, feature = "null_backend"))]
+mod tests {
+    use crate::backend::null::{build_context, NullBackend};
+    use crate::texture::{Texture2d, MipmapsOption};
+
+    #[test]
+    fn es2_npot_mipmaps() {
+        let (_, context) = build_context(NullBackend::with_version("OpenGL ES 2.0",
+                                                                   "OpenGL ES GLSL ES 1.00",
+                                                                   &[]));
+
+        // without `GL_OES_texture_npot` the mipmaps of non-power-of-two textures are skipped
+        let texture = Texture2d::empty_with_mipmaps(&context, MipmapsOption::AutoGeneratedMipmaps,
+                                                    100, 60).unwrap();
+        assert_eq!(texture.get_mipmap_levels(), 1);
+
+        let texture = Texture2d::empty_with_mipmaps(&context, MipmapsOption::AutoGeneratedMipmaps,
+                                                    64, 64).unwrap();
+        assert_eq!(texture.get_mipmap_levels(), 7);
+    }
+}
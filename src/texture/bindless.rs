@@ -51,6 +51,10 @@ let uniform_buffer = glium::uniforms::UniformBuffer::new(&display, UniformBuffer
 # }
 ```
 
+A `TextureHandle` can also be passed directly as a regular uniform. In this situation the
+uniform must be declared as a `uint64_t` (which requires `GL_ARB_gpu_shader_int64`), and you
+can then build a sampler from it in your shader with for example `sampler2D(handle)`.
+
 Inside your shader, you can refer to the texture with a traditional `sampler*` variable. Glium
 currently doesn't check whether the type of your texture matches the expected type (but it may
 do in the future). Binding the wrong type of texture may lead to undefined values when sampling
@@ -178,8 +182,8 @@ impl<'a> TextureHandle<'a> {
 impl<'a> AsUniformValue for TextureHandle<'a> {
     #[inline]
     fn as_uniform_value(&self) -> UniformValue<'_> {
-        // TODO: u64
-        unimplemented!();
+        // the handle is passed as a raw `uint64_t`, which the shader can cast to a sampler
+        UniformValue::UnsignedInt64(self.value)
     }
 }
 
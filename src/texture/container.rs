@@ -0,0 +1,751 @@
+/*!
+Loading textures from KTX and DDS files.
+
+KTX and DDS are the two most common formats used to store textures that are ready to be
+uploaded to the GPU, with their mipmaps already generated and most of the time compressed.
+
+```no_run
+# let display: glium::Display = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+let bytes = std::fs::read("texture.ktx").unwrap();
+let texture = glium::texture::CompressedTexture2d::from_ktx(&display, &bytes).unwrap();
+```
+
+The `TextureContainer` struct can also be used to parse a file without creating a texture, in
+order to inspect its content or to upload it yourself.
+
+Only the most common subset of these formats is supported: two-dimensional textures and
+cubemaps, with or without mipmaps, stored with one of the formats of `CompressedFormat` or
+`CompressedSrgbFormat`. Cubemaps are loaded with `CompressedCubemap::from_ktx` and similar
+functions. ETC and ASTC textures can only be loaded from KTX files, and ETC1 data is loaded as
+`CompressedFormat::Etc2Rgb8`. Uncompressed data, arrays and 3D textures produce an
+`Unsupported` error.
+
+*/
+use std::error::Error;
+use std::fmt;
+
+use crate::backend::Facade;
+use crate::image_format::ClientFormatAny;
+use crate::texture::{CompressedFormat, CompressedSrgbFormat, CompressedMipmapsOption};
+use crate::texture::{CompressedTexture2d, CompressedSrgbTexture2d, TextureCreationError};
+use crate::texture::{CompressedCubemap, CompressedSrgbCubemap, CubeLayer};
+use crate::Rect;
+
+/// Format of the data inside a container.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ContainerFormat {
+    /// A compressed format in the linear color space.
+    Compressed(CompressedFormat),
+    /// A compressed format in the sRGB color space.
+    CompressedSrgb(CompressedSrgbFormat),
+}
+
+impl ContainerFormat {
+    /// Returns the dimensions in texels and the size in bytes of a block.
+    fn block_info(&self) -> (u32, u32, usize) {
+        match *self {
+            ContainerFormat::Compressed(CompressedFormat::RgtcFormatU) |
+            ContainerFormat::Compressed(CompressedFormat::RgtcFormatI) |
+            ContainerFormat::Compressed(CompressedFormat::S3tcDxt1NoAlpha) |
+            ContainerFormat::Compressed(CompressedFormat::S3tcDxt1Alpha) |
+            ContainerFormat::Compressed(CompressedFormat::Etc2Rgb8) |
+            ContainerFormat::Compressed(CompressedFormat::Etc2Rgb8PunchthroughAlpha1) |
+            ContainerFormat::Compressed(CompressedFormat::EacR11) |
+            ContainerFormat::Compressed(CompressedFormat::EacSignedR11) |
+            ContainerFormat::CompressedSrgb(CompressedSrgbFormat::S3tcDxt1NoAlpha) |
+            ContainerFormat::CompressedSrgb(CompressedSrgbFormat::S3tcDxt1Alpha) |
+            ContainerFormat::CompressedSrgb(CompressedSrgbFormat::Etc2) |
+            ContainerFormat::CompressedSrgb(CompressedSrgbFormat::Etc2PunchthroughAlpha1) =>
+                (4, 4, 8),
+            ContainerFormat::Compressed(format) => {
+                let (width, height) = format.get_block_dimensions();
+                (width, height, 16)
+            },
+            ContainerFormat::CompressedSrgb(_) => (4, 4, 16),
+        }
+    }
+
+    /// Returns the format that corresponds to an OpenGL internal format.
+    fn from_glenum(value: u32) -> Option<ContainerFormat> {
+        // `GL_ETC1_RGB8_OES`, ETC2 decoders can read ETC1 data
+        const ETC1_RGB8_OES: u32 = 0x8D64;
+        if value == ETC1_RGB8_OES {
+            return Some(ContainerFormat::Compressed(CompressedFormat::Etc2Rgb8));
+        }
+
+        match ClientFormatAny::from_internal_compressed_format(value) {
+            Some(ClientFormatAny::CompressedFormat(format)) =>
+                Some(ContainerFormat::Compressed(format)),
+            Some(ClientFormatAny::CompressedSrgbFormat(format)) =>
+                Some(ContainerFormat::CompressedSrgb(format)),
+            _ => None,
+        }
+    }
+
+    /// Returns the format that corresponds to a `DXGI_FORMAT` of a DDS file.
+    fn from_dxgi(value: u32) -> Option<ContainerFormat> {
+        Some(match value {
+            71 => ContainerFormat::Compressed(CompressedFormat::S3tcDxt1Alpha),
+            72 => ContainerFormat::CompressedSrgb(CompressedSrgbFormat::S3tcDxt1Alpha),
+            74 => ContainerFormat::Compressed(CompressedFormat::S3tcDxt3Alpha),
+            75 => ContainerFormat::CompressedSrgb(CompressedSrgbFormat::S3tcDxt3Alpha),
+            77 => ContainerFormat::Compressed(CompressedFormat::S3tcDxt5Alpha),
+            78 => ContainerFormat::CompressedSrgb(CompressedSrgbFormat::S3tcDxt5Alpha),
+            80 => ContainerFormat::Compressed(CompressedFormat::RgtcFormatU),
+            81 => ContainerFormat::Compressed(CompressedFormat::RgtcFormatI),
+            83 => ContainerFormat::Compressed(CompressedFormat::RgtcFormatUU),
+            84 => ContainerFormat::Compressed(CompressedFormat::RgtcFormatII),
+            95 => ContainerFormat::Compressed(CompressedFormat::BptcUnsignedFloat3),
+            96 => ContainerFormat::Compressed(CompressedFormat::BptcSignedFloat3),
+            98 => ContainerFormat::Compressed(CompressedFormat::BptcUnorm4),
+            99 => ContainerFormat::CompressedSrgb(CompressedSrgbFormat::Bptc),
+            _ => return None,
+        })
+    }
+}
+
+/// Error that can happen when loading a texture container.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContainerError {
+    /// The data doesn't start with the signature of the format.
+    WrongSignature,
+
+    /// The data is shorter than what the header announces.
+    Truncated,
+
+    /// The header contains invalid values.
+    InvalidHeader,
+
+    /// The container uses a feature or a format that glium doesn't handle.
+    Unsupported(&'static str),
+
+    /// The container doesn't contain the kind of texture that has been requested.
+    ///
+    /// For example, the file contains a cubemap and you tried to load it as a 2D texture.
+    WrongKind,
+
+    /// The format of the texture isn't supported by the backend.
+    FormatNotSupported,
+
+    /// Error while creating the texture.
+    TextureCreationError(TextureCreationError),
+}
+
+impl fmt::Display for ContainerError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::ContainerError::*;
+        let desc = match *self {
+            WrongSignature =>
+                "The data doesn't start with the signature of the format",
+            Truncated =>
+                "The data is shorter than what the header announces",
+            InvalidHeader =>
+                "The header contains invalid values",
+            Unsupported(_) =>
+                "The container uses a feature or a format that isn't supported",
+            WrongKind =>
+                "The container doesn't contain the requested kind of texture",
+            FormatNotSupported =>
+                "The format of the texture is not supported by the backend",
+            TextureCreationError(_) =>
+                "Error while creating the texture",
+        };
+        match *self {
+            Unsupported(what) => write!(fmt, "{}: {}", desc, what),
+            TextureCreationError(ref err) => write!(fmt, "{}: {}", desc, err),
+            _ => fmt.write_str(desc),
+        }
+    }
+}
+
+impl Error for ContainerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ContainerError::TextureCreationError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<TextureCreationError> for ContainerError {
+    #[inline]
+    fn from(err: TextureCreationError) -> ContainerError {
+        ContainerError::TextureCreationError(err)
+    }
+}
+
+/// The content of a KTX or DDS file.
+///
+/// The data isn't copied and is borrowed from the content of the file.
+#[derive(Debug, Clone)]
+pub struct TextureContainer<'a> {
+    format: ContainerFormat,
+    width: u32,
+    height: u32,
+    cubemap: bool,
+    // the data of each face, for each mipmap level
+    levels: Vec<Vec<&'a [u8]>>,
+}
+
+impl<'a> TextureContainer<'a> {
+    /// Parses the content of a KTX file (version 1).
+    pub fn from_ktx(data: &'a [u8]) -> Result<TextureContainer<'a>, ContainerError> {
+        const SIGNATURE: [u8; 12] = [0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB,
+                                     0x0D, 0x0A, 0x1A, 0x0A];
+
+        if data.len() < 12 || data[.. 12] != SIGNATURE {
+            return Err(ContainerError::WrongSignature);
+        }
+
+        let mut reader = Reader { data, offset: 12, big_endian: false };
+        match reader.u32()? {
+            0x04030201 => (),
+            0x01020304 => reader.big_endian = true,
+            _ => return Err(ContainerError::InvalidHeader),
+        }
+
+        let gl_type = reader.u32()?;
+        let _gl_type_size = reader.u32()?;
+        let _gl_format = reader.u32()?;
+        let gl_internal_format = reader.u32()?;
+        let _gl_base_internal_format = reader.u32()?;
+        let width = reader.u32()?;
+        let height = reader.u32()?;
+        let depth = reader.u32()?;
+        let array_elements = reader.u32()?;
+        let faces = reader.u32()?;
+        let levels = reader.u32()?;
+        let key_value_bytes = reader.u32()?;
+
+        if gl_type != 0 {
+            return Err(ContainerError::Unsupported("uncompressed data"));
+        }
+        if height == 0 || depth != 0 {
+            return Err(ContainerError::Unsupported("textures that aren't two-dimensional"));
+        }
+        if array_elements != 0 {
+            return Err(ContainerError::Unsupported("texture arrays"));
+        }
+        if width == 0 || (faces != 1 && faces != 6) {
+            return Err(ContainerError::InvalidHeader);
+        }
+
+        let format = ContainerFormat::from_glenum(gl_internal_format)
+                                     .ok_or(ContainerError::Unsupported("this internal format"))?;
+
+        // a value of 0 means that the mipmaps must be generated at runtime
+        let levels = levels.max(1);
+        if levels > 32 {
+            return Err(ContainerError::InvalidHeader);
+        }
+
+        reader.skip(key_value_bytes as usize)?;
+
+        let mut result = Vec::with_capacity(levels as usize);
+        for level in 0 .. levels {
+            let expected = level_size(format, width, height, level)?;
+            let image_size = reader.u32()? as usize;
+            if image_size != expected {
+                return Err(ContainerError::InvalidHeader);
+            }
+
+            let mut level_data = Vec::with_capacity(faces as usize);
+            for _ in 0 .. faces {
+                level_data.push(reader.bytes(image_size)?);
+                // each face is padded to four bytes
+                reader.skip((4 - image_size % 4) % 4)?;
+            }
+
+            result.push(level_data);
+        }
+
+        Ok(TextureContainer {
+            format,
+            width,
+            height,
+            cubemap: faces == 6,
+            levels: result,
+        })
+    }
+
+    /// Parses the content of a DDS file, with or without the DX10 header.
+    pub fn from_dds(data: &'a [u8]) -> Result<TextureContainer<'a>, ContainerError> {
+        const DDSD_MIPMAPCOUNT: u32 = 0x20000;
+        const DDPF_ALPHAPIXELS: u32 = 0x1;
+        const DDPF_FOURCC: u32 = 0x4;
+        const DDSCAPS2_CUBEMAP: u32 = 0x200;
+        const DDSCAPS2_CUBEMAP_ALLFACES: u32 = 0xFC00;
+        const DDSCAPS2_VOLUME: u32 = 0x200000;
+
+        if data.len() < 4 || &data[.. 4] != b"DDS " {
+            return Err(ContainerError::WrongSignature);
+        }
+
+        let mut reader = Reader { data, offset: 4, big_endian: false };
+        if reader.u32()? != 124 {
+            return Err(ContainerError::InvalidHeader);
+        }
+
+        let flags = reader.u32()?;
+        let height = reader.u32()?;
+        let width = reader.u32()?;
+        let _pitch_or_linear_size = reader.u32()?;
+        let _depth = reader.u32()?;
+        let mipmap_count = reader.u32()?;
+        reader.skip(11 * 4)?;
+
+        // pixel format
+        let _pf_size = reader.u32()?;
+        let pf_flags = reader.u32()?;
+        let four_cc = reader.bytes(4)?;
+        reader.skip(5 * 4)?;
+
+        let _caps = reader.u32()?;
+        let caps2 = reader.u32()?;
+        reader.skip(3 * 4)?;
+
+        if width == 0 || height == 0 {
+            return Err(ContainerError::InvalidHeader);
+        }
+        if caps2 & DDSCAPS2_VOLUME != 0 {
+            return Err(ContainerError::Unsupported("textures that aren't two-dimensional"));
+        }
+        if pf_flags & DDPF_FOURCC == 0 {
+            return Err(ContainerError::Unsupported("uncompressed data"));
+        }
+
+        let mut cubemap = caps2 & DDSCAPS2_CUBEMAP != 0;
+        if cubemap && caps2 & DDSCAPS2_CUBEMAP_ALLFACES != DDSCAPS2_CUBEMAP_ALLFACES {
+            return Err(ContainerError::Unsupported("cubemaps with missing faces"));
+        }
+
+        let format = match four_cc {
+            b"DXT1" if pf_flags & DDPF_ALPHAPIXELS != 0 =>
+                ContainerFormat::Compressed(CompressedFormat::S3tcDxt1Alpha),
+            b"DXT1" => ContainerFormat::Compressed(CompressedFormat::S3tcDxt1NoAlpha),
+            b"DXT3" => ContainerFormat::Compressed(CompressedFormat::S3tcDxt3Alpha),
+            b"DXT5" => ContainerFormat::Compressed(CompressedFormat::S3tcDxt5Alpha),
+            b"ATI1" | b"BC4U" => ContainerFormat::Compressed(CompressedFormat::RgtcFormatU),
+            b"BC4S" => ContainerFormat::Compressed(CompressedFormat::RgtcFormatI),
+            b"ATI2" | b"BC5U" => ContainerFormat::Compressed(CompressedFormat::RgtcFormatUU),
+            b"BC5S" => ContainerFormat::Compressed(CompressedFormat::RgtcFormatII),
+            b"DX10" => {
+                let dxgi_format = reader.u32()?;
+                let resource_dimension = reader.u32()?;
+                let misc_flag = reader.u32()?;
+                let array_size = reader.u32()?;
+                let _misc_flags2 = reader.u32()?;
+
+                // D3D10_RESOURCE_DIMENSION_TEXTURE2D
+                if resource_dimension != 3 {
+                    return Err(ContainerError::Unsupported("textures that aren't two-dimensional"));
+                }
+                if array_size > 1 {
+                    return Err(ContainerError::Unsupported("texture arrays"));
+                }
+
+                // D3D11_RESOURCE_MISC_TEXTURECUBE
+                cubemap = misc_flag & 0x4 != 0;
+
+                ContainerFormat::from_dxgi(dxgi_format)
+                                .ok_or(ContainerError::Unsupported("this DXGI format"))?
+            },
+            _ => return Err(ContainerError::Unsupported("this FourCC")),
+        };
+
+        let levels = if flags & DDSD_MIPMAPCOUNT != 0 { mipmap_count.max(1) } else { 1 };
+        if levels > 32 {
+            return Err(ContainerError::InvalidHeader);
+        }
+
+        let faces = if cubemap { 6 } else { 1 };
+
+        // contrary to KTX, the data is stored face by face
+        let mut result = vec![Vec::with_capacity(faces); levels as usize];
+        for _ in 0 .. faces {
+            for (level, level_data) in result.iter_mut().enumerate() {
+                let size = level_size(format, width, height, level as u32)?;
+                level_data.push(reader.bytes(size)?);
+            }
+        }
+
+        Ok(TextureContainer {
+            format,
+            width,
+            height,
+            cubemap,
+            levels: result,
+        })
+    }
+
+    /// Returns the format of the data.
+    #[inline]
+    pub fn get_format(&self) -> ContainerFormat {
+        self.format
+    }
+
+    /// Returns the width and height of the main level.
+    #[inline]
+    pub fn get_dimensions(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Returns true if the container holds the six faces of a cubemap.
+    #[inline]
+    pub fn is_cubemap(&self) -> bool {
+        self.cubemap
+    }
+
+    /// Returns the number of mipmap levels, including the main level.
+    #[inline]
+    pub fn get_mipmap_levels(&self) -> u32 {
+        self.levels.len() as u32
+    }
+
+    /// Returns the width and height of a mipmap level.
+    #[inline]
+    pub fn get_level_dimensions(&self, level: u32) -> (u32, u32) {
+        ((self.width >> level).max(1), (self.height >> level).max(1))
+    }
+
+    /// Returns the data of a face of a mipmap level, or `None` if it doesn't exist.
+    ///
+    /// The faces of cubemaps are in the order +X, -X, +Y, -Y, +Z, -Z. Other textures only have
+    /// the face `0`.
+    #[inline]
+    pub fn get_data(&self, level: u32, face: u32) -> Option<&'a [u8]> {
+        self.levels.get(level as usize).and_then(|l| l.get(face as usize)).map(|d| *d)
+    }
+
+    /// Checks that glium can upload the container as a 2D texture, or as a cubemap if `cubemap`
+    /// is true.
+    fn check_kind(&self, cubemap: bool) -> Result<(), ContainerError> {
+        if self.cubemap != cubemap {
+            return Err(ContainerError::WrongKind);
+        }
+
+        if cubemap && self.width != self.height {
+            return Err(ContainerError::InvalidHeader);
+        }
+
+        // glium requires the dimensions of the blocks to be complete, except for the levels that
+        // are smaller than a block, but ETC and ASTC blocks can be partial
+        let partial_blocks = match self.format {
+            ContainerFormat::Compressed(CompressedFormat::Etc2Rgb8) |
+            ContainerFormat::Compressed(CompressedFormat::Etc2Rgb8PunchthroughAlpha1) |
+            ContainerFormat::Compressed(CompressedFormat::Etc2Rgba8) |
+            ContainerFormat::Compressed(CompressedFormat::EacR11) |
+            ContainerFormat::Compressed(CompressedFormat::EacSignedR11) |
+            ContainerFormat::Compressed(CompressedFormat::EacRg11) |
+            ContainerFormat::Compressed(CompressedFormat::EacSignedRg11) |
+            ContainerFormat::CompressedSrgb(CompressedSrgbFormat::Etc2) |
+            ContainerFormat::CompressedSrgb(CompressedSrgbFormat::Etc2PunchthroughAlpha1) |
+            ContainerFormat::CompressedSrgb(CompressedSrgbFormat::Etc2Alpha8) => true,
+            ContainerFormat::Compressed(CompressedFormat::Astc4x4) => true,
+            ContainerFormat::Compressed(format) => format.get_block_dimensions() != (4, 4),
+            ContainerFormat::CompressedSrgb(_) => false,
+        };
+
+        if partial_blocks {
+            return Ok(());
+        }
+
+        for level in 0 .. self.get_mipmap_levels() {
+            let (width, height) = self.get_level_dimensions(level);
+            if (width > 4 && width % 4 != 0) || (height > 4 && height % 4 != 0) {
+                return Err(ContainerError::Unsupported("dimensions that aren't multiples of 4"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the mipmaps option to use to create a texture for the container.
+    fn mipmaps_option(&self) -> CompressedMipmapsOption {
+        if self.levels.len() >= 2 {
+            CompressedMipmapsOption::EmptyMipmapsMax(self.levels.len() as u32 - 1)
+        } else {
+            CompressedMipmapsOption::NoMipmap
+        }
+    }
+}
+
+impl CompressedTexture2d {
+    /// Builds a texture from the content of a KTX file.
+    ///
+    /// See the documentation of the `container` module.
+    #[inline]
+    pub fn from_ktx<F: ?Sized>(facade: &F, data: &[u8])
+                               -> Result<CompressedTexture2d, ContainerError> where F: Facade
+    {
+        CompressedTexture2d::from_container(facade, &TextureContainer::from_ktx(data)?)
+    }
+
+    /// Builds a texture from the content of a DDS file.
+    ///
+    /// See the documentation of the `container` module.
+    #[inline]
+    pub fn from_dds<F: ?Sized>(facade: &F, data: &[u8])
+                               -> Result<CompressedTexture2d, ContainerError> where F: Facade
+    {
+        CompressedTexture2d::from_container(facade, &TextureContainer::from_dds(data)?)
+    }
+
+    /// Builds a texture from a container, and uploads all its mipmap levels.
+    ///
+    /// Returns `WrongKind` if the container holds a cubemap or data in the sRGB color space.
+    pub fn from_container<F: ?Sized>(facade: &F, container: &TextureContainer<'_>)
+                                     -> Result<CompressedTexture2d, ContainerError>
+                                     where F: Facade
+    {
+        let format = match container.format {
+            ContainerFormat::Compressed(format) => format,
+            ContainerFormat::CompressedSrgb(_) => return Err(ContainerError::WrongKind),
+        };
+
+        container.check_kind(false)?;
+
+        if !format.is_supported(facade.get_context()) {
+            return Err(ContainerError::FormatNotSupported);
+        }
+
+        let (width, height) = container.get_dimensions();
+        let texture = CompressedTexture2d::with_compressed_data(facade,
+                                                                container.get_data(0, 0).unwrap(),
+                                                                width, height, format,
+                                                                container.mipmaps_option())?;
+
+        for level in 1 .. container.get_mipmap_levels() {
+            let (width, height) = container.get_level_dimensions(level);
+            let rect = Rect { left: 0, bottom: 0, width, height };
+            let mipmap = texture.mipmap(level).ok_or(ContainerError::InvalidHeader)?;
+            mipmap.write_compressed_data(rect, container.get_data(level, 0).unwrap(),
+                                         width, height, format)
+                  .map_err(|_| ContainerError::FormatNotSupported)?;
+        }
+
+        Ok(texture)
+    }
+}
+
+impl CompressedSrgbTexture2d {
+    /// Builds a texture from the content of a KTX file.
+    ///
+    /// See the documentation of the `container` module.
+    #[inline]
+    pub fn from_ktx<F: ?Sized>(facade: &F, data: &[u8])
+                               -> Result<CompressedSrgbTexture2d, ContainerError> where F: Facade
+    {
+        CompressedSrgbTexture2d::from_container(facade, &TextureContainer::from_ktx(data)?)
+    }
+
+    /// Builds a texture from the content of a DDS file.
+    ///
+    /// See the documentation of the `container` module.
+    #[inline]
+    pub fn from_dds<F: ?Sized>(facade: &F, data: &[u8])
+                               -> Result<CompressedSrgbTexture2d, ContainerError> where F: Facade
+    {
+        CompressedSrgbTexture2d::from_container(facade, &TextureContainer::from_dds(data)?)
+    }
+
+    /// Builds a texture from a container, and uploads all its mipmap levels.
+    ///
+    /// Returns `WrongKind` if the container holds a cubemap or data in the linear color space.
+    pub fn from_container<F: ?Sized>(facade: &F, container: &TextureContainer<'_>)
+                                     -> Result<CompressedSrgbTexture2d, ContainerError>
+                                     where F: Facade
+    {
+        let format = match container.format {
+            ContainerFormat::CompressedSrgb(format) => format,
+            ContainerFormat::Compressed(_) => return Err(ContainerError::WrongKind),
+        };
+
+        container.check_kind(false)?;
+
+        if !format.is_supported(facade.get_context()) {
+            return Err(ContainerError::FormatNotSupported);
+        }
+
+        let (width, height) = container.get_dimensions();
+        let texture = CompressedSrgbTexture2d::with_compressed_data(facade,
+                                                                    container.get_data(0, 0).unwrap(),
+                                                                    width, height, format,
+                                                                    container.mipmaps_option())?;
+
+        for level in 1 .. container.get_mipmap_levels() {
+            let (width, height) = container.get_level_dimensions(level);
+            let rect = Rect { left: 0, bottom: 0, width, height };
+            let mipmap = texture.mipmap(level).ok_or(ContainerError::InvalidHeader)?;
+            mipmap.write_compressed_data(rect, container.get_data(level, 0).unwrap(),
+                                         width, height, format)
+                  .map_err(|_| ContainerError::FormatNotSupported)?;
+        }
+
+        Ok(texture)
+    }
+}
+
+impl CompressedCubemap {
+    /// Builds a cubemap from the content of a KTX file.
+    ///
+    /// See the documentation of the `container` module.
+    #[inline]
+    pub fn from_ktx<F: ?Sized>(facade: &F, data: &[u8])
+                               -> Result<CompressedCubemap, ContainerError> where F: Facade
+    {
+        CompressedCubemap::from_container(facade, &TextureContainer::from_ktx(data)?)
+    }
+
+    /// Builds a cubemap from the content of a DDS file.
+    ///
+    /// See the documentation of the `container` module.
+    #[inline]
+    pub fn from_dds<F: ?Sized>(facade: &F, data: &[u8])
+                               -> Result<CompressedCubemap, ContainerError> where F: Facade
+    {
+        CompressedCubemap::from_container(facade, &TextureContainer::from_dds(data)?)
+    }
+
+    /// Builds a cubemap from a container, and uploads all the faces of all its mipmap levels.
+    ///
+    /// Returns `WrongKind` if the container doesn't hold a cubemap or holds data in the sRGB
+    /// color space.
+    pub fn from_container<F: ?Sized>(facade: &F, container: &TextureContainer<'_>)
+                                     -> Result<CompressedCubemap, ContainerError>
+                                     where F: Facade
+    {
+        let format = match container.format {
+            ContainerFormat::Compressed(format) => format,
+            ContainerFormat::CompressedSrgb(_) => return Err(ContainerError::WrongKind),
+        };
+
+        container.check_kind(true)?;
+
+        if !format.is_supported(facade.get_context()) {
+            return Err(ContainerError::FormatNotSupported);
+        }
+
+        let texture = CompressedCubemap::empty_with_format(facade, format,
+                                                           container.mipmaps_option(),
+                                                           container.width)?;
+
+        for level in 0 .. container.get_mipmap_levels() {
+            let mipmap = texture.mipmap(level).ok_or(ContainerError::InvalidHeader)?;
+            for (face, &layer) in CUBE_LAYERS.iter().enumerate() {
+                mipmap.image(layer)
+                      .write_compressed_data(container.get_data(level, face as u32).unwrap(),
+                                             format)
+                      .map_err(|_| ContainerError::FormatNotSupported)?;
+            }
+        }
+
+        Ok(texture)
+    }
+}
+
+impl CompressedSrgbCubemap {
+    /// Builds a cubemap from the content of a KTX file.
+    ///
+    /// See the documentation of the `container` module.
+    #[inline]
+    pub fn from_ktx<F: ?Sized>(facade: &F, data: &[u8])
+                               -> Result<CompressedSrgbCubemap, ContainerError> where F: Facade
+    {
+        CompressedSrgbCubemap::from_container(facade, &TextureContainer::from_ktx(data)?)
+    }
+
+    /// Builds a cubemap from the content of a DDS file.
+    ///
+    /// See the documentation of the `container` module.
+    #[inline]
+    pub fn from_dds<F: ?Sized>(facade: &F, data: &[u8])
+                               -> Result<CompressedSrgbCubemap, ContainerError> where F: Facade
+    {
+        CompressedSrgbCubemap::from_container(facade, &TextureContainer::from_dds(data)?)
+    }
+
+    /// Builds a cubemap from a container, and uploads all the faces of all its mipmap levels.
+    ///
+    /// Returns `WrongKind` if the container doesn't hold a cubemap or holds data in the linear
+    /// color space.
+    pub fn from_container<F: ?Sized>(facade: &F, container: &TextureContainer<'_>)
+                                     -> Result<CompressedSrgbCubemap, ContainerError>
+                                     where F: Facade
+    {
+        let format = match container.format {
+            ContainerFormat::CompressedSrgb(format) => format,
+            ContainerFormat::Compressed(_) => return Err(ContainerError::WrongKind),
+        };
+
+        container.check_kind(true)?;
+
+        if !format.is_supported(facade.get_context()) {
+            return Err(ContainerError::FormatNotSupported);
+        }
+
+        let texture = CompressedSrgbCubemap::empty_with_format(facade, format,
+                                                               container.mipmaps_option(),
+                                                               container.width)?;
+
+        for level in 0 .. container.get_mipmap_levels() {
+            let mipmap = texture.mipmap(level).ok_or(ContainerError::InvalidHeader)?;
+            for (face, &layer) in CUBE_LAYERS.iter().enumerate() {
+                mipmap.image(layer)
+                      .write_compressed_data(container.get_data(level, face as u32).unwrap(),
+                                             format)
+                      .map_err(|_| ContainerError::FormatNotSupported)?;
+            }
+        }
+
+        Ok(texture)
+    }
+}
+
+/// The faces of a cubemap, in the order in which they are stored in the containers.
+const CUBE_LAYERS: [CubeLayer; 6] = [CubeLayer::PositiveX, CubeLayer::NegativeX,
+                                     CubeLayer::PositiveY, CubeLayer::NegativeY,
+                                     CubeLayer::PositiveZ, CubeLayer::NegativeZ];
+
+/// Returns the size in bytes of a mipmap level of a compressed texture.
+///
+/// Returns `InvalidHeader` if the size doesn't fit in a `usize`.
+fn level_size(format: ContainerFormat, width: u32, height: u32, level: u32)
+              -> Result<usize, ContainerError>
+{
+    let (block_width, block_height, block_size) = format.block_info();
+    let width = width.checked_shr(level).unwrap_or(0).max(1);
+    let height = height.checked_shr(level).unwrap_or(0).max(1);
+    let blocks_x = width / block_width + if width % block_width != 0 { 1 } else { 0 };
+    let blocks_y = height / block_height + if height % block_height != 0 { 1 } else { 0 };
+    (blocks_x as usize).checked_mul(blocks_y as usize)
+                       .and_then(|blocks| blocks.checked_mul(block_size))
+                       .ok_or(ContainerError::InvalidHeader)
+}
+
+/// Reads the values of a header.
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+    big_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], ContainerError> {
+        let end = self.offset.checked_add(len).ok_or(ContainerError::Truncated)?;
+        let bytes = self.data.get(self.offset .. end).ok_or(ContainerError::Truncated)?;
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    #[inline]
+    fn skip(&mut self, len: usize) -> Result<(), ContainerError> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u32(&mut self) -> Result<u32, ContainerError> {
+        let bytes = self.bytes(4)?;
+        let bytes = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        Ok(if self.big_endian { u32::from_be_bytes(bytes) } else { u32::from_le_bytes(bytes) })
+    }
+}
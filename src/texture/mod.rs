@@ -51,6 +51,12 @@ All textures except depth, stencil and depth-stencil textures have **mipmaps**.
 smaller version of the texture whose purpose is to be used during rendering when the texture will
 be small on the screen.
 
+OpenGL ES 2 without the `GL_OES_texture_npot` extension only supports textures whose dimensions
+aren't powers of two if they don't have mipmaps and use the `Clamp` wrap function. When such a
+texture is created, the automatically generated mipmaps are skipped and the default wrap function
+of the texture is `Clamp` instead of `Repeat`. Explicitly allocating mipmaps returns
+`TextureCreationError::DimensionsNotSupported`.
+
 # Texture types in glium
 
 In addition to the nine different dimensions types, there are nine kinds of texture formats:
@@ -123,7 +129,9 @@ pub use crate::image_format::{UncompressedFloatFormat, UncompressedIntFormat, Un
 pub use crate::image_format::{CompressedFormat, DepthFormat, DepthStencilFormat, StencilFormat};
 pub use crate::image_format::{CompressedSrgbFormat, SrgbFormat};
 pub use self::any::{TextureAny, TextureAnyMipmap, TextureAnyLayer, TextureAnyLayerMipmap};
-pub use self::any::{TextureAnyImage, Dimensions};
+pub use self::any::{TextureAnyImage, Dimensions, SrgbDecodeNotSupportedError};
+pub use self::any::{DepthStencilTextureModeError, MipmapRangeNotSupportedError};
+pub(crate) use self::any::destroy_texture;
 pub use self::bindless::{ResidentTexture, TextureHandle, BindlessTexturesNotSupportedError};
 pub use self::get_format::{InternalFormat, InternalFormatType, GetFormatError};
 pub use self::pixel::PixelValue;
@@ -131,10 +139,12 @@ pub use self::ty_support::{is_texture_1d_supported, is_texture_2d_supported};
 pub use self::ty_support::{is_texture_3d_supported, is_texture_1d_array_supported};
 pub use self::ty_support::{is_texture_2d_array_supported, is_texture_2d_multisample_supported};
 pub use self::ty_support::{is_texture_2d_multisample_array_supported, is_cubemaps_supported};
-pub use self::ty_support::is_cubemap_arrays_supported;
+pub use self::ty_support::{is_cubemap_arrays_supported, is_texture_gather_supported};
+pub use self::ty_support::is_texture_gather_components_supported;
 
 pub mod bindless;
 pub mod buffer_texture;
+pub mod container;
 pub mod pixel_buffer;
 
 mod any;
@@ -186,6 +196,20 @@ pub enum TextureKind {
     DepthStencil,
 }
 
+/// Which component of a depth-stencil texture is read when it is sampled.
+///
+/// See `TextureAny::set_depth_stencil_texture_mode`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DepthStencilTextureMode {
+    /// The depth component is read. The texture must be used with a `sampler*` or a
+    /// `sampler*Shadow`. This is the default.
+    Depth,
+
+    /// The stencil component is read. The texture must be used with a `usampler*`, and
+    /// filtering must be `Nearest`.
+    Stencil,
+}
+
 /// Describes what to do about mipmaps during texture creation.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum MipmapsOption {
@@ -568,6 +592,46 @@ impl<'a, P: PixelValue + Clone> Texture2dDataSource<'a> for RawImage2d<'a, P> {
     }
 }
 
+/// An RGBA image whose first pixel is the top-left hand corner, as returned by the
+/// `capture_image` methods of the surfaces.
+///
+/// This is the layout expected by most image libraries. Use `RawImage2d` or
+/// `Texture2dDataSink` if you want the raw data in the OpenGL order instead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturedImage {
+    /// The pixels as interleaved RGBA values, progressing left-to-right and top-to-bottom.
+    pub data: Vec<u8>,
+
+    /// Number of pixels per row.
+    pub width: u32,
+
+    /// Number of pixels per column.
+    pub height: u32,
+}
+
+impl CapturedImage {
+    /// Builds an image from pixels that start by the bottom-left hand corner, as returned by
+    /// `glReadPixels`.
+    pub(crate) fn from_bottom_left(data: &[(u8, u8, u8, u8)], width: u32, height: u32)
+                                   -> CapturedImage
+    {
+        let mut pixels = Vec::with_capacity(data.len() * 4);
+        if width != 0 {
+            for row in data.chunks(width as usize).rev() {
+                for &(r, g, b, a) in row {
+                    pixels.extend_from_slice(&[r, g, b, a]);
+                }
+            }
+        }
+
+        CapturedImage {
+            data: pixels,
+            width,
+            height,
+        }
+    }
+}
+
 impl<P> Texture2dDataSink<P> for Vec<Vec<P>> where P: Copy + Clone {
     fn from_raw(data: Cow<'_, [P]>, width: u32, height: u32) -> Self {
         data.chunks(width as usize).map(|e| e.to_vec()).collect()
@@ -203,6 +203,47 @@ unsafe impl PixelValue for (f32, f32, f32, f32) {
     }
 }
 
+// arrays have the same layout as their C equivalent, which is what OpenGL expects
+macro_rules! impl_pixel_value_for_arrays {
+    ($ty:ty, $one:ident, $two:ident, $three:ident, $four:ident) => {
+        unsafe impl PixelValue for [$ty; 1] {
+            #[inline]
+            fn get_format() -> super::ClientFormat {
+                super::ClientFormat::$one
+            }
+        }
+
+        unsafe impl PixelValue for [$ty; 2] {
+            #[inline]
+            fn get_format() -> super::ClientFormat {
+                super::ClientFormat::$two
+            }
+        }
+
+        unsafe impl PixelValue for [$ty; 3] {
+            #[inline]
+            fn get_format() -> super::ClientFormat {
+                super::ClientFormat::$three
+            }
+        }
+
+        unsafe impl PixelValue for [$ty; 4] {
+            #[inline]
+            fn get_format() -> super::ClientFormat {
+                super::ClientFormat::$four
+            }
+        }
+    };
+}
+
+impl_pixel_value_for_arrays!(i8, I8, I8I8, I8I8I8, I8I8I8I8);
+impl_pixel_value_for_arrays!(u8, U8, U8U8, U8U8U8, U8U8U8U8);
+impl_pixel_value_for_arrays!(i16, I16, I16I16, I16I16I16, I16I16I16I16);
+impl_pixel_value_for_arrays!(u16, U16, U16U16, U16U16U16, U16U16U16U16);
+impl_pixel_value_for_arrays!(i32, I32, I32I32, I32I32I32, I32I32I32I32);
+impl_pixel_value_for_arrays!(u32, U32, U32U32, U32U32U32, U32U32U32U32);
+impl_pixel_value_for_arrays!(f32, F32, F32F32, F32F32F32, F32F32F32F32);
+
 #[cfg(feature = "image")]
 unsafe impl PixelValue for image::Rgb<u8> {
     #[inline]
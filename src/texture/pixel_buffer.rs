@@ -5,14 +5,17 @@ Contrary to textures, pixel buffers are stored in a client-defined format. They
 to transfer data to or from the video memory, before or after being turned into a texture.
 */
 use std::borrow::Cow;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::ops::{Deref, DerefMut};
 
 use crate::backend::Facade;
 
 use crate::GlObject;
+use crate::ContextExt;
 use crate::buffer::{ReadError, Buffer, BufferType, BufferMode};
+use crate::context::CommandContext;
 use crate::gl;
+use crate::sync::{self, LinearSyncFence};
 
 use crate::texture::PixelValue;
 use crate::texture::Texture2dDataSink;
@@ -23,6 +26,8 @@ use crate::texture::Texture2dDataSink;
 pub struct PixelBuffer<T> where T: PixelValue {
     buffer: Buffer<[T]>,
     dimensions: Cell<Option<(u32, u32)>>,
+    // fence inserted after the last read of pixels into this buffer
+    fence: RefCell<Option<LinearSyncFence>>,
 }
 
 impl<T> PixelBuffer<T> where T: PixelValue {
@@ -33,9 +38,50 @@ impl<T> PixelBuffer<T> where T: PixelValue {
             buffer: Buffer::empty_array(facade, BufferType::PixelPackBuffer, capacity,
                                             BufferMode::Default).unwrap(),
             dimensions: Cell::new(None),
+            fence: RefCell::new(None),
         }
     }
 
+    /// Returns true if the pixels that have been read into this buffer are available.
+    ///
+    /// Reading pixels into a pixel buffer is done asynchronously. If this function returns
+    /// `false`, calling `read` or `read_as_texture_2d` will block until the GPU has finished
+    /// writing the pixels. You can instead call this function once per frame and only read the
+    /// content of the buffer when it returns `true`, so that the read-back never stalls.
+    ///
+    /// Always returns `true` if fences are not supported by the backend.
+    pub fn is_ready(&self) -> bool {
+        let mut fence = self.fence.borrow_mut();
+        if fence.is_none() {
+            return true;
+        }
+
+        let mut ctxt = self.buffer.get_context().make_current();
+        let signaled = unsafe {
+            sync::is_linear_sync_fence_signaled(&mut ctxt, fence.as_ref().unwrap())
+        };
+
+        if signaled {
+            unsafe { sync::destroy_linear_sync_fence(&mut ctxt, fence.take().unwrap()) };
+        }
+
+        signaled
+    }
+
+    /// Records that pixels of the given dimensions have been read into this buffer, and
+    /// inserts a fence that is signaled once the GPU has finished writing them.
+    ///
+    /// Must be called right after the `glReadPixels` command.
+    pub(crate) fn finish_read(&self, ctxt: &mut CommandContext<'_>, dimensions: (u32, u32)) {
+        self.dimensions.set(Some(dimensions));
+
+        let mut fence = self.fence.borrow_mut();
+        if let Some(old) = fence.take() {
+            unsafe { sync::destroy_linear_sync_fence(ctxt, old) };
+        }
+        *fence = unsafe { sync::new_linear_sync_fence(ctxt) }.ok();
+    }
+
     /// Reads the content of the pixel buffer.
     #[inline]
     pub fn read_as_texture_2d<S>(&self) -> Result<S, ReadError> where S: Texture2dDataSink<T> {
@@ -45,6 +91,16 @@ impl<T> PixelBuffer<T> where T: PixelValue {
     }
 }
 
+impl<T> Drop for PixelBuffer<T> where T: PixelValue {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(fence) = self.fence.borrow_mut().take() {
+            let mut ctxt = self.buffer.get_context().make_current();
+            unsafe { sync::destroy_linear_sync_fence(&mut ctxt, fence) };
+        }
+    }
+}
+
 impl<T> Deref for PixelBuffer<T> where T: PixelValue {
     type Target = Buffer<[T]>;
 
@@ -70,10 +126,3 @@ impl<T> GlObject for PixelBuffer<T> where T: PixelValue {
         self.buffer.get_id()
     }
 }
-
-// TODO: remove this hack
-#[doc(hidden)]
-#[inline]
-pub fn store_infos<T>(b: &PixelBuffer<T>, dimensions: (u32, u32)) where T: PixelValue {
-    b.dimensions.set(Some(dimensions));
-}
@@ -80,3 +80,26 @@ pub fn is_cubemap_arrays_supported<C: ?Sized>(context: &C) -> bool where C: Capa
     context.get_extensions().gl_ext_texture_cube_map_array ||
     context.get_extensions().gl_oes_texture_cube_map_array
 }
+
+/// Returns true is the `textureGather` and `textureGatherOffset` GLSL functions are supported.
+///
+/// With `GL_ARB_texture_gather` alone, these functions can only return the first component of
+/// the four texels and can't be used with shadow samplers.
+/// See `is_texture_gather_components_supported`.
+#[inline]
+pub fn is_texture_gather_supported<C: ?Sized>(context: &C) -> bool where C: CapabilitiesSource {
+    context.get_version() >= &Version(Api::Gl, 4, 0) ||
+    context.get_version() >= &Version(Api::GlEs, 3, 1) ||
+    context.get_extensions().gl_arb_texture_gather
+}
+
+/// Returns true is the `comp` parameter of `textureGather` and gathering from shadow samplers
+/// are supported.
+#[inline]
+pub fn is_texture_gather_components_supported<C: ?Sized>(context: &C) -> bool
+                                                 where C: CapabilitiesSource
+{
+    context.get_version() >= &Version(Api::Gl, 4, 0) ||
+    context.get_version() >= &Version(Api::GlEs, 3, 1) ||
+    context.get_extensions().gl_arb_gpu_shader5
+}
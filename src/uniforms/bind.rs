@@ -5,6 +5,7 @@ Handles binding uniforms to the OpenGL state machine.
 */
 use crate::gl;
 
+use std::cmp;
 use std::collections::HashMap;
 use std::hash::BuildHasherDefault;
 
@@ -138,6 +139,53 @@ impl<U> UniformsExt for U where U: Uniforms {
 
         visiting_result
     }
+
+    fn set_persistent_uniforms<P>(&self, mut ctxt: &mut CommandContext<'_>, program: &P)
+                                  -> Result<(), DrawError> where P: ProgramExt
+    {
+        // texture bind points are not used, since textures are rejected
+        let mut texture_bind_points = Bitsfield::new();
+
+        let mut visiting_result = Ok(());
+        self.visit_values(|name, value| {
+            if visiting_result.is_err() { return; }
+
+            let uniform = match program.get_uniform(name) {
+                Some(u) => u,
+                None => {
+                    if program.get_uniform_blocks().contains_key(name) ||
+                       program.get_shader_storage_blocks().contains_key(name) ||
+                       program.get_atomic_counters().contains_key(name)
+                    {
+                        visiting_result = Err(DrawError::UniformNotPersistent {
+                            name: name.to_owned(),
+                        });
+                    }
+                    return;
+                }
+            };
+
+            if !is_persistent_value(&value) {
+                visiting_result = Err(DrawError::UniformNotPersistent {
+                    name: name.to_owned(),
+                });
+                return;
+            }
+
+            if !value.is_usable_with(&uniform.ty) {
+                visiting_result = Err(DrawError::UniformTypeMismatch {
+                    name: name.to_owned(),
+                    expected: uniform.ty,
+                });
+                return;
+            }
+
+            visiting_result = bind_uniform(&mut ctxt, &value, program, uniform.location,
+                                           &mut texture_bind_points, name);
+        });
+
+        visiting_result
+    }
 }
 
 fn bind_subroutine_uniforms<P>(ctxt: &mut context::CommandContext<'_>, program: &P,
@@ -199,10 +247,18 @@ fn bind_uniform_block<'a, P>(ctxt: &mut context::CommandContext<'_>, value: &Uni
                 }
             }
 
+            let alignment = ctxt.capabilities.uniform_buffer_offset_alignment as usize;
+            let offset = buffer.get_offset_bytes();
+            if alignment != 0 && offset % alignment != 0 {
+                return Err(DrawError::BlockOffsetNotAligned {
+                    name: name.to_owned(),
+                    offset,
+                    alignment,
+                });
+            }
+
             let bind_point = buffer_bind_points.get_unused().expect("Not enough buffer units");
             buffer_bind_points.set_used(bind_point);
-
-            assert!(buffer.get_offset_bytes() == 0);     // TODO: not implemented
             let fence = buffer.add_fence();
             let block_id = block.id as gl::types::GLuint;
 
@@ -235,10 +291,18 @@ fn bind_shared_storage_block<'a, P>(ctxt: &mut context::CommandContext<'_>, valu
                 }
             }
 
+            let alignment = ctxt.capabilities.shader_storage_buffer_offset_alignment as usize;
+            let offset = buffer.get_offset_bytes();
+            if alignment != 0 && offset % alignment != 0 {
+                return Err(DrawError::BlockOffsetNotAligned {
+                    name: name.to_owned(),
+                    offset,
+                    alignment,
+                });
+            }
+
             let bind_point = buffer_bind_points.get_unused().expect("Not enough buffer units");
             buffer_bind_points.set_used(bind_point);
-
-            assert!(buffer.get_offset_bytes() == 0);     // TODO: not implemented
             let fence = buffer.add_fence();
             let block_id = block.id as gl::types::GLuint;
 
@@ -284,6 +348,27 @@ fn bind_atomic_counter<'a, P>(ctxt: &mut context::CommandContext<'_>, value: &Un
     }
 }
 
+/// Returns true if the value is stored in the program itself, and therefore stays set until it
+/// is changed.
+fn is_persistent_value(value: &UniformValue<'_>) -> bool {
+    match *value {
+        UniformValue::SignedInt(_) | UniformValue::UnsignedInt(_) | UniformValue::Float(_) |
+        UniformValue::Mat2(_) | UniformValue::Mat3(_) | UniformValue::Mat4(_) |
+        UniformValue::Vec2(_) | UniformValue::Vec3(_) | UniformValue::Vec4(_) |
+        UniformValue::IntVec2(_) | UniformValue::IntVec3(_) | UniformValue::IntVec4(_) |
+        UniformValue::UnsignedIntVec2(_) | UniformValue::UnsignedIntVec3(_) |
+        UniformValue::UnsignedIntVec4(_) | UniformValue::Bool(_) | UniformValue::BoolVec2(_) |
+        UniformValue::BoolVec3(_) | UniformValue::BoolVec4(_) | UniformValue::Double(_) |
+        UniformValue::DoubleVec2(_) | UniformValue::DoubleVec3(_) |
+        UniformValue::DoubleVec4(_) | UniformValue::DoubleMat2(_) |
+        UniformValue::DoubleMat3(_) | UniformValue::DoubleMat4(_) | UniformValue::Int64(_) |
+        UniformValue::Int64Vec2(_) | UniformValue::Int64Vec3(_) | UniformValue::Int64Vec4(_) |
+        UniformValue::UnsignedInt64(_) | UniformValue::UnsignedInt64Vec2(_) |
+        UniformValue::UnsignedInt64Vec3(_) | UniformValue::UnsignedInt64Vec4(_) => true,
+        _ => false,
+    }
+}
+
 fn bind_uniform<P>(ctxt: &mut context::CommandContext<'_>,
                    value: &UniformValue<'_>, program: &P, location: gl::types::GLint,
                    texture_bind_points: &mut Bitsfield, name: &str)
@@ -484,6 +569,12 @@ fn bind_uniform<P>(ctxt: &mut context::CommandContext<'_>,
         UniformValue::DepthTexture2d(texture, sampler) => {
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
+        UniformValue::StencilTexture2d(texture, sampler) => {
+            bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
+        },
+        UniformValue::DepthStencilTexture2d(texture, sampler) => {
+            bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
+        },
         UniformValue::Texture2dMultisample(texture, sampler) => {
             bind_texture_uniform(ctxt, &**texture, sampler, location, program, texture_bind_points)
         },
@@ -639,25 +730,31 @@ fn bind_texture_uniform<P, T>(ctxt: &mut context::CommandContext<'_>,
 
     let sampler = sampler.unwrap_or(0);
 
+    // the first units may be reserved by the user
+    let reserved = ctxt.reserved_texture_units.get();
+
     // finding an appropriate texture unit
     let texture_unit =
         ctxt.state.texture_units
             .iter().enumerate()
+            .skip(reserved as usize)
             .find(|&(unit, content)| {
                 content.texture == texture.get_texture_id() && (content.sampler == sampler ||
                                                         !texture_bind_points.is_used(unit as u16))
             })
             .map(|(unit, _)| unit as u16)
             .or_else(|| {
-                if ctxt.state.texture_units.len() <
-                    ctxt.capabilities.max_combined_texture_image_units as usize
-                {
-                    Some(ctxt.state.texture_units.len() as u16)
+                let next = cmp::max(ctxt.state.texture_units.len(), reserved as usize);
+                if next < ctxt.capabilities.max_combined_texture_image_units as usize {
+                    Some(next as u16)
                 } else {
                     None
                 }
             })
             .unwrap_or_else(|| {
+                for unit in 0 .. reserved {
+                    texture_bind_points.set_used(unit);
+                }
                 texture_bind_points.get_unused().expect("Not enough texture units available")
             });
     assert!((texture_unit as gl::types::GLint) <
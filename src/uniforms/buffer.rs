@@ -1,11 +1,14 @@
 use crate::buffer::{Content, Buffer, BufferAny, BufferType, BufferMode, BufferCreationError};
-use crate::buffer::{BufferSlice, BufferMutSlice};
+use crate::buffer::{BufferSlice, BufferMutSlice, BufferAnySlice};
 use crate::uniforms::{AsUniformValue, UniformBlock, UniformValue, LayoutMismatchError};
 use crate::program;
 
 use crate::gl;
 use crate::GlObject;
 
+use std::error::Error;
+use std::fmt;
+use std::mem;
 use std::ops::{Deref, DerefMut};
 
 use crate::backend::Facade;
@@ -201,6 +204,115 @@ impl<T: ?Sized> UniformBuffer<T> where T: Content {
     }
 }
 
+impl<T: ?Sized> UniformBuffer<T> where T: Content {
+    /// Returns a range of this buffer that contains a block of type `U` starting at `offset`
+    /// bytes, and that can be passed to a uniform block instead of the whole buffer.
+    ///
+    /// This allows storing the uniforms of many objects in a single large buffer, and using a
+    /// different range of it for each draw call. The offset must be a multiple of
+    /// `get_uniform_buffer_offset_alignment()`, which is usually between 16 and 256 bytes.
+    ///
+    /// ```no_run
+    /// # #[macro_use] extern crate glium;
+    /// # fn main() {
+    /// # use glium::Surface;
+    /// # let display: glium::Display = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// # let mut target = display.draw();
+    /// # let vertex_buffer: glium::VertexBuffer<u8> = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// # let indices = glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList);
+    /// # let program: glium::Program = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    /// let alignment = display.get_uniform_buffer_offset_alignment();
+    /// let stride = (std::mem::size_of::<[f32; 4]>() + alignment - 1) / alignment * alignment;
+    ///
+    /// let buffer = glium::uniforms::UniformBuffer::<[u8]>::empty_unsized_dynamic(&display,
+    ///                                                                            100 * stride)
+    ///                                                                            .unwrap();
+    ///
+    /// for object in 0 .. 100 {
+    ///     let block = buffer.block_range::<[f32; 4]>(object * stride).unwrap();
+    ///     target.draw(&vertex_buffer, &indices, &program, &uniform! { Object: block },
+    ///                 &Default::default()).unwrap();
+    /// }
+    /// # }
+    /// ```
+    pub fn block_range<U>(&self, offset: usize) -> Result<UniformBufferRange<'_>, BlockRangeError>
+                          where U: UniformBlock + Content + Copy
+    {
+        let alignment = self.buffer.get_context().get_uniform_buffer_offset_alignment();
+        if alignment != 0 && offset % alignment != 0 {
+            return Err(BlockRangeError::OffsetNotAligned { offset, alignment });
+        }
+
+        let slice = self.buffer.as_slice_any().slice_bytes(offset, mem::size_of::<U>())
+                                              .ok_or(BlockRangeError::OutOfRange)?;
+
+        #[inline]
+        fn f<U>(block: &program::UniformBlock) -> Result<(), LayoutMismatchError>
+                where U: UniformBlock + Copy
+        {
+            U::matches(&block.layout, 0)
+        }
+
+        Ok(UniformBufferRange {
+            slice,
+            layout: f::<U>,
+        })
+    }
+}
+
+/// A range of a uniform buffer that contains a uniform block.
+///
+/// Built with `UniformBuffer::block_range`.
+#[derive(Copy, Clone)]
+pub struct UniformBufferRange<'a> {
+    slice: BufferAnySlice<'a>,
+    layout: fn(&program::UniformBlock) -> Result<(), LayoutMismatchError>,
+}
+
+impl<'a> fmt::Debug for UniformBufferRange<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "UniformBufferRange({:?})", self.slice)
+    }
+}
+
+impl<'a> AsUniformValue for UniformBufferRange<'a> {
+    #[inline]
+    fn as_uniform_value(&self) -> UniformValue<'_> {
+        UniformValue::Block(self.slice, self.layout)
+    }
+}
+
+/// Error that can happen when building a range of a uniform buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BlockRangeError {
+    /// The offset isn't a multiple of `GL_UNIFORM_BUFFER_OFFSET_ALIGNMENT`.
+    OffsetNotAligned {
+        /// The offset that was requested.
+        offset: usize,
+        /// The required alignment.
+        alignment: usize,
+    },
+
+    /// The range is outside of the buffer.
+    OutOfRange,
+}
+
+impl fmt::Display for BlockRangeError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            BlockRangeError::OffsetNotAligned { offset, alignment } => {
+                write!(fmt, "The offset {} isn't a multiple of the uniform buffer offset \
+                             alignment ({})", offset, alignment)
+            },
+            BlockRangeError::OutOfRange => {
+                fmt.write_str("The range is outside of the buffer")
+            },
+        }
+    }
+}
+
+impl Error for BlockRangeError {}
+
 impl<T: ?Sized> Deref for UniformBuffer<T> where T: Content {
     type Target = Buffer<T>;
 
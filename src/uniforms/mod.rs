@@ -139,11 +139,22 @@ let program = glium::Program::from_source(&display,
     };
 # }
 ```
+
+## Performance
+
+Uniforms are not uploaded individually. When you draw, all the uniform values are visited
+and applied with the same command context as the draw command itself. In addition to this,
+each program keeps a copy of the last value of each of its uniforms, and `glUniform` is only
+called for the values that have changed since the previous draw with this program.
+
+This means that passing the same twenty uniforms to every draw command is cheap, as only
+the ones that actually change result in a call to the OpenGL driver. If you have a lot of
+values that change at every draw, consider putting them in a `UniformBuffer` instead.
 */
-pub use self::buffer::UniformBuffer;
+pub use self::buffer::{UniformBuffer, UniformBufferRange, BlockRangeError};
 pub use self::sampler::{SamplerWrapFunction, MagnifySamplerFilter, MinifySamplerFilter, DepthTextureComparison};
 pub use self::sampler::{Sampler, SamplerBehavior};
-pub use self::uniforms::{EmptyUniforms, UniformsStorage};
+pub use self::uniforms::{EmptyUniforms, UniformsStorage, UniformsArray, UniformsArrayError};
 pub use self::value::{UniformValue, UniformType};
 
 use std::error::Error;
@@ -1,3 +1,5 @@
+use std::hash::{Hash, Hasher};
+
 use crate::ToGlEnum;
 use crate::gl;
 
@@ -15,7 +17,9 @@ pub enum SamplerWrapFunction {
     /// Samples at coord `x + 1` map to coord `1`.
     Clamp,
 
-    /// Use texture border.
+    /// Samples outside of the texture return the border color of the sampler.
+    ///
+    /// Requires OpenGL ES 3.2 or `GL_EXT_texture_border_clamp` when using OpenGL ES.
     BorderClamp,
 
     /// Same as Mirror, but only for one repetition,
@@ -174,6 +178,25 @@ impl<'t, T: 't> Sampler<'t, T> {
         self
     }
 
+    /// Sets the color returned when sampling outside of the texture with
+    /// `SamplerWrapFunction::BorderClamp`.
+    pub fn border_color(mut self, color: (f32, f32, f32, f32)) -> Sampler<'t, T> {
+        self.1.border_color = Some(color);
+        self
+    }
+
+    /// Sets the bias added to the level of detail when choosing the mipmap to sample from.
+    pub fn lod_bias(mut self, bias: f32) -> Sampler<'t, T> {
+        self.1.lod_bias = bias;
+        self
+    }
+
+    /// Chooses whether the texels of sRGB textures are converted to linear RGB when sampled.
+    pub fn srgb_decode(mut self, decode: bool) -> Sampler<'t, T> {
+        self.1.srgb_decode = decode;
+        self
+    }
+
     /// Changes the magnifying filter of the sampler.
     pub fn anisotropy(mut self, level: u16) -> Sampler<'t, T> {
         self.1.max_anisotropy = level;
@@ -190,8 +213,8 @@ impl<'t, T: 't> Clone for Sampler<'t, T> {
 }
 
 /// Behavior of a sampler.
-// TODO: GL_TEXTURE_BORDER_COLOR, GL_TEXTURE_MIN_LOD, GL_TEXTURE_MAX_LOD, GL_TEXTURE_LOD_BIAS
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+// TODO: GL_TEXTURE_MIN_LOD, GL_TEXTURE_MAX_LOD
+#[derive(Debug, Clone, Copy)]
 pub struct SamplerBehavior {
     /// Functions to use for the X, Y, and Z coordinates.
     pub wrap_function: (SamplerWrapFunction, SamplerWrapFunction, SamplerWrapFunction),
@@ -215,6 +238,71 @@ pub struct SamplerBehavior {
     /// If you set the value to a value higher than what the hardware supports, it will
     /// be clamped.
     pub max_anisotropy: u16,
+
+    /// The color returned when sampling outside of the texture with
+    /// `SamplerWrapFunction::BorderClamp`. `None` means the default of `(0.0, 0.0, 0.0, 0.0)`.
+    ///
+    /// ## Compatibility
+    ///
+    /// Requires OpenGL ES 3.2 or `GL_EXT_texture_border_clamp` when using OpenGL ES.
+    pub border_color: Option<(f32, f32, f32, f32)>,
+
+    /// Value added to the level of detail computed by the GPU before choosing the mipmap to
+    /// sample from. Positive values select smaller mipmaps. Default value is `0.0`.
+    ///
+    /// ## Compatibility
+    ///
+    /// This parameter is ignored on OpenGL ES, which doesn't support it.
+    pub lod_bias: f32,
+
+    /// Whether the texels of sRGB textures are converted to linear RGB when sampled. Default
+    /// value is `true`. This has no effect on textures that aren't in sRGB.
+    ///
+    /// When a sampler is used, this value replaces the one set with
+    /// `TextureAny::set_srgb_decode`.
+    ///
+    /// ## Compatibility
+    ///
+    /// Setting this to `false` requires `GL_EXT_texture_sRGB_decode`.
+    pub srgb_decode: bool,
+}
+
+impl SamplerBehavior {
+    /// Returns the bit patterns of the border color, so that it can be compared and hashed.
+    #[inline]
+    fn border_color_bits(&self) -> Option<(u32, u32, u32, u32)> {
+        self.border_color.map(|(r, g, b, a)| (r.to_bits(), g.to_bits(), b.to_bits(), a.to_bits()))
+    }
+}
+
+impl PartialEq for SamplerBehavior {
+    #[inline]
+    fn eq(&self, other: &SamplerBehavior) -> bool {
+        self.wrap_function == other.wrap_function &&
+        self.minify_filter == other.minify_filter &&
+        self.magnify_filter == other.magnify_filter &&
+        self.depth_texture_comparison == other.depth_texture_comparison &&
+        self.max_anisotropy == other.max_anisotropy &&
+        self.border_color_bits() == other.border_color_bits() &&
+        self.lod_bias.to_bits() == other.lod_bias.to_bits() &&
+        self.srgb_decode == other.srgb_decode
+    }
+}
+
+impl Eq for SamplerBehavior {}
+
+impl Hash for SamplerBehavior {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.wrap_function.hash(state);
+        self.minify_filter.hash(state);
+        self.magnify_filter.hash(state);
+        self.depth_texture_comparison.hash(state);
+        self.max_anisotropy.hash(state);
+        self.border_color_bits().hash(state);
+        self.lod_bias.to_bits().hash(state);
+        self.srgb_decode.hash(state);
+    }
 }
 
 impl Default for SamplerBehavior {
@@ -230,6 +318,9 @@ impl Default for SamplerBehavior {
             magnify_filter: MagnifySamplerFilter::Linear,
             depth_texture_comparison: None,
             max_anisotropy: 1,
+            border_color: None,
+            lod_bias: 0.0,
+            srgb_decode: true,
         }
     }
 }
@@ -1,3 +1,8 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::Write;
+
+use crate::Program;
 use crate::uniforms::{Uniforms, UniformValue, AsUniformValue};
 
 /// Object that can be used when you don't have any uniforms.
@@ -53,3 +58,107 @@ impl<'n, T, R> Uniforms for UniformsStorage<'n, T, R> where T: AsUniformValue, R
         self.rest.visit_values(output);
     }
 }
+
+/// Uniforms that contain the values of an array of structs.
+///
+/// Each element is itself a `Uniforms` whose names are the names of the fields of the struct.
+/// For example, the values of element `2` are passed to the program as `lights[2].position`,
+/// `lights[2].color`, and so on.
+///
+/// ```no_run
+/// # #[macro_use] extern crate glium;
+/// # fn main() {
+/// # let program: glium::Program = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// // uniform Light lights[8];
+/// let lights = [
+///     uniform! { position: [0.0, 1.0, 0.0f32], color: [1.0, 0.0, 0.0f32] },
+///     uniform! { position: [1.0, 0.0, 0.0f32], color: [0.0, 0.0, 1.0f32] },
+/// ];
+///
+/// let lights = glium::uniforms::UniformsArray::new(&program, "lights", &lights).unwrap();
+/// # }
+/// ```
+///
+/// To pass other uniforms in the same draw, implement `Uniforms` for a struct that calls
+/// `visit_values` on both the array and the other uniforms.
+pub struct UniformsArray<'n, 'a, U: 'a> where U: Uniforms {
+    name: &'n str,
+    elements: &'a [U],
+}
+
+impl<'n, 'a, U> UniformsArray<'n, 'a, U> where U: Uniforms {
+    /// Builds the uniforms of the array `name` of `program`.
+    ///
+    /// Returns an error if the program doesn't have an array with this name, or if there are
+    /// more elements than the length returned by `Program::get_uniform_array_len`.
+    pub fn new(program: &Program, name: &'n str, elements: &'a [U])
+               -> Result<UniformsArray<'n, 'a, U>, UniformsArrayError>
+    {
+        let max = program.get_uniform_array_len(name).ok_or(UniformsArrayError::NotFound)?;
+
+        if elements.len() > max {
+            return Err(UniformsArrayError::TooManyElements { len: elements.len(), max });
+        }
+
+        Ok(UniformsArray {
+            name,
+            elements,
+        })
+    }
+
+    /// Returns the number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns true if there isn't any element.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+}
+
+impl<'n, 'b, U> Uniforms for UniformsArray<'n, 'b, U> where U: Uniforms {
+    fn visit_values<'a, F: FnMut(&str, UniformValue<'a>)>(&'a self, mut output: F) {
+        // the same buffer is used for the names of all the fields
+        let mut full_name = String::with_capacity(self.name.len() + 16);
+
+        for (index, element) in self.elements.iter().enumerate() {
+            element.visit_values(|field, value| {
+                full_name.clear();
+                write!(full_name, "{}[{}].{}", self.name, index, field).unwrap();
+                output(&full_name, value);
+            });
+        }
+    }
+}
+
+/// Error that can happen when building a `UniformsArray`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum UniformsArrayError {
+    /// The program doesn't have any active uniform array with this name.
+    NotFound,
+
+    /// There are more elements than the length of the array in the program.
+    TooManyElements {
+        /// Number of elements that were passed.
+        len: usize,
+        /// Length of the array in the program.
+        max: usize,
+    },
+}
+
+impl fmt::Display for UniformsArrayError {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            UniformsArrayError::NotFound =>
+                fmt.write_str("The program doesn't have an active uniform array with this name"),
+            UniformsArrayError::TooManyElements { len, max } =>
+                write!(fmt, "{} elements were passed but the array only has {} elements",
+                       len, max),
+        }
+    }
+}
+
+impl Error for UniformsArrayError {}
@@ -194,6 +194,8 @@ pub enum UniformValue<'a> {
     IntegralTexture2d(&'a texture::IntegralTexture2d, Option<SamplerBehavior>),
     UnsignedTexture2d(&'a texture::UnsignedTexture2d, Option<SamplerBehavior>),
     DepthTexture2d(&'a texture::DepthTexture2d, Option<SamplerBehavior>),
+    StencilTexture2d(&'a texture::StencilTexture2d, Option<SamplerBehavior>),
+    DepthStencilTexture2d(&'a texture::DepthStencilTexture2d, Option<SamplerBehavior>),
     Texture2dMultisample(&'a texture::Texture2dMultisample, Option<SamplerBehavior>),
     SrgbTexture2dMultisample(&'a texture::SrgbTexture2dMultisample, Option<SamplerBehavior>),
     IntegralTexture2dMultisample(&'a texture::IntegralTexture2dMultisample, Option<SamplerBehavior>),
@@ -279,6 +281,14 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::DoubleVec2(_), UniformType::DoubleVec2) => true,
             (&UniformValue::DoubleVec3(_), UniformType::DoubleVec3) => true,
             (&UniformValue::DoubleVec4(_), UniformType::DoubleVec4) => true,
+            (&UniformValue::Int64(_), UniformType::Int64) => true,
+            (&UniformValue::Int64Vec2(_), UniformType::Int64Vec2) => true,
+            (&UniformValue::Int64Vec3(_), UniformType::Int64Vec3) => true,
+            (&UniformValue::Int64Vec4(_), UniformType::Int64Vec4) => true,
+            (&UniformValue::UnsignedInt64(_), UniformType::UnsignedInt64) => true,
+            (&UniformValue::UnsignedInt64Vec2(_), UniformType::UnsignedInt64Vec2) => true,
+            (&UniformValue::UnsignedInt64Vec3(_), UniformType::UnsignedInt64Vec3) => true,
+            (&UniformValue::UnsignedInt64Vec4(_), UniformType::UnsignedInt64Vec4) => true,
             (&UniformValue::Texture1d(_, _), UniformType::Sampler1d) => true,
             (&UniformValue::CompressedTexture1d(_, _), UniformType::Sampler1d) => true,
             (&UniformValue::SrgbTexture1d(_, _), UniformType::Sampler1d) => true,
@@ -295,6 +305,10 @@ impl<'a> UniformValue<'a> {
             (&UniformValue::UnsignedTexture2d(_, _), UniformType::USampler2d) => true,
             (&UniformValue::DepthTexture2d(_, _), UniformType::Sampler2d) => true,
             (&UniformValue::DepthTexture2d(_, _), UniformType::Sampler2dShadow) => true,
+            (&UniformValue::StencilTexture2d(_, _), UniformType::USampler2d) => true,
+            (&UniformValue::DepthStencilTexture2d(_, _), UniformType::Sampler2d) => true,
+            (&UniformValue::DepthStencilTexture2d(_, _), UniformType::Sampler2dShadow) => true,
+            (&UniformValue::DepthStencilTexture2d(_, _), UniformType::USampler2d) => true,
             (&UniformValue::Texture3d(_, _), UniformType::Sampler3d) => true,
             (&UniformValue::CompressedTexture3d(_, _), UniformType::Sampler3d) => true,
             (&UniformValue::SrgbTexture3d(_, _), UniformType::Sampler3d) => true,
@@ -71,6 +71,31 @@ pub unsafe fn get_gl_version(gl: &gl::Gl) -> Version {
     )
 }
 
+/// Parses the value returned by `glGetString(GL_SHADING_LANGUAGE_VERSION)`.
+///
+/// The version uses the same format as `get_supported_glsl_version`: the minor version only has
+/// one digit, which means that for example GLSL 4.60 is `Version(Api::Gl, 4, 6)`. The string
+/// may start with `OpenGL ES GLSL ES` or `WebGL GLSL ES`, in which case the API is `GlEs`.
+/// Otherwise `api` is used. Anything after the version number is ignored.
+///
+/// Returns `None` if the string doesn't contain a version number.
+pub fn parse_glsl_version(version: &str, api: Api) -> Option<Version> {
+    let (version, api) = match version.find("GLSL ES") {
+        Some(pos) => (&version[pos + 7 ..], Api::GlEs),
+        None => (version, api),
+    };
+
+    let version = version.trim_start().split(|c: char| c.is_whitespace()).next()?;
+
+    let mut iter = version.split('.');
+    let major = iter.next()?.parse().ok()?;
+
+    // "1.10" is GLSL 1.1, and "1.0" or "1.00" is GLSL ES 1.0
+    let minor = iter.next()?.chars().next()?.to_digit(10)? as u8;
+
+    Some(Version(api, major, minor))
+}
+
 /// Given an API version, this function returns the GLSL version that the implementation is
 /// required to support.
 ///
@@ -113,7 +138,7 @@ pub fn get_supported_glsl_version(gl_version: &Version) -> Version {
 
 #[cfg(test)]
 mod tests {
-    use super::{Version, Api, get_supported_glsl_version};
+    use super::{Version, Api, get_supported_glsl_version, parse_glsl_version};
 
     macro_rules! assert_versions {
         ( $api:path, $gl_major:expr, $gl_minor:expr => $glsl_major:expr, $glsl_minor:expr) => {
@@ -149,6 +174,23 @@ mod tests {
         assert_versions!(Api::GlEs, 3, 1 => 3, 1);
     }
 
+    #[test]
+    fn glsl_version_strings() {
+        assert_eq!(parse_glsl_version("4.60 NVIDIA", Api::Gl), Some(Version(Api::Gl, 4, 6)));
+        assert_eq!(parse_glsl_version("1.10", Api::Gl), Some(Version(Api::Gl, 1, 1)));
+        assert_eq!(parse_glsl_version("4.50 - Build 26.20.100.7263", Api::Gl),
+                   Some(Version(Api::Gl, 4, 5)));
+        assert_eq!(parse_glsl_version("OpenGL ES GLSL ES 3.20", Api::GlEs),
+                   Some(Version(Api::GlEs, 3, 2)));
+        assert_eq!(parse_glsl_version("OpenGL ES GLSL ES 1.00 build 1.0.0", Api::GlEs),
+                   Some(Version(Api::GlEs, 1, 0)));
+        assert_eq!(parse_glsl_version("WebGL GLSL ES 1.0 (OpenGL ES GLSL ES 1.0 Chromium)",
+                                      Api::GlEs),
+                   Some(Version(Api::GlEs, 1, 0)));
+        assert_eq!(parse_glsl_version("", Api::Gl), None);
+        assert_eq!(parse_glsl_version("unknown", Api::Gl), None);
+    }
+
     #[test]
     #[should_panic]
     fn invalid_gl_version() {
@@ -319,6 +319,35 @@ impl AttributeType {
         }
     }
 
+    /// Returns true if this type is made of 8, 16 or 32 bits integers, in other words if it can
+    /// be used for an `int`, `uint`, `ivec*` or `uvec*` shader input without being converted.
+    pub fn is_integral(&self) -> bool {
+        match *self {
+            AttributeType::I8 | AttributeType::I8I8 | AttributeType::I8I8I8 |
+            AttributeType::I8I8I8I8 | AttributeType::U8 | AttributeType::U8U8 |
+            AttributeType::U8U8U8 | AttributeType::U8U8U8U8 | AttributeType::I16 |
+            AttributeType::I16I16 | AttributeType::I16I16I16 | AttributeType::I16I16I16I16 |
+            AttributeType::U16 | AttributeType::U16U16 | AttributeType::U16U16U16 |
+            AttributeType::U16U16U16U16 | AttributeType::I32 | AttributeType::I32I32 |
+            AttributeType::I32I32I32 | AttributeType::I32I32I32I32 | AttributeType::U32 |
+            AttributeType::U32U32 | AttributeType::U32U32U32 | AttributeType::U32U32U32U32 => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if this type is made of `f64`s, in other words if it corresponds to a
+    /// `double`, `dvec*` or `dmat*` shader input.
+    pub fn is_double(&self) -> bool {
+        match *self {
+            AttributeType::F64 | AttributeType::F64F64 | AttributeType::F64F64F64 |
+            AttributeType::F64F64F64F64 | AttributeType::F64x2x2 | AttributeType::F64x2x3 |
+            AttributeType::F64x2x4 | AttributeType::F64x3x2 | AttributeType::F64x3x3 |
+            AttributeType::F64x3x4 | AttributeType::F64x4x2 | AttributeType::F64x4x3 |
+            AttributeType::F64x4x4 => true,
+            _ => false,
+        }
+    }
+
     /// Returns the number of values for this type.
     pub fn get_num_components(&self) -> usize {
         match *self {
@@ -944,6 +973,71 @@ unsafe impl Attribute for [[f64; 4]; 4] {
     }
 }
 
+/// Four components packed in a 32 bits signed integer. From MSB to LSB: two bits for the fourth
+/// component, and ten bits for each of the third, second and first components.
+///
+/// Corresponds to `AttributeType::I2I10I10I10Reversed`. Usually used with `normalize(true)` in
+/// order to store normals.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PackedI2I10I10I10Reversed(pub u32);
+
+impl PackedI2I10I10I10Reversed {
+    /// Packs four values. Each value is truncated to its number of bits.
+    #[inline]
+    pub fn new(x: i16, y: i16, z: i16, w: i8) -> PackedI2I10I10I10Reversed {
+        PackedI2I10I10I10Reversed((x as u32 & 0x3ff) | ((y as u32 & 0x3ff) << 10) |
+                                  ((z as u32 & 0x3ff) << 20) | ((w as u32 & 0x3) << 30))
+    }
+
+    /// Packs four values between `-1.0` and `1.0`, for use with normalization.
+    #[inline]
+    pub fn from_normalized(x: f32, y: f32, z: f32, w: f32) -> PackedI2I10I10I10Reversed {
+        fn conv(v: f32, max: f32) -> i16 { (v.max(-1.0).min(1.0) * max).round() as i16 }
+        PackedI2I10I10I10Reversed::new(conv(x, 511.0), conv(y, 511.0), conv(z, 511.0),
+                                       conv(w, 1.0) as i8)
+    }
+}
+
+unsafe impl Attribute for PackedI2I10I10I10Reversed {
+    #[inline]
+    fn get_type() -> AttributeType {
+        AttributeType::I2I10I10I10Reversed
+    }
+}
+
+/// Four components packed in a 32 bits unsigned integer. From MSB to LSB: two bits for the fourth
+/// component, and ten bits for each of the third, second and first components.
+///
+/// Corresponds to `AttributeType::U2U10U10U10Reversed`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct PackedU2U10U10U10Reversed(pub u32);
+
+impl PackedU2U10U10U10Reversed {
+    /// Packs four values. Each value is truncated to its number of bits.
+    #[inline]
+    pub fn new(x: u16, y: u16, z: u16, w: u8) -> PackedU2U10U10U10Reversed {
+        PackedU2U10U10U10Reversed((x as u32 & 0x3ff) | ((y as u32 & 0x3ff) << 10) |
+                                  ((z as u32 & 0x3ff) << 20) | ((w as u32 & 0x3) << 30))
+    }
+
+    /// Packs four values between `0.0` and `1.0`, for use with normalization.
+    #[inline]
+    pub fn from_normalized(x: f32, y: f32, z: f32, w: f32) -> PackedU2U10U10U10Reversed {
+        fn conv(v: f32, max: f32) -> u16 { (v.max(0.0).min(1.0) * max).round() as u16 }
+        PackedU2U10U10U10Reversed::new(conv(x, 1023.0), conv(y, 1023.0), conv(z, 1023.0),
+                                       conv(w, 3.0) as u8)
+    }
+}
+
+unsafe impl Attribute for PackedU2U10U10U10Reversed {
+    #[inline]
+    fn get_type() -> AttributeType {
+        AttributeType::U2U10U10U10Reversed
+    }
+}
+
 
 #[cfg(feature="cgmath")]
 unsafe impl Attribute for cgmath::Point2<i8> {
@@ -59,6 +59,36 @@ let data = &[
 let vertex_buffer = glium::vertex::VertexBuffer::new(&display, data);
 ```
 
+## Compact attributes
+
+Attributes don't need to be made of floats. Integers can be passed to a `float` or `vec*` shader
+input, in which case they are converted to floats. If you pass `normalize(true)` to the macro,
+unsigned integers are mapped to the `[0.0, 1.0]` range and signed integers to the `[-1.0, 1.0]`
+range, which allows for example storing a color in four bytes instead of sixteen:
+
+```
+# #[macro_use]
+# extern crate glium;
+# fn main() {
+#[derive(Copy, Clone)]
+struct MyVertex {
+    position: [f32; 3],
+    normal: glium::vertex::PackedI2I10I10I10Reversed,
+    color: [u8; 4],
+}
+
+implement_vertex!(MyVertex, position normalize(false), normal normalize(true),
+                  color normalize(true));
+# }
+```
+
+`int`, `uint`, `ivec*` and `uvec*` shader inputs must be fed with integers that aren't
+normalized, signed for `int` and `ivec*` and unsigned for `uint` and `uvec*`. `double`, `dvec*`
+and `dmat*` shader inputs must be fed with `f64`s of the same dimensions, and `f64`s can only be
+fed to them. These rules are checked by every draw call, which otherwise returns
+`DrawError::AttributeTypeMismatch`. Double-precision shader inputs require OpenGL 4.1 or
+`GL_ARB_vertex_attrib_64bit`.
+
 ## Drawing
 
 When you draw, you can pass either a single vertex source or a tuple of multiple sources.
@@ -120,6 +150,16 @@ be the same, or a `DrawError::VerticesSourcesLengthMismatch` will be produced.
 In all situation, the length of all per-instance sources must match, or
 `DrawError::InstancesCountMismatch` will be returned.
 
+# Vertex array objects
+
+You don't need to create vertex array objects yourself. The first time you draw with a given
+combination of vertex buffers, index buffer and program, glium builds a VAO that holds the
+attribute bindings and keeps it in a cache. Drawing again with the same combination only binds
+the cached VAO, so static meshes don't pay for re-specifying their attributes every frame.
+
+The cached VAOs are destroyed when one of the buffers or the program is destroyed. If the
+backend doesn't support VAOs, the attributes are bound again at each draw call instead.
+
 # Transform feedback
 
 Transform feedback allows you to write in a buffer the list of primitives that are generated by
@@ -139,7 +179,9 @@ pub use self::buffer::{VertexBuffer, VertexBufferAny};
 pub use self::buffer::VertexBufferSlice;
 pub use self::buffer::CreationError as BufferCreationError;
 pub use self::format::{AttributeType, VertexFormat};
+pub use self::format::{PackedI2I10I10I10Reversed, PackedU2U10U10U10Reversed};
 pub use self::transform_feedback::{is_transform_feedback_supported, TransformFeedbackSession};
+pub use self::transform_feedback::{is_transform_feedback_stream_supported, TransformFeedbackStream};
 
 use crate::buffer::BufferAnySlice;
 use crate::CapabilitiesSource;
@@ -170,6 +212,39 @@ pub enum VerticesSource<'a> {
 }
 
 /// Marker that can be passed instead of a buffer to indicate an empty list of buffers.
+///
+/// The draw call then runs the vertex shader `len` times without any vertex attribute, and the
+/// shader computes the position of each vertex by itself, usually from `gl_VertexID`. This is
+/// the usual way to draw a triangle that covers the whole surface for post-processing passes,
+/// without having to create a vertex buffer for it:
+///
+/// ```no_run
+/// # use glium::Surface;
+/// # let display: glium::Display = unsafe { ::std::mem::MaybeUninit::uninit().assume_init() };
+/// # let mut frame = display.draw();
+/// let program = glium::Program::from_source(&display, "
+///     #version 140
+///
+///     void main() {
+///         vec2 position = vec2(float((gl_VertexID & 1) << 2) - 1.0,
+///                              float((gl_VertexID & 2) << 1) - 1.0);
+///         gl_Position = vec4(position, 0.0, 1.0);
+///     }
+/// ", "
+///     #version 140
+///
+///     out vec4 color;
+///
+///     void main() {
+///         color = vec4(1.0, 0.0, 0.0, 1.0);
+///     }
+/// ", None).unwrap();
+///
+/// frame.draw(glium::vertex::EmptyVertexAttributes { len: 3 },
+///            glium::index::NoIndices(glium::index::PrimitiveType::TrianglesList),
+///            &program, &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+/// ```
+#[derive(Copy, Clone, Debug)]
 pub struct EmptyVertexAttributes {
     /// Number of phantom vertices.
     pub len: usize,
@@ -183,6 +258,7 @@ impl<'a> Into<VerticesSource<'a>> for EmptyVertexAttributes {
 }
 
 /// Marker that can be passed instead of a buffer to indicate an empty list of buffers.
+#[derive(Copy, Clone, Debug)]
 pub struct EmptyInstanceAttributes {
     /// Number of phantom vertices.
     pub len: usize,
@@ -225,6 +301,15 @@ impl<'a, T> MultiVerticesSource<'a> for T
     }
 }
 
+impl<'a> MultiVerticesSource<'a> for Vec<VerticesSource<'a>> {
+    type Iterator = ::std::vec::IntoIter<VerticesSource<'a>>;
+
+    #[inline]
+    fn iter(self) -> ::std::vec::IntoIter<VerticesSource<'a>> {
+        self.into_iter()
+    }
+}
+
 macro_rules! impl_for_tuple {
     ($t:ident) => (
         impl<'a, $t> MultiVerticesSource<'a> for ($t,)
@@ -1,9 +1,11 @@
 use std::{ mem, fmt };
+use std::cell::Cell;
 use std::error::Error;
+use std::rc::Rc;
 
 use crate::version::Api;
 use crate::version::Version;
-use crate::context::CommandContext;
+use crate::context::{Context, CommandContext};
 use crate::backend::Facade;
 use crate::BufferExt;
 use crate::GlObject;
@@ -11,7 +13,7 @@ use crate::ContextExt;
 use crate::CapabilitiesSource;
 use crate::TransformFeedbackSessionExt;
 use crate::buffer::{Buffer, BufferAnySlice};
-use crate::index::PrimitiveType;
+use crate::index::{IndicesSource, PrimitiveType};
 use crate::program::OutputPrimitives;
 use crate::program::Program;
 use crate::vertex::Vertex;
@@ -94,6 +96,57 @@ use crate::gl;
 pub struct TransformFeedbackSession<'a> {
     buffer: BufferAnySlice<'a>,
     program: &'a Program,
+    // transform feedback object, or 0 if they are not supported
+    object: gl::types::GLuint,
+    // primitives passed to the latest `glBeginTransformFeedback`
+    primitives: Cell<Option<gl::types::GLenum>>,
+}
+
+/// The vertices written by a transform feedback session, which can be drawn again without
+/// querying how many vertices have been written.
+///
+/// You obtain a stream with `TransformFeedbackSession::into_stream`. To draw it, pass the buffer
+/// that the session wrote to as the vertices source and a reference to the stream as the
+/// indices source. This corresponds to `glDrawTransformFeedback`.
+///
+/// ```no_run
+/// # #[macro_use]
+/// # extern crate glium;
+/// # use glium::Surface;
+/// # fn main() {
+/// # #[derive(Copy, Clone)]
+/// # struct Vertex { output_val: (f32, f32) }
+/// # implement_vertex!(Vertex, output_val);
+/// # let display: glium::Display = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// # let program: glium::Program = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// # let program2: glium::Program = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// # let vb: glium::vertex::VertexBufferAny = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// # let ib: glium::index::IndexBuffer<u16> = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+/// let mut out_buffer: glium::VertexBuffer<Vertex> = glium::VertexBuffer::empty(&display, 6).unwrap();
+///
+/// let stream = {
+///     let session = glium::vertex::TransformFeedbackSession::new(&display, &program,
+///                                                                &mut out_buffer).unwrap();
+///
+///     let params = glium::DrawParameters {
+///         transform_feedback: Some(&session),
+///         draw_primitives: false,
+///         .. Default::default()
+///     };
+///
+///     display.draw().draw(&vb, &ib, &program, &uniform!{}, &params).unwrap();
+///     session.into_stream().unwrap()
+/// };
+///
+/// let mut frame = display.draw();
+/// frame.draw(&out_buffer, &stream, &program2, &uniform!{}, &Default::default()).unwrap();
+/// frame.finish().unwrap();
+/// # }
+/// ```
+pub struct TransformFeedbackStream {
+    context: Rc<Context>,
+    id: gl::types::GLuint,
+    primitives: PrimitiveType,
 }
 
 /// Error that can happen when creating a `TransformFeedbackSession`.
@@ -131,6 +184,15 @@ pub fn is_transform_feedback_supported<F: ?Sized>(facade: &F) -> bool where F: F
     context.get_extensions().gl_ext_transform_feedback
 }
 
+/// Returns true if the OpenGL implementation supports drawing a `TransformFeedbackStream`.
+#[inline]
+pub fn is_transform_feedback_stream_supported<F: ?Sized>(facade: &F) -> bool where F: Facade {
+    let context = facade.get_context();
+
+    context.get_version() >= &Version(Api::Gl, 4, 0) ||
+    context.get_extensions().gl_arb_transform_feedback2
+}
+
 impl<'a> TransformFeedbackSession<'a> {
     /// Builds a new transform feedback session.
     ///
@@ -150,44 +212,178 @@ impl<'a> TransformFeedbackSession<'a> {
             return Err(TransformFeedbackSessionCreationError::WrongVertexFormat);
         }
 
+        let object = if is_transform_feedback_stream_supported(facade) {
+            let ctxt = facade.get_context().make_current();
+            unsafe {
+                let mut id = 0;
+                ctxt.gl.GenTransformFeedbacks(1, &mut id);
+                id
+            }
+        } else {
+            0
+        };
+
         Ok(TransformFeedbackSession {
             buffer: buffer.as_slice_any(),
             program,
+            object,
+            primitives: Cell::new(None),
+        })
+    }
+
+    /// Ends the session and returns the vertices that have been written, so that they can be
+    /// drawn again without knowing how many there are.
+    ///
+    /// Returns `None` if nothing has been drawn with this session, or if the backend doesn't
+    /// support it. See `is_transform_feedback_stream_supported`.
+    pub fn into_stream(mut self) -> Option<TransformFeedbackStream> {
+        let primitives = match self.primitives.get()? {
+            gl::POINTS => PrimitiveType::Points,
+            gl::LINES => PrimitiveType::LinesList,
+            gl::TRIANGLES => PrimitiveType::TrianglesList,
+            _ => unreachable!(),
+        };
+
+        if self.object == 0 {
+            return None;
+        }
+
+        let mut ctxt = self.buffer.get_context().make_current();
+        Self::ensure_buffer_out_of_transform_feedback(&mut ctxt, self.buffer.get_id());
+
+        Some(TransformFeedbackStream {
+            context: self.buffer.get_context().clone(),
+            id: mem::replace(&mut self.object, 0),
+            primitives,
         })
     }
 }
 
-impl<'a> TransformFeedbackSessionExt for TransformFeedbackSession<'a> {
-    fn bind(&self, ctxt: &mut CommandContext<'_>, draw_primitives: PrimitiveType) {
-        // TODO: check that the state matches what is required
+impl TransformFeedbackStream {
+    /// Returns the type of primitives that have been written. This is always `Points`,
+    /// `LinesList` or `TrianglesList`.
+    #[inline]
+    pub fn get_primitives_type(&self) -> PrimitiveType {
+        self.primitives
+    }
+
+    /// Returns the context corresponding to this stream.
+    #[inline]
+    pub fn get_context(&self) -> &Rc<Context> {
+        &self.context
+    }
+}
+
+impl GlObject for TransformFeedbackStream {
+    type Id = gl::types::GLuint;
+
+    #[inline]
+    fn get_id(&self) -> gl::types::GLuint {
+        self.id
+    }
+}
+
+impl fmt::Debug for TransformFeedbackStream {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "Transform feedback stream #{} ({:?})", self.id, self.primitives)
+    }
+}
+
+impl<'a> From<&'a TransformFeedbackStream> for IndicesSource<'a> {
+    #[inline]
+    fn from(stream: &'a TransformFeedbackStream) -> IndicesSource<'a> {
+        IndicesSource::TransformFeedback {
+            stream,
+            primitives: stream.primitives,
+        }
+    }
+}
+
+impl Drop for TransformFeedbackStream {
+    fn drop(&mut self) {
+        let mut ctxt = self.context.make_current();
+        unsafe { delete_transform_feedback(&mut ctxt, self.id); }
+    }
+}
+
+/// Binds a transform feedback object to `GL_TRANSFORM_FEEDBACK`.
+///
+/// The current object can't be changed while transform feedback is active, therefore the active
+/// session is ended first.
+unsafe fn bind_transform_feedback(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint) {
+    if ctxt.state.transform_feedback != id {
         if ctxt.state.transform_feedback_enabled.is_some() {
-            unimplemented!();
+            ctxt.gl.EndTransformFeedback();
+            ctxt.state.transform_feedback_enabled = None;
+            ctxt.state.transform_feedback_paused = false;
+        }
+
+        ctxt.gl.BindTransformFeedback(gl::TRANSFORM_FEEDBACK, id);
+        ctxt.state.transform_feedback = id;
+
+        // the indexed buffer bindings are part of the transform feedback object
+        for point in ctxt.state.indexed_transform_feedback_buffer_bindings.iter_mut() {
+            point.buffer = 0;
         }
+    }
+}
+
+/// Destroys a transform feedback object. It must not be active.
+unsafe fn delete_transform_feedback(ctxt: &mut CommandContext<'_>, id: gl::types::GLuint) {
+    if ctxt.state.transform_feedback == id {
+        bind_transform_feedback(ctxt, 0);
+    }
+
+    ctxt.gl.DeleteTransformFeedbacks(1, &id);
+}
 
-        // FIXME: use the memory barrier system
-        self.buffer.bind_to_transform_feedback(ctxt, 0);
+impl<'a> TransformFeedbackSessionExt for TransformFeedbackSession<'a> {
+    fn bind(&self, ctxt: &mut CommandContext<'_>, draw_primitives: PrimitiveType) {
+        let primitives = match (self.program.get_output_primitives(), draw_primitives) {
+            (Some(OutputPrimitives::Points), _) => gl::POINTS,
+            (Some(OutputPrimitives::Lines), _) => gl::LINES,
+            (Some(OutputPrimitives::Triangles), _) => gl::TRIANGLES,
+            (Some(OutputPrimitives::Quads), _) => panic!(),         // TODO: return a proper error
+            (None, PrimitiveType::Points) => gl::POINTS,
+            (None, PrimitiveType::LinesList) => gl::LINES,
+            (None, PrimitiveType::LinesListAdjacency) => gl::LINES,
+            (None, PrimitiveType::LineStrip) => gl::LINES,
+            (None, PrimitiveType::LineStripAdjacency) => gl::LINES,
+            (None, PrimitiveType::LineLoop) => gl::LINES,
+            (None, PrimitiveType::TrianglesList) => gl::TRIANGLES,
+            (None, PrimitiveType::TrianglesListAdjacency) => gl::TRIANGLES,
+            (None, PrimitiveType::TriangleStrip) => gl::TRIANGLES,
+            (None, PrimitiveType::TriangleStripAdjacency) => gl::TRIANGLES,
+            (None, PrimitiveType::TriangleFan) => gl::TRIANGLES,
+            (None, PrimitiveType::Patches { .. }) => unreachable!(),
+        };
 
         unsafe {
-            let primitives = match (self.program.get_output_primitives(), draw_primitives) {
-                (Some(OutputPrimitives::Points), _) => gl::POINTS,
-                (Some(OutputPrimitives::Lines), _) => gl::LINES,
-                (Some(OutputPrimitives::Triangles), _) => gl::TRIANGLES,
-                (Some(OutputPrimitives::Quads), _) => panic!(),         // TODO: return a proper error
-                (None, PrimitiveType::Points) => gl::POINTS,
-                (None, PrimitiveType::LinesList) => gl::LINES,
-                (None, PrimitiveType::LinesListAdjacency) => gl::LINES,
-                (None, PrimitiveType::LineStrip) => gl::LINES,
-                (None, PrimitiveType::LineStripAdjacency) => gl::LINES,
-                (None, PrimitiveType::LineLoop) => gl::LINES,
-                (None, PrimitiveType::TrianglesList) => gl::TRIANGLES,
-                (None, PrimitiveType::TrianglesListAdjacency) => gl::TRIANGLES,
-                (None, PrimitiveType::TriangleStrip) => gl::TRIANGLES,
-                (None, PrimitiveType::TriangleStripAdjacency) => gl::TRIANGLES,
-                (None, PrimitiveType::TriangleFan) => gl::TRIANGLES,
-                (None, PrimitiveType::Patches { .. }) => unreachable!(),
-            };
+            // if this session is still active in its own transform feedback object, we continue
+            // writing after the primitives that have already been captured
+            if self.object != 0 && ctxt.state.transform_feedback == self.object &&
+               ctxt.state.transform_feedback_enabled == Some(primitives)
+            {
+                if ctxt.state.transform_feedback_paused {
+                    ctxt.gl.ResumeTransformFeedback();
+                    ctxt.state.transform_feedback_paused = false;
+                }
+
+                return;
+            }
+
+            // the buffers and the object can't be changed while transform feedback is active
+            Self::unbind(ctxt);
+
+            if self.object != 0 {
+                bind_transform_feedback(ctxt, self.object);
+            }
+
+            // FIXME: use the memory barrier system
+            self.buffer.bind_to_transform_feedback(ctxt, 0);
 
             ctxt.gl.BeginTransformFeedback(primitives);
+            self.primitives.set(Some(primitives));
             ctxt.state.transform_feedback_enabled = Some(primitives);
             ctxt.state.transform_feedback_paused = false;
         }
@@ -233,5 +429,9 @@ impl<'a> Drop for TransformFeedbackSession<'a> {
         // However we end the session now anyway.
         let mut ctxt = self.buffer.get_context().make_current();
         Self::ensure_buffer_out_of_transform_feedback(&mut ctxt, self.buffer.get_id());
+
+        if self.object != 0 {
+            unsafe { delete_transform_feedback(&mut ctxt, self.object); }
+        }
     }
 }
@@ -32,6 +32,7 @@ pub struct Binder<'a, 'b, 'c> {
     element_array_buffer: Option<BufferAnySlice<'a>>,
     vertex_buffers: SmallVec<[(gl::types::GLuint, VertexFormat, usize, usize, Option<u32>); 2]>,
     base_vertex: bool,
+    base_instance: bool,
 }
 
 impl VertexAttributesSystem {
@@ -47,9 +48,13 @@ impl VertexAttributesSystem {
     ///
     /// `base_vertex` should be set to true if the backend supports the `glDraw*BaseVertex`
     /// functions. If `base_vertex` is true, then `bind` will return the base vertex to use.
+    ///
+    /// `base_instance` should be set to true if the backend supports the `glDraw*BaseInstance`
+    /// functions. If `base_instance` is true, then `bind` will return the base instance to use.
     #[inline]
     pub fn start<'a, 'b, 'c: 'b>(ctxt: &'b mut CommandContext<'c>, program: &'a Program,
-                                 indices: Option<BufferAnySlice<'a>>, base_vertex: bool)
+                                 indices: Option<BufferAnySlice<'a>>, base_vertex: bool,
+                                 base_instance: bool)
                                  -> Binder<'a, 'b, 'c>
     {
         if let Some(indices) = indices {
@@ -62,6 +67,7 @@ impl VertexAttributesSystem {
             element_array_buffer: indices,
             vertex_buffers: SmallVec::new(),
             base_vertex,
+            base_instance,
         }
     }
 
@@ -158,8 +164,9 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
 
     /// Finish binding the vertex attributes.
     ///
-    /// If `base_vertex` was set to true, returns the base vertex to use when drawing.
-    pub fn bind(mut self) -> Option<gl::types::GLint> {
+    /// If `base_vertex` was set to true, returns the base vertex to use when drawing. If
+    /// `base_instance` was set to true, returns the base instance to use when drawing.
+    pub fn bind(mut self) -> (Option<gl::types::GLint>, Option<gl::types::GLuint>) {
         let ctxt = self.context;
 
         if ctxt.version >= &Version(Api::Gl, 3, 0) || ctxt.version >= &Version(Api::GlEs, 3, 0) ||
@@ -180,8 +187,28 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
 
             // removing the offset corresponding to the base vertex
             if let Some(base_vertex) = base_vertex {
-                for &mut (_, _, ref mut off, stride, _) in self.vertex_buffers.iter_mut() {
-                    *off -= base_vertex * stride;
+                for &mut (_, _, ref mut off, stride, div) in self.vertex_buffers.iter_mut() {
+                    if div.is_none() {
+                        *off -= base_vertex * stride;
+                    }
+                }
+            }
+
+            // same for the base instance
+            let base_instance = if self.base_instance {
+                Some(self.vertex_buffers.iter()
+                                        .filter(|&&(_, _, _, _, div)| div.is_some())
+                                        .map(|&(_, _, off, stride, _)| off / stride)
+                                        .min().unwrap_or(0))
+            } else {
+                None
+            };
+
+            if let Some(base_instance) = base_instance {
+                for &mut (_, _, ref mut off, stride, div) in self.vertex_buffers.iter_mut() {
+                    if div.is_some() {
+                        *off -= base_instance * stride;
+                    }
                 }
             }
 
@@ -191,14 +218,13 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
             buffers_list.push((self.element_array_buffer.map(|b| b.get_id()).unwrap_or(0), 0));
             buffers_list.sort_unstable();
 
-            let program_id = self.program.get_id();
+            let key = (buffers_list, self.program.get_id());
 
             // trying to find an existing VAO in the cache
-            if let Some(value) = ctxt.vertex_array_objects.vaos.borrow_mut()
-                                     .get(&(buffers_list.clone(), program_id))
-            {
+            if let Some(value) = ctxt.vertex_array_objects.vaos.borrow().get(&key) {
                 value.bind(ctxt);
-                return base_vertex.map(|v| v as gl::types::GLint);
+                return (base_vertex.map(|v| v as gl::types::GLint),
+                        base_instance.map(|v| v as gl::types::GLuint));
             }
 
             // if not found, building a new one
@@ -208,9 +234,10 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
             };
 
             new_vao.bind(ctxt);
-            ctxt.vertex_array_objects.vaos.borrow_mut().insert((buffers_list, program_id), new_vao);
+            ctxt.vertex_array_objects.vaos.borrow_mut().insert(key, new_vao);
 
-            base_vertex.map(|v| v as gl::types::GLint)
+            (base_vertex.map(|v| v as gl::types::GLint),
+             base_instance.map(|v| v as gl::types::GLuint))
 
         } else {
             // VAOs are not supported
@@ -231,11 +258,8 @@ impl<'a, 'b, 'c> Binder<'a, 'b, 'c> {
 
             // TODO: it is unlikely that a backend supports base vertex but not VAOs, so we just
             //       ignore this case ; however it would ideally be better to handle it
-            if self.base_vertex {
-                Some(0)
-            } else {
-                None
-            }
+            (if self.base_vertex { Some(0) } else { None },
+             if self.base_instance { Some(0) } else { None })
         }
     }
 }
@@ -556,6 +580,16 @@ unsafe fn bind_attribute(ctxt: &mut CommandContext<'_>, program: &Program,
                     },
 
                     gl::DOUBLE | gl::INT64_NV | gl::UNSIGNED_INT64_NV => {
+                        // `glVertexAttribLPointer` is provided by both GL 4.1 and the extensions
+                        // that allow 64bits shader inputs
+                        if !(ctxt.version >= &Version(Api::Gl, 4, 1)) &&
+                           !ctxt.extensions.gl_arb_vertex_attrib_64bit &&
+                           !ctxt.extensions.gl_nv_vertex_attrib_integer_64bit
+                        {
+                            // checked in the frontend
+                            unreachable!();
+                        }
+
                         for i in 0..instances_count {
                             ctxt.gl.VertexAttribLPointer((attribute.location + i) as u32,
                                                          elements_count as gl::types::GLint, data_type,
@@ -89,6 +89,24 @@ fn buffer_dynamic_empty_creation() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn buffer_memory_size() {
+    let display = support::build_display();
+    let before = display.estimated_gpu_memory();
+
+    let buffer = glium::buffer::Buffer::new(&display, &[0u32; 16],
+                                            glium::buffer::BufferType::ArrayBuffer,
+                                            BufferMode::Default).unwrap();
+
+    assert_eq!(buffer.memory_size(), 64);
+    assert_eq!(display.estimated_gpu_memory(), before + 64);
+
+    drop(buffer);
+    assert_eq!(display.estimated_gpu_memory(), before);
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn buffer_immutable_mapping_read() {
     let display = support::build_display();
@@ -295,6 +313,19 @@ fn buffer_dynamic_read() {
     display.assert_no_error(None);
 }
 
+#[test]
+#[should_panic(expected = "The size of the buffer is not a multiple of the size of the data")]
+fn buffer_any_read_wrong_size() {
+    let display = support::build_display();
+
+    let buffer = glium::buffer::Buffer::new(&display, &[1u8, 2, 3, 4, 5, 6, 7][..],
+                                            glium::buffer::BufferType::ArrayBuffer,
+                                            BufferMode::Default).unwrap();
+    let buffer: glium::buffer::BufferAny = buffer.into();
+
+    let _ = unsafe { buffer.read::<[(u8, u8, u8, u8)]>() };
+}
+
 #[test]
 fn buffer_immutable_read_slice() {
     let display = support::build_display();
@@ -976,3 +1007,31 @@ fn copy_to_slice() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn map_write_explicit_flush() {
+    let display = support::build_display();
+
+    for &mode in &[BufferMode::Default, BufferMode::Immutable, BufferMode::Dynamic] {
+        let mut buffer = glium::buffer::Buffer::<[u8]>::new(&display, &[1, 2, 3, 4, 5, 6],
+                                                            glium::buffer::BufferType::ArrayBuffer,
+                                                            mode).unwrap();
+
+        {
+            let mut mapping = buffer.map_write_explicit_flush();
+            mapping.set(1, 20);
+            mapping.set(4, 50);
+            mapping.flush_range(1, 1);
+            mapping.flush_range(4, 1);
+        }
+
+        let result = match buffer.read() {
+            Ok(r) => r,
+            Err(_) => return
+        };
+
+        assert_eq!(result, [1, 20, 3, 4, 50, 6]);
+    }
+
+    display.assert_no_error(None);
+}
@@ -14,6 +14,39 @@ fn get_opengl_version() {
     assert!(version.1 >= 1);
 }
 
+#[test]
+fn get_glsl_version() {
+    let display = support::build_display();
+    let version = display.get_glsl_version().unwrap();
+    display.assert_no_error(None);
+
+    // the compiler supports at least the version required by the OpenGL version
+    assert!(version >= display.get_supported_glsl_version());
+}
+
+#[test]
+fn get_shader_precision_format() {
+    use glium::program::{PrecisionType, ShaderType};
+
+    let display = support::build_display();
+
+    // `highp` is always available in vertex shaders
+    let highp = display.get_shader_precision_format(ShaderType::Vertex,
+                                                    PrecisionType::HighFloat).unwrap();
+    assert!(highp.is_supported());
+    assert!(highp.precision >= 16);
+
+    let mediump = display.get_shader_precision_format(ShaderType::Fragment,
+                                                      PrecisionType::MediumInt).unwrap();
+    assert!(mediump.is_supported());
+    assert_eq!(mediump.precision, 0);
+
+    assert!(display.get_shader_precision_format(ShaderType::Geometry,
+                                                PrecisionType::HighFloat).is_none());
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn clear_color() {
     let display = support::build_display();
@@ -64,6 +97,179 @@ fn release_shader_compiler() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn get_error() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    assert_eq!(display.get_error(), None);
+}
+
+#[test]
+fn is_current() {
+    let display = support::build_display();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    assert!(display.is_current());
+}
+
+#[test]
+fn wait_until_idle() {
+    let display = support::build_display();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    assert!(display.wait_until_idle(std::time::Duration::from_secs(10)));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn deferred_deletion() {
+    let display = support::build_display();
+    display.set_deferred_deletion(true);
+
+    // objects are destroyed immediately if fences aren't supported
+    let fences_supported = glium::SyncFence::new(&display).map(|f| f.wait()).is_ok();
+
+    {
+        let _buffer = glium::buffer::Buffer::new(&display, &[1u8, 2, 3, 4],
+                                                 glium::buffer::BufferType::ArrayBuffer,
+                                                 glium::buffer::BufferMode::Default).unwrap();
+        let _texture = support::build_renderable_texture(&display);
+    }
+
+    assert_eq!(display.get_pending_deletions_count(), if fences_supported { 2 } else { 0 });
+
+    display.finish();
+    display.process_deferred_deletions();
+    assert_eq!(display.get_pending_deletions_count(), 0);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn set_hint() {
+    let display = support::build_display();
+
+    for &hint in &[glium::Hint::GenerateMipmap, glium::Hint::FragmentShaderDerivative] {
+        match display.set_hint(hint, glium::HintMode::Nicest) {
+            Ok(()) => display.assert_no_error(None),
+            Err(glium::HintNotSupportedError) => (),
+        }
+    }
+}
+
+#[test]
+fn clamp_color_disabled() {
+    let display = support::build_display();
+
+    if display.set_clamp_color(false).is_err() {
+        return;
+    }
+
+    let texture = match glium::Texture2d::empty_with_format(&display,
+                                                            glium::texture::UncompressedFloatFormat::F32F32F32F32,
+                                                            glium::texture::MipmapsOption::NoMipmap,
+                                                            64, 64)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = program!(&display,
+        140 => {
+            vertex: "
+                #version 140
+
+                in vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 140
+
+                out vec4 color;
+                void main() {
+                    color = vec4(2.0, 0.5, 4.0, 1.0);
+                }
+            "
+        },
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(2.0, 0.5, 4.0, 1.0);
+                }
+            "
+        },
+    ).unwrap();
+
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    let image = texture.main_level().first_layer().into_image(None).unwrap();
+    let rect = glium::Rect { left: 0, bottom: 0, width: 64, height: 64 };
+    let read_back: Vec<Vec<(f32, f32, f32, f32)>> = image.raw_read(&rect);
+    assert_eq!(read_back[0][0], (2.0, 0.5, 4.0, 1.0));
+    assert_eq!(read_back[63][63], (2.0, 0.5, 4.0, 1.0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn swap_interval() {
+    let display = support::build_display();
+
+    match display.set_swap_interval(0) {
+        Ok(()) => (),
+        Err(glium::SwapIntervalNotSupportedError) => return,
+    }
+
+    for _ in 0 .. 3 {
+        let mut frame = display.draw();
+        frame.clear_color(0.0, 0.0, 0.0, 1.0);
+        frame.finish().unwrap();
+    }
+
+    display.set_swap_interval(1).unwrap();
+    display.assert_no_error(None);
+}
+
+#[test]
+fn backbuffer_format() {
+    let display = support::build_display();
+    let format = display.backbuffer_format();
+
+    assert!(format.red_bits <= 32);
+    assert!(format.alpha_bits <= 32);
+    assert_ne!(format.samples, Some(1));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn timestamp_query() {
     let display = support::build_display();
@@ -149,3 +355,54 @@ fn is_context_lost() {
     display.is_context_lost();
     display.assert_no_error(None);
 }
+
+#[test]
+fn is_no_error() {
+    // the tests use a debug context, which can't be in no-error mode
+    let display = support::build_display();
+    assert!(!display.is_no_error());
+    display.assert_no_error(None);
+}
+
+#[test]
+fn on_context_lost() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let display = support::build_display();
+
+    let called = Arc::new(AtomicBool::new(false));
+    let called2 = called.clone();
+    display.on_context_lost(move || called2.store(true, Ordering::SeqCst));
+
+    let target = display.draw();
+    target.finish().unwrap();
+
+    // the context isn't lost during the tests
+    assert!(!called.load(Ordering::SeqCst));
+    display.assert_no_error(None);
+}
+
+#[test]
+fn internal_format_info() {
+    let display = support::build_display();
+
+    let format = glium::texture::UncompressedFloatFormat::U8U8U8U8.to_texture_format();
+    let info = display.internal_format_info(glium::InternalFormatTarget::Texture2d, format);
+    assert!(info.supported);
+    assert!(info.renderable);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn memory_barrier_by_region() {
+    // tests that `memory_barrier_by_region` doesn't trigger an OpenGL error
+    let display = support::build_display();
+    display.memory_barrier_by_region(glium::RegionBarrier {
+        framebuffer: true,
+        shader_image_access: true,
+        .. Default::default()
+    });
+    display.assert_no_error(None);
+}
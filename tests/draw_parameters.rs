@@ -31,6 +31,157 @@ fn color_mask() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn dithering_disabled() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        dithering: false,
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    // drawing again with the default value to check that it is restored
+    texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                              &Default::default()).unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn color_logic_op_xor() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        color_logic_op: Some(glium::draw_parameters::LogicOp::Xor),
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(1.0, 0.0, 1.0, 1.0);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Ok(()) => (),
+        Err(glium::DrawError::LogicOpNotSupported) => return,
+        e => panic!("{:?}", e),
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 0, 255, 0));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn color_logic_op_with_blending() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        color_logic_op: Some(glium::draw_parameters::LogicOp::Xor),
+        blend: glium::Blend::alpha_blending(),
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::LogicOpWithBlending) => (),
+        Err(glium::DrawError::LogicOpNotSupported) => (),
+        e => panic!("{:?}", e),
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn line_stipple() {
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        position: [f32; 2],
+    }
+
+    implement_vertex!(Vertex, position);
+
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        line_stipple: Some((2, 0x00FF)),
+        .. Default::default()
+    };
+
+    let (_, _, program) = support::build_fullscreen_red_pipeline(&display);
+
+    // a horizontal line that goes through the only row of the texture
+    let vb = glium::VertexBuffer::new(&display, &[
+        Vertex { position: [-1.0, 0.0] }, Vertex { position: [1.0, 0.0] },
+    ]).unwrap();
+    let indices = glium::index::NoIndices(PrimitiveType::LinesList);
+
+    let texture = glium::Texture2d::empty(&display, 32, 1).unwrap();
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    match texture.as_surface().draw(&vb, &indices, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Ok(()) => (),
+        Err(glium::DrawError::LineStippleNotSupported) => return,
+        e => panic!("{:?}", e),
+    };
+
+    // each bit covers two pixels, so the first 16 pixels are drawn and the next 16 aren't ;
+    // the pixels around the boundaries are skipped
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for x in 2 .. 14 {
+        assert_eq!(data[0][x], (255, 0, 0, 255));
+    }
+    for x in 18 .. 30 {
+        assert_eq!(data[0][x], (0, 0, 0, 0));
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn line_stipple_invalid_factor() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        line_stipple: Some((0, 0x00FF)),
+        .. Default::default()
+    };
+
+    let (vb, _, program) = support::build_fullscreen_red_pipeline(&display);
+    let indices = glium::index::NoIndices(PrimitiveType::LineStrip);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &indices, &program, &glium::uniforms::EmptyUniforms,
+                                    &params)
+    {
+        Err(glium::DrawError::InvalidLineStippleFactor) => (),
+        Err(glium::DrawError::LineStippleNotSupported) => (),
+        e => panic!("{:?}", e),
+    };
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn viewport_too_large() {
     let display = support::build_display();
@@ -374,6 +525,105 @@ fn cull_clockwise() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn cull_front_face_with_clockwise_winding() {
+    let display = support::build_display();
+
+    let vertex_buffer = {
+        #[derive(Copy, Clone)]
+        struct Vertex {
+            position: [f32; 2],
+        }
+
+        implement_vertex!(Vertex, position);
+
+        glium::VertexBuffer::new(&display, &[
+            Vertex { position: [-1.0,  1.0] },      // top-left
+            Vertex { position: [ 1.0,  1.0] },      // top-right
+            Vertex { position: [-1.0, -1.0] },      // bottom-left
+            Vertex { position: [ 1.0, -1.0] }       // bottom-right
+        ]).unwrap()
+    };
+
+    // first triangle covers the top-left side of the screen and is clockwise
+    // second triangle covers the bottom-right side of the screen and is ccw
+    let index_buffer = glium::IndexBuffer::new(&display, PrimitiveType::TrianglesList,
+                                               &[0u16, 1, 2, 1, 2, 3]).unwrap();
+
+    let program = program!(&display,
+        140 => {
+            vertex: "
+                #version 140
+
+                in vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 140
+
+                out vec4 color;
+                void main() {
+                    color = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+        100 => {
+            vertex: "
+                #version 100
+
+                attribute lowp vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 100
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vertex_buffer, &index_buffer, &program, &glium::uniforms::EmptyUniforms,
+        &glium::DrawParameters {
+            front_face: glium::draw_parameters::Winding::Clockwise,
+            cull_face: Some(glium::draw_parameters::CullFace::Front),
+            .. Default::default()
+        }).unwrap();
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(read_back[0].last().unwrap(), &(255, 0, 0, 255));
+    assert_eq!(read_back.last().unwrap()[0], (0, 0, 0, 0));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn cull_counterclockwise() {
     let display = support::build_display();
@@ -668,6 +918,10 @@ fn cull_counterclockwise_trianglestrip() {
 
 macro_rules! blending_test {
     ($name:ident, $func:expr, $source:expr, $dest:expr, $result:expr) => (
+        blending_test!($name, $func, (1.0, 1.0, 1.0, 1.0), $source, $dest, $result);
+    );
+
+    ($name:ident, $func:expr, $constant:expr, $source:expr, $dest:expr, $result:expr) => (
         #[test]
         fn $name() {
             let display = support::build_display();
@@ -676,7 +930,7 @@ macro_rules! blending_test {
                 blend: glium::Blend {
                     color: $func,
                     alpha: $func,
-                    constant_value: (1.0, 1.0, 1.0, 1.0)
+                    constant_value: $constant
                 },
                 .. Default::default()
             };
@@ -771,6 +1025,13 @@ blending_test!(one_plus_one, glium::BlendingFunction::Addition {
                },
                (0.0, 1.0, 1.0, 0.0), (1.0, 0.0, 0.0, 1.0), (255, 255, 255, 255));
 
+blending_test!(constant_color, glium::BlendingFunction::Addition {
+                   source: glium::LinearBlendingFactor::ConstantColor,
+                   destination: glium::LinearBlendingFactor::One,
+               },
+               (0.0, 1.0, 0.0, 1.0),
+               (0.0, 1.0, 1.0, 0.0), (1.0, 0.0, 0.0, 1.0), (0, 255, 255, 255));
+
 
 #[test]
 fn provoking_vertex_last() {
@@ -1280,6 +1541,51 @@ fn primitive_bounding_box() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn too_many_viewports() {
+    let display = support::build_display();
+
+    let rect = glium::Rect { left: 0, bottom: 0, width: 1, height: 1 };
+    let viewports = vec![rect; 4096];
+
+    let params = glium::DrawParameters {
+        viewports: Some(&viewports),
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let mut frame = display.draw();
+    match frame.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::TooManyViewports) => (),
+        Err(glium::DrawError::ViewportArrayNotSupported) => (),
+        a => panic!("{:?}", a)
+    };
+    frame.finish().unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn sample_mask() {
+    let display = support::build_display();
+
+    let params = glium::DrawParameters {
+        sample_mask: Some(0x5),
+        .. Default::default()
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Ok(_) => (),
+        Err(glium::DrawError::SampleMaskNotSupported) => return,
+        e => e.unwrap()
+    };
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn primitive_restart_index() {
 
@@ -1408,3 +1714,152 @@ fn primitive_restart_index() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn solid_with_wireframe() {
+    let display = support::build_display();
+    if display.get_opengl_version().0 != glium::Api::Gl {
+        return;
+    }
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                attribute vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                uniform vec4 color;
+
+                void main() {
+                    gl_FragColor = color;
+                }
+            ",
+        },
+    ).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw_solid_with_wireframe(&vb, &ib, &program,
+                                                   &uniform!{ color: [0.0, 0.0, 1.0, 1.0f32] },
+                                                   &uniform!{ color: [1.0, 0.0, 0.0, 1.0f32] },
+                                                   &Default::default(), 1.0).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[256][768], (0, 0, 255, 255));
+    assert_eq!(data[768][256], (0, 0, 255, 255));
+    assert!(data.iter().flat_map(|row| row.iter()).any(|p| p == &(255, 0, 0, 255)));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clear_stencil_ignores_write_mask() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let color = support::build_renderable_texture(&display);
+    let stencil = match glium::framebuffer::StencilRenderBuffer::new(&display,
+                                              glium::texture::StencilFormat::I8, 1024, 1024)
+    {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_stencil_buffer(&display,
+                                                                 &color, &stencil).unwrap();
+
+    // writes `0x0f` to the stencil buffer and leaves the write mask to `0x0f`
+    let params = glium::DrawParameters {
+        stencil: glium::draw_parameters::Stencil {
+            reference_value_clockwise: 0xff,
+            reference_value_counter_clockwise: 0xff,
+            write_mask_clockwise: 0x0f,
+            write_mask_counter_clockwise: 0x0f,
+            depth_pass_operation_clockwise: glium::StencilOperation::Replace,
+            depth_pass_operation_counter_clockwise: glium::StencilOperation::Replace,
+            .. Default::default()
+        },
+        .. Default::default()
+    };
+
+    framebuffer.clear_stencil(0);
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    // all the bits must be cleared, not only the ones of the previous write mask
+    framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+    framebuffer.clear_stencil(0xf0);
+
+    let params = glium::DrawParameters {
+        stencil: glium::draw_parameters::Stencil {
+            test_clockwise: glium::StencilTest::IfEqual { mask: 0xff },
+            test_counter_clockwise: glium::StencilTest::IfEqual { mask: 0xff },
+            reference_value_clockwise: 0xf0,
+            reference_value_counter_clockwise: 0xf0,
+            .. Default::default()
+        },
+        .. Default::default()
+    };
+
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = color.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn clear_stencil_masked() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let color = support::build_renderable_texture(&display);
+    let stencil = match glium::framebuffer::StencilRenderBuffer::new(&display,
+                                              glium::texture::StencilFormat::I8, 1024, 1024)
+    {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::with_stencil_buffer(&display,
+                                                                 &color, &stencil).unwrap();
+
+    // only the lower bits are cleared, the upper bits keep their previous value
+    framebuffer.clear_stencil(0xff);
+    framebuffer.clear_stencil_masked(0x00, 0x0f);
+    framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+
+    let params = glium::DrawParameters {
+        stencil: glium::draw_parameters::Stencil {
+            test_clockwise: glium::StencilTest::IfEqual { mask: 0xff },
+            test_counter_clockwise: glium::StencilTest::IfEqual { mask: 0xff },
+            reference_value_clockwise: 0xf0,
+            reference_value_counter_clockwise: 0xf0,
+            .. Default::default()
+        },
+        .. Default::default()
+    };
+
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = color.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
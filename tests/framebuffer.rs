@@ -276,6 +276,132 @@ fn array_level() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn texture3d_slice() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::Texture3d::empty(&display, 128, 128, 4) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    assert!(texture.main_level().slice(4).is_none());
+
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display,
+                                                          texture.main_level().slice(2).unwrap()).unwrap();
+    assert_eq!(framebuffer.get_dimensions(), (128, 128));
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms,
+                     &Default::default()).unwrap();
+
+    let image: glium::texture::TextureAnyImage = texture.main_level().slice(2).unwrap().into();
+    let rect = glium::Rect { left: 0, bottom: 0, width: 128, height: 128 };
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = image.raw_read(&rect);
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data[127][127], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn layered_array() {
+    let display = support::build_display();
+
+    if !glium::framebuffer::LayeredFrameBuffer::is_supported(&display) {
+        return;
+    }
+
+    let texture = match glium::texture::Texture2dArray::empty(&display, 128, 128, 4) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let mut framebuffer = glium::framebuffer::LayeredFrameBuffer::new(&display,
+                                                                      texture.main_level()).unwrap();
+    assert_eq!(framebuffer.get_dimensions(), (128, 128));
+
+    // clearing a layered framebuffer clears all the layers
+    framebuffer.clear_color(1.0, 0.0, 0.0, 1.0);
+
+    for layer in 0 .. 4 {
+        let image: glium::texture::TextureAnyImage = texture.main_level().layer(layer).unwrap()
+                                                            .into();
+        let rect = glium::Rect { left: 0, bottom: 0, width: 128, height: 128 };
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = image.raw_read(&rect);
+        assert_eq!(data[0][0], (255, 0, 0, 255));
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn layered_array_clear_layer() {
+    let display = support::build_display();
+
+    if !glium::framebuffer::LayeredFrameBuffer::is_supported(&display) {
+        return;
+    }
+
+    let texture = match glium::texture::Texture2dArray::empty(&display, 128, 128, 4) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let mut framebuffer = glium::framebuffer::LayeredFrameBuffer::new(&display,
+                                                                      texture.main_level()).unwrap();
+    framebuffer.clear_color(1.0, 0.0, 0.0, 1.0);
+    framebuffer.clear_layer(2, Some((0.0, 1.0, 0.0, 1.0)), None);
+
+    for layer in 0 .. 4 {
+        let image: glium::texture::TextureAnyImage = texture.main_level().layer(layer).unwrap()
+                                                            .into();
+        let rect = glium::Rect { left: 0, bottom: 0, width: 128, height: 128 };
+        let data: Vec<Vec<(u8, u8, u8, u8)>> = image.raw_read(&rect);
+
+        if layer == 2 {
+            assert_eq!(data[0][0], (0, 255, 0, 255));
+        } else {
+            assert_eq!(data[0][0], (255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn layered_array_blit_color() {
+    let display = support::build_display();
+
+    if !glium::framebuffer::LayeredFrameBuffer::is_supported(&display) {
+        return;
+    }
+
+    let texture = match glium::texture::Texture2dArray::empty(&display, 128, 128, 4) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let mut framebuffer = glium::framebuffer::LayeredFrameBuffer::new(&display,
+                                                                      texture.main_level()).unwrap();
+    framebuffer.clear_color(0.0, 0.0, 1.0, 1.0);
+    framebuffer.clear_layer(0, Some((1.0, 0.0, 0.0, 1.0)), None);
+
+    // blitting reads the first layer
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    framebuffer.fill(&output.as_surface(), glium::uniforms::MagnifySamplerFilter::Nearest);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn cubemap_layer() {
     // ignoring test on travis
@@ -447,3 +573,278 @@ fn empty_framebuffer_simple_draw() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn simple_framebuffer_capture_image() {
+    let display = support::build_display();
+
+    // bottom row is red, top row is blue
+    let data = vec![
+        vec![(255u8, 0u8, 0u8, 255u8), (255, 0, 0, 255)],
+        vec![(0, 0, 255, 255), (0, 0, 255, 255)],
+    ];
+    let texture = glium::texture::Texture2d::new(&display, data).unwrap();
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
+
+    let image = match framebuffer.capture_image() {
+        Ok(image) => image,
+        Err(_) => return
+    };
+
+    assert_eq!((image.width, image.height), (2, 2));
+    assert_eq!(image.data, vec![0, 0, 255, 255, 0, 0, 255, 255,
+                                255, 0, 0, 255, 255, 0, 0, 255]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn simple_framebuffer_read_to_pixel_buffer() {
+    let display = support::build_display();
+
+    let data = vec![vec![(0u8, 255u8, 0u8, 255u8); 4]; 4];
+    let texture = glium::texture::Texture2d::new(&display, data).unwrap();
+    let framebuffer = glium::framebuffer::SimpleFrameBuffer::new(&display, &texture).unwrap();
+
+    let pb = framebuffer.read_to_pixel_buffer().unwrap();
+    while !pb.is_ready() {}
+
+    let result: Vec<Vec<(u8, u8, u8, u8)>> = match pb.read_as_texture_2d() {
+        Ok(r) => r,
+        Err(_) => return
+    };
+
+    assert_eq!(result, vec![vec![(0, 255, 0, 255); 4]; 4]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multioutput_framebuffer_clear_color_buffer_u() {
+    let display = support::build_display();
+
+    let color = glium::texture::Texture2d::empty(&display, 2, 2).unwrap();
+    let ids = match glium::texture::UnsignedTexture2d::empty_with_format(&display,
+                                    glium::texture::UncompressedUintFormat::U32,
+                                    glium::texture::MipmapsOption::NoMipmap, 2, 2)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    use glium::framebuffer::ToColorAttachment;
+
+    let outputs = vec![("color", color.to_color_attachment()), ("ids", ids.to_color_attachment())];
+    let mut framebuffer = match glium::framebuffer::MultiOutputFrameBuffer::new(&display, outputs)
+    {
+        Ok(fb) => fb,
+        Err(_) => return
+    };
+
+    framebuffer.clear_color_buffer(0, [1.0, 0.0, 0.0, 1.0]);
+    framebuffer.clear_color_buffer_u(1, [42, 0, 0, 0]);
+
+    assert_eq!(ids.read_pixel(1, 1).0, 42);
+
+    let color: Vec<Vec<(u8, u8, u8, u8)>> = color.read();
+    assert_eq!(color[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn blending_with_integral_attachment() {
+    let display = support::build_display();
+
+    let ids = match glium::texture::UnsignedTexture2d::empty_with_format(&display,
+                                    glium::texture::UncompressedUintFormat::U32,
+                                    glium::texture::MipmapsOption::NoMipmap, 2, 2)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let mut framebuffer = match glium::framebuffer::SimpleFrameBuffer::new(&display, &ids) {
+        Ok(fb) => fb,
+        Err(_) => return
+    };
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let params = glium::DrawParameters {
+        blend: glium::Blend::alpha_blending(),
+        .. Default::default()
+    };
+
+    match framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::BlendingWithIntegralAttachment) => (),
+        e => panic!("{:?}", e),
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multisample_resolve() {
+    let display = support::build_display();
+
+    if !glium::texture::is_texture_2d_multisample_supported(&display) {
+        return;
+    }
+
+    let multisampled = glium::texture::Texture2dMultisample::empty(&display, 64, 64, 4).unwrap();
+    multisampled.as_surface().clear_color(0.0, 1.0, 0.0, 1.0);
+
+    let resolve = match glium::framebuffer::MultisampleResolve::new(&display, "
+        #version 150
+
+        uniform sampler2DMS source;
+        uniform int samples;
+        uniform float factor;
+
+        out vec4 color;
+
+        void main() {
+            vec4 sum = vec4(0.0);
+            for (int i = 0; i < samples; ++i) {
+                sum += texelFetch(source, ivec2(gl_FragCoord.xy), i);
+            }
+            color = vec4(factor, 0.0, 0.0, 0.0) + sum / float(samples);
+        }
+    ") {
+        Ok(r) => r,
+        Err(_) => return,
+    };
+
+    let texture = glium::texture::Texture2d::empty_with_format(&display,
+                            glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                            glium::texture::MipmapsOption::NoMipmap, 64, 64).unwrap();
+    resolve.resolve_with_uniforms(&multisampled, &mut texture.as_surface(),
+                                  &uniform!{ factor: 1.0f32 }).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn complete_framebuffer_status() {
+    let display = support::build_display();
+
+    let texture = glium::Texture2d::empty_with_format(&display,
+                                              glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                            glium::texture::MipmapsOption::NoMipmap,
+                                              128, 128).unwrap();
+    let depth = glium::framebuffer::DepthRenderBuffer::new(&display,
+                                      glium::texture::DepthFormat::I24, 128, 128).unwrap();
+
+    match glium::framebuffer::SimpleFrameBuffer::with_depth_buffer(&display, &texture, &depth) {
+        Ok(_) => (),
+        Err(glium::framebuffer::ValidationError::Incomplete(err)) => {
+            panic!("framebuffer reported as incomplete: {}", err)
+        },
+        Err(err) => panic!("{}", err),
+    };
+
+    let err = glium::framebuffer::ValidationError::Incomplete(
+                                        glium::framebuffer::FramebufferError::MissingAttachment);
+    assert!(err.to_string().ends_with("The framebuffer doesn't have any attachment"));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn multioutput_color_masks() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = match glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+
+            layout(location = 0) out vec4 color1;
+            layout(location = 1) out vec4 color2;
+
+            void main() {
+                color1 = vec4(1.0, 1.0, 1.0, 1.0);
+                color2 = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+        None)
+    {
+        Err(glium::CompilationError(..)) => return,
+        Ok(p) => p,
+        e => e.unwrap()
+    };
+
+    let color1 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+    color1.as_surface().clear_color(0.0, 0.0, 0.0, 1.0);
+
+    let color2 = glium::Texture2d::empty_with_format(&display,
+                                               glium::texture::UncompressedFloatFormat::U8U8U8U8,
+                                               glium::texture::MipmapsOption::NoMipmap,
+                                               128, 128).unwrap();
+    color2.as_surface().clear_color(0.0, 0.0, 0.0, 1.0);
+
+    let mut framebuffer = glium::framebuffer::MultiOutputFrameBuffer::new(&display,
+                               [("color1", &color1), ("color2", &color2)].iter().cloned()).unwrap();
+
+    let params = glium::DrawParameters {
+        color_masks: Some(&[(true, false, true, false), (false, false, false, false)]),
+        .. Default::default()
+    };
+
+    match framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Ok(()) => (),
+        Err(glium::DrawError::IndexedColorMaskNotSupported) => return,
+        e => e.unwrap(),
+    };
+
+    let read_back1: Vec<Vec<(u8, u8, u8, u8)>> = color1.read();
+    assert_eq!(read_back1[64][64], (255, 0, 255, 255));
+
+    let read_back2: Vec<Vec<(u8, u8, u8, u8)>> = color2.read();
+    assert_eq!(read_back2[64][64], (0, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn command_list_execute() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+    let texture = support::build_renderable_texture(&display);
+
+    let mut list = glium::command_list::CommandList::new(&display);
+    let command = list.draw(&vb, &ib, &program, glium::uniforms::EmptyUniforms,
+                            &Default::default()).unwrap();
+    list.set_uniforms(command, glium::uniforms::EmptyUniforms);
+    assert_eq!(list.len(), 1);
+
+    let mut framebuffer = texture.as_surface();
+    framebuffer.clear_color(0.0, 0.0, 1.0, 1.0);
+    framebuffer.execute(&list).unwrap();
+    framebuffer.execute(&list).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
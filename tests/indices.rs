@@ -371,6 +371,60 @@ fn multidraw_array() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn multidraw_array_slice() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let vb = glium::VertexBuffer::new(&display, &[
+        Vertex { position: [-1.0,  1.0] }, Vertex { position: [1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] }, Vertex { position: [1.0, -1.0] },
+    ]).unwrap();
+
+    let multidraw = glium::index::DrawCommandsNoIndicesBuffer::empty(&display, 2);
+    let multidraw = match multidraw {
+        Ok(buf) => buf,
+        Err(_) => return
+    };
+
+    multidraw.write(&[
+        glium::index::DrawCommandNoIndices {
+            count: 3,
+            instance_count: 1,
+            first_index: 0,
+            base_instance: 0,
+        },
+        glium::index::DrawCommandNoIndices {
+            count: 4,
+            instance_count: 1,
+            first_index: 0,
+            base_instance: 0,
+        }
+    ]);
+
+    assert!(multidraw.slice_with_primitive_type(2 .. 3, PrimitiveType::TriangleStrip).is_none());
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    match texture.as_surface().draw(&vb, multidraw.slice_with_primitive_type(1 .. 2,
+                                         PrimitiveType::TriangleStrip).unwrap(),
+                                    &program, &uniform!{}, &Default::default())
+    {
+        Ok(_) => (),
+        Err(glium::DrawError::IndirectDrawNotSupported) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn multidraw_elements() {
     let display = support::build_display();
@@ -414,3 +468,105 @@ fn multidraw_elements() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn patches_without_tessellation() {
+    let display = support::build_display();
+    let program = build_program(&display);
+
+    let primitives = PrimitiveType::Patches { vertices_per_patch: 3 };
+    if !primitives.is_supported(&display) {
+        return;
+    }
+
+    let vb = glium::VertexBuffer::new(&display, &[
+        Vertex { position: [-1.0,  1.0] },
+        Vertex { position: [ 1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] },
+    ]).unwrap();
+
+    let indices = index::NoIndices(primitives);
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &indices, &program, &glium::uniforms::EmptyUniforms,
+                                    &Default::default())
+    {
+        Err(glium::DrawError::PatchesWithoutTessellation) => (),
+        a => panic!("{:?}", a)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn geometry_shader_input_mismatch() {
+    let display = support::build_display();
+
+    if !glium::program::is_geometry_shader_supported(&display) {
+        return;
+    }
+
+    let program = program!(&display,
+        150 => {
+            vertex: "
+                #version 150
+
+                in vec2 position;
+
+                void main() {
+                    gl_Position = vec4(position, 0.0, 1.0);
+                }
+            ",
+            geometry: "
+                #version 150
+
+                layout(triangles) in;
+                layout(triangle_strip, max_vertices = 3) out;
+
+                void main() {
+                    for (int i = 0; i < 3; ++i) {
+                        gl_Position = gl_in[i].gl_Position;
+                        EmitVertex();
+                    }
+                    EndPrimitive();
+                }
+            ",
+            fragment: "
+                #version 150
+
+                out vec4 color;
+
+                void main() {
+                    color = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            ",
+        },
+    );
+
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    assert_eq!(program.get_geometry_input_primitives(),
+               Some(glium::program::InputPrimitives::Triangles));
+
+    let vb = glium::VertexBuffer::new(&display, &[
+        Vertex { position: [-1.0,  1.0] },
+        Vertex { position: [ 1.0,  1.0] },
+        Vertex { position: [-1.0, -1.0] },
+    ]).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &index::NoIndices(PrimitiveType::Points), &program,
+                                    &glium::uniforms::EmptyUniforms, &Default::default())
+    {
+        Err(glium::DrawError::GeometryShaderInputMismatch { .. }) => (),
+        a => panic!("{:?}", a)
+    };
+
+    texture.as_surface().draw(&vb, &index::NoIndices(PrimitiveType::TrianglesList), &program,
+                              &glium::uniforms::EmptyUniforms, &Default::default()).unwrap();
+
+    display.assert_no_error(None);
+}
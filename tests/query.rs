@@ -190,6 +190,74 @@ fn primitives_generated() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn pipeline_statistics() {
+    let display = support::build_display();
+
+    let vertices = match glium::draw_parameters::PipelineStatisticsQuery::new(&display,
+                            glium::draw_parameters::PipelineStatistic::VerticesSubmitted)
+    {
+        Err(_) => return,
+        Ok(q) => q
+    };
+
+    let clipping = glium::draw_parameters::PipelineStatisticsQuery::new(&display,
+                            glium::draw_parameters::PipelineStatistic::ClippingInputPrimitives)
+                            .unwrap();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+
+    {
+        let params = glium::DrawParameters {
+            pipeline_statistics_queries: &[&vertices, &clipping],
+            .. Default::default()
+        };
+
+        texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params)
+               .unwrap();
+    }
+
+    assert_eq!(vertices.get(), 4);
+    assert_eq!(clipping.get(), 2);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn pipeline_statistics_same_statistic() {
+    let display = support::build_display();
+
+    let query1 = match glium::draw_parameters::PipelineStatisticsQuery::new(&display,
+                            glium::draw_parameters::PipelineStatistic::VerticesSubmitted)
+    {
+        Err(_) => return,
+        Ok(q) => q
+    };
+
+    let query2 = glium::draw_parameters::PipelineStatisticsQuery::new(&display,
+                            glium::draw_parameters::PipelineStatistic::VerticesSubmitted)
+                            .unwrap();
+
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    let texture = support::build_renderable_texture(&display);
+
+    let params = glium::DrawParameters {
+        pipeline_statistics_queries: &[&query1, &query2],
+        .. Default::default()
+    };
+
+    match texture.as_surface().draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params) {
+        Err(glium::DrawError::WrongQueryOperation) => (),
+        e => panic!("{:?}", e),
+    };
+
+    display.assert_no_error(None);
+}
+
 // FIXME: add test for transform feedback query
 
 // FIXME: add more tests for conditional rendering
@@ -47,6 +47,53 @@ fn program_creation() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn program_without_fragment_shader() {
+    let display = support::build_display();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.5, 1.0);
+            }
+        ",
+        "",
+        None);
+
+    let program = match program {
+        Err(glium::ProgramCreationError::FragmentShaderRequired) => {
+            assert_eq!(display.get_opengl_version().0, glium::Api::GlEs);
+            return;
+        },
+        p => p.unwrap(),
+    };
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let depth = glium::framebuffer::DepthRenderBuffer::new(&display,
+                                      glium::texture::DepthFormat::I24, 128, 128).unwrap();
+    let mut framebuffer = glium::framebuffer::SimpleFrameBuffer::depth_only(&display, &depth)
+                                                                                    .unwrap();
+    framebuffer.clear_depth(1.0);
+
+    let params = glium::DrawParameters {
+        depth: glium::Depth {
+            test: glium::DepthTest::IfLess,
+            write: true,
+            .. Default::default()
+        },
+        .. Default::default()
+    };
+
+    framebuffer.draw(&vb, &ib, &program, &glium::uniforms::EmptyUniforms, &params).unwrap();
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn program_compilation_error() {
     let display = support::build_display();
@@ -69,6 +116,59 @@ fn program_compilation_error() {
         // geometry shader
         None);
 
+    match program {
+        Err(err @ glium::CompilationError(..)) => {
+            assert!(err.diagnostics().iter()
+                       .any(|d| d.severity == glium::program::DiagnosticSeverity::Error));
+        },
+        _ => panic!()
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn program_new_async() {
+    let display = support::build_display();
+
+    let source = |vertex_shader| glium::program::SourceCode {
+        vertex_shader,
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        fragment_shader: "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+    };
+
+    let mut pending = glium::Program::new_async(&display, source("
+        #version 110
+
+        void main() {
+            gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+        }
+    ")).unwrap();
+
+    let program = loop {
+        if let Some(program) = pending.poll() {
+            break program;
+        }
+    };
+
+    program.unwrap();
+
+    let mut pending = glium::Program::new_async(&display, source("invalid glsl code")).unwrap();
+
+    let program = loop {
+        if let Some(program) = pending.poll() {
+            break program;
+        }
+    };
+
     match program {
         Err(glium::CompilationError(..)) => (),
         _ => panic!()
@@ -266,6 +366,7 @@ fn get_program_binary() {
     };
 
     assert!(binary.content.len() >= 1);
+    assert!(display.program_binary_formats().contains(&binary.format));
 
     display.assert_no_error(None);
 }
@@ -355,6 +456,156 @@ fn program_binary_working() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn program_validate() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{}, &Default::default()).unwrap();
+
+    program.validate().unwrap();
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn program_validation_on_draw() {
+    let display = support::build_display();
+    let (vb, ib, program) = support::build_fullscreen_red_pipeline(&display);
+
+    display.set_program_validation(true);
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{}, &Default::default()).unwrap();
+
+    display.set_program_validation(false);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn program_warm_up() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            void main() {
+                gl_FragColor = vec4(1.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    program.warm_up();
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn program_reload() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let vertex_shader = "
+        #version 110
+
+        attribute vec2 position;
+
+        void main() {
+            gl_Position = vec4(position, 0.0, 1.0);
+        }
+    ";
+
+    let program = glium::Program::from_source(&display, vertex_shader,
+        "
+            #version 110
+
+            uniform vec4 color;
+
+            void main() {
+                gl_FragColor = color;
+            }
+        ",
+        None).unwrap();
+
+    program.set_persistent_uniforms(&uniform!{ color: [0.0, 1.0, 0.0, 1.0f32] }).unwrap();
+
+    let source = |fragment_shader| glium::program::SourceCode {
+        vertex_shader,
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        fragment_shader,
+    };
+
+    match program.reload(source("invalid glsl code")) {
+        Err(glium::CompilationError(..)) => (),
+        _ => panic!()
+    };
+
+    // the persistent uniforms keep their value in the new program
+    program.reload(source("
+        #version 110
+
+        uniform vec4 color;
+
+        void main() {
+            gl_FragColor = color.yxzw;
+        }
+    ")).unwrap();
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{}, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(255, 0, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn get_transform_feedback_varyings() {
     let display = support::build_display();
@@ -365,6 +616,8 @@ fn get_transform_feedback_varyings() {
         geometry_shader: None,
         outputs_srgb: false,
         uses_point_size: false,
+        geometry_shader_parameters: None,
+        frag_data_locations: &[],
 
         vertex_shader: "
             #version 110
@@ -856,3 +1109,89 @@ fn array_layout_offsets() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn frag_data_locations() {
+    let display = support::build_display();
+
+    let locations = [("color1".to_string(), 1), ("color2".to_string(), 0)];
+
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        transform_feedback_varyings: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        geometry_shader_parameters: None,
+        frag_data_locations: &locations,
+
+        vertex_shader: "
+            #version 130
+
+            void main() {
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 130
+
+            out vec4 color1;
+            out vec4 color2;
+
+            void main() {
+                color1 = vec4(1.0, 0.0, 0.0, 1.0);
+                color2 = vec4(0.0, 1.0, 0.0, 1.0);
+            }
+        ",
+    };
+
+    let program = match glium::Program::new(&display, source) {
+        Ok(p) => p,
+        Err(glium::program::ProgramCreationError::FragDataLocationNotSupported) => return,
+        Err(glium::program::ProgramCreationError::CompilationError(..)) => return,
+        Err(e) => panic!("{:?}", e)
+    };
+
+    assert_eq!(program.get_frag_data_location("color1"), Some(1));
+    assert_eq!(program.get_frag_data_location("color2"), Some(0));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn invalid_geometry_shader_parameters() {
+    let display = support::build_display();
+
+    let result = program!(&display,
+        110 => {
+            vertex: "
+                #version 110
+
+                void main() {
+                    gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 110
+
+                void main() {
+                    gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+                }
+            ",
+            geometry_shader_parameters: Some(glium::program::GeometryShaderParameters {
+                input_type: glium::index::PrimitiveType::Patches { vertices_per_patch: 3 },
+                output_type: glium::program::OutputPrimitives::Triangles,
+                vertices_out: 3,
+            }),
+        },
+    );
+
+    match result {
+        Err(glium::program::ProgramChooserCreationError::ProgramCreationError(
+            glium::program::ProgramCreationError::InvalidGeometryShaderParameters)) => (),
+        r => panic!("{:?}", r.map(|_| ())),
+    }
+
+    display.assert_no_error(None);
+}
@@ -370,3 +370,25 @@ fn mismatched_subroutines() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn subroutine_index() {
+    let display = support::build_display();
+    if !is_subroutine_supported(display.get_context()) {
+        println!("Backend does not support subroutines");
+        return
+    };
+
+    let program = build_program_complex(&display);
+
+    let red = program.get_subroutine_index(ShaderStage::Fragment, "ColorRed");
+    let blue = program.get_subroutine_index(ShaderStage::Fragment, "ColorBlue");
+    assert!(red.is_some());
+    assert!(blue.is_some());
+    assert!(red != blue);
+
+    assert!(program.get_subroutine_index(ShaderStage::Vertex, "ColorRed").is_none());
+    assert!(program.get_subroutine_index(ShaderStage::Fragment, "NotASubroutine").is_none());
+
+    display.assert_no_error(None);
+}
@@ -27,12 +27,14 @@ pub fn build_display() -> glium::Display {
 #[cfg(feature = "test_headless")]
 pub fn build_display() -> glium::HeadlessRenderer {
     let version = parse_version();
-    let hrb = glutin::HeadlessRendererBuilder::new(1024, 768)
+    let event_loop = glutin::event_loop::EventLoop::new();
+    let size = glutin::dpi::PhysicalSize::new(1024, 768);
+    let context = glutin::ContextBuilder::new()
         .with_gl_debug_flag(true)
         .with_gl(version)
-        .build()
+        .build_headless(&event_loop, size)
         .unwrap();
-    glium::HeadlessRenderer::new(hrb).unwrap()
+    glium::HeadlessRenderer::new(context).unwrap()
 }
 
 /// Rebuilds an existing display.
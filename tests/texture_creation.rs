@@ -2,6 +2,7 @@
 extern crate glium;
 
 use glium::Surface;
+use glium::CapabilitiesSource;
 
 mod support;
 
@@ -51,6 +52,31 @@ fn get_format_u8u8u8u8() {
     }
 }
 
+#[test]
+fn memory_size_u8u8u8u8() {
+    let display = support::build_display();
+    let before = display.estimated_gpu_memory();
+
+    let texture = glium::texture::Texture2d::empty_with_format(&display,
+                                                       glium::texture::UncompressedFloatFormat::
+                                                       U8U8U8U8,
+                                                       glium::texture::MipmapsOption::NoMipmap,
+                                                       64, 32).unwrap();
+
+    let size = match texture.memory_size() {
+        None => return,
+        Some(s) => s
+    };
+
+    assert!(size >= 64 * 32 * 4);
+    assert_eq!(display.estimated_gpu_memory(), before + size);
+
+    drop(texture);
+    assert_eq!(display.estimated_gpu_memory(), before);
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn depth_texture_1d_creation() {
     let display = support::build_display();
@@ -101,6 +127,37 @@ fn empty_texture2d_u8u8u8u8() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn texture2d_u8u8u8_with_format() {
+    let display = support::build_display();
+
+    let data = vec![vec![(0u8, 128u8, 255u8); 16]; 16];
+    let texture = glium::texture::Texture2d::with_format(&display, data,
+                                                         glium::texture::UncompressedFloatFormat::
+                                                             U8U8U8,
+                                                         glium::texture::MipmapsOption::NoMipmap)
+                                                         .unwrap();
+
+    display.assert_no_error(None);
+    drop(texture);
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture2d_one_and_two_channels() {
+    let display = support::build_display();
+
+    let data = vec![vec![128u8; 16]; 16];
+    let texture = glium::texture::Texture2d::new(&display, data).unwrap();
+    assert_eq!(texture.get_width(), 16);
+
+    let data = vec![vec![(0u8, 255u8); 16]; 16];
+    let texture = glium::texture::Texture2d::new(&display, data).unwrap();
+    assert_eq!(texture.get_width(), 16);
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn depth_texture_2d_creation() {
     let display = support::build_display();
@@ -385,3 +442,284 @@ fn upload_from_pixel_buffer_inverted() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn upload_from_pixel_buffer_odd_width() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty(&display, 3, 2).unwrap();
+
+    // each row is 9 bytes long, which isn't a multiple of the default unpack alignment
+    let buffer = glium::texture::pixel_buffer::PixelBuffer::new_empty(&display, 6);
+    buffer.write(&[(255u8, 0u8, 0u8), (255, 0, 0), (255, 0, 0),
+                   (0, 255, 0), (0, 255, 0), (0, 255, 0)]);
+
+    texture.main_level().raw_upload_from_pixel_buffer(buffer.as_slice(), 0 .. 3, 0 .. 2, 0 .. 1);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0], vec![(255, 0, 0, 255); 3]);
+    assert_eq!(data[1], vec![(0, 255, 0, 255); 3]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn upload_from_pixel_buffer_row_length() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty(&display, 2, 2).unwrap();
+
+    // a 3x2 image of which only the first two columns are uploaded
+    let buffer = glium::texture::pixel_buffer::PixelBuffer::new_empty(&display, 6);
+    buffer.write(&[(255u8, 0u8, 0u8, 255u8), (255, 0, 0, 255), (0, 0, 255, 255),
+                   (0, 255, 0, 255), (0, 255, 0, 255), (0, 0, 255, 255)]);
+
+    texture.main_level().raw_upload_from_pixel_buffer_with_row_length(buffer.as_slice(),
+                                                                      0 .. 2, 0 .. 2, 0 .. 1, 3);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0], vec![(255, 0, 0, 255); 2]);
+    assert_eq!(data[1], vec![(0, 255, 0, 255); 2]);
+
+    // the row length must not leak into uploads that follow
+    texture.main_level().raw_upload_from_pixel_buffer(buffer.as_slice(), 0 .. 2, 0 .. 2, 0 .. 1);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0], vec![(255, 0, 0, 255), (255, 0, 0, 255)]);
+    assert_eq!(data[1], vec![(0, 0, 255, 255), (0, 255, 0, 255)]);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_creation_from_arrays() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![[0u8, 255, 0, 255], [255, 0, 255, 0]],
+        vec![[255u8, 255, 0, 255], [0, 0, 255, 255]],
+    ]).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (0, 255, 0, 255));
+    assert_eq!(data[0][1], (255, 0, 255, 0));
+    assert_eq!(data[1][0], (255, 255, 0, 255));
+    assert_eq!(data[1][1], (0, 0, 255, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn npot_texture_2d_mipmaps() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_mipmaps(&display,
+                                            glium::texture::MipmapsOption::AutoGeneratedMipmaps,
+                                            100, 60).unwrap();
+
+    // OpenGL ES 2 doesn't support mipmaps for non-power-of-two textures without
+    // `GL_OES_texture_npot`, in which case they are skipped
+    let version = display.get_opengl_version();
+    if *version >= glium::Version(glium::Api::GlEs, 2, 0) &&
+       *version < glium::Version(glium::Api::GlEs, 3, 0) &&
+       !display.get_extensions().gl_oes_texture_npot
+    {
+        assert_eq!(texture.get_mipmap_levels(), 1);
+    } else {
+        assert_eq!(texture.get_mipmap_levels(), 7);
+    }
+
+    display.assert_no_error(None);
+}
+
+/// Builds a KTX file that contains an 8x8 DXT1 texture with all its mipmaps.
+fn build_dxt1_ktx() -> Vec<u8> {
+    let mut data = vec![0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+
+    // endianness, type, type size, format, internal format (COMPRESSED_RGB_S3TC_DXT1_EXT),
+    // base internal format, width, height, depth, array elements, faces, levels, key/values
+    for value in &[0x04030201u32, 0, 1, 0, 0x83F0, 0x1907, 8, 8, 0, 0, 1, 4, 0] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    for &size in &[32u32, 8, 8, 8] {
+        data.extend_from_slice(&size.to_le_bytes());
+        data.extend(std::iter::repeat(0).take(size as usize));
+    }
+
+    data
+}
+
+#[test]
+fn ktx_container_parsing() {
+    use glium::texture::CompressedFormat;
+    use glium::texture::container::{ContainerError, ContainerFormat, TextureContainer};
+
+    let data = build_dxt1_ktx();
+
+    let container = TextureContainer::from_ktx(&data).unwrap();
+    assert_eq!(container.get_format(),
+               ContainerFormat::Compressed(CompressedFormat::S3tcDxt1NoAlpha));
+    assert_eq!(container.get_dimensions(), (8, 8));
+    assert_eq!(container.get_mipmap_levels(), 4);
+    assert!(!container.is_cubemap());
+    assert_eq!(container.get_data(0, 0).unwrap().len(), 32);
+    assert_eq!(container.get_data(3, 0).unwrap().len(), 8);
+    assert!(container.get_data(4, 0).is_none());
+
+    assert_eq!(TextureContainer::from_ktx(&data[.. data.len() - 1]).unwrap_err(),
+               ContainerError::Truncated);
+    assert_eq!(TextureContainer::from_dds(&data).unwrap_err(), ContainerError::WrongSignature);
+}
+
+#[test]
+fn ktx_container_size_overflow() {
+    use glium::texture::container::{ContainerError, TextureContainer};
+
+    let mut data = vec![0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+
+    // a DXT5 texture whose size in bytes doesn't fit in 64 bits
+    for value in &[0x04030201u32, 0, 1, 0, 0x83F3, 0x1908, 0xffffffff, 0xffffffff, 0, 0, 1, 1,
+                   0, 0]
+    {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    assert_eq!(TextureContainer::from_ktx(&data).unwrap_err(), ContainerError::InvalidHeader);
+}
+
+#[test]
+fn dds_container_parsing() {
+    use glium::texture::CompressedFormat;
+    use glium::texture::container::{ContainerFormat, TextureContainer};
+
+    let mut data = b"DDS ".to_vec();
+
+    // size, flags (with DDSD_MIPMAPCOUNT), height, width, linear size, depth, mipmap count
+    for value in &[124u32, 0x21007, 4, 8, 32, 0, 2] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend(std::iter::repeat(0).take(11 * 4));
+
+    // pixel format: size, flags (DDPF_FOURCC), FourCC, then the unused masks
+    data.extend_from_slice(&32u32.to_le_bytes());
+    data.extend_from_slice(&4u32.to_le_bytes());
+    data.extend_from_slice(b"DXT5");
+    data.extend(std::iter::repeat(0).take(5 * 4));
+
+    // caps, caps2, caps3, caps4, reserved
+    data.extend_from_slice(&0x401008u32.to_le_bytes());
+    data.extend(std::iter::repeat(0).take(4 * 4));
+
+    // 8x4 then 4x2
+    data.extend(std::iter::repeat(0).take(32 + 16));
+
+    let container = TextureContainer::from_dds(&data).unwrap();
+    assert_eq!(container.get_format(),
+               ContainerFormat::Compressed(CompressedFormat::S3tcDxt5Alpha));
+    assert_eq!(container.get_dimensions(), (8, 4));
+    assert_eq!(container.get_mipmap_levels(), 2);
+    assert_eq!(container.get_level_dimensions(1), (4, 2));
+    assert_eq!(container.get_data(1, 0).unwrap().len(), 16);
+}
+
+#[test]
+fn compressed_texture_2d_from_ktx() {
+    use glium::texture::container::ContainerError;
+
+    let display = support::build_display();
+
+    let texture = match glium::texture::CompressedTexture2d::from_ktx(&display,
+                                                                      &build_dxt1_ktx())
+    {
+        Ok(t) => t,
+        Err(ContainerError::FormatNotSupported) => return,
+        Err(e) => panic!("{:?}", e),
+    };
+
+    assert_eq!(texture.get_mipmap_levels(), 4);
+    assert_eq!(texture.dimensions(), (8, 8));
+
+    display.assert_no_error(None);
+}
+
+/// Builds a KTX file that contains a 4x4 ETC2 cubemap with all its mipmaps.
+fn build_etc2_cubemap_ktx() -> Vec<u8> {
+    let mut data = vec![0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A];
+
+    // endianness, type, type size, format, internal format (COMPRESSED_RGB8_ETC2),
+    // base internal format, width, height, depth, array elements, faces, levels, key/values
+    for value in &[0x04030201u32, 0, 1, 0, 0x9274, 0x1907, 4, 4, 0, 0, 6, 3, 0] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    // each level is a single block per face
+    for _ in 0 .. 3 {
+        data.extend_from_slice(&8u32.to_le_bytes());
+        for face in 0 .. 6u8 {
+            data.extend(std::iter::repeat(face).take(8));
+        }
+    }
+
+    data
+}
+
+#[test]
+fn ktx_cubemap_container_parsing() {
+    use glium::texture::CompressedFormat;
+    use glium::texture::container::{ContainerFormat, TextureContainer};
+
+    let data = build_etc2_cubemap_ktx();
+
+    let container = TextureContainer::from_ktx(&data).unwrap();
+    assert_eq!(container.get_format(), ContainerFormat::Compressed(CompressedFormat::Etc2Rgb8));
+    assert!(container.is_cubemap());
+    assert_eq!(container.get_mipmap_levels(), 3);
+    assert_eq!(container.get_data(2, 5).unwrap(), &[5; 8][..]);
+    assert!(container.get_data(0, 6).is_none());
+}
+
+#[test]
+fn compressed_cubemap_from_ktx() {
+    use glium::texture::container::ContainerError;
+
+    let display = support::build_display();
+    let data = build_etc2_cubemap_ktx();
+
+    match glium::texture::CompressedTexture2d::from_ktx(&display, &data) {
+        Err(ContainerError::WrongKind) => (),
+        Err(e) => panic!("{:?}", e),
+        Ok(_) => panic!(),
+    };
+
+    let cubemap = match glium::texture::CompressedCubemap::from_ktx(&display, &data) {
+        Ok(t) => t,
+        Err(ContainerError::FormatNotSupported) => return,
+        Err(e) => panic!("{:?}", e),
+    };
+
+    assert_eq!(cubemap.get_mipmap_levels(), 3);
+    assert_eq!(cubemap.dimensions(), 4);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn compressed_texture_2d_astc() {
+    use glium::texture::CompressedFormat;
+
+    let display = support::build_display();
+
+    if !CompressedFormat::Astc6x6.is_supported(&display) {
+        return;
+    }
+
+    // the dimensions aren't multiples of the blocks, so the texture needs 2x2 blocks
+    let texture = glium::texture::CompressedTexture2d::with_compressed_data(&display,
+                        &[0; 4 * 16], 10, 10, CompressedFormat::Astc6x6,
+                        glium::texture::CompressedMipmapsOption::NoMipmap).unwrap();
+
+    assert_eq!(texture.dimensions(), (10, 10));
+
+    display.assert_no_error(None);
+}
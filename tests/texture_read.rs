@@ -134,3 +134,22 @@ read_texture_test!(read_unsignedtexture2darray, UnsignedTexture2dArray, (u8, u8,
 read_texture_test!(read_unsignedtexture3d, UnsignedTexture3d, (u8, u8, u8, u8),
     vec![vec![vec![(0, 1, 2, 3), (4, 5, 6, 7)], vec![(8, 9, 10, 11), (12, 13, 14, 15)]]]);*/
 // TODO: srgb textures
+
+#[test]
+fn unsigned_texture_2d_read_pixel() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::UnsignedTexture2d::with_format(&display,
+                                    vec![vec![7u32, 8u32], vec![9u32, 10u32]],
+                                    glium::texture::UncompressedUintFormat::U32,
+                                    glium::texture::MipmapsOption::NoMipmap)
+    {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    assert_eq!(texture.read_pixel(1, 0).0, 8);
+    assert_eq!(texture.read_pixel(0, 1).0, 9);
+
+    display.assert_no_error(None);
+}
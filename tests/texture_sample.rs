@@ -130,3 +130,311 @@ fn bindless_texture() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn mipmap_range() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let texture = glium::texture::Texture2d::empty_with_mipmaps(&display,
+                                                   glium::texture::MipmapsOption::EmptyMipmaps,
+                                                   2, 2).unwrap();
+    texture.main_level().write(glium::Rect { left: 0, bottom: 0, width: 2, height: 2 },
+                               vec![vec![(255u8, 0, 0, 255); 2]; 2]);
+    texture.mipmap(1).unwrap().write(glium::Rect { left: 0, bottom: 0, width: 1, height: 1 },
+                                     vec![vec![(0u8, 255, 0, 255)]]);
+
+    texture.set_mipmap_range(1, 1).unwrap();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = texture2D(texture, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &texture },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn depth_stencil_texture_stencil_mode() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    if display.get_opengl_version() < &glium::Version(glium::Api::Gl, 4, 3) {
+        return;
+    }
+
+    let texture = match glium::texture::DepthStencilTexture2d::empty(&display, 16, 16) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    let color = glium::Texture2d::empty(&display, 16, 16).unwrap();
+    glium::framebuffer::SimpleFrameBuffer::with_depth_stencil_buffer(&display, &color, &texture)
+        .unwrap().clear_all((0.0, 0.0, 0.0, 0.0), 1.0, 7);
+
+    texture.set_depth_stencil_texture_mode(glium::texture::DepthStencilTextureMode::Stencil)
+           .unwrap();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 130
+
+            in vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 130
+
+            uniform usampler2D tex;
+
+            void main() {
+                gl_FragColor = vec4(float(texture(tex, vec2(0.5, 0.5)).r) / 255.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    let sampled = texture.sampled()
+                         .minify_filter(glium::uniforms::MinifySamplerFilter::Nearest)
+                         .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest);
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ tex: sampled },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (7, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn srgb_skip_decode() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let texture = glium::texture::SrgbTexture2d::new(&display, vec![
+        vec![(128u8, 128u8, 128u8, 255u8)],
+    ]).unwrap();
+
+    match texture.set_srgb_decode(false) {
+        Ok(()) => (),
+        Err(_) => return
+    };
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = texture2D(texture, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &texture },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (128, 128, 128, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn srgb_skip_decode_sampler() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let texture = glium::texture::SrgbTexture2d::new(&display, vec![
+        vec![(128u8, 128u8, 128u8, 255u8)],
+    ]).unwrap();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = texture2D(texture, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    let sampled = texture.sampled()
+                         .minify_filter(glium::uniforms::MinifySamplerFilter::Nearest)
+                         .magnify_filter(glium::uniforms::MagnifySamplerFilter::Nearest)
+                         .srgb_decode(false);
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    match output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: sampled },
+                                   &Default::default())
+    {
+        Ok(()) => (),
+        Err(glium::DrawError::SamplersNotSupported) => return,
+        Err(glium::DrawError::SrgbDecodeNotSupported) => return,
+        Err(e) => panic!("{:?}", e),
+    };
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (128, 128, 128, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_creation_between_draws() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let red = glium::texture::Texture2d::new(&display, vec![
+        vec![(255, 0, 0, 255), (255, 0, 0, 255)],
+        vec![(255, 0, 0, 255), (255, 0, 0, 255u8)],
+    ]).unwrap();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = texture2D(texture, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &red },
+                             &Default::default()).unwrap();
+
+    // creating and modifying other textures must not change the textures used by the next draw
+    let green = glium::texture::Texture2d::new(&display, vec![
+        vec![(0, 255, 0, 255), (0, 255, 0, 255)],
+        vec![(0, 255, 0, 255), (0, 255, 0, 255u8)],
+    ]).unwrap();
+    unsafe { green.generate_mipmaps(); }
+    green.set_mipmap_range(0, 0).unwrap();
+
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &red },
+                             &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_gather() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let texture = glium::texture::Texture2d::new(&display, vec![
+        vec![(0, 0, 0, 255), (85, 0, 0, 255)],
+        vec![(170, 0, 0, 255), (255, 0, 0, 255u8)],
+    ]).unwrap();
+
+    if !texture.supports_gather() {
+        return;
+    }
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 130
+            #extension GL_ARB_texture_gather : enable
+
+            uniform sampler2D texture;
+
+            void main() {
+                gl_FragColor = textureGather(texture, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    let output = support::build_renderable_texture(&display);
+    output.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    output.as_surface().draw(&vb, &ib, &program, &uniform!{ texture: &texture },
+                             &Default::default()).unwrap();
+
+    // the texels are returned in the order (0, 1), (1, 1), (1, 0), (0, 0)
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = output.read();
+    assert_eq!(data[0][0], (170, 255, 85, 0));
+
+    display.assert_no_error(None);
+}
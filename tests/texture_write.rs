@@ -24,3 +24,68 @@ fn texture_2d_write() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn unsigned_texture_2d_write() {
+    let display = support::build_display();
+
+    let texture = match glium::texture::UnsignedTexture2d::new(&display, vec![
+        vec![(0u8, 1u8, 2u8, 3u8), (4u8, 5u8, 6u8, 7u8)],
+        vec![(8u8, 9u8, 10u8, 11u8), (12u8, 13u8, 14u8, 15u8)],
+    ]) {
+        Ok(t) => t,
+        Err(_) => return
+    };
+
+    texture.write(glium::Rect { bottom: 1, left: 1, width: 1, height: 1 },
+                  vec![vec![(20u8, 21u8, 22u8, 23u8)]]);
+
+    assert_eq!(texture.read_pixel(0, 0), (0, 1, 2, 3));
+    assert_eq!(texture.read_pixel(1, 0), (4, 5, 6, 7));
+    assert_eq!(texture.read_pixel(0, 1), (8, 9, 10, 11));
+    assert_eq!(texture.read_pixel(1, 1), (20, 21, 22, 23));
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn texture_2d_write_level() {
+    use glium::Surface;
+
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_mipmaps(&display,
+                                                   glium::texture::MipmapsOption::EmptyMipmaps,
+                                                   4, 4).unwrap();
+
+    texture.write_level(1, vec![
+        vec![(0u8, 255u8, 0u8, 255u8), (0u8, 255u8, 0u8, 255u8)],
+        vec![(0u8, 255u8, 0u8, 255u8), (0u8, 255u8, 0u8, 255u8)],
+    ]);
+
+    let level = glium::framebuffer::SimpleFrameBuffer::new(&display,
+                                                           texture.mipmap(1).unwrap()).unwrap();
+    let dest = glium::texture::Texture2d::empty(&display, 2, 2).unwrap();
+    level.fill(&dest.as_surface(), glium::uniforms::MagnifySamplerFilter::Nearest);
+
+    let read_back: Vec<Vec<(u8, u8, u8, u8)>> = dest.read();
+    for row in read_back.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 255, 0, 255));
+        }
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+#[should_panic]
+fn texture_2d_write_level_wrong_dimensions() {
+    let display = support::build_display();
+
+    let texture = glium::texture::Texture2d::empty_with_mipmaps(&display,
+                                                   glium::texture::MipmapsOption::EmptyMipmaps,
+                                                   4, 4).unwrap();
+
+    texture.write_level(1, vec![vec![(0u8, 255u8, 0u8, 255u8)]]);
+}
@@ -157,6 +157,167 @@ fn block() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn block_range() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {
+                vec3 color;
+            };
+
+            void main() {
+                gl_FragColor = vec4(color, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    #[derive(Copy, Clone)]
+    struct Data {
+        color: (f32, f32, f32),
+    }
+
+    implement_uniform_block!(Data, color);
+
+    let alignment = display.get_uniform_buffer_offset_alignment();
+    let buffer = match glium::uniforms::UniformBuffer::<[u8]>::empty_unsized(&display,
+                                                                             alignment + 12)
+    {
+        Err(_) => return,
+        Ok(b) => b
+    };
+
+    let color: Vec<u8> = [0.0f32, 1.0, 0.0].iter().flat_map(|c| c.to_ne_bytes().to_vec()).collect();
+    buffer.slice(alignment .. alignment + 12).unwrap().write(&color);
+
+    let uniforms = uniform!{
+        MyBlock: buffer.block_range::<Data>(alignment).unwrap()
+    };
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    for row in data.iter() {
+        for pixel in row.iter() {
+            assert_eq!(pixel, &(0, 255, 0, 255));
+        }
+    }
+
+    if alignment > 1 {
+        match buffer.block_range::<Data>(1) {
+            Err(glium::uniforms::BlockRangeError::OffsetNotAligned { offset: 1, .. }) => (),
+            r => panic!("{:?}", r)
+        }
+    }
+
+    match buffer.block_range::<Data>(alignment * 2) {
+        Err(glium::uniforms::BlockRangeError::OutOfRange) => (),
+        r => panic!("{:?}", r)
+    }
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn block_offset_not_aligned() {
+    let display = support::build_display();
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {
+                vec3 color;
+            };
+
+            void main() {
+                gl_FragColor = vec4(color, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    let alignment = display.get_uniform_buffer_offset_alignment();
+    if alignment <= 1 {
+        return;
+    }
+
+    let buffer = match glium::uniforms::UniformBuffer::<[u8]>::empty_unsized(&display,
+                                                                             alignment + 12)
+    {
+        Err(_) => return,
+        Ok(b) => b
+    };
+
+    // binds a slice of the buffer that starts at offset 1, which can't be built with
+    // `block_range`
+    struct Misaligned<'b>(glium::buffer::BufferAnySlice<'b>);
+
+    impl<'b> glium::uniforms::Uniforms for Misaligned<'b> {
+        fn visit_values<'a, F: FnMut(&str, glium::uniforms::UniformValue<'a>)>(&'a self,
+                                                                               mut output: F)
+        {
+            fn layout(_: &glium::program::UniformBlock)
+                      -> Result<(), glium::uniforms::LayoutMismatchError>
+            {
+                Ok(())
+            }
+
+            output("MyBlock", glium::uniforms::UniformValue::Block(self.0, layout));
+        }
+    }
+
+    let uniforms = Misaligned(buffer.slice(1 .. 13).unwrap().as_slice_any());
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()) {
+        Err(glium::DrawError::BlockOffsetNotAligned { offset: 1, .. }) => (),
+        r => panic!("{:?}", r)
+    }
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn block_wrong_type() {
     let display = support::build_display();
@@ -337,3 +498,123 @@ fn empty_uniform_buffer() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn block_members() {
+    let display = support::build_display();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {
+                float factor;
+                vec3 color;
+                float weights[3];
+                mat4 matrix;
+            };
+
+            void main() {
+                gl_FragColor = matrix * vec4(color * factor * weights[0] * weights[2], 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    assert!(program.get_uniform_block_members("OtherBlock").is_none());
+
+    let members = program.get_uniform_block_members("MyBlock").unwrap();
+    assert_eq!(members.len(), 4);
+
+    assert_eq!(members[0].name, "factor");
+    assert_eq!(members[0].offset, 0);
+    assert_eq!(members[0].size, None);
+
+    assert_eq!(members[1].name, "color");
+    assert_eq!(members[1].offset, 16);
+    assert_eq!(members[1].ty, glium::uniforms::UniformType::FloatVec3);
+
+    assert_eq!(members[2].name, "weights[0]");
+    assert_eq!(members[2].offset, 32);
+    assert_eq!(members[2].size, Some(3));
+    assert_eq!(members[2].array_stride, Some(16));
+
+    assert_eq!(members[3].name, "matrix");
+    assert_eq!(members[3].offset, 80);
+    assert_eq!(members[3].matrix_stride, Some(16));
+    assert!(!members[3].row_major);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn unset_uniforms_check_ignores_block_members() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 330
+            uniform layout(std140);
+
+            uniform MyBlock {
+                vec3 color;
+            };
+
+            void main() {
+                gl_FragColor = vec4(color, 1.0);
+            }
+        ",
+        None);
+
+    // ignoring test in case of compilation error (version may not be supported)
+    let program = match program {
+        Ok(p) => p,
+        Err(_) => return
+    };
+
+    #[derive(Copy, Clone)]
+    struct Data {
+        color: (f32, f32, f32),
+    }
+
+    implement_uniform_block!(Data, color);
+
+    let buffer = match glium::uniforms::UniformBuffer::new(&display, Data { color: (1.0f32, 1.0f32, 0.0f32) }) {
+        Err(_) => return,
+        Ok(b) => b
+    };
+
+    display.set_unset_uniforms_check(Some(&[]));
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().draw(&vb, &ib, &program, &uniform!{ MyBlock: &buffer },
+                              &Default::default()).unwrap();
+
+    display.set_unset_uniforms_check(None);
+    display.assert_no_error(None);
+}
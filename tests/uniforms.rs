@@ -133,6 +133,66 @@ fn uniforms_storage_ignore_inactive_uniforms() {
     display.assert_no_error(None);
 }
 
+#[test]
+fn uniforms_array_of_structs() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            struct Light {
+                vec3 color;
+                float intensity;
+            };
+
+            uniform Light lights[2];
+
+            void main() {
+                vec3 color = lights[0].color * lights[0].intensity +
+                             lights[1].color * lights[1].intensity;
+                gl_FragColor = vec4(color, 1.0);
+            }
+        ",
+        None).unwrap();
+
+    assert_eq!(program.get_uniform_array_len("lights"), Some(2));
+    assert_eq!(program.get_uniform_array_len("light"), None);
+
+    let lights = [
+        uniform! { color: [1.0, 0.0, 0.0f32], intensity: 1.0f32 },
+        uniform! { color: [0.0, 0.0, 1.0f32], intensity: 0.5f32 },
+        uniform! { color: [0.0, 1.0, 0.0f32], intensity: 1.0f32 },
+    ];
+
+    match glium::uniforms::UniformsArray::new(&program, "lights", &lights) {
+        Err(glium::uniforms::UniformsArrayError::TooManyElements { len: 3, max: 2 }) => (),
+        _ => panic!()
+    };
+
+    let uniforms = glium::uniforms::UniformsArray::new(&program, "lights", &lights[.. 2]).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniforms, &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 128, 255));
+    assert_eq!(data.last().unwrap().last().unwrap(), &(255, 0, 128, 255));
+
+    display.assert_no_error(None);
+}
+
 #[test]
 fn uniform_wrong_type() {
     let display = support::build_display();
@@ -358,3 +418,182 @@ uniform_test!(uniform_type_boolarr_boolvec3, "bvec3", [true, true, true]);
 uniform_test!(uniform_type_booltup_boolvec3, "bvec3", (false, false, false));
 uniform_test!(uniform_type_boolarr_boolvec4, "bvec4", [true, false, false, true]);
 uniform_test!(uniform_type_booltup_boolvec4, "bvec4", (false, true, true, false));
+
+#[test]
+fn persistent_uniforms() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color1;
+            uniform vec4 color2;
+
+            void main() {
+                gl_FragColor = color1 + color2;
+            }
+        ",
+        None).unwrap();
+
+    program.set_persistent_uniforms(&uniform! { color1: [0.0, 1.0, 0.0, 0.0f32] }).unwrap();
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&vb, &ib, &program, &uniform! { color2: [1.0, 0.0, 0.0, 1.0f32] },
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 255, 0, 255));
+
+    let sampled = support::build_unicolor_texture2d(&display, 1.0, 1.0, 1.0);
+    match program.set_persistent_uniforms(&uniform! { color1: &sampled }) {
+        Err(glium::DrawError::UniformNotPersistent { .. }) => (),
+        r => panic!("{:?}", r)
+    };
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn get_uniform_values() {
+    let display = support::build_display();
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color;
+            uniform int index;
+
+            void main() {
+                gl_FragColor = color * float(index);
+            }
+        ",
+        None).unwrap();
+
+    program.set_persistent_uniforms(&uniform! { color: [1.0, 0.5, 0.25, 1.0f32], index: 3i32 })
+           .unwrap();
+
+    assert_eq!(program.get_uniform_f32("color"), Some(vec![1.0, 0.5, 0.25, 1.0]));
+    assert_eq!(program.get_uniform_i32("index"), Some(vec![3]));
+    assert_eq!(program.get_uniform_f32("missing"), None);
+
+    display.assert_no_error(None);
+}
+
+#[test]
+fn unset_uniforms_check() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform vec4 color1;
+            uniform vec4 color2;
+
+            void main() {
+                gl_FragColor = color1 + color2;
+            }
+        ",
+        None).unwrap();
+
+    display.set_unset_uniforms_check(Some(&[]));
+
+    let texture = support::build_renderable_texture(&display);
+    match texture.as_surface().draw(&vb, &ib, &program, &uniform! { color2: [1.0, 0.0, 0.0, 1.0f32] },
+                                    &Default::default())
+    {
+        Err(glium::DrawError::UniformNotSet { ref name }) if name == "color1" => (),
+        r => panic!("{:?}", r)
+    };
+
+    display.set_unset_uniforms_check(Some(&["color1"]));
+    texture.as_surface().draw(&vb, &ib, &program, &uniform! { color2: [1.0, 0.0, 0.0, 1.0f32] },
+                              &Default::default()).unwrap();
+
+    display.set_unset_uniforms_check(None);
+    display.assert_no_error(None);
+}
+
+#[test]
+fn reserved_texture_units() {
+    let display = support::build_display();
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let program = glium::Program::from_source(&display,
+        "
+            #version 110
+
+            attribute vec2 position;
+
+            void main() {
+                gl_Position = vec4(position, 0.0, 1.0);
+            }
+        ",
+        "
+            #version 110
+
+            uniform sampler2D texture1;
+            uniform sampler2D texture2;
+
+            void main() {
+                gl_FragColor = texture2D(texture1, vec2(0.5, 0.5)) +
+                               texture2D(texture2, vec2(0.5, 0.5));
+            }
+        ",
+        None).unwrap();
+
+    display.reserve_texture_units(2);
+    assert_eq!(display.get_reserved_texture_units(), 2);
+
+    let texture1 = support::build_unicolor_texture2d(&display, 1.0, 0.0, 0.0);
+    let texture2 = support::build_unicolor_texture2d(&display, 0.0, 1.0, 0.0);
+
+    let target = support::build_renderable_texture(&display);
+    target.as_surface().draw(&vb, &ib, &program,
+                             &uniform!{ texture1: &texture1, texture2: &texture2 },
+                             &Default::default()).unwrap();
+
+    let unit1 = program.get_uniform_i32("texture1").unwrap()[0];
+    let unit2 = program.get_uniform_i32("texture2").unwrap()[0];
+    assert!(unit1 >= 2);
+    assert!(unit2 >= 2);
+    assert!(unit1 != unit2);
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = target.read();
+    assert_eq!(data[0][0], (255, 255, 0, 255));
+
+    display.reserve_texture_units(0);
+    display.assert_no_error(None);
+}
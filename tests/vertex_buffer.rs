@@ -24,6 +24,8 @@ fn transform_feedback() {
         geometry_shader: None,
         outputs_srgb: false,
         uses_point_size: false,
+        geometry_shader_parameters: None,
+        frag_data_locations: &[],
 
         vertex_shader: "
             #version 110
@@ -88,3 +90,112 @@ fn transform_feedback() {
 
     display.assert_no_error(None);
 }
+
+#[test]
+fn transform_feedback_stream() {
+    let display = support::build_display();
+
+    if !glium::vertex::is_transform_feedback_stream_supported(&display) {
+        return;
+    }
+
+    #[derive(Copy, Clone, PartialEq)]
+    struct Vertex {
+        output_val: (f32, f32),
+    }
+
+    implement_vertex!(Vertex, output_val);
+
+    let (vb, ib) = support::build_rectangle_vb_ib(&display);
+
+    let source = glium::program::ProgramCreationInput::SourceCode {
+        tessellation_control_shader: None,
+        tessellation_evaluation_shader: None,
+        geometry_shader: None,
+        outputs_srgb: false,
+        uses_point_size: false,
+        geometry_shader_parameters: None,
+        frag_data_locations: &[],
+
+        vertex_shader: "
+            #version 110
+
+            attribute vec2 position;
+
+            varying vec2 output_val;
+
+            void main() {
+                output_val = position;
+                gl_Position = vec4(0.0, 0.0, 0.0, 1.0);
+            }
+        ",
+        fragment_shader: "
+            #version 130
+
+            out vec4 color;
+
+            void main() {
+                color = vec4(1.0, 1.0, 1.0, 1.0);
+            }
+        ",
+
+        transform_feedback_varyings: Some((
+            vec!["output_val".to_string()],
+            glium::program::TransformFeedbackMode::Separate
+        )),
+    };
+
+    let program = glium::Program::new(&display, source).unwrap();
+
+    let draw_program = program!(&display,
+        140 => {
+            vertex: "
+                #version 140
+
+                in vec2 output_val;
+
+                void main() {
+                    gl_Position = vec4(output_val, 0.0, 1.0);
+                }
+            ",
+            fragment: "
+                #version 140
+
+                out vec4 color;
+
+                void main() {
+                    color = vec4(1.0, 0.0, 0.0, 1.0);
+                }
+            "
+        },
+    ).unwrap();
+
+    let mut out_buffer: glium::VertexBuffer<Vertex> = glium::VertexBuffer::empty(&display, 6).unwrap();
+
+    let stream = {
+        let session = glium::vertex::TransformFeedbackSession::new(&display, &program,
+                                                                   &mut out_buffer).unwrap();
+
+        let params = glium::DrawParameters {
+            transform_feedback: Some(&session),
+            draw_primitives: false,
+            .. Default::default()
+        };
+
+        let texture = support::build_renderable_texture(&display);
+        texture.as_surface().draw(&vb, &ib, &program, &uniform!{}, &params).unwrap();
+        session.into_stream().unwrap()
+    };
+
+    assert_eq!(stream.get_primitives_type(), glium::index::PrimitiveType::TrianglesList);
+
+    let texture = support::build_renderable_texture(&display);
+    texture.as_surface().clear_color(0.0, 0.0, 0.0, 0.0);
+    texture.as_surface().draw(&out_buffer, &stream, &draw_program, &uniform!{},
+                              &Default::default()).unwrap();
+
+    let data: Vec<Vec<(u8, u8, u8, u8)>> = texture.read();
+    assert_eq!(data[0][0], (255, 0, 0, 255));
+
+    display.assert_no_error(None);
+}